@@ -0,0 +1,727 @@
+//! OmniscopeAction executor.
+//!
+//! Every mutating action goes through [`ActionExecutor::execute`]: the whole
+//! action (including every step of a `Transaction`) is first applied to an
+//! in-memory staging set, so a step that fails validation leaves the library
+//! untouched. Only then are the changed cards written — JSON card first, then
+//! the SQLite index — and an [`ActionLogEntry`] is recorded with the state of
+//! every touched card beforehand. [`ActionExecutor::reverse`] restores that
+//! state.
+//!
+//! UI actions (`ShowMessage`, `NavigateTo`, …) and network-bound ones
+//! (`EnrichMetadata`, `FetchAndAdd`, `ExtractReferences`) are the caller's
+//! business and are rejected with [`ActionError::NotExecutable`].
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use omniscope_core::storage::json_cards;
+use omniscope_core::{ActionLogEntry, BookCard, Database, Folder, OmniscopeAction, OmniscopeError};
+
+/// `action_type` recorded for the log entry of a reversal.
+pub const REVERSE_ACTION_TYPE: &str = "reverse";
+
+#[derive(Debug, thiserror::Error)]
+pub enum ActionError {
+    /// A `ConfirmThen` was executed without the user's confirmation.
+    #[error("confirmation required: {0}")]
+    ConfirmationRequired(String),
+
+    #[error("action '{0}' cannot be executed against the library")]
+    NotExecutable(&'static str),
+
+    #[error("action log entry not found: {0}")]
+    LogEntryNotFound(Uuid),
+
+    #[error("action {0} has already been reversed")]
+    AlreadyReversed(Uuid),
+
+    #[error(transparent)]
+    Core(#[from] OmniscopeError),
+}
+
+impl From<serde_json::Error> for ActionError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Core(err.into())
+    }
+}
+
+pub type Result<T> = std::result::Result<T, ActionError>;
+
+fn invalid(message: impl Into<String>) -> ActionError {
+    ActionError::Core(OmniscopeError::ValidationError(message.into()))
+}
+
+/// A card written by an executed or reversed action.
+#[derive(Debug, Clone)]
+pub enum CardChange {
+    Upserted(BookCard),
+    Deleted(Uuid),
+}
+
+#[derive(Debug, Clone)]
+pub struct ActionOutcome {
+    /// The `action_log` entry recording this change.
+    pub log_id: Uuid,
+    /// JSON summary of the effect, e.g. the updated card.
+    pub result: Value,
+    pub changes: Vec<CardChange>,
+    /// The changed cards as they were before, as stored in the log.
+    pub snapshot: ActionSnapshot,
+}
+
+/// Contents of `action_log.snapshot_before`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ActionSnapshot {
+    pub cards: Vec<CardSnapshot>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardSnapshot {
+    pub id: Uuid,
+    /// `None` when the card did not exist before the action.
+    pub before: Option<BookCard>,
+}
+
+pub struct ActionExecutor<'a> {
+    db: &'a Database,
+    cards_dir: PathBuf,
+}
+
+impl<'a> ActionExecutor<'a> {
+    pub fn new(db: &'a Database, cards_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            db,
+            cards_dir: cards_dir.into(),
+        }
+    }
+
+    /// Check an action against the library without changing anything.
+    pub fn validate(&self, action: &OmniscopeAction, confirmed: bool) -> Result<()> {
+        let mut staging = Staging::new(self);
+        staging.apply(action, confirmed)?;
+        Ok(())
+    }
+
+    /// Apply `action` atomically and record it in the action log.
+    ///
+    /// `confirmed` is the user's answer to any `ConfirmThen` in the action;
+    /// without it those fail with [`ActionError::ConfirmationRequired`].
+    pub fn execute(&self, action: &OmniscopeAction, confirmed: bool) -> Result<ActionOutcome> {
+        let mut staging = Staging::new(self);
+        let result = staging.apply(action, confirmed)?;
+        let (snapshot, changes) = self.commit(staging)?;
+
+        let mut entry = ActionLogEntry::new(action);
+        entry.snapshot_before = Some(serde_json::to_value(&snapshot)?);
+        self.db.log_action(&entry)?;
+
+        Ok(ActionOutcome {
+            log_id: entry.id,
+            result,
+            changes,
+            snapshot,
+        })
+    }
+
+    /// Record a change made outside [`Self::execute`] (a duplicate merge,
+    /// say) so [`Self::reverse`] can undo it like any action. Call it before
+    /// making the change, with every card it will touch as it is now.
+    pub fn journal(&self, action_type: &str, payload: Value, before: &[BookCard]) -> Result<Uuid> {
+        let snapshot = ActionSnapshot {
+            cards: before
+                .iter()
+                .map(|card| CardSnapshot {
+                    id: card.id,
+                    before: Some(card.clone()),
+                })
+                .collect(),
+        };
+        let entry = ActionLogEntry {
+            id: Uuid::now_v7(),
+            action_type: action_type.to_string(),
+            payload,
+            snapshot_before: Some(serde_json::to_value(&snapshot)?),
+            created_at: Utc::now(),
+            reversed: false,
+        };
+        self.db.log_action(&entry)?;
+        Ok(entry.id)
+    }
+
+    /// Restore every card touched by a logged action to its earlier state.
+    ///
+    /// The reversal is itself logged, so it can be reversed in turn. Changes
+    /// made to those cards after the original action are overwritten.
+    pub fn reverse(&self, log_id: &Uuid) -> Result<ActionOutcome> {
+        let entry = self
+            .db
+            .get_action_log_entry(log_id)?
+            .ok_or(ActionError::LogEntryNotFound(*log_id))?;
+        if entry.reversed {
+            return Err(ActionError::AlreadyReversed(*log_id));
+        }
+        let snapshot: ActionSnapshot = match entry.snapshot_before {
+            Some(value) => serde_json::from_value(value)?,
+            None => ActionSnapshot::default(),
+        };
+
+        let mut staging = Staging::new(self);
+        for card in &snapshot.cards {
+            staging.current(&card.id)?;
+            staging.cards.insert(card.id, card.before.clone());
+        }
+        let restored: Vec<Uuid> = snapshot.cards.iter().map(|card| card.id).collect();
+        let (snapshot, changes) = self.commit(staging)?;
+        self.db.mark_action_reversed(log_id)?;
+
+        let reversal = ActionLogEntry {
+            id: Uuid::now_v7(),
+            action_type: REVERSE_ACTION_TYPE.to_string(),
+            payload: json!({ "reverses": log_id }),
+            snapshot_before: Some(serde_json::to_value(&snapshot)?),
+            created_at: Utc::now(),
+            reversed: false,
+        };
+        self.db.log_action(&reversal)?;
+
+        Ok(ActionOutcome {
+            log_id: reversal.id,
+            result: json!({ "reversed": log_id, "restored": restored }),
+            changes,
+            snapshot,
+        })
+    }
+
+    /// Most recent log entries first.
+    pub fn history(&self, limit: usize) -> Result<Vec<ActionLogEntry>> {
+        Ok(self.db.list_action_log(limit)?)
+    }
+
+    /// The card's JSON file, or `None` if the card does not exist. A card
+    /// the index knows but whose JSON is missing or unreadable is an error:
+    /// the index row lacks notes, files and other fields, and writing it
+    /// back as the card would lose them.
+    fn load(&self, id: &Uuid) -> Result<Option<BookCard>> {
+        match json_cards::load_card_by_id(&self.cards_dir, id) {
+            Ok(card) => Ok(Some(card)),
+            Err(OmniscopeError::BookNotFound(_)) => match self.db.get_book_card(&id.to_string()) {
+                Ok(_) => Err(invalid(format!(
+                    "card {id} is in the index but its JSON file is missing"
+                ))),
+                Err(OmniscopeError::BookNotFound(_)) => Ok(None),
+                Err(err) => Err(err.into()),
+            },
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Write staged cards. If a write fails, cards already written are put
+    /// back the way they were before the error is returned.
+    fn commit(&self, staging: Staging<'_, '_>) -> Result<(ActionSnapshot, Vec<CardChange>)> {
+        let mut snapshot = ActionSnapshot::default();
+        let mut changes = Vec::new();
+
+        for id in &staging.order {
+            let before = staging.originals[id].clone();
+            let after = staging.cards[id].clone();
+            if !card_changed(before.as_ref(), after.as_ref()) {
+                continue;
+            }
+
+            if let Err(err) = self.write(id, after.as_ref()) {
+                for written in snapshot.cards.iter().rev() {
+                    let _ = self.write(&written.id, written.before.as_ref());
+                }
+                let _ = self.write(id, before.as_ref());
+                return Err(err);
+            }
+
+            changes.push(match after {
+                Some(card) => CardChange::Upserted(card),
+                None => CardChange::Deleted(*id),
+            });
+            snapshot.cards.push(CardSnapshot { id: *id, before });
+        }
+
+        Ok((snapshot, changes))
+    }
+
+    fn write(&self, id: &Uuid, card: Option<&BookCard>) -> Result<()> {
+        match card {
+            Some(card) => {
+                json_cards::save_card(&self.cards_dir, card)?;
+                self.db.upsert_book(card)?;
+            }
+            None => {
+                json_cards::delete_card(&self.cards_dir, id)?;
+                match self.db.delete_book(&id.to_string()) {
+                    Ok(()) | Err(OmniscopeError::BookNotFound(_)) => {}
+                    Err(err) => return Err(err.into()),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn card_file_exists(&self, id: &Uuid) -> bool {
+        self.cards_dir.join(format!("{id}.json")).exists()
+    }
+
+    pub fn cards_dir(&self) -> &Path {
+        &self.cards_dir
+    }
+}
+
+fn card_changed(before: Option<&BookCard>, after: Option<&BookCard>) -> bool {
+    match (before, after) {
+        (None, None) => false,
+        (Some(before), Some(after)) => {
+            serde_json::to_value(before).ok() != serde_json::to_value(after).ok()
+        }
+        _ => true,
+    }
+}
+
+/// Cards as they will look once the action is committed.
+struct Staging<'e, 'a> {
+    executor: &'e ActionExecutor<'a>,
+    /// Card ids in the order they were first touched.
+    order: Vec<Uuid>,
+    originals: HashMap<Uuid, Option<BookCard>>,
+    cards: HashMap<Uuid, Option<BookCard>>,
+    folders: Option<Vec<Folder>>,
+}
+
+impl<'e, 'a> Staging<'e, 'a> {
+    fn new(executor: &'e ActionExecutor<'a>) -> Self {
+        Self {
+            executor,
+            order: Vec::new(),
+            originals: HashMap::new(),
+            cards: HashMap::new(),
+            folders: None,
+        }
+    }
+
+    /// The staged state of a card, loading it on first access.
+    fn current(&mut self, id: &Uuid) -> Result<Option<&mut BookCard>> {
+        if !self.cards.contains_key(id) {
+            let card = self.executor.load(id)?;
+            self.order.push(*id);
+            self.originals.insert(*id, card.clone());
+            self.cards.insert(*id, card);
+        }
+        Ok(self.cards.get_mut(id).and_then(Option::as_mut))
+    }
+
+    fn existing(&mut self, id: &Uuid) -> Result<&mut BookCard> {
+        self.current(id)?
+            .ok_or_else(|| OmniscopeError::BookNotFound(id.to_string()).into())
+    }
+
+    fn apply(&mut self, action: &OmniscopeAction, confirmed: bool) -> Result<Value> {
+        match action {
+            OmniscopeAction::CreateCard { card } => {
+                let card = card_from_value(card)?;
+                let exists = self.executor.card_file_exists(&card.id)
+                    || matches!(self.cards.get(&card.id), Some(Some(_)));
+                if exists {
+                    return Err(invalid(format!("card {} already exists", card.id)));
+                }
+                self.current(&card.id)?;
+                let value = serde_json::to_value(&card)?;
+                self.cards.insert(card.id, Some(card));
+                Ok(value)
+            }
+            OmniscopeAction::UpdateCard { id, fields } => {
+                let card = self.existing(id)?;
+                update_card(card, fields)?;
+                Ok(serde_json::to_value(&*card)?)
+            }
+            OmniscopeAction::BatchUpdate { ids, fields } => {
+                if ids.is_empty() {
+                    return Err(invalid("batch_update requires at least one id"));
+                }
+                for id in ids {
+                    update_card(self.existing(id)?, fields)?;
+                }
+                Ok(json!({ "updated": ids }))
+            }
+            OmniscopeAction::DeleteCard { id } => {
+                self.existing(id)?;
+                self.cards.insert(*id, None);
+                Ok(json!({ "deleted": id }))
+            }
+            OmniscopeAction::AddTag { book_id, tag } => {
+                let tag = tag.trim();
+                if tag.is_empty() {
+                    return Err(invalid("tag must not be empty"));
+                }
+                let card = self.existing(book_id)?;
+                if !card.organization.tags.iter().any(|t| t == tag) {
+                    card.organization.tags.push(tag.to_string());
+                    card.touch();
+                }
+                Ok(json!({ "id": book_id, "tags": card.organization.tags }))
+            }
+            OmniscopeAction::RemoveTag { book_id, tag } => {
+                let card = self.existing(book_id)?;
+                if !card.organization.tags.contains(tag) {
+                    return Err(invalid(format!("book {book_id} has no tag '{tag}'")));
+                }
+                card.organization.tags.retain(|t| t != tag);
+                card.touch();
+                Ok(json!({ "id": book_id, "tags": card.organization.tags }))
+            }
+            OmniscopeAction::MoveBooks {
+                ids,
+                to_library,
+                to_folder,
+            } => {
+                if to_library.is_none() && to_folder.is_none() {
+                    return Err(invalid("move_books needs to_library or to_folder"));
+                }
+                let folder_id = match to_folder {
+                    Some(folder) => Some(self.resolve_folder(folder)?),
+                    None => None,
+                };
+                for id in ids {
+                    let card = self.existing(id)?;
+                    if let Some(library) = to_library {
+                        card.organization.libraries = vec![library.clone()];
+                    }
+                    if let Some(folder_id) = &folder_id {
+                        card.organization.folders = vec![folder_id.clone()];
+                    }
+                    card.touch();
+                }
+                Ok(json!({ "moved": ids, "library": to_library, "folder": folder_id }))
+            }
+            OmniscopeAction::Transaction { actions } => {
+                let results = actions
+                    .iter()
+                    .map(|action| self.apply(action, confirmed))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(Value::Array(results))
+            }
+            OmniscopeAction::ConfirmThen { message, action } => {
+                if !confirmed {
+                    return Err(ActionError::ConfirmationRequired(message.clone()));
+                }
+                self.apply(action, confirmed)
+            }
+            other => Err(ActionError::NotExecutable(other.type_name())),
+        }
+    }
+
+    /// Find a folder by id, name or path relative to the library root.
+    fn resolve_folder(&mut self, folder: &str) -> Result<String> {
+        if self.folders.is_none() {
+            self.folders = Some(self.executor.db.list_all_folders()?);
+        }
+        self.folders
+            .iter()
+            .flatten()
+            .find(|f| f.id == folder || f.name == folder || f.disk_path.as_deref() == Some(folder))
+            .map(|f| f.id.clone())
+            .ok_or_else(|| invalid(format!("folder not found: {folder}")))
+    }
+}
+
+/// Build a new card from a full or partial card object.
+///
+/// Partial objects are layered over `BookCard::new`, so `metadata.title` is
+/// the only required field.
+fn card_from_value(value: &Value) -> Result<BookCard> {
+    if let Ok(card) = serde_json::from_value::<BookCard>(value.clone()) {
+        return Ok(card);
+    }
+    let title = value
+        .pointer("/metadata/title")
+        .and_then(Value::as_str)
+        .filter(|title| !title.trim().is_empty())
+        .ok_or_else(|| invalid("create_card requires metadata.title"))?;
+    let mut card = serde_json::to_value(BookCard::new(title))?;
+    merge_json(&mut card, value);
+    serde_json::from_value(card).map_err(|err| invalid(format!("invalid card: {err}")))
+}
+
+fn update_card(card: &mut BookCard, fields: &Value) -> Result<()> {
+    if !fields.is_object() {
+        return Err(invalid("fields must be a JSON object"));
+    }
+    let id = card.id;
+    let created_at = card.created_at;
+    let mut value = serde_json::to_value(&*card)?;
+    merge_json(&mut value, fields);
+    let mut updated: BookCard = serde_json::from_value(value)
+        .map_err(|err| invalid(format!("invalid fields for {id}: {err}")))?;
+    updated.id = id;
+    updated.created_at = created_at;
+    updated.touch();
+    *card = updated;
+    Ok(())
+}
+
+/// Recursively merge `patch` into `target` (objects merge, everything else replaces).
+pub fn merge_json(target: &mut Value, patch: &Value) {
+    match (target, patch) {
+        (Value::Object(target), Value::Object(patch)) => {
+            for (key, value) in patch {
+                merge_json(target.entry(key.clone()).or_insert(Value::Null), value);
+            }
+        }
+        (target, patch) => *target = patch.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> (tempfile::TempDir, Database) {
+        (
+            tempfile::tempdir().unwrap(),
+            Database::open_in_memory().unwrap(),
+        )
+    }
+
+    fn save(executor: &ActionExecutor<'_>, card: &BookCard) {
+        json_cards::save_card(executor.cards_dir(), card).unwrap();
+        executor.db.upsert_book(card).unwrap();
+    }
+
+    fn load(executor: &ActionExecutor<'_>, id: &Uuid) -> Option<BookCard> {
+        executor.load(id).unwrap()
+    }
+
+    #[test]
+    fn test_execute_logs_snapshot_and_reverse_restores() {
+        let (dir, db) = setup();
+        let executor = ActionExecutor::new(&db, dir.path());
+        let card = BookCard::new("Old Title");
+        save(&executor, &card);
+
+        let outcome = executor
+            .execute(
+                &OmniscopeAction::UpdateCard {
+                    id: card.id,
+                    fields: json!({ "metadata": { "title": "New Title" } }),
+                },
+                false,
+            )
+            .unwrap();
+        assert_eq!(
+            load(&executor, &card.id).unwrap().metadata.title,
+            "New Title"
+        );
+
+        let entry = db.get_action_log_entry(&outcome.log_id).unwrap().unwrap();
+        assert_eq!(entry.action_type, "update_card");
+        let snapshot: ActionSnapshot =
+            serde_json::from_value(entry.snapshot_before.unwrap()).unwrap();
+        assert_eq!(
+            snapshot.cards[0].before.as_ref().unwrap().metadata.title,
+            "Old Title"
+        );
+
+        executor.reverse(&outcome.log_id).unwrap();
+        assert_eq!(
+            load(&executor, &card.id).unwrap().metadata.title,
+            "Old Title"
+        );
+        assert!(
+            db.get_action_log_entry(&outcome.log_id)
+                .unwrap()
+                .unwrap()
+                .reversed
+        );
+        assert!(matches!(
+            executor.reverse(&outcome.log_id),
+            Err(ActionError::AlreadyReversed(_))
+        ));
+    }
+
+    #[test]
+    fn test_journaled_change_can_be_reversed() {
+        let (dir, db) = setup();
+        let executor = ActionExecutor::new(&db, dir.path());
+        let card = BookCard::new("Before");
+        save(&executor, &card);
+
+        let log_id = executor
+            .journal("merge_duplicates", json!({}), std::slice::from_ref(&card))
+            .unwrap();
+        let mut changed = card.clone();
+        changed.metadata.title = "After".to_string();
+        save(&executor, &changed);
+
+        executor.reverse(&log_id).unwrap();
+        assert_eq!(load(&executor, &card.id).unwrap().metadata.title, "Before");
+    }
+
+    #[test]
+    fn test_failed_transaction_changes_nothing() {
+        let (dir, db) = setup();
+        let executor = ActionExecutor::new(&db, dir.path());
+        let card = BookCard::new("Untouched");
+        save(&executor, &card);
+
+        let result = executor.execute(
+            &OmniscopeAction::Transaction {
+                actions: vec![
+                    OmniscopeAction::AddTag {
+                        book_id: card.id,
+                        tag: "rust".to_string(),
+                    },
+                    OmniscopeAction::DeleteCard { id: Uuid::new_v4() },
+                ],
+            },
+            false,
+        );
+
+        assert!(matches!(
+            result,
+            Err(ActionError::Core(OmniscopeError::BookNotFound(_)))
+        ));
+        assert!(
+            load(&executor, &card.id)
+                .unwrap()
+                .organization
+                .tags
+                .is_empty()
+        );
+        assert!(executor.history(10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_confirm_then_requires_confirmation() {
+        let (dir, db) = setup();
+        let executor = ActionExecutor::new(&db, dir.path());
+        let card = BookCard::new("Doomed");
+        save(&executor, &card);
+        let action = OmniscopeAction::ConfirmThen {
+            message: "Delete 1 book?".to_string(),
+            action: Box::new(OmniscopeAction::DeleteCard { id: card.id }),
+        };
+
+        assert!(matches!(
+            executor.execute(&action, false),
+            Err(ActionError::ConfirmationRequired(_))
+        ));
+        assert!(load(&executor, &card.id).is_some());
+
+        let outcome = executor.execute(&action, true).unwrap();
+        assert!(load(&executor, &card.id).is_none());
+
+        executor.reverse(&outcome.log_id).unwrap();
+        assert_eq!(load(&executor, &card.id).unwrap().metadata.title, "Doomed");
+    }
+
+    #[test]
+    fn test_create_and_batch_update_in_one_transaction() {
+        let (dir, db) = setup();
+        let executor = ActionExecutor::new(&db, dir.path());
+        let existing = BookCard::new("Existing");
+        save(&executor, &existing);
+        let new_id = Uuid::now_v7();
+
+        let outcome = executor
+            .execute(
+                &OmniscopeAction::Transaction {
+                    actions: vec![
+                        OmniscopeAction::CreateCard {
+                            card: json!({ "id": new_id, "metadata": { "title": "Created" } }),
+                        },
+                        OmniscopeAction::BatchUpdate {
+                            ids: vec![existing.id, new_id],
+                            fields: json!({ "organization": { "rating": 5 } }),
+                        },
+                    ],
+                },
+                false,
+            )
+            .unwrap();
+
+        assert_eq!(outcome.changes.len(), 2);
+        assert_eq!(
+            load(&executor, &new_id).unwrap().organization.rating,
+            Some(5)
+        );
+        assert_eq!(
+            load(&executor, &existing.id).unwrap().organization.rating,
+            Some(5)
+        );
+
+        executor.reverse(&outcome.log_id).unwrap();
+        assert!(load(&executor, &new_id).is_none());
+        assert_eq!(
+            load(&executor, &existing.id).unwrap().organization.rating,
+            None
+        );
+    }
+
+    #[test]
+    fn test_move_books_validates_folder() {
+        let (dir, db) = setup();
+        let executor = ActionExecutor::new(&db, dir.path());
+        let card = BookCard::new("Moving");
+        save(&executor, &card);
+        let folder_id = db.create_folder("Papers", None, None).unwrap();
+
+        let missing = OmniscopeAction::MoveBooks {
+            ids: vec![card.id],
+            to_library: None,
+            to_folder: Some("Nowhere".to_string()),
+        };
+        assert!(executor.validate(&missing, false).is_err());
+
+        executor
+            .execute(
+                &OmniscopeAction::MoveBooks {
+                    ids: vec![card.id],
+                    to_library: Some("research".to_string()),
+                    to_folder: Some("Papers".to_string()),
+                },
+                false,
+            )
+            .unwrap();
+        let moved = load(&executor, &card.id).unwrap();
+        assert_eq!(moved.organization.folders, vec![folder_id]);
+        assert_eq!(moved.organization.libraries, vec!["research"]);
+    }
+
+    #[test]
+    fn test_index_only_card_is_not_rewritten_from_the_index() {
+        let (dir, db) = setup();
+        let executor = ActionExecutor::new(&db, dir.path());
+        let card = BookCard::new("Indexed only");
+        db.upsert_book(&card).unwrap();
+
+        let result = executor.execute(
+            &OmniscopeAction::AddTag {
+                book_id: card.id,
+                tag: "lost".to_string(),
+            },
+            false,
+        );
+        assert!(result.is_err());
+        assert!(!executor.card_file_exists(&card.id));
+    }
+
+    #[test]
+    fn test_ui_actions_are_not_executable() {
+        let (dir, db) = setup();
+        let executor = ActionExecutor::new(&db, dir.path());
+        assert!(matches!(
+            executor.execute(&OmniscopeAction::OpenSearch { query: "x".into() }, false),
+            Err(ActionError::NotExecutable("open_search"))
+        ));
+    }
+}
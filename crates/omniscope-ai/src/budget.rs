@@ -0,0 +1,453 @@
+//! Token budgets.
+//!
+//! Providers built by [`provider::from_config`] are [`MeteredProvider`]s:
+//! every call is counted against the workstation's [`UserProfile`], and once
+//! the daily or monthly budget is spent calls are refused — or sent to the
+//! cheaper `ai.budget_fallback` backend when one is configured.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{Local, NaiveDate};
+use futures::StreamExt;
+
+use omniscope_core::UserProfile;
+use omniscope_core::config::AiConfig;
+
+use crate::memory::estimate_tokens;
+use crate::provider::{
+    self, AiProvider, ChatChunk, ChatRequest, ChatResponse, ChatStream, TokenUsage,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum BudgetError {
+    #[error("daily AI token budget exhausted ({used} of {budget} used); it resets tomorrow")]
+    Daily { used: u64, budget: u64 },
+    #[error("monthly AI token budget exhausted ({used} of {budget} used); it resets next month")]
+    Monthly { used: u64, budget: u64 },
+}
+
+/// Usage counters kept in a profile file.
+///
+/// The file is re-read on every check and update, so the TUI, CLI and
+/// server running on one machine share the same budget. Updates hold a
+/// lock on a sibling `.lock` file and replace the profile by renaming, so
+/// concurrent calls neither lose counts nor see a half-written file.
+#[derive(Debug, Clone)]
+pub struct TokenBudget {
+    path: PathBuf,
+}
+
+impl TokenBudget {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Budget of this workstation (`~/.config/omniscope/profile.json`).
+    pub fn workstation() -> Self {
+        Self::new(UserProfile::profile_path())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The profile with its counters rolled over to today.
+    pub fn profile(&self) -> Result<UserProfile> {
+        let mut profile = UserProfile::load_from(&self.path)
+            .with_context(|| format!("failed to read {}", self.path.display()))?;
+        profile.roll_over(today());
+        Ok(profile)
+    }
+
+    /// Tokens that may still be spent (`None` when unlimited), or a
+    /// [`BudgetError`] once either budget is used up.
+    pub fn check(&self) -> Result<Option<u64>> {
+        let profile = self.profile()?;
+        if profile.remaining_today() == Some(0) {
+            return Err(BudgetError::Daily {
+                used: profile.tokens_used_today,
+                budget: profile.daily_token_budget,
+            }
+            .into());
+        }
+        if profile.remaining_month() == Some(0) {
+            return Err(BudgetError::Monthly {
+                used: profile.tokens_used_month,
+                budget: profile.monthly_token_budget,
+            }
+            .into());
+        }
+        Ok(profile.remaining_tokens())
+    }
+
+    pub fn record(&self, tokens: u64) -> Result<()> {
+        if tokens == 0 {
+            return Ok(());
+        }
+        self.update(|profile| profile.record_tokens(tokens, today()))
+            .with_context(|| format!("failed to record token usage in {}", self.path.display()))
+    }
+
+    /// Read-modify-write the profile under the lock.
+    pub fn update(&self, change: impl FnOnce(&mut UserProfile)) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let lock = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(self.path.with_extension("lock"))?;
+        lock.lock()?;
+
+        let mut profile = UserProfile::load_from(&self.path)?;
+        change(&mut profile);
+        let tmp = self.path.with_extension("json.tmp");
+        profile.save_to(&tmp)?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+/// An [`AiProvider`] that enforces a [`TokenBudget`].
+pub struct MeteredProvider {
+    inner: Box<dyn AiProvider>,
+    /// Takes over chat once the budget is spent.
+    fallback: Option<Box<dyn AiProvider>>,
+    budget: TokenBudget,
+    /// The backend's default completion limit, so requests are only
+    /// shortened when the budget is tighter than it.
+    max_tokens: Option<u32>,
+}
+
+impl MeteredProvider {
+    pub fn new(inner: Box<dyn AiProvider>, budget: TokenBudget) -> Self {
+        Self {
+            inner,
+            fallback: None,
+            budget,
+            max_tokens: None,
+        }
+    }
+
+    pub fn with_fallback(mut self, fallback: Box<dyn AiProvider>) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    /// The configured backend metered against this workstation's budget.
+    pub fn from_config(config: &AiConfig) -> Result<Self> {
+        let mut metered = Self::new(
+            provider::backend_from_config(config)?,
+            TokenBudget::workstation(),
+        );
+        metered.max_tokens = Some(config.max_tokens);
+        if let Some(spec) = &config.budget_fallback {
+            let fallback = fallback_config(config, spec)?;
+            metered = metered.with_fallback(provider::backend_from_config(&fallback)?);
+        }
+        Ok(metered)
+    }
+
+    pub fn budget(&self) -> &TokenBudget {
+        &self.budget
+    }
+
+    /// Backend for the next chat call, with the tokens it may spend.
+    fn route(&self) -> Result<(&dyn AiProvider, Option<u64>)> {
+        match self.budget.check() {
+            Ok(remaining) => Ok((self.inner.as_ref(), remaining)),
+            Err(err) if err.is::<BudgetError>() => match &self.fallback {
+                Some(fallback) => Ok((fallback.as_ref(), None)),
+                None => Err(err),
+            },
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Cap the reply length at what is left of the budget once the prompt
+    /// is paid for.
+    fn clamp(&self, request: &mut ChatRequest, remaining: Option<u64>, prompt: u64) {
+        let Some(remaining) = remaining else {
+            return;
+        };
+        let remaining = remaining.saturating_sub(prompt).max(1);
+        let limit = request.max_tokens.or(self.max_tokens);
+        if limit.is_none_or(|limit| u64::from(limit) > remaining) {
+            request.max_tokens = Some(u32::try_from(remaining).unwrap_or(u32::MAX));
+        }
+    }
+}
+
+/// `"ollama:llama3.1"` → the config with provider and model replaced.
+fn fallback_config(config: &AiConfig, spec: &str) -> Result<AiConfig> {
+    let (provider, model) = spec
+        .split_once(':')
+        .with_context(|| format!("ai.budget_fallback '{spec}' should be 'provider:model'"))?;
+    Ok(AiConfig {
+        provider: provider.to_string(),
+        model: model.to_string(),
+        base_url: None,
+        ..config.clone()
+    })
+}
+
+fn prompt_tokens(request: &ChatRequest) -> u64 {
+    request
+        .messages
+        .iter()
+        .map(|message| estimate_tokens(&message.content) as u64)
+        .sum()
+}
+
+/// Tallies a streamed reply and records it when the stream is dropped, so
+/// replies the user interrupts are still counted.
+struct StreamMeter {
+    budget: TokenBudget,
+    prompt_tokens: u64,
+    completion_tokens: u64,
+    reported: Option<TokenUsage>,
+}
+
+impl StreamMeter {
+    fn observe(&mut self, chunk: &Result<ChatChunk>) {
+        match chunk {
+            Ok(ChatChunk::Delta(text)) => self.completion_tokens += estimate_tokens(text) as u64,
+            Ok(ChatChunk::Usage(usage)) => self.reported = Some(*usage),
+            Err(_) => {}
+        }
+    }
+}
+
+impl Drop for StreamMeter {
+    fn drop(&mut self) {
+        let tokens = match self.reported {
+            Some(usage) if usage.total() > 0 => u64::from(usage.total()),
+            _ => self.prompt_tokens + self.completion_tokens,
+        };
+        let _ = self.budget.record(tokens);
+    }
+}
+
+#[async_trait]
+impl AiProvider for MeteredProvider {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn model(&self) -> &str {
+        self.inner.model()
+    }
+
+    fn embedding_model(&self) -> &str {
+        self.inner.embedding_model()
+    }
+
+    async fn chat(&self, mut request: ChatRequest) -> Result<ChatResponse> {
+        let (backend, remaining) = self.route()?;
+        let prompt = prompt_tokens(&request);
+        self.clamp(&mut request, remaining, prompt);
+
+        let response = backend.chat(request).await?;
+        let used = match response.usage.total() {
+            0 => prompt + estimate_tokens(&response.content) as u64,
+            total => u64::from(total),
+        };
+        self.budget.record(used)?;
+        Ok(response)
+    }
+
+    async fn chat_stream(&self, mut request: ChatRequest) -> Result<ChatStream> {
+        let (backend, remaining) = self.route()?;
+        let prompt = prompt_tokens(&request);
+        self.clamp(&mut request, remaining, prompt);
+
+        let stream = backend.chat_stream(request).await?;
+        let mut meter = StreamMeter {
+            budget: self.budget.clone(),
+            prompt_tokens: prompt,
+            completion_tokens: 0,
+            reported: None,
+        };
+        Ok(Box::pin(stream.map(move |chunk| {
+            meter.observe(&chunk);
+            chunk
+        })))
+    }
+
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        // No fallback here: vectors from another model can't be compared
+        // with the ones already stored.
+        self.budget.check()?;
+        let vectors = self.inner.embed_batch(texts).await?;
+        let used = texts.iter().map(|text| estimate_tokens(text) as u64).sum();
+        self.budget.record(used)?;
+        Ok(vectors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::ChatMessage;
+
+    /// Answers every question with a fixed reply and usage.
+    struct Canned {
+        name: &'static str,
+        tokens: u32,
+    }
+
+    #[async_trait]
+    impl AiProvider for Canned {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn model(&self) -> &str {
+            self.name
+        }
+
+        fn embedding_model(&self) -> &str {
+            self.name
+        }
+
+        async fn chat(&self, request: ChatRequest) -> Result<ChatResponse> {
+            Ok(ChatResponse {
+                content: format!("{} (max {:?})", self.name, request.max_tokens),
+                usage: TokenUsage {
+                    prompt_tokens: 0,
+                    completion_tokens: self.tokens,
+                },
+            })
+        }
+
+        async fn chat_stream(&self, _request: ChatRequest) -> Result<ChatStream> {
+            let chunks = vec![
+                Ok(ChatChunk::Delta("hello".to_string())),
+                Ok(ChatChunk::Usage(TokenUsage {
+                    prompt_tokens: 0,
+                    completion_tokens: self.tokens,
+                })),
+            ];
+            Ok(Box::pin(futures::stream::iter(chunks)))
+        }
+
+        async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+            Ok(texts.iter().map(|_| vec![1.0]).collect())
+        }
+    }
+
+    fn budget_with(dir: &Path, daily: u64, used_today: u64) -> TokenBudget {
+        let budget = TokenBudget::new(dir.join("profile.json"));
+        UserProfile {
+            daily_token_budget: daily,
+            tokens_used_today: used_today,
+            tokens_used_month: used_today,
+            usage_date: Some(today()),
+            ..UserProfile::default()
+        }
+        .save_to(budget.path())
+        .unwrap();
+        budget
+    }
+
+    fn question() -> ChatRequest {
+        ChatRequest::new(vec![ChatMessage::user("hi")])
+    }
+
+    #[tokio::test]
+    async fn test_calls_are_recorded_and_refused_when_spent() {
+        let dir = tempfile::tempdir().unwrap();
+        let budget = budget_with(dir.path(), 1_000, 900);
+        let provider = MeteredProvider::new(
+            Box::new(Canned {
+                name: "paid",
+                tokens: 150,
+            }),
+            budget.clone(),
+        );
+
+        let reply = provider.chat(question()).await.unwrap();
+        assert_eq!(reply.content, "paid (max Some(99))");
+        assert_eq!(budget.profile().unwrap().tokens_used_today, 1_050);
+
+        let err = provider.chat(question()).await.unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<BudgetError>(),
+            Some(BudgetError::Daily { .. })
+        ));
+        assert!(provider.embed("text").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fallback_takes_over_when_spent() {
+        let dir = tempfile::tempdir().unwrap();
+        let budget = budget_with(dir.path(), 1_000, 1_000);
+        let provider = MeteredProvider::new(
+            Box::new(Canned {
+                name: "paid",
+                tokens: 10,
+            }),
+            budget.clone(),
+        )
+        .with_fallback(Box::new(Canned {
+            name: "local",
+            tokens: 10,
+        }));
+
+        let reply = provider.chat(question()).await.unwrap();
+        assert_eq!(reply.content, "local (max None)");
+    }
+
+    #[tokio::test]
+    async fn test_streamed_usage_is_recorded() {
+        let dir = tempfile::tempdir().unwrap();
+        let budget = budget_with(dir.path(), 0, 0);
+        let provider = MeteredProvider::new(
+            Box::new(Canned {
+                name: "paid",
+                tokens: 42,
+            }),
+            budget.clone(),
+        );
+
+        let stream = provider.chat_stream(question()).await.unwrap();
+        let chunks: Vec<_> = stream.collect().await;
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(budget.profile().unwrap().tokens_used_today, 42);
+    }
+
+    #[test]
+    fn test_concurrent_records_are_all_counted() {
+        let dir = tempfile::tempdir().unwrap();
+        let budget = budget_with(dir.path(), 0, 0);
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                let budget = budget.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..10 {
+                        budget.record(1).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        assert_eq!(budget.profile().unwrap().tokens_used_today, 80);
+    }
+
+    #[test]
+    fn test_fallback_config_parses_provider_and_model() {
+        let config = fallback_config(&AiConfig::default(), "ollama:llama3.1").unwrap();
+        assert_eq!(config.provider, "ollama");
+        assert_eq!(config.model, "llama3.1");
+        assert!(fallback_config(&AiConfig::default(), "ollama").is_err());
+    }
+}
@@ -0,0 +1,345 @@
+//! Book-aware chat: prompt assembly, suggested actions and per-book history.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use omniscope_core::{BookCard, LibraryMap, OmniscopeAction};
+
+use crate::provider::{ChatMessage, Role};
+
+/// Earlier turns replayed to the model with each question.
+const HISTORY_TURNS: usize = 20;
+/// Tags listed in the library overview.
+const MAP_TAGS: usize = 40;
+
+const SYSTEM_PROMPT: &str = "\
+You are Omniscope, an assistant embedded in a personal library manager.
+Answer questions about the user's books concisely, using the library
+overview and the selected book's card below.
+
+When a change to the library would help, propose it as an action in a
+```json fenced block: one object, or an array of objects, shaped like
+{\"type\": \"add_tag\", \"book_id\": \"<uuid>\", \"tag\": \"rust\"}.
+Available types: add_tag, remove_tag, update_card {id, fields},
+batch_update {ids, fields}, move_books {ids, to_library, to_folder},
+delete_card {id}. The user reviews every action before it is applied;
+never claim a change has been made.";
+
+/// One message of a stored conversation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatTurn {
+    pub role: Role,
+    pub content: String,
+    pub at: DateTime<Utc>,
+}
+
+impl ChatTurn {
+    pub fn new(role: Role, content: impl Into<String>) -> Self {
+        Self {
+            role,
+            content: content.into(),
+            at: Utc::now(),
+        }
+    }
+}
+
+/// The conversation about one book, stored as `{chats_dir}/{book_id}.json`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChatHistory {
+    pub book_id: Uuid,
+    pub turns: Vec<ChatTurn>,
+}
+
+impl ChatHistory {
+    pub fn path(chats_dir: &Path, book_id: &Uuid) -> PathBuf {
+        chats_dir.join(format!("{book_id}.json"))
+    }
+
+    /// Load a stored conversation; a missing file is an empty history.
+    pub fn load(chats_dir: &Path, book_id: Uuid) -> Result<Self> {
+        let path = Self::path(chats_dir, &book_id);
+        if !path.exists() {
+            return Ok(Self {
+                book_id,
+                turns: Vec::new(),
+            });
+        }
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, chats_dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(chats_dir)?;
+        let path = Self::path(chats_dir, &self.book_id);
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(tmp, path)?;
+        Ok(())
+    }
+}
+
+/// Assemble the request for `question`: system prompt with library and book
+/// context, the tail of the stored history, then the question itself.
+pub fn build_messages(
+    card: Option<&BookCard>,
+    map: Option<&LibraryMap>,
+    history: &[ChatTurn],
+    question: &str,
+) -> Vec<ChatMessage> {
+    let mut system = SYSTEM_PROMPT.to_string();
+    if let Some(map) = map {
+        system.push_str("\n\n## Library overview\n");
+        system.push_str(&library_map_context(map));
+    }
+    if let Some(card) = card {
+        system.push_str("\n\n## Selected book\n");
+        system.push_str(&book_context(card));
+    }
+
+    let mut messages = vec![ChatMessage::system(system)];
+    let skip = history.len().saturating_sub(HISTORY_TURNS);
+    messages.extend(history[skip..].iter().map(|turn| ChatMessage {
+        role: turn.role,
+        content: turn.content.clone(),
+    }));
+    messages.push(ChatMessage::user(question));
+    messages
+}
+
+/// The parts of a card worth showing a model: metadata, abstract, notes.
+pub fn book_context(card: &BookCard) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "id: {}", card.id);
+    let _ = writeln!(out, "title: {}", card.metadata.title);
+    if let Some(subtitle) = &card.metadata.subtitle {
+        let _ = writeln!(out, "subtitle: {subtitle}");
+    }
+    if !card.metadata.authors.is_empty() {
+        let _ = writeln!(out, "authors: {}", card.metadata.authors.join(", "));
+    }
+    if let Some(year) = card.metadata.year {
+        let _ = writeln!(out, "year: {year}");
+    }
+    if let Some(publisher) = &card.metadata.publisher {
+        let _ = writeln!(out, "publisher: {publisher}");
+    }
+    if let Some(ids) = &card.identifiers {
+        if let Some(doi) = &ids.doi {
+            let _ = writeln!(out, "doi: {doi}");
+        }
+        if let Some(arxiv) = &ids.arxiv_id {
+            let _ = writeln!(out, "arxiv: {arxiv}");
+        }
+    }
+    let org = &card.organization;
+    if !org.tags.is_empty() {
+        let _ = writeln!(out, "tags: {}", org.tags.join(", "));
+    }
+    if !org.libraries.is_empty() {
+        let _ = writeln!(out, "libraries: {}", org.libraries.join(", "));
+    }
+    let _ = writeln!(out, "status: {}", org.read_status);
+    if let Some(rating) = org.rating {
+        let _ = writeln!(out, "rating: {rating}/5");
+    }
+    if let Some(tldr) = &card.ai.tldr {
+        let _ = writeln!(out, "tl;dr: {tldr}");
+    }
+    if let Some(summary) = &card.ai.summary {
+        let _ = writeln!(out, "abstract: {summary}");
+    }
+    if !card.ai.key_topics.is_empty() {
+        let _ = writeln!(out, "key topics: {}", card.ai.key_topics.join(", "));
+    }
+    if !card.notes.is_empty() {
+        let _ = writeln!(out, "notes:");
+        for note in &card.notes {
+            let _ = writeln!(out, "- ({}) {}", note.author, note.text.trim());
+        }
+    }
+    out
+}
+
+/// A compact textual rendering of the library for the system prompt.
+///
+/// Lists every book in `map`; trim it with
+/// [`trim_to_budget`](crate::memory::trim_to_budget) first.
+pub fn library_map_context(map: &LibraryMap) -> String {
+    let stats = &map.stats;
+    let mut out = format!(
+        "{} books ({} unread, {} reading, {} read)\n",
+        stats.total, stats.unread, stats.reading, stats.read
+    );
+
+    let mut tags: Vec<(&String, &u32)> = map.tag_cloud.iter().collect();
+    tags.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    if !tags.is_empty() {
+        let tags: Vec<String> = tags
+            .into_iter()
+            .take(MAP_TAGS)
+            .map(|(tag, count)| format!("{tag}({count})"))
+            .collect();
+        let _ = writeln!(out, "tags: {}", tags.join(" "));
+    }
+
+    for book in &map.books {
+        let _ = write!(out, "{} | {}", book.id, book.title);
+        if !book.authors.is_empty() {
+            let _ = write!(out, " | {}", book.authors.join(", "));
+        }
+        if let Some(year) = book.year {
+            let _ = write!(out, " | {year}");
+        }
+        if !book.tags.is_empty() {
+            let _ = write!(out, " | #{}", book.tags.join(" #"));
+        }
+        out.push('\n');
+    }
+    if stats.total > map.books.len() {
+        let _ = writeln!(out, "… and {} more", stats.total - map.books.len());
+    }
+    out
+}
+
+/// Pull `OmniscopeAction`s out of ```json blocks in a reply.
+///
+/// Blocks that aren't valid actions are ignored, so ordinary JSON examples
+/// in an answer don't turn into suggestions.
+pub fn extract_actions(reply: &str) -> Vec<OmniscopeAction> {
+    let mut actions = Vec::new();
+    let mut rest = reply;
+    while let Some(start) = rest.find("```") {
+        let after_fence = &rest[start + 3..];
+        let Some(body_start) = after_fence.find('\n') else {
+            break;
+        };
+        let lang = after_fence[..body_start].trim();
+        let body = &after_fence[body_start + 1..];
+        let Some(end) = body.find("```") else {
+            break;
+        };
+        if lang.is_empty() || lang.eq_ignore_ascii_case("json") {
+            actions.extend(parse_actions(&body[..end]));
+        }
+        rest = &body[end + 3..];
+    }
+    actions
+}
+
+fn parse_actions(block: &str) -> Vec<OmniscopeAction> {
+    match serde_json::from_str::<Value>(block.trim()) {
+        Ok(Value::Array(items)) => items
+            .into_iter()
+            .filter_map(|item| serde_json::from_value(item).ok())
+            .collect(),
+        Ok(value) => serde_json::from_value(value).into_iter().collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// One-line label for a suggested action.
+pub fn describe_action(action: &OmniscopeAction) -> String {
+    match action {
+        OmniscopeAction::AddTag { tag, .. } => format!("add tag #{tag}"),
+        OmniscopeAction::RemoveTag { tag, .. } => format!("remove tag #{tag}"),
+        OmniscopeAction::UpdateCard { fields, .. } => {
+            format!("update {}", field_names(fields))
+        }
+        OmniscopeAction::BatchUpdate { ids, fields } => {
+            format!("update {} on {} books", field_names(fields), ids.len())
+        }
+        OmniscopeAction::MoveBooks {
+            ids,
+            to_library,
+            to_folder,
+        } => {
+            let target = to_folder.as_ref().or(to_library.as_ref());
+            format!(
+                "move {} books to {}",
+                ids.len(),
+                target.map(String::as_str).unwrap_or("?")
+            )
+        }
+        OmniscopeAction::DeleteCard { .. } => "delete card".to_string(),
+        OmniscopeAction::CreateCard { card } => format!(
+            "add \"{}\"",
+            card.pointer("/metadata/title")
+                .and_then(Value::as_str)
+                .unwrap_or("card")
+        ),
+        OmniscopeAction::Transaction { actions } => format!("{} changes", actions.len()),
+        OmniscopeAction::ConfirmThen { message, .. } => message.clone(),
+        other => other.type_name().replace('_', " "),
+    }
+}
+
+fn field_names(fields: &Value) -> String {
+    match fields.as_object() {
+        Some(object) if !object.is_empty() => object.keys().cloned().collect::<Vec<_>>().join(", "),
+        _ => "fields".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_actions_from_fenced_blocks() {
+        let id = Uuid::nil();
+        let reply = format!(
+            "Sure.\n```json\n{{\"type\": \"add_tag\", \"book_id\": \"{id}\", \"tag\": \"rust\"}}\n```\n\
+             Example output:\n```json\n{{\"title\": \"not an action\"}}\n```\n\
+             ```\n[{{\"type\": \"remove_tag\", \"book_id\": \"{id}\", \"tag\": \"old\"}}]\n```"
+        );
+
+        let actions = extract_actions(&reply);
+
+        assert_eq!(actions.len(), 2);
+        assert_eq!(describe_action(&actions[0]), "add tag #rust");
+        assert_eq!(actions[1].type_name(), "remove_tag");
+    }
+
+    #[test]
+    fn test_build_messages_includes_card_and_history() {
+        let mut card = BookCard::new("The Rust Programming Language");
+        card.ai.summary = Some("An introduction to Rust.".to_string());
+        let history = vec![
+            ChatTurn::new(Role::User, "Hi"),
+            ChatTurn::new(Role::Assistant, "Hello!"),
+        ];
+
+        let messages = build_messages(Some(&card), None, &history, "What is it about?");
+
+        assert_eq!(messages.len(), 4);
+        assert_eq!(messages[0].role, Role::System);
+        assert!(
+            messages[0]
+                .content
+                .contains("abstract: An introduction to Rust.")
+        );
+        assert_eq!(messages[2].content, "Hello!");
+        assert_eq!(messages[3], ChatMessage::user("What is it about?"));
+    }
+
+    #[test]
+    fn test_history_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let id = Uuid::now_v7();
+        assert!(ChatHistory::load(dir.path(), id).unwrap().turns.is_empty());
+
+        let mut history = ChatHistory::load(dir.path(), id).unwrap();
+        history
+            .turns
+            .push(ChatTurn::new(Role::User, "Summarise chapter 3"));
+        history.save(dir.path()).unwrap();
+
+        let loaded = ChatHistory::load(dir.path(), id).unwrap();
+        assert_eq!(loaded.turns, history.turns);
+    }
+}
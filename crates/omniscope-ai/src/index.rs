@@ -0,0 +1,411 @@
+//! AI indexing: summary, TL;DR, key topics, difficulty and table of contents
+//! generated from the full text of a card's file.
+//!
+//! Long texts are split into chunks; each sampled chunk is condensed into
+//! notes, and the notes are turned into the index in one final call.
+
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use chrono::Utc;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use omniscope_core::storage::json_cards;
+use omniscope_core::{BookCard, Database, TocEntry};
+use omniscope_science::enrichment::{BookCardMergeExt, MetadataSource, PartialMetadata};
+use omniscope_science::fulltext::extract_card_text;
+
+use crate::budget::BudgetError;
+use crate::provider::{AiProvider, ChatMessage, ChatRequest};
+
+/// Bumped when the prompts or fields change, so cards are indexed again.
+pub const INDEX_VERSION: u32 = 1;
+
+/// Characters per chunk, roughly 3k tokens.
+const CHUNK_CHARS: usize = 12_000;
+/// Chunks read per book; longer books are sampled evenly.
+const MAX_CHUNKS: usize = 12;
+const NOTES_MAX_TOKENS: u32 = 400;
+const MAX_TOPICS: usize = 10;
+
+const NOTES_PROMPT: &str = "\
+You are reading one part of a longer document. Write compact notes on
+what this part covers: main ideas, defined terms, chapter or section
+headings with page numbers if visible. At most 150 words, no preamble.";
+
+const INDEX_PROMPT: &str = "\
+You index documents for a personal library. From the material below,
+reply with one JSON object and nothing else:
+{\"summary\": \"one paragraph, 80-150 words\",
+ \"tldr\": \"one sentence\",
+ \"key_topics\": [\"3-10 short topic names\"],
+ \"difficulty\": \"beginner\" | \"intermediate\" | \"advanced\",
+ \"table_of_contents\": [{\"chapter\": 1, \"title\": \"...\", \"page\": 12}]}
+Leave table_of_contents empty if the material shows no chapter structure;
+use null for unknown pages.";
+
+/// Cards with a file that were never indexed, or indexed by older prompts.
+pub fn needs_index(card: &BookCard) -> bool {
+    card.file.is_some() && card.ai.index_version.is_none_or(|v| v < INDEX_VERSION)
+}
+
+/// Split `text` at paragraph breaks into chunks of at most `max_chars`.
+pub fn chunk_text(text: &str, max_chars: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    for paragraph in text.split("\n\n").map(str::trim).filter(|p| !p.is_empty()) {
+        if !current.is_empty() && current.len() + paragraph.len() + 2 > max_chars {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if paragraph.len() > max_chars {
+            let mut rest = paragraph;
+            while rest.len() > max_chars {
+                let mut cut = max_chars;
+                while !rest.is_char_boundary(cut) {
+                    cut -= 1;
+                }
+                chunks.push(rest[..cut].to_string());
+                rest = &rest[cut..];
+            }
+            current.push_str(rest);
+            continue;
+        }
+        if !current.is_empty() {
+            current.push_str("\n\n");
+        }
+        current.push_str(paragraph);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// At most `max` chunks spread evenly over the document, first and last included.
+fn sample_chunks(chunks: Vec<String>, max: usize) -> Vec<(usize, String)> {
+    let total = chunks.len();
+    if total <= max {
+        return chunks.into_iter().enumerate().collect();
+    }
+    let picks: Vec<usize> = (0..max).map(|i| i * (total - 1) / (max - 1)).collect();
+    chunks
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| picks.contains(i))
+        .collect()
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct IndexReply {
+    summary: Option<String>,
+    tldr: Option<String>,
+    key_topics: Vec<String>,
+    difficulty: Option<String>,
+    table_of_contents: Vec<TocReply>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TocReply {
+    #[serde(default)]
+    chapter: Option<u32>,
+    title: String,
+    #[serde(default)]
+    page: Option<u32>,
+}
+
+fn parse_index_reply(content: &str) -> Result<IndexReply> {
+    let start = content.find('{');
+    let end = content.rfind('}');
+    let json = match (start, end) {
+        (Some(start), Some(end)) if start < end => &content[start..=end],
+        _ => bail!("model did not return the index as JSON"),
+    };
+    serde_json::from_str(json).context("model returned a malformed index")
+}
+
+fn card_heading(card: &BookCard) -> String {
+    let mut heading = format!("Title: {}", card.metadata.title);
+    if !card.metadata.authors.is_empty() {
+        heading.push_str(&format!("\nAuthors: {}", card.metadata.authors.join(", ")));
+    }
+    if let Some(year) = card.metadata.year {
+        heading.push_str(&format!("\nYear: {year}"));
+    }
+    heading
+}
+
+/// Fill the card's AI fields from `text` and stamp `indexed_at`/`index_version`.
+///
+/// Summary and TL;DR go through the metadata merge as AI-inferred, so an
+/// abstract from CrossRef or arXiv is kept.
+pub async fn index_text(provider: &dyn AiProvider, card: &mut BookCard, text: &str) -> Result<()> {
+    let chunks = chunk_text(text, CHUNK_CHARS);
+    if chunks.is_empty() {
+        bail!("no text to index");
+    }
+    let heading = card_heading(card);
+
+    let material = if chunks.len() == 1 {
+        chunks.into_iter().next().unwrap_or_default()
+    } else {
+        let total = chunks.len();
+        let mut notes = Vec::new();
+        for (i, chunk) in sample_chunks(chunks, MAX_CHUNKS) {
+            let request = ChatRequest {
+                messages: vec![
+                    ChatMessage::system(NOTES_PROMPT),
+                    ChatMessage::user(format!("{heading}\nPart {} of {total}:\n\n{chunk}", i + 1)),
+                ],
+                max_tokens: Some(NOTES_MAX_TOKENS),
+                ..ChatRequest::default()
+            };
+            let reply = provider.chat(request).await?;
+            notes.push(format!(
+                "Part {} of {total}:\n{}",
+                i + 1,
+                reply.content.trim()
+            ));
+        }
+        notes.join("\n\n")
+    };
+
+    let request = ChatRequest {
+        messages: vec![
+            ChatMessage::system(INDEX_PROMPT),
+            ChatMessage::user(format!("{heading}\n\n{material}")),
+        ],
+        json: true,
+        ..ChatRequest::default()
+    };
+    let reply = parse_index_reply(&provider.chat(request).await?.content)?;
+    apply_index(card, reply);
+    Ok(())
+}
+
+fn apply_index(card: &mut BookCard, reply: IndexReply) {
+    card.merge_metadata(
+        PartialMetadata {
+            abstract_text: reply.summary,
+            tldr: reply.tldr,
+            ..PartialMetadata::default()
+        },
+        MetadataSource::AiInferred,
+    );
+
+    let topics: Vec<String> = reply
+        .key_topics
+        .iter()
+        .map(|topic| topic.trim().to_string())
+        .filter(|topic| !topic.is_empty())
+        .take(MAX_TOPICS)
+        .collect();
+    if !topics.is_empty() {
+        card.ai.key_topics = topics;
+    }
+    if let Some(difficulty) = reply.difficulty {
+        let difficulty = difficulty.trim().to_lowercase();
+        if !difficulty.is_empty() {
+            card.ai.difficulty = Some(difficulty);
+        }
+    }
+    card.ai.table_of_contents = reply
+        .table_of_contents
+        .into_iter()
+        .filter(|entry| !entry.title.trim().is_empty())
+        .enumerate()
+        .map(|(i, entry)| TocEntry {
+            chapter: entry.chapter.unwrap_or(i as u32 + 1),
+            title: entry.title.trim().to_string(),
+            page: entry.page,
+        })
+        .collect();
+
+    let now = Utc::now();
+    card.ai.indexed_at = Some(now);
+    card.ai.index_version = Some(INDEX_VERSION);
+    card.updated_at = now;
+}
+
+#[derive(Debug, Default)]
+pub struct IndexReport {
+    pub indexed: Vec<Uuid>,
+    /// Cards without a file, or whose file has no extractable text.
+    pub skipped: Vec<(Uuid, String)>,
+    pub failed: Vec<(Uuid, String)>,
+    /// Set when the token budget ran out and the remaining cards were left.
+    pub stopped: Option<String>,
+}
+
+/// Ids of the cards in `cards_dir` that [`needs_index`].
+pub fn cards_needing_index(cards_dir: &Path) -> Result<Vec<Uuid>> {
+    Ok(json_cards::list_cards(cards_dir)?
+        .iter()
+        .filter(|card| needs_index(card))
+        .map(|card| card.id)
+        .collect())
+}
+
+/// Index each card in turn, saving it as soon as it is done.
+///
+/// `on_progress` gets the position and title of the card about to be read.
+pub async fn index_cards(
+    db: &Database,
+    cards_dir: &Path,
+    provider: &dyn AiProvider,
+    ids: &[Uuid],
+    mut on_progress: impl FnMut(usize, &str),
+) -> Result<IndexReport> {
+    let mut report = IndexReport::default();
+    for (position, id) in ids.iter().enumerate() {
+        let Ok(mut card) = json_cards::load_card_by_id(cards_dir, id) else {
+            report.skipped.push((*id, "card not found".to_string()));
+            continue;
+        };
+        on_progress(position, &card.metadata.title);
+
+        let text = match extract_card_text(&card) {
+            Ok(Some(text)) if !text.trim().is_empty() => text,
+            Ok(Some(_)) => {
+                report.skipped.push((*id, "file has no text".to_string()));
+                continue;
+            }
+            Ok(None) => {
+                report.skipped.push((*id, "no readable file".to_string()));
+                continue;
+            }
+            Err(err) => {
+                report.failed.push((*id, err.to_string()));
+                continue;
+            }
+        };
+
+        match index_text(provider, &mut card, &text).await {
+            Ok(()) => {
+                json_cards::save_card(cards_dir, &card)?;
+                db.upsert_book(&card)?;
+                report.indexed.push(*id);
+            }
+            Err(err) if err.is::<BudgetError>() => {
+                report.stopped = Some(err.to_string());
+                break;
+            }
+            Err(err) => report.failed.push((*id, format!("{err:#}"))),
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::provider::{ChatResponse, ChatStream, TokenUsage};
+    use async_trait::async_trait;
+
+    /// Replies with notes to note requests and a fixed index to the rest.
+    #[derive(Default)]
+    struct ScriptedIndexer {
+        requests: Mutex<Vec<ChatRequest>>,
+    }
+
+    #[async_trait]
+    impl AiProvider for ScriptedIndexer {
+        fn name(&self) -> &str {
+            "test"
+        }
+
+        fn model(&self) -> &str {
+            "test"
+        }
+
+        fn embedding_model(&self) -> &str {
+            "test"
+        }
+
+        async fn chat(&self, request: ChatRequest) -> Result<ChatResponse> {
+            let content = if request.json {
+                r#"Sure: {"summary": "A book about ownership.", "tldr": "Ownership, explained.",
+                   "key_topics": ["ownership", " borrowing ", ""], "difficulty": "Intermediate",
+                   "table_of_contents": [{"title": "Basics", "page": 1}, {"chapter": 2, "title": "Lifetimes"}]}"#
+                    .to_string()
+            } else {
+                "notes".to_string()
+            };
+            self.requests.lock().unwrap().push(request);
+            Ok(ChatResponse {
+                content,
+                usage: TokenUsage::default(),
+            })
+        }
+
+        async fn chat_stream(&self, _request: ChatRequest) -> Result<ChatStream> {
+            anyhow::bail!("streaming is not supported")
+        }
+
+        async fn embed_batch(&self, _texts: &[String]) -> Result<Vec<Vec<f32>>> {
+            anyhow::bail!("embeddings are not supported")
+        }
+    }
+
+    #[test]
+    fn test_chunk_text_respects_paragraphs_and_limit() {
+        let text = "alpha alpha\n\nbeta beta\n\n".to_string() + &"g".repeat(25);
+        let chunks = chunk_text(&text, 22);
+        assert_eq!(chunks[0], "alpha alpha\n\nbeta beta");
+        assert_eq!(chunks[1], "g".repeat(22));
+        assert_eq!(chunks[2], "ggg");
+        assert!(chunks.iter().all(|chunk| chunk.len() <= 22));
+    }
+
+    #[test]
+    fn test_sample_chunks_keeps_first_and_last() {
+        let chunks: Vec<String> = (0..30).map(|i| i.to_string()).collect();
+        let sampled = sample_chunks(chunks, 4);
+        let picked: Vec<usize> = sampled.iter().map(|(i, _)| *i).collect();
+        assert_eq!(picked, vec![0, 9, 19, 29]);
+    }
+
+    #[tokio::test]
+    async fn test_index_text_fills_fields() {
+        let provider = ScriptedIndexer::default();
+        let mut card = BookCard::new("Rust Ownership");
+        let text = "Chapter one.\n\n".repeat(2_000);
+
+        index_text(&provider, &mut card, &text).await.unwrap();
+
+        assert_eq!(card.ai.summary.as_deref(), Some("A book about ownership."));
+        assert_eq!(card.ai.tldr.as_deref(), Some("Ownership, explained."));
+        assert_eq!(card.ai.key_topics, vec!["ownership", "borrowing"]);
+        assert_eq!(card.ai.difficulty.as_deref(), Some("intermediate"));
+        assert_eq!(card.ai.table_of_contents.len(), 2);
+        assert_eq!(card.ai.table_of_contents[0].chapter, 1);
+        assert_eq!(card.ai.index_version, Some(INDEX_VERSION));
+        assert!(card.ai.indexed_at.is_some());
+
+        let requests = provider.requests.lock().unwrap();
+        assert!(requests.len() > 2, "long text is condensed chunk by chunk");
+        assert!(requests.last().unwrap().json);
+    }
+
+    #[tokio::test]
+    async fn test_index_keeps_publisher_abstract() {
+        let provider = ScriptedIndexer::default();
+        let mut card = BookCard::new("Attention Is All You Need");
+        card.ai.summary = Some(
+            "The dominant sequence transduction models are based on complex recurrent networks."
+                .to_string(),
+        );
+        card.metadata_sources
+            .insert("ai.summary".to_string(), "crossref".to_string());
+
+        index_text(&provider, &mut card, "Short text.")
+            .await
+            .unwrap();
+
+        assert!(card.ai.summary.unwrap().starts_with("The dominant"));
+        assert_eq!(card.ai.key_topics.len(), 2);
+    }
+}
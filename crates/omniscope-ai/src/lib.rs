@@ -1,14 +1,10 @@
-//! Omniscope AI — AI provider traits, action executor, memory management.
-//! Stub for Phase 4 implementation.
+//! Omniscope AI — AI provider traits, token budgets, action executor, chat,
+//! memory management, full-text indexing, semantic search.
 
-pub mod provider {
-    //! AI provider abstraction layer. Full implementation in Phase 4.
-}
-
-pub mod actions {
-    //! OmniscopeAction executor. Full implementation in Phase 4.
-}
-
-pub mod memory {
-    //! Library Map generation and caching. Full implementation in Phase 4.
-}
+pub mod actions;
+pub mod budget;
+pub mod chat;
+pub mod index;
+pub mod memory;
+pub mod provider;
+pub mod semantic;
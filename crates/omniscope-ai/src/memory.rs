@@ -0,0 +1,618 @@
+//! Library map generation and caching.
+//!
+//! The [`LibraryMap`] is the library overview sent as context with AI and
+//! MCP requests. [`LibraryMapCache`] keeps the per-book entries the map is
+//! aggregated from, both in memory and in `.libr/cache/library_map.json`.
+//! Single-card changes are applied in place; the database is only re-read
+//! when the cache has expired or the library changed behind its back.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use omniscope_core::config::AiConfig;
+use omniscope_core::sync::LibraryEvent;
+use omniscope_core::{
+    BookCard, BookSummaryCompact, Database, FileFormat, LibraryBrief, LibraryMap, LibraryMapEntry,
+    LibraryRoot, LibraryStats,
+};
+
+use crate::actions::CardChange;
+
+/// Used when `ai.library_map_cache` can't be parsed.
+const DEFAULT_TTL: Duration = Duration::from_secs(60 * 60);
+const CACHE_VERSION: u32 = 1;
+const LIBRARY_TOP_TAGS: usize = 5;
+/// Tag cloud entries kept when trimming to a budget.
+const BUDGET_TAGS: usize = 100;
+
+/// Rough token count used for budgeting, about four bytes per token.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.len().div_ceil(4)
+}
+
+/// Parse a cache lifetime such as `"90s"`, `"15m"`, `"1h"` or `"2d"`.
+///
+/// A bare number is seconds; `"0"` or `"off"` disables the on-disk cache.
+pub fn parse_ttl(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("off") {
+        return Some(Duration::ZERO);
+    }
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: u64 = number.parse().ok()?;
+    let unit_secs = match unit.trim() {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return None,
+    };
+    Some(Duration::from_secs(number.checked_mul(unit_secs)?))
+}
+
+/// Drop the least relevant parts of `map` until it fits `token_budget`.
+///
+/// Stats and library briefs are always kept; the tag cloud is cut to the
+/// most used tags and books are kept in order (most used first) while they
+/// fit.
+pub fn trim_to_budget(map: &mut LibraryMap, token_budget: usize) {
+    if map.tag_cloud.len() > BUDGET_TAGS {
+        let mut tags: Vec<(String, u32)> = map.tag_cloud.drain().collect();
+        tags.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        tags.truncate(BUDGET_TAGS);
+        map.tag_cloud = tags.into_iter().collect();
+    }
+
+    let books = std::mem::take(&mut map.books);
+    let mut used = estimate_tokens(&serde_json::to_string(map).unwrap_or_default());
+    for book in books {
+        let cost = estimate_tokens(&serde_json::to_string(&book).unwrap_or_default()) + 1;
+        if used + cost > token_budget {
+            break;
+        }
+        used += cost;
+        map.books.push(book);
+    }
+}
+
+/// On-disk form of the cache.
+#[derive(Deserialize)]
+struct CacheFile {
+    version: u32,
+    built_at: DateTime<Utc>,
+    #[serde(default)]
+    fingerprint: Option<String>,
+    entries: Vec<LibraryMapEntry>,
+}
+
+#[derive(Serialize)]
+struct CacheFileRef<'a> {
+    version: u32,
+    built_at: DateTime<Utc>,
+    fingerprint: Option<&'a str>,
+    entries: Vec<&'a LibraryMapEntry>,
+}
+
+#[derive(Default)]
+struct LibraryTally {
+    book_count: u32,
+    unread_count: u32,
+    tags: HashMap<String, u32>,
+}
+
+/// Entries plus the aggregates derived from them, kept in step.
+struct MapState {
+    built_at: DateTime<Utc>,
+    /// Database fingerprint the entries match, advanced with each in-place
+    /// edit; `None` when an edit can't be accounted for.
+    fingerprint: Option<String>,
+    entries: HashMap<Uuid, LibraryMapEntry>,
+    stats: LibraryStats,
+    tag_cloud: HashMap<String, u32>,
+    libraries: HashMap<String, LibraryTally>,
+}
+
+impl MapState {
+    fn new(
+        built_at: DateTime<Utc>,
+        fingerprint: Option<String>,
+        entries: Vec<LibraryMapEntry>,
+    ) -> Self {
+        let mut state = Self {
+            built_at,
+            fingerprint,
+            entries: HashMap::with_capacity(entries.len()),
+            stats: LibraryStats::default(),
+            tag_cloud: HashMap::new(),
+            libraries: HashMap::new(),
+        };
+        for entry in entries {
+            state.insert(entry);
+        }
+        state
+    }
+
+    fn insert(&mut self, entry: LibraryMapEntry) {
+        if let Some(old) = self.entries.remove(&entry.book.id) {
+            self.count(&old, false);
+        }
+        self.count(&entry, true);
+        self.entries.insert(entry.book.id, entry);
+    }
+
+    fn remove(&mut self, id: &Uuid) -> bool {
+        match self.entries.remove(id) {
+            Some(old) => {
+                self.count(&old, false);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Move the fingerprint to what the database should report after an
+    /// in-place edit: the current entry count and, for an upsert, the
+    /// card's `updated_at` if it is the newest (see
+    /// [`Database::library_fingerprint`]). Anything else that touched the
+    /// database since shows up as a mismatch on the next refresh.
+    fn advance_fingerprint(&mut self, updated_at: Option<&str>) {
+        self.fingerprint = self.fingerprint.as_deref().and_then(|known| {
+            let (_, latest) = known.split_once(':')?;
+            let latest = match updated_at {
+                Some(updated_at) if updated_at > latest => updated_at,
+                _ => latest,
+            };
+            Some(format!("{}:{latest}", self.entries.len()))
+        });
+    }
+
+    /// Add (or subtract) one entry's contribution to the aggregates.
+    fn count(&mut self, entry: &LibraryMapEntry, add: bool) {
+        let stats = &mut self.stats;
+        bump(&mut stats.total, add);
+        let unread = entry.book.status == "unread";
+        match entry.book.status.as_str() {
+            "unread" => bump(&mut stats.unread, add),
+            "reading" => bump(&mut stats.reading, add),
+            "read" => bump(&mut stats.read, add),
+            "dnf" => bump(&mut stats.dnf, add),
+            _ => {}
+        }
+        if entry.has_file {
+            bump(&mut stats.with_file, add);
+        }
+        if entry.has_summary {
+            bump(&mut stats.with_summary, add);
+        }
+        match entry.format {
+            Some(FileFormat::Pdf) => bump(&mut stats.pdf, add),
+            Some(FileFormat::Epub) => bump(&mut stats.epub, add),
+            Some(_) => bump(&mut stats.other_format, add),
+            None => {}
+        }
+
+        for tag in &entry.book.tags {
+            bump_key(&mut self.tag_cloud, tag, add);
+        }
+        for name in &entry.libraries {
+            let tally = self.libraries.entry(name.clone()).or_default();
+            bump32(&mut tally.book_count, add);
+            if unread {
+                bump32(&mut tally.unread_count, add);
+            }
+            for tag in &entry.book.tags {
+                bump_key(&mut tally.tags, tag, add);
+            }
+            if tally.book_count == 0 {
+                self.libraries.remove(name);
+            }
+        }
+    }
+
+    fn to_map(&self) -> LibraryMap {
+        let mut books: Vec<_> = self
+            .entries
+            .values()
+            .map(|entry| entry.book.clone())
+            .collect();
+        books.sort_by(|a, b| {
+            b.frecency
+                .total_cmp(&a.frecency)
+                .then_with(|| a.title.cmp(&b.title))
+        });
+
+        let libraries = self
+            .libraries
+            .iter()
+            .map(|(name, tally)| {
+                let mut tags: Vec<(&String, &u32)> = tally.tags.iter().collect();
+                tags.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+                let brief = LibraryBrief {
+                    book_count: tally.book_count,
+                    unread_count: tally.unread_count,
+                    top_tags: tags
+                        .into_iter()
+                        .take(LIBRARY_TOP_TAGS)
+                        .map(|(tag, _)| tag.clone())
+                        .collect(),
+                };
+                (name.clone(), brief)
+            })
+            .collect();
+
+        LibraryMap {
+            generated_at: self.built_at.to_rfc3339(),
+            stats: self.stats.clone(),
+            libraries,
+            tag_cloud: self.tag_cloud.clone(),
+            books,
+        }
+    }
+}
+
+fn bump(n: &mut usize, add: bool) {
+    *n = if add { *n + 1 } else { n.saturating_sub(1) };
+}
+
+fn bump32(n: &mut u32, add: bool) {
+    *n = if add { *n + 1 } else { n.saturating_sub(1) };
+}
+
+fn bump_key(counts: &mut HashMap<String, u32>, key: &str, add: bool) {
+    if add {
+        *counts.entry(key.to_string()).or_insert(0) += 1;
+    } else if let Some(count) = counts.get_mut(key) {
+        *count = count.saturating_sub(1);
+        if *count == 0 {
+            counts.remove(key);
+        }
+    }
+}
+
+/// Cached [`LibraryMap`] for one library.
+///
+/// Feed it card changes with [`card_upserted`](Self::card_upserted),
+/// [`apply_changes`](Self::apply_changes) or
+/// [`apply_event`](Self::apply_event); [`map`](Self::map) rebuilds from the
+/// database when the cache is older than its TTL or the database was
+/// modified by someone who didn't report the change. Pending in-place
+/// changes are written back on [`flush`](Self::flush) and on drop.
+pub struct LibraryMapCache {
+    path: PathBuf,
+    ttl: Duration,
+    state: Option<MapState>,
+    dirty: bool,
+}
+
+impl LibraryMapCache {
+    /// A zero `ttl` disables the on-disk cache: every map is rebuilt.
+    pub fn new(path: impl Into<PathBuf>, ttl: Duration) -> Self {
+        Self {
+            path: path.into(),
+            ttl,
+            state: None,
+            dirty: false,
+        }
+    }
+
+    /// Cache at `.libr/cache/library_map.json`, expiring after
+    /// `ai.library_map_cache`.
+    pub fn for_library(root: &LibraryRoot, config: &AiConfig) -> Self {
+        Self::new(root.library_map_path(), Self::ttl_from_config(config))
+    }
+
+    pub fn ttl_from_config(config: &AiConfig) -> Duration {
+        parse_ttl(&config.library_map_cache).unwrap_or(DEFAULT_TTL)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The full map, rebuilt from `db` if the cache is stale.
+    pub fn map(&mut self, db: &Database) -> Result<LibraryMap> {
+        self.refresh(db)?;
+        Ok(self
+            .state
+            .as_ref()
+            .map(MapState::to_map)
+            .unwrap_or_default())
+    }
+
+    /// The map trimmed to fit `token_budget`, for use as prompt context.
+    pub fn map_within(&mut self, db: &Database, token_budget: usize) -> Result<LibraryMap> {
+        let mut map = self.map(db)?;
+        trim_to_budget(&mut map, token_budget);
+        Ok(map)
+    }
+
+    pub fn card_upserted(&mut self, card: &BookCard) {
+        if !self.ensure_loaded() {
+            return;
+        }
+        let Some(state) = self.state.as_mut() else {
+            return;
+        };
+        let mut entry = LibraryMapEntry::from(card);
+        if let Some(old) = state.entries.get(&card.id) {
+            entry.book.frecency = old.book.frecency;
+        }
+        state.insert(entry);
+        state.advance_fingerprint(Some(&card.updated_at.to_rfc3339()));
+        self.dirty = true;
+    }
+
+    pub fn card_deleted(&mut self, id: &Uuid) {
+        if !self.ensure_loaded() {
+            return;
+        }
+        let Some(state) = self.state.as_mut() else {
+            return;
+        };
+        if state.remove(id) {
+            state.advance_fingerprint(None);
+            self.dirty = true;
+        }
+    }
+
+    /// Apply the cards changed by an executed action.
+    pub fn apply_changes(&mut self, changes: &[CardChange]) {
+        for change in changes {
+            match change {
+                CardChange::Upserted(card) => self.card_upserted(card),
+                CardChange::Deleted(id) => self.card_deleted(id),
+            }
+        }
+    }
+
+    /// Apply a change-feed event. Upserts only carry a summary, so library
+    /// membership and summary flags are kept from the previous entry, and
+    /// the next [`map`](Self::map) rebuilds from the database: the summary
+    /// has no `updated_at` to check the database against.
+    pub fn apply_event(&mut self, event: &LibraryEvent) {
+        match event {
+            LibraryEvent::CardUpserted { book } => {
+                if !self.ensure_loaded() {
+                    return;
+                }
+                let Some(state) = self.state.as_mut() else {
+                    return;
+                };
+                let previous = state.entries.get(&book.id);
+                let entry = LibraryMapEntry {
+                    book: BookSummaryCompact {
+                        id: book.id,
+                        title: book.title.clone(),
+                        authors: book.authors.clone(),
+                        year: book.year,
+                        tags: book.tags.clone(),
+                        status: book.read_status.to_string(),
+                        frecency: previous.map_or(book.frecency_score, |old| old.book.frecency),
+                    },
+                    libraries: previous
+                        .map(|old| old.libraries.clone())
+                        .unwrap_or_default(),
+                    format: book.format,
+                    has_file: book.has_file,
+                    has_summary: previous.is_some_and(|old| old.has_summary),
+                };
+                state.insert(entry);
+                state.fingerprint = None;
+                self.dirty = true;
+            }
+            LibraryEvent::CardDeleted { id } => self.card_deleted(id),
+            _ => {}
+        }
+    }
+
+    /// Forget the cached map; the next [`map`](Self::map) rebuilds it.
+    pub fn invalidate(&mut self) -> Result<()> {
+        self.state = None;
+        self.dirty = false;
+        if self.path.exists() {
+            std::fs::remove_file(&self.path)?;
+        }
+        Ok(())
+    }
+
+    /// Write pending in-place changes to disk.
+    pub fn flush(&mut self) -> Result<()> {
+        if !self.dirty || self.ttl.is_zero() {
+            return Ok(());
+        }
+        let Some(state) = &self.state else {
+            return Ok(());
+        };
+        let file = CacheFileRef {
+            version: CACHE_VERSION,
+            built_at: state.built_at,
+            fingerprint: state.fingerprint.as_deref(),
+            entries: state.entries.values().collect(),
+        };
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec(&file)?)?;
+        std::fs::rename(&tmp, &self.path)?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    fn refresh(&mut self, db: &Database) -> Result<()> {
+        self.ensure_loaded();
+        let fingerprint = db.library_fingerprint()?;
+        let fresh = self.state.as_ref().is_some_and(|state| {
+            !is_expired(state.built_at, self.ttl)
+                && state.fingerprint.as_ref() == Some(&fingerprint)
+        });
+
+        if !fresh {
+            let entries = db.library_map_entries()?;
+            self.state = Some(MapState::new(Utc::now(), Some(fingerprint), entries));
+            self.dirty = true;
+        }
+        self.flush()
+    }
+
+    /// Load the on-disk cache if nothing is in memory yet. Returns whether
+    /// there is a (non-expired) map to update.
+    fn ensure_loaded(&mut self) -> bool {
+        if self.state.is_none() && !self.ttl.is_zero() {
+            self.state = self.load_file();
+        }
+        self.state.is_some()
+    }
+
+    fn load_file(&self) -> Option<MapState> {
+        let bytes = std::fs::read(&self.path).ok()?;
+        let file: CacheFile = serde_json::from_slice(&bytes).ok()?;
+        if file.version != CACHE_VERSION || is_expired(file.built_at, self.ttl) {
+            return None;
+        }
+        Some(MapState::new(file.built_at, file.fingerprint, file.entries))
+    }
+}
+
+impl Drop for LibraryMapCache {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+fn is_expired(built_at: DateTime<Utc>, ttl: Duration) -> bool {
+    ttl.is_zero()
+        || Utc::now()
+            .signed_duration_since(built_at)
+            .to_std()
+            .is_ok_and(|age| age >= ttl)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(title: &str, tags: &[&str], library: &str) -> BookCard {
+        let mut card = BookCard::new(title);
+        card.organization.tags = tags.iter().map(|t| t.to_string()).collect();
+        card.organization.libraries = vec![library.to_string()];
+        card
+    }
+
+    #[test]
+    fn test_parse_ttl() {
+        assert_eq!(parse_ttl("1h"), Some(Duration::from_secs(3600)));
+        assert_eq!(parse_ttl("15m"), Some(Duration::from_secs(900)));
+        assert_eq!(parse_ttl("90"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_ttl("2d"), Some(Duration::from_secs(172_800)));
+        assert_eq!(parse_ttl("off"), Some(Duration::ZERO));
+        assert_eq!(parse_ttl("soon"), None);
+    }
+
+    #[test]
+    fn test_map_is_built_and_updated_in_place() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open_in_memory().unwrap();
+        let rust = card("Rust in Action", &["rust", "systems"], "programming");
+        db.upsert_book(&rust).unwrap();
+        db.upsert_book(&card("SICP", &["lisp"], "programming"))
+            .unwrap();
+
+        let path = dir.path().join("library_map.json");
+        let mut cache = LibraryMapCache::new(&path, Duration::from_secs(3600));
+        let map = cache.map(&db).unwrap();
+        assert_eq!(map.stats.total, 2);
+        assert_eq!(map.libraries["programming"].book_count, 2);
+        assert!(path.exists());
+
+        let mut read = rust.clone();
+        read.organization.read_status = omniscope_core::ReadStatus::Read;
+        read.organization.tags = vec!["rust".to_string()];
+        db.upsert_book(&read).unwrap();
+        cache.card_upserted(&read);
+        cache.card_deleted(&Uuid::nil());
+
+        let map = cache.map(&db).unwrap();
+        assert_eq!(map.stats.total, 2);
+        assert_eq!(map.stats.read, 1);
+        assert_eq!(map.stats.unread, 1);
+        assert!(!map.tag_cloud.contains_key("systems"));
+        assert_eq!(map.libraries["programming"].unread_count, 1);
+    }
+
+    #[test]
+    fn test_unreported_changes_trigger_rebuild() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open_in_memory().unwrap();
+        db.upsert_book(&card("SICP", &["lisp"], "programming"))
+            .unwrap();
+        let path = dir.path().join("library_map.json");
+        LibraryMapCache::new(&path, Duration::from_secs(3600))
+            .map(&db)
+            .unwrap();
+
+        db.upsert_book(&card("Dune", &["scifi"], "fiction"))
+            .unwrap();
+        let map = LibraryMapCache::new(&path, Duration::from_secs(3600))
+            .map(&db)
+            .unwrap();
+
+        assert_eq!(map.stats.total, 2);
+        assert_eq!(map.tag_cloud.get("scifi"), Some(&1));
+    }
+
+    #[test]
+    fn test_unreported_changes_after_in_place_edit_trigger_rebuild() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open_in_memory().unwrap();
+        db.upsert_book(&card("SICP", &["lisp"], "programming"))
+            .unwrap();
+        let mut cache = LibraryMapCache::new(
+            dir.path().join("library_map.json"),
+            Duration::from_secs(3600),
+        );
+        cache.map(&db).unwrap();
+
+        let reported = card("Rust in Action", &["rust"], "programming");
+        db.upsert_book(&reported).unwrap();
+        cache.card_upserted(&reported);
+        db.upsert_book(&card("Dune", &["scifi"], "fiction"))
+            .unwrap();
+
+        let map = cache.map(&db).unwrap();
+        assert_eq!(map.stats.total, 3);
+        assert_eq!(map.tag_cloud.get("scifi"), Some(&1));
+    }
+
+    #[test]
+    fn test_trim_to_budget_keeps_most_used_books() {
+        let mut map = LibraryMap::default();
+        for i in 0..500 {
+            map.books.push(BookSummaryCompact {
+                id: Uuid::now_v7(),
+                title: format!("Book number {i}"),
+                authors: vec!["Some Author".to_string()],
+                year: Some(2000),
+                tags: Vec::new(),
+                status: "unread".to_string(),
+                frecency: (500 - i) as f64,
+            });
+        }
+
+        trim_to_budget(&mut map, 1_000);
+
+        assert!(!map.books.is_empty());
+        assert!(map.books.len() < 500);
+        assert_eq!(map.books[0].title, "Book number 0");
+        assert!(estimate_tokens(&serde_json::to_string(&map).unwrap()) <= 1_000);
+    }
+}
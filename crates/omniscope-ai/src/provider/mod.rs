@@ -0,0 +1,324 @@
+//! AI provider abstraction layer.
+//!
+//! Everything above this module talks to a model through [`AiProvider`].
+//! Two HTTP backends are built in: [`OpenAiCompatible`] (OpenAI itself, and
+//! any server speaking its API — llama.cpp, vLLM, LM Studio) and [`Ollama`].
+//! [`from_config`] picks one from the `[ai]` section of the config and wraps
+//! it in the token budget.
+
+mod ollama;
+mod openai;
+
+pub use ollama::Ollama;
+pub use openai::OpenAiCompatible;
+
+use std::pin::Pin;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use async_trait::async_trait;
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+
+use omniscope_core::config::AiConfig;
+
+use crate::budget::MeteredProvider;
+
+/// Generous default: local models on CPU can take minutes per reply.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    System,
+    User,
+    Assistant,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub role: Role,
+    pub content: String,
+}
+
+impl ChatMessage {
+    pub fn system(content: impl Into<String>) -> Self {
+        Self {
+            role: Role::System,
+            content: content.into(),
+        }
+    }
+
+    pub fn user(content: impl Into<String>) -> Self {
+        Self {
+            role: Role::User,
+            content: content.into(),
+        }
+    }
+
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self {
+            role: Role::Assistant,
+            content: content.into(),
+        }
+    }
+}
+
+/// A chat completion request. `None` options fall back to the provider's
+/// configured defaults.
+#[derive(Debug, Clone, Default)]
+pub struct ChatRequest {
+    pub messages: Vec<ChatMessage>,
+    pub max_tokens: Option<u32>,
+    pub temperature: Option<f64>,
+    /// Ask the model to reply with a single JSON object.
+    pub json: bool,
+}
+
+impl ChatRequest {
+    pub fn new(messages: Vec<ChatMessage>) -> Self {
+        Self {
+            messages,
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+}
+
+impl TokenUsage {
+    pub fn total(&self) -> u32 {
+        self.prompt_tokens + self.completion_tokens
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChatResponse {
+    pub content: String,
+    pub usage: TokenUsage,
+}
+
+/// One item of a streamed reply.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChatChunk {
+    /// The next piece of assistant text.
+    Delta(String),
+    /// Token accounting, sent once at the end if the backend reports it.
+    Usage(TokenUsage),
+}
+
+pub type ChatStream = Pin<Box<dyn Stream<Item = Result<ChatChunk>> + Send>>;
+
+#[async_trait]
+pub trait AiProvider: Send + Sync {
+    /// Backend name, e.g. `"ollama"`.
+    fn name(&self) -> &str;
+    /// Chat model requests are sent to.
+    fn model(&self) -> &str;
+    /// Model used by [`embed_batch`](Self::embed_batch).
+    fn embedding_model(&self) -> &str;
+
+    async fn chat(&self, request: ChatRequest) -> Result<ChatResponse>;
+
+    async fn chat_stream(&self, request: ChatRequest) -> Result<ChatStream>;
+
+    /// Embed each text; the result has one vector per input, in order.
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>>;
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        self.embed_batch(&[text.to_string()])
+            .await?
+            .into_iter()
+            .next()
+            .context("provider returned no embedding")
+    }
+}
+
+/// Build the provider selected by `config.provider`, metered against the
+/// workstation's token budget (see [`crate::budget`]).
+pub fn from_config(config: &AiConfig) -> Result<Box<dyn AiProvider>> {
+    Ok(Box::new(MeteredProvider::from_config(config)?))
+}
+
+/// Build the bare backend selected by `config.provider`, without metering.
+///
+/// `openai`, `llamacpp`, `vllm`, `lmstudio` and `custom` all use the
+/// OpenAI-compatible backend; `base_url` points it at the right server.
+/// The Anthropic defaults older releases saved fall back to the current
+/// default backend (see [`legacy_provider_warning`]).
+pub fn backend_from_config(config: &AiConfig) -> Result<Box<dyn AiProvider>> {
+    let config = config.without_legacy_provider();
+    let provider: Box<dyn AiProvider> = match config.provider.to_ascii_lowercase().as_str() {
+        "ollama" => Box::new(Ollama::from_config(&config)?),
+        "openai" | "openai-compatible" | "llamacpp" | "llama.cpp" | "vllm" | "lmstudio"
+        | "custom" => Box::new(OpenAiCompatible::from_config(&config)?),
+        other => bail!(
+            "unsupported AI provider '{other}': set [ai] provider = \"ollama\", or \
+             provider = \"openai\" with base_url for an OpenAI-compatible server"
+        ),
+    };
+    Ok(provider)
+}
+
+/// One-line notice for a config.toml still carrying the Anthropic defaults
+/// an older release wrote, which [`backend_from_config`] replaces.
+pub fn legacy_provider_warning(config: &AiConfig) -> Option<String> {
+    if !config.has_legacy_provider() {
+        return None;
+    }
+    let current = AiConfig::default();
+    Some(format!(
+        "[ai] still has the old anthropic defaults; using {} ({}). Set [ai] provider to silence this.",
+        current.provider, current.model
+    ))
+}
+
+pub(crate) fn http_client() -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .context("failed to build HTTP client")
+}
+
+/// Fail with the response body when the server returned an error status.
+pub(crate) async fn check_status(response: reqwest::Response) -> Result<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    bail!("AI provider returned HTTP {status}: {}", body.trim())
+}
+
+/// Split a streaming response body into lines (SSE and NDJSON alike).
+pub(crate) fn body_lines(response: reqwest::Response) -> impl Stream<Item = Result<String>> + Send {
+    let bytes = Box::pin(response.bytes_stream());
+    futures::stream::unfold(
+        (bytes, Vec::<u8>::new(), false),
+        |(mut bytes, mut buffer, mut finished)| async move {
+            loop {
+                if let Some(pos) = buffer.iter().position(|b| *b == b'\n') {
+                    let line: Vec<u8> = buffer.drain(..=pos).collect();
+                    let line = String::from_utf8_lossy(&line).trim_end().to_string();
+                    return Some((Ok(line), (bytes, buffer, finished)));
+                }
+                if finished {
+                    if buffer.is_empty() {
+                        return None;
+                    }
+                    let line = String::from_utf8_lossy(&buffer).trim_end().to_string();
+                    buffer.clear();
+                    return Some((Ok(line), (bytes, buffer, finished)));
+                }
+                match bytes.next().await {
+                    Some(Ok(chunk)) => buffer.extend_from_slice(&chunk),
+                    Some(Err(err)) => {
+                        buffer.clear();
+                        return Some((Err(err.into()), (bytes, buffer, true)));
+                    }
+                    None => finished = true,
+                }
+            }
+        },
+    )
+}
+
+/// Turn a line stream into chunks. A line may carry no chunk (skipped) or
+/// several, e.g. the last delta together with the usage report.
+pub(crate) fn chunk_stream<S, F>(lines: S, parse: F) -> ChatStream
+where
+    S: Stream<Item = Result<String>> + Send + 'static,
+    F: Fn(&str) -> Result<Vec<ChatChunk>> + Send + Sync + 'static,
+{
+    Box::pin(lines.flat_map(move |line| {
+        let chunks = match line.and_then(|line| parse(&line)) {
+            Ok(chunks) => chunks.into_iter().map(Ok).collect(),
+            Err(err) => vec![Err(err)],
+        };
+        futures::stream::iter(chunks)
+    }))
+}
+
+/// Collect a whole streamed reply into one response.
+pub async fn collect_stream(mut stream: ChatStream) -> Result<ChatResponse> {
+    let mut content = String::new();
+    let mut usage = TokenUsage::default();
+    while let Some(chunk) = stream.next().await {
+        match chunk? {
+            ChatChunk::Delta(delta) => content.push_str(&delta),
+            ChatChunk::Usage(reported) => usage = reported,
+        }
+    }
+    Ok(ChatResponse { content, usage })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(provider: &str) -> AiConfig {
+        AiConfig {
+            provider: provider.to_string(),
+            model: "test-model".to_string(),
+            base_url: Some("http://127.0.0.1:9".to_string()),
+            ..AiConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_from_config_selects_backend() {
+        assert_eq!(from_config(&config("ollama")).unwrap().name(), "ollama");
+        assert_eq!(from_config(&config("vllm")).unwrap().name(), "openai");
+        assert_eq!(
+            from_config(&config("llamacpp")).unwrap().model(),
+            "test-model"
+        );
+    }
+
+    #[test]
+    fn test_default_config_builds_a_provider() {
+        let provider = from_config(&AiConfig::default()).unwrap();
+        assert_eq!(provider.name(), "ollama");
+    }
+
+    #[test]
+    fn test_from_config_rejects_unknown_provider() {
+        let err = from_config(&config("carrier-pigeon")).err().unwrap();
+        assert!(err.to_string().contains("provider = \"ollama\""));
+    }
+
+    #[test]
+    fn test_config_saved_by_an_older_release_builds_a_provider() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            r#"libraries = []
+
+[global]
+theme = "catppuccin-mocha"
+
+[ai]
+provider = "anthropic"
+model = "claude-sonnet-4-20250514"
+api_key_env = "ANTHROPIC_API_KEY"
+max_tokens = 4096
+temperature = 0.1
+auto_index = true
+auto_summary = false
+library_map_cache = "1h"
+"#,
+        )
+        .unwrap();
+        let config = omniscope_core::config::GlobalConfig::load_from(&path).unwrap();
+
+        let provider = from_config(&config.ai).unwrap();
+        assert_eq!(provider.name(), "ollama");
+        assert!(legacy_provider_warning(&config.ai).is_some());
+        assert!(legacy_provider_warning(&AiConfig::default()).is_none());
+    }
+}
@@ -0,0 +1,248 @@
+//! Ollama's native API (`/api/chat`, `/api/embed`).
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use omniscope_core::config::AiConfig;
+
+use super::{
+    AiProvider, ChatChunk, ChatRequest, ChatResponse, ChatStream, TokenUsage, body_lines,
+    check_status, chunk_stream, http_client,
+};
+
+const DEFAULT_BASE_URL: &str = "http://localhost:11434";
+const DEFAULT_EMBEDDING_MODEL: &str = "nomic-embed-text";
+
+pub struct Ollama {
+    client: reqwest::Client,
+    base_url: String,
+    model: String,
+    embedding_model: String,
+    max_tokens: u32,
+    temperature: f64,
+}
+
+impl Ollama {
+    pub fn new(base_url: impl Into<String>, model: impl Into<String>) -> Result<Self> {
+        Ok(Self {
+            client: http_client()?,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            model: model.into(),
+            embedding_model: DEFAULT_EMBEDDING_MODEL.to_string(),
+            max_tokens: AiConfig::default().max_tokens,
+            temperature: AiConfig::default().temperature,
+        })
+    }
+
+    pub fn from_config(config: &AiConfig) -> Result<Self> {
+        let base_url = config.base_url.as_deref().unwrap_or(DEFAULT_BASE_URL);
+        let mut provider = Self::new(base_url, config.model.clone())?;
+        if let Some(model) = &config.embedding_model {
+            provider.embedding_model = model.clone();
+        }
+        provider.max_tokens = config.max_tokens;
+        provider.temperature = config.temperature;
+        Ok(provider)
+    }
+
+    pub fn with_embedding_model(mut self, model: impl Into<String>) -> Self {
+        self.embedding_model = model.into();
+        self
+    }
+
+    fn chat_body(&self, request: &ChatRequest, stream: bool) -> Value {
+        let mut body = json!({
+            "model": self.model,
+            "messages": request.messages,
+            "stream": stream,
+            "options": {
+                "num_predict": request.max_tokens.unwrap_or(self.max_tokens),
+                "temperature": request.temperature.unwrap_or(self.temperature),
+            },
+        });
+        if request.json {
+            body["format"] = json!("json");
+        }
+        body
+    }
+}
+
+/// A `/api/chat` reply, or one NDJSON line of a streamed reply.
+#[derive(Deserialize)]
+struct ChatReply {
+    #[serde(default)]
+    message: Option<Message>,
+    #[serde(default)]
+    done: bool,
+    #[serde(default)]
+    prompt_eval_count: u32,
+    #[serde(default)]
+    eval_count: u32,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Message {
+    #[serde(default)]
+    content: String,
+}
+
+impl ChatReply {
+    fn usage(&self) -> TokenUsage {
+        TokenUsage {
+            prompt_tokens: self.prompt_eval_count,
+            completion_tokens: self.eval_count,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct EmbedResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
+fn parse_ndjson_line(line: &str) -> Result<Vec<ChatChunk>> {
+    if line.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let reply: ChatReply = serde_json::from_str(line).context("malformed streaming chunk")?;
+    if let Some(error) = reply.error {
+        anyhow::bail!("ollama: {error}");
+    }
+    if reply.done {
+        return Ok(vec![ChatChunk::Usage(reply.usage())]);
+    }
+    Ok(reply
+        .message
+        .map(|message| message.content)
+        .filter(|content| !content.is_empty())
+        .map(ChatChunk::Delta)
+        .into_iter()
+        .collect())
+}
+
+#[async_trait]
+impl AiProvider for Ollama {
+    fn name(&self) -> &str {
+        "ollama"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn embedding_model(&self) -> &str {
+        &self.embedding_model
+    }
+
+    async fn chat(&self, request: ChatRequest) -> Result<ChatResponse> {
+        let response = self
+            .client
+            .post(format!("{}/api/chat", self.base_url))
+            .json(&self.chat_body(&request, false))
+            .send()
+            .await?;
+        let reply: ChatReply = check_status(response).await?.json().await?;
+        let usage = reply.usage();
+        let content = reply
+            .message
+            .map(|message| message.content)
+            .context("ollama reply contained no message")?;
+        Ok(ChatResponse { content, usage })
+    }
+
+    async fn chat_stream(&self, request: ChatRequest) -> Result<ChatStream> {
+        let response = self
+            .client
+            .post(format!("{}/api/chat", self.base_url))
+            .json(&self.chat_body(&request, true))
+            .send()
+            .await?;
+        let response = check_status(response).await?;
+        Ok(chunk_stream(body_lines(response), parse_ndjson_line))
+    }
+
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let response = self
+            .client
+            .post(format!("{}/api/embed", self.base_url))
+            .json(&json!({ "model": self.embedding_model, "input": texts }))
+            .send()
+            .await?;
+        let embedded: EmbedResponse = check_status(response).await?.json().await?;
+        Ok(embedded.embeddings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::{ChatMessage, collect_stream};
+
+    #[tokio::test]
+    async fn test_chat_stream_parses_ndjson() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/api/chat")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "model": "qwen2.5",
+                "stream": true,
+            })))
+            .with_header("content-type", "application/x-ndjson")
+            .with_body(concat!(
+                "{\"message\":{\"role\":\"assistant\",\"content\":\"Hel\"},\"done\":false}\n",
+                "{\"message\":{\"role\":\"assistant\",\"content\":\"lo\"},\"done\":false}\n",
+                "{\"message\":{\"role\":\"assistant\",\"content\":\"\"},\"done\":true,",
+                "\"prompt_eval_count\":12,\"eval_count\":2}\n",
+            ))
+            .create_async()
+            .await;
+
+        let provider = Ollama::new(server.url(), "qwen2.5").unwrap();
+        let stream = provider
+            .chat_stream(ChatRequest::new(vec![ChatMessage::user("Hi")]))
+            .await
+            .unwrap();
+        let response = collect_stream(stream).await.unwrap();
+
+        assert_eq!(response.content, "Hello");
+        assert_eq!(response.usage.total(), 14);
+    }
+
+    #[tokio::test]
+    async fn test_chat_and_embed() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/api/chat")
+            .with_body(
+                r#"{"message":{"role":"assistant","content":"Hi!"},"done":true,"eval_count":1}"#,
+            )
+            .create_async()
+            .await;
+        server
+            .mock("POST", "/api/embed")
+            .match_body(mockito::Matcher::PartialJson(
+                json!({ "model": "mxbai-embed-large" }),
+            ))
+            .with_body(r#"{"embeddings":[[0.5,0.25]]}"#)
+            .create_async()
+            .await;
+
+        let provider = Ollama::new(server.url(), "qwen2.5")
+            .unwrap()
+            .with_embedding_model("mxbai-embed-large");
+
+        let reply = provider
+            .chat(ChatRequest::new(vec![ChatMessage::user("Hello")]))
+            .await
+            .unwrap();
+        assert_eq!(reply.content, "Hi!");
+        assert_eq!(provider.embed("text").await.unwrap(), vec![0.5, 0.25]);
+    }
+}
@@ -0,0 +1,363 @@
+//! OpenAI-compatible chat and embeddings (`/chat/completions`, `/embeddings`).
+//!
+//! Also serves llama.cpp's `llama-server`, vLLM and LM Studio, which expose
+//! the same API under their own base URL.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use omniscope_core::config::AiConfig;
+
+use super::{
+    AiProvider, ChatChunk, ChatRequest, ChatResponse, ChatStream, TokenUsage, body_lines,
+    check_status, chunk_stream, http_client,
+};
+
+const DEFAULT_BASE_URL: &str = "https://api.openai.com/v1";
+const DEFAULT_EMBEDDING_MODEL: &str = "text-embedding-3-small";
+
+pub struct OpenAiCompatible {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: Option<String>,
+    model: String,
+    embedding_model: String,
+    max_tokens: u32,
+    temperature: f64,
+}
+
+impl OpenAiCompatible {
+    /// `base_url` includes the API version prefix, e.g. `http://localhost:8000/v1`.
+    pub fn new(
+        base_url: impl Into<String>,
+        api_key: Option<String>,
+        model: impl Into<String>,
+    ) -> Result<Self> {
+        Ok(Self {
+            client: http_client()?,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            api_key,
+            model: model.into(),
+            embedding_model: DEFAULT_EMBEDDING_MODEL.to_string(),
+            max_tokens: AiConfig::default().max_tokens,
+            temperature: AiConfig::default().temperature,
+        })
+    }
+
+    /// The API key is optional: local servers usually run without one.
+    pub fn from_config(config: &AiConfig) -> Result<Self> {
+        let base_url = config.base_url.as_deref().unwrap_or(DEFAULT_BASE_URL);
+        let api_key = std::env::var(&config.api_key_env)
+            .ok()
+            .filter(|key| !key.is_empty());
+        let mut provider = Self::new(base_url, api_key, config.model.clone())?;
+        if let Some(model) = &config.embedding_model {
+            provider.embedding_model = model.clone();
+        }
+        provider.max_tokens = config.max_tokens;
+        provider.temperature = config.temperature;
+        Ok(provider)
+    }
+
+    pub fn with_embedding_model(mut self, model: impl Into<String>) -> Self {
+        self.embedding_model = model.into();
+        self
+    }
+
+    fn post(&self, path: &str) -> reqwest::RequestBuilder {
+        let request = self.client.post(format!("{}{path}", self.base_url));
+        match &self.api_key {
+            Some(key) => request.bearer_auth(key),
+            None => request,
+        }
+    }
+
+    fn chat_body(&self, request: &ChatRequest, stream: bool) -> Value {
+        let mut body = json!({
+            "model": self.model,
+            "messages": request.messages,
+            "max_tokens": request.max_tokens.unwrap_or(self.max_tokens),
+            "temperature": request.temperature.unwrap_or(self.temperature),
+            "stream": stream,
+        });
+        if stream {
+            body["stream_options"] = json!({ "include_usage": true });
+        }
+        if request.json {
+            body["response_format"] = json!({ "type": "json_object" });
+        }
+        body
+    }
+}
+
+#[derive(Deserialize)]
+struct CompletionResponse {
+    choices: Vec<Choice>,
+    usage: Option<Usage>,
+}
+
+#[derive(Deserialize)]
+struct Choice {
+    #[serde(default)]
+    message: Option<Message>,
+    #[serde(default)]
+    delta: Option<Message>,
+}
+
+#[derive(Deserialize)]
+struct Message {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Usage {
+    #[serde(default)]
+    prompt_tokens: u32,
+    #[serde(default)]
+    completion_tokens: u32,
+}
+
+impl From<Usage> for TokenUsage {
+    fn from(usage: Usage) -> Self {
+        Self {
+            prompt_tokens: usage.prompt_tokens,
+            completion_tokens: usage.completion_tokens,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+/// Parse one server-sent-events line of a streamed completion.
+///
+/// vLLM and llama.cpp send the usage report in the same chunk as the last
+/// delta, so a line can yield both.
+fn parse_sse_line(line: &str) -> Result<Vec<ChatChunk>> {
+    let Some(data) = line.strip_prefix("data:").map(str::trim) else {
+        return Ok(Vec::new());
+    };
+    if data.is_empty() || data == "[DONE]" {
+        return Ok(Vec::new());
+    }
+    let event: CompletionResponse =
+        serde_json::from_str(data).context("malformed streaming chunk")?;
+    let delta = event
+        .choices
+        .into_iter()
+        .next()
+        .and_then(|choice| choice.delta)
+        .and_then(|delta| delta.content)
+        .filter(|content| !content.is_empty());
+    Ok(delta
+        .map(ChatChunk::Delta)
+        .into_iter()
+        .chain(event.usage.map(|usage| ChatChunk::Usage(usage.into())))
+        .collect())
+}
+
+#[async_trait]
+impl AiProvider for OpenAiCompatible {
+    fn name(&self) -> &str {
+        "openai"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn embedding_model(&self) -> &str {
+        &self.embedding_model
+    }
+
+    async fn chat(&self, request: ChatRequest) -> Result<ChatResponse> {
+        let response = self
+            .post("/chat/completions")
+            .json(&self.chat_body(&request, false))
+            .send()
+            .await?;
+        let completion: CompletionResponse = check_status(response).await?.json().await?;
+        let content = completion
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|choice| choice.message)
+            .and_then(|message| message.content)
+            .context("completion contained no choices")?;
+        Ok(ChatResponse {
+            content,
+            usage: completion.usage.map(TokenUsage::from).unwrap_or_default(),
+        })
+    }
+
+    async fn chat_stream(&self, request: ChatRequest) -> Result<ChatStream> {
+        let response = self
+            .post("/chat/completions")
+            .json(&self.chat_body(&request, true))
+            .send()
+            .await?;
+        let response = check_status(response).await?;
+        Ok(chunk_stream(body_lines(response), parse_sse_line))
+    }
+
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let response = self
+            .post("/embeddings")
+            .json(&json!({ "model": self.embedding_model, "input": texts }))
+            .send()
+            .await?;
+        let mut embeddings: EmbeddingResponse = check_status(response).await?.json().await?;
+        embeddings.data.sort_by_key(|data| data.index);
+        Ok(embeddings
+            .data
+            .into_iter()
+            .map(|data| data.embedding)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::{ChatMessage, collect_stream};
+
+    fn provider(server: &mockito::Server) -> OpenAiCompatible {
+        OpenAiCompatible::new(
+            format!("{}/v1", server.url()),
+            Some("sk-test".into()),
+            "local-model",
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/chat/completions")
+            .match_header("authorization", "Bearer sk-test")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "model": "local-model",
+                "stream": false,
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"choices":[{"message":{"role":"assistant","content":"Hello"}}],
+                    "usage":{"prompt_tokens":7,"completion_tokens":2}}"#,
+            )
+            .create_async()
+            .await;
+
+        let response = provider(&server)
+            .chat(ChatRequest::new(vec![ChatMessage::user("Hi")]))
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(response.content, "Hello");
+        assert_eq!(response.usage.total(), 9);
+    }
+
+    #[tokio::test]
+    async fn test_chat_stream_parses_sse() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/v1/chat/completions")
+            .with_header("content-type", "text/event-stream")
+            .with_body(concat!(
+                "data: {\"choices\":[{\"delta\":{\"role\":\"assistant\"}}]}\n\n",
+                "data: {\"choices\":[{\"delta\":{\"content\":\"Hel\"}}]}\n\n",
+                "data: {\"choices\":[{\"delta\":{\"content\":\"lo\"}}]}\n\n",
+                "data: {\"choices\":[],\"usage\":{\"prompt_tokens\":3,\"completion_tokens\":2}}\n\n",
+                "data: [DONE]\n\n",
+            ))
+            .create_async()
+            .await;
+
+        let stream = provider(&server)
+            .chat_stream(ChatRequest::new(vec![ChatMessage::user("Hi")]))
+            .await
+            .unwrap();
+        let response = collect_stream(stream).await.unwrap();
+
+        assert_eq!(response.content, "Hello");
+        assert_eq!(response.usage.prompt_tokens, 3);
+    }
+
+    #[tokio::test]
+    async fn test_chat_stream_keeps_usage_sent_with_the_last_delta() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/v1/chat/completions")
+            .with_header("content-type", "text/event-stream")
+            .with_body(concat!(
+                "data: {\"choices\":[{\"delta\":{\"content\":\"Hel\"}}]}\n\n",
+                "data: {\"choices\":[{\"delta\":{\"content\":\"lo\"}}],",
+                "\"usage\":{\"prompt_tokens\":3,\"completion_tokens\":2}}\n\n",
+                "data: [DONE]\n\n",
+            ))
+            .create_async()
+            .await;
+
+        let stream = provider(&server)
+            .chat_stream(ChatRequest::new(vec![ChatMessage::user("Hi")]))
+            .await
+            .unwrap();
+        let response = collect_stream(stream).await.unwrap();
+
+        assert_eq!(response.content, "Hello");
+        assert_eq!(response.usage.prompt_tokens, 3);
+        assert_eq!(response.usage.completion_tokens, 2);
+    }
+
+    #[tokio::test]
+    async fn test_embeddings_keep_input_order() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/v1/embeddings")
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"data":[{"index":1,"embedding":[0.0,1.0]},{"index":0,"embedding":[1.0,0.0]}]}"#,
+            )
+            .create_async()
+            .await;
+
+        let vectors = provider(&server)
+            .embed_batch(&["a".to_string(), "b".to_string()])
+            .await
+            .unwrap();
+
+        assert_eq!(vectors, vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+    }
+
+    #[tokio::test]
+    async fn test_error_status_includes_body() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/v1/chat/completions")
+            .with_status(500)
+            .with_body("model not loaded")
+            .create_async()
+            .await;
+
+        let err = provider(&server)
+            .chat(ChatRequest::new(vec![ChatMessage::user("Hi")]))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("model not loaded"));
+    }
+}
@@ -0,0 +1,279 @@
+//! Semantic search: card text embedded through the AI provider, stored in
+//! the database, and ranked against an embedded concept query.
+
+use std::path::Path;
+
+use anyhow::Result;
+use uuid::Uuid;
+
+use omniscope_core::storage::json_cards;
+use omniscope_core::{BookCard, BookEmbedding, Database};
+
+use crate::provider::AiProvider;
+
+/// Texts sent per embeddings request.
+const EMBED_BATCH: usize = 32;
+/// Card text beyond this is not embedded; long notes would dominate.
+const MAX_TEXT_CHARS: usize = 8_000;
+
+/// The text a card is embedded from: title, authors, abstract, summary and
+/// notes.
+pub fn embedding_text(card: &BookCard) -> String {
+    let mut parts = vec![card.metadata.title.clone()];
+    if let Some(subtitle) = &card.metadata.subtitle {
+        parts.push(subtitle.clone());
+    }
+    if !card.metadata.authors.is_empty() {
+        parts.push(card.metadata.authors.join(", "));
+    }
+    if let Some(summary) = &card.ai.summary {
+        parts.push(summary.clone());
+    }
+    if let Some(tldr) = &card.ai.tldr {
+        parts.push(tldr.clone());
+    }
+    if !card.ai.key_topics.is_empty() {
+        parts.push(card.ai.key_topics.join(", "));
+    }
+    parts.extend(card.notes.iter().map(|note| note.text.trim().to_string()));
+
+    let text = parts.join("\n");
+    match text.char_indices().nth(MAX_TEXT_CHARS) {
+        Some((cut, _)) => text[..cut].to_string(),
+        None => text,
+    }
+}
+
+/// FNV-1a (64-bit) fingerprint of embedded text; a changed hash means the
+/// card has to be embedded again. The hashes are stored, so this must not
+/// change between builds the way `std`'s `DefaultHasher` may.
+pub fn content_hash(text: &str) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    let hash = text.bytes().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    });
+    format!("{hash:016x}")
+}
+
+/// Scale `vector` to unit length so similarity is a dot product.
+pub fn normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EmbeddingReport {
+    /// Cards sent to the provider.
+    pub embedded: usize,
+    /// Cards edited without touching the embedded text.
+    pub unchanged: usize,
+    /// Cards in the index whose JSON could not be loaded.
+    pub skipped: usize,
+}
+
+/// Embed every card that has no up-to-date embedding from the provider's
+/// embedding model.
+pub async fn refresh_embeddings(
+    db: &Database,
+    cards_dir: &Path,
+    provider: &dyn AiProvider,
+) -> Result<EmbeddingReport> {
+    let model = provider.embedding_model().to_string();
+    let mut report = EmbeddingReport::default();
+    let mut pending: Vec<(BookCard, String, String)> = Vec::new();
+
+    for id in db.list_stale_embeddings(&model)? {
+        let Some(card) = load_card(db, cards_dir, &id) else {
+            report.skipped += 1;
+            continue;
+        };
+        let text = embedding_text(&card);
+        let hash = content_hash(&text);
+        match db.get_embedding(&id)? {
+            Some(mut existing) if existing.model == model && existing.content_hash == hash => {
+                existing.source_updated_at = card.updated_at;
+                db.save_embedding(&existing)?;
+                report.unchanged += 1;
+            }
+            _ => pending.push((card, text, hash)),
+        }
+    }
+
+    for batch in pending.chunks(EMBED_BATCH) {
+        let texts: Vec<String> = batch.iter().map(|(_, text, _)| text.clone()).collect();
+        let vectors = provider.embed_batch(&texts).await?;
+        for ((card, _, hash), mut vector) in batch.iter().zip(vectors) {
+            normalize(&mut vector);
+            db.save_embedding(&BookEmbedding {
+                book_id: card.id,
+                model: model.clone(),
+                vector,
+                content_hash: hash.clone(),
+                source_updated_at: card.updated_at,
+            })?;
+            mark_embedded(cards_dir, card, &model)?;
+            report.embedded += 1;
+        }
+    }
+
+    Ok(report)
+}
+
+/// Embed `query` for comparison with stored card embeddings.
+pub async fn embed_query(provider: &dyn AiProvider, query: &str) -> Result<Vec<f32>> {
+    let mut vector = provider.embed(query).await?;
+    normalize(&mut vector);
+    Ok(vector)
+}
+
+/// The `limit` books closest in meaning to `query`, with their similarity.
+pub async fn semantic_search(
+    db: &Database,
+    provider: &dyn AiProvider,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<(Uuid, f32)>> {
+    let vector = embed_query(provider, query).await?;
+    Ok(db.nearest_books(&vector, provider.embedding_model(), limit)?)
+}
+
+fn load_card(db: &Database, cards_dir: &Path, id: &Uuid) -> Option<BookCard> {
+    json_cards::load_card_by_id(cards_dir, id)
+        .ok()
+        .or_else(|| db.get_book_card(&id.to_string()).ok())
+}
+
+/// Record on the card which model its stored embedding came from.
+fn mark_embedded(cards_dir: &Path, card: &BookCard, model: &str) -> Result<()> {
+    if card.ai.embedding_stored && card.ai.embedding_model.as_deref() == Some(model) {
+        return Ok(());
+    }
+    if json_cards::load_card_by_id(cards_dir, &card.id).is_err() {
+        return Ok(());
+    }
+    let mut card = card.clone();
+    card.ai.embedding_model = Some(model.to_string());
+    card.ai.embedding_stored = true;
+    json_cards::save_card(cards_dir, &card)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::{ChatRequest, ChatResponse, ChatStream};
+    use async_trait::async_trait;
+
+    /// Embeds text as counts of a few marker words.
+    struct KeywordEmbedder;
+
+    #[async_trait]
+    impl AiProvider for KeywordEmbedder {
+        fn name(&self) -> &str {
+            "test"
+        }
+
+        fn model(&self) -> &str {
+            "test"
+        }
+
+        fn embedding_model(&self) -> &str {
+            "keywords"
+        }
+
+        async fn chat(&self, _request: ChatRequest) -> Result<ChatResponse> {
+            anyhow::bail!("chat is not supported")
+        }
+
+        async fn chat_stream(&self, _request: ChatRequest) -> Result<ChatStream> {
+            anyhow::bail!("chat is not supported")
+        }
+
+        async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+            Ok(texts
+                .iter()
+                .map(|text| {
+                    let text = text.to_lowercase();
+                    ["ownership", "lambda", "galaxy"]
+                        .iter()
+                        .map(|word| text.matches(word).count() as f32 + 0.01)
+                        .collect()
+                })
+                .collect())
+        }
+    }
+
+    fn save(db: &Database, cards_dir: &Path, title: &str, summary: &str) -> BookCard {
+        let mut card = BookCard::new(title);
+        card.ai.summary = Some(summary.to_string());
+        json_cards::save_card(cards_dir, &card).unwrap();
+        db.upsert_book(&card).unwrap();
+        card
+    }
+
+    #[tokio::test]
+    async fn test_refresh_and_search() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open_in_memory().unwrap();
+        let rust = save(
+            &db,
+            dir.path(),
+            "Rust in Action",
+            "Ownership and borrowing.",
+        );
+        let sicp = save(
+            &db,
+            dir.path(),
+            "SICP",
+            "Lambda calculus and lambda everywhere.",
+        );
+
+        let report = refresh_embeddings(&db, dir.path(), &KeywordEmbedder)
+            .await
+            .unwrap();
+        assert_eq!(report.embedded, 2);
+        assert!(
+            json_cards::load_card_by_id(dir.path(), &rust.id)
+                .unwrap()
+                .ai
+                .embedding_stored
+        );
+
+        let hits = semantic_search(&db, &KeywordEmbedder, "anonymous lambda functions", 2)
+            .await
+            .unwrap();
+        assert_eq!(hits[0].0, sicp.id);
+        assert_eq!(hits.len(), 2);
+
+        let again = refresh_embeddings(&db, dir.path(), &KeywordEmbedder)
+            .await
+            .unwrap();
+        assert_eq!(again.embedded, 0);
+    }
+
+    #[test]
+    fn test_content_hash_is_fnv1a() {
+        assert_eq!(content_hash(""), "cbf29ce484222325");
+        assert_eq!(content_hash("a"), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn test_embedding_text_includes_notes() {
+        let mut card = BookCard::new("Dune");
+        card.ai.summary = Some("Desert planet.".to_string());
+        card.notes.push(omniscope_core::BookNote {
+            id: Uuid::now_v7(),
+            text: "Re-read the appendix".to_string(),
+            created_at: chrono::Utc::now(),
+            author: "me".to_string(),
+        });
+
+        let text = embedding_text(&card);
+        assert!(text.starts_with("Dune\n"));
+        assert!(text.contains("Desert planet."));
+        assert!(text.contains("Re-read the appendix"));
+    }
+}
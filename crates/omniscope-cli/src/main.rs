@@ -45,6 +45,9 @@ enum Commands {
         query: String,
         #[arg(long, default_value = "20")]
         limit: usize,
+        /// Rank by meaning using stored embeddings (same as a `~` query).
+        #[arg(long)]
+        semantic: bool,
     },
 
     /// Operations on a single book.
@@ -53,9 +56,28 @@ enum Commands {
         action: BookAction,
     },
 
-    /// Add a book to the library.
+    /// Add a book to the library, by hand, from a file, or by identifier.
+    /// With an identifier the metadata is fetched from CrossRef, arXiv,
+    /// Open Library, PubMed or Semantic Scholar.
     Add {
-        #[arg(long)]
+        /// A DOI, arXiv id, ISBN or PMID (or a URL to one), detected
+        /// automatically.
+        #[arg(group = "lookup")]
+        identifier: Option<String>,
+        #[arg(long, group = "lookup")]
+        doi: Option<String>,
+        #[arg(long, group = "lookup")]
+        arxiv: Option<String>,
+        #[arg(long, group = "lookup")]
+        isbn: Option<String>,
+        #[arg(long, group = "lookup")]
+        pmid: Option<String>,
+        /// Download the open-access PDF: the arXiv copy for arXiv papers,
+        /// Unpaywall's best location for DOIs, and the PMC copy (else
+        /// Unpaywall's) for PMIDs. ISBNs have no PDF source.
+        #[arg(long, requires = "lookup", conflicts_with = "isbn")]
+        pdf: bool,
+        #[arg(long, conflicts_with = "lookup")]
         file: Option<String>,
         #[arg(long)]
         title: Option<String>,
@@ -76,6 +98,82 @@ enum Commands {
         recursive: bool,
     },
 
+    /// Import a BibTeX (.bib), RIS (.ris) or CSL-JSON (.json) export, e.g. from
+    /// Zotero, JabRef or Pandoc.
+    ImportBib {
+        file: String,
+        /// Report what would be imported without writing anything.
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Fetch metadata for a set of books from the online sources. An
+    /// interrupted run resumes when started again with the same query.
+    Enrich {
+        /// Search DSL selecting the books, e.g. '#unread y:>2015' (default: all).
+        #[arg(long, default_value = "")]
+        query: String,
+        /// Books enriched at a time. Each source keeps to its own rate limit.
+        #[arg(long, default_value = "4")]
+        jobs: usize,
+        /// Ignore the checkpoint of an earlier run and start over.
+        #[arg(long)]
+        restart: bool,
+    },
+
+    /// Export a set of books as BibTeX, RIS, CSL-JSON or a formatted
+    /// bibliography (apa, ieee, gost, or any installed .csl style).
+    Export {
+        #[arg(long, default_value = "bibtex")]
+        format: String,
+        /// Search DSL selecting the books, e.g. '#thesis y:>2015' (default: all).
+        #[arg(long, default_value = "")]
+        query: String,
+        /// Only books in this folder (id, name or path) or its subfolders.
+        #[arg(long)]
+        folder: Option<String>,
+        /// Write to this file instead of stdout.
+        #[arg(long, short)]
+        out: Option<String>,
+    },
+
+    /// Keep a .bib file in sync with a search. Without --query and --out,
+    /// regenerates every target registered in library.toml.
+    BibSync {
+        /// Search DSL selecting the entries, e.g. '#thesis'.
+        #[arg(long)]
+        query: Option<String>,
+        /// Output .bib file (relative paths are resolved against the library root).
+        #[arg(long)]
+        out: Option<String>,
+        /// Register the target in library.toml so it is kept up to date.
+        #[arg(long)]
+        save: bool,
+        /// Unregister the target writing to --out.
+        #[arg(long, conflicts_with_all = ["query", "save", "watch"])]
+        remove: bool,
+        /// Keep running and regenerate whenever cards change.
+        #[arg(long)]
+        watch: bool,
+    },
+
+    /// Find duplicate books by DOI, ISBN or title, and merge them with --apply.
+    Dedup {
+        /// Match only by doi, isbn or title (default: all, strongest first).
+        #[arg(long)]
+        strategy: Option<String>,
+        /// Merge each group into its most complete card. The cards as they
+        /// were are recorded in the action journal first.
+        #[arg(long)]
+        apply: bool,
+    },
+
+    /// arXiv tools.
+    Arxiv {
+        #[command(subcommand)]
+        action: ArxivAction,
+    },
+
     /// Tag management.
     Tag {
         #[command(subcommand)]
@@ -143,6 +241,110 @@ enum Commands {
         #[command(subcommand)]
         action: LibrariesAction,
     },
+
+    /// Serve the REST API (bearer token from [server] auth_token_env).
+    Serve {
+        /// Address to bind (defaults to [server] host/port).
+        #[arg(long)]
+        bind: Option<String>,
+    },
+
+    /// Run an MCP server for AI agents (stdio by default).
+    Mcp {
+        /// Serve streamable HTTP instead of stdio (bearer token from
+        /// [server] auth_token_env).
+        #[arg(long)]
+        http: bool,
+        /// Address to bind in HTTP mode (defaults to [server] host/port).
+        #[arg(long)]
+        bind: Option<String>,
+    },
+
+    /// AI provider tools.
+    Ai {
+        #[command(subcommand)]
+        action: AiAction,
+    },
+}
+
+// ─── AI Actions ─────────────────────────────────────────────────────────────
+
+#[derive(Subcommand)]
+enum AiAction {
+    /// Show token usage against this workstation's budgets.
+    Usage {
+        /// Set the daily token budget (0 = unlimited).
+        #[arg(long)]
+        daily: Option<u64>,
+        /// Set the monthly token budget (0 = unlimited).
+        #[arg(long)]
+        monthly: Option<u64>,
+    },
+    /// Generate summaries, topics and tables of contents from book files.
+    Index {
+        /// Index every book that has not been indexed yet.
+        #[arg(long, conflicts_with = "id")]
+        all: bool,
+        /// Index these books (repeatable).
+        #[arg(long, action = clap::ArgAction::Append)]
+        id: Vec<String>,
+        /// With --all, index books again even if they are up to date.
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+// ─── arXiv Actions ──────────────────────────────────────────────────────────
+
+#[derive(Subcommand)]
+enum ArxivAction {
+    /// Look for newer versions of the library's arXiv papers. Papers checked
+    /// within `[arxiv] update_check_interval_hours` are skipped.
+    CheckUpdates {
+        /// Apply every pending update to its card.
+        #[arg(long)]
+        apply: bool,
+        /// With --apply, download each new PDF next to the old one.
+        #[arg(long, requires = "apply")]
+        download_pdf: bool,
+        /// Check every paper, however recently it was checked.
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Manage saved arXiv searches.
+    Feed {
+        #[command(subcommand)]
+        action: FeedAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum FeedAction {
+    /// Save a search; papers match every given field and any value within it.
+    Add {
+        name: String,
+        /// arXiv category, e.g. cs.LG (repeatable).
+        #[arg(long = "category", action = clap::ArgAction::Append)]
+        categories: Vec<String>,
+        /// Author name (repeatable).
+        #[arg(long = "author", action = clap::ArgAction::Append)]
+        authors: Vec<String>,
+        /// Word or phrase anywhere in the paper (repeatable).
+        #[arg(long = "keyword", action = clap::ArgAction::Append)]
+        keywords: Vec<String>,
+    },
+    /// List saved feeds with their count of new submissions.
+    List,
+    /// Delete a saved feed.
+    Remove { name: String },
+    /// Show a feed's newest submissions and mark them as seen.
+    Show {
+        name: String,
+        /// Fetch from arXiv even if the last fetch is recent.
+        #[arg(long)]
+        refresh: bool,
+    },
 }
 
 // ─── Libraries Actions ──────────────────────────────────────────────────────
@@ -199,6 +401,16 @@ enum BookAction {
         #[command(subcommand)]
         action: NoteAction,
     },
+
+    /// Lock a field at its current value so enrichment leaves it alone.
+    Lock {
+        id: String,
+        /// Field path or name, e.g. `metadata.title`, `year`, `doi`.
+        field: String,
+    },
+
+    /// Unlock a field so enrichment may update it again.
+    Unlock { id: String, field: String },
 }
 
 #[derive(Subcommand)]
@@ -310,6 +522,9 @@ fn main() -> Result<()> {
 
     // Load global config
     let global_config = GlobalConfig::load()?;
+    if let Some(warning) = omniscope_ai::provider::legacy_provider_warning(&global_config.ai) {
+        eprintln!("warning: {warning}");
+    }
 
     // Load legacy AppConfig (for backward compat during migration)
     let mut config = AppConfig::load()?;
@@ -369,23 +584,83 @@ fn main() -> Result<()> {
             }
         }
 
-        Some(Commands::Search { query, limit }) => {
-            let db = open_db(&config)?;
-            let results = db.search_fts(&query, limit)?;
-            let dur = start.elapsed().as_millis();
+        Some(Commands::Search {
+            query,
+            limit,
+            semantic,
+        }) => {
+            let parsed = omniscope_core::SearchQuery::parse(&query);
+            let concept = if semantic {
+                Some(query.clone())
+            } else {
+                parsed.semantic_text()
+            };
+            if let Some(concept) = concept {
+                if !global_config.search.semantic_search {
+                    anyhow::bail!(
+                        "semantic search is disabled; set `semantic_search = true` under [search] in the config"
+                    );
+                }
+                let db = resolve_db(&library_root, &config)?;
+                let cards_dir = resolve_cards_dir(&library_root, &config);
+                // With `--semantic` the whole query is the concept; `~` terms may
+                // be combined with DSL filters, so rank everything and filter.
+                let filter = (!semantic).then_some(&parsed);
+                let results = run_semantic_search(
+                    &db,
+                    &cards_dir,
+                    &global_config.ai,
+                    &concept,
+                    filter,
+                    limit,
+                    json_output,
+                )?;
+                let dur = start.elapsed().as_millis();
 
-            if json_output {
-                print_json(&serde_json::json!({
-                    "status": "ok",
-                    "data": { "items": results, "total": results.len(), "query": query },
-                    "meta": { "duration_ms": dur }
-                }))?;
-            } else if results.is_empty() {
-                println!("No results for: {query}");
+                if json_output {
+                    let items: Vec<serde_json::Value> = results
+                        .iter()
+                        .map(|(book, score)| {
+                            let mut item = serde_json::to_value(book).unwrap_or_default();
+                            item["score"] = serde_json::json!(score);
+                            item
+                        })
+                        .collect();
+                    print_json(&serde_json::json!({
+                        "status": "ok",
+                        "data": { "items": items, "total": items.len(), "query": query, "semantic": concept },
+                        "meta": { "duration_ms": dur }
+                    }))?;
+                } else if results.is_empty() {
+                    println!("No semantic matches for: {concept}");
+                } else {
+                    println!("Closest {} results:", results.len());
+                    for (book, score) in &results {
+                        println!(
+                            "  {} {score:.3} — {}",
+                            &book.id.to_string()[..8],
+                            book.title
+                        );
+                    }
+                }
             } else {
-                println!("Found {} results:", results.len());
-                for book in &results {
-                    println!("  {} — {}", &book.id.to_string()[..8], book.title);
+                let db = open_db(&config)?;
+                let results = db.search_fts(&query, limit)?;
+                let dur = start.elapsed().as_millis();
+
+                if json_output {
+                    print_json(&serde_json::json!({
+                        "status": "ok",
+                        "data": { "items": results, "total": results.len(), "query": query },
+                        "meta": { "duration_ms": dur }
+                    }))?;
+                } else if results.is_empty() {
+                    println!("No results for: {query}");
+                } else {
+                    println!("Found {} results:", results.len());
+                    for book in &results {
+                        println!("  {} — {}", &book.id.to_string()[..8], book.title);
+                    }
                 }
             }
         }
@@ -403,6 +678,7 @@ fn main() -> Result<()> {
                         )?;
                     } else {
                         println!("{}", serde_json::to_string_pretty(&card)?);
+                        print_provenance(&card);
                     }
                 } else {
                     let db = open_db(&config)?;
@@ -602,8 +878,109 @@ fn main() -> Result<()> {
                     }
                 }
             }
+
+            BookAction::Lock { id, field } => {
+                set_field_lock(&config, &id, &field, true, json_output, start)?;
+            }
+
+            BookAction::Unlock { id, field } => {
+                set_field_lock(&config, &id, &field, false, json_output, start)?;
+            }
         },
 
+        Some(Commands::Add {
+            identifier,
+            doi,
+            arxiv,
+            isbn,
+            pmid,
+            pdf,
+            title,
+            author,
+            year,
+            tag,
+            library,
+            ..
+        }) if identifier.is_some()
+            || doi.is_some()
+            || arxiv.is_some()
+            || isbn.is_some()
+            || pmid.is_some() =>
+        {
+            use omniscope_science::arxiv::{ArxivAddOptions, ArxivAddService};
+
+            let science = omniscope_science::ScienceConfig::load().unwrap_or_default();
+            let opts = ArxivAddOptions {
+                download_pdf: pdf,
+                download_dir: science.download_directory.clone(),
+                auto_index: global_config.ai.auto_index_enabled(),
+            };
+            let db = resolve_db(&library_root, &config)?;
+            let cards_dir = resolve_cards_dir(&library_root, &config);
+            let service = ArxivAddService::from_env();
+            let runtime = tokio::runtime::Runtime::new()?;
+            let mut card = runtime.block_on(async {
+                if let Some(doi) = doi {
+                    service.add_from_doi(&doi, opts, &db).await
+                } else if let Some(arxiv) = arxiv {
+                    service.add_from_arxiv(&arxiv, opts, &db).await
+                } else if let Some(isbn) = isbn {
+                    service.add_from_isbn(&isbn, opts, &db).await
+                } else if let Some(pmid) = pmid {
+                    service.add_from_pmid(&pmid, opts, &db).await
+                } else {
+                    let identifier = identifier.unwrap_or_default();
+                    service.add_from_identifier(&identifier, opts, &db).await
+                }
+            })?;
+
+            // The service returns the library's card when it already has the work.
+            let existing =
+                omniscope_core::storage::json_cards::load_card_by_id(&cards_dir, &card.id).is_ok();
+            if !existing {
+                if let Some(t) = title {
+                    card.metadata.title = t;
+                }
+                if !author.is_empty() {
+                    card.metadata.authors = author;
+                }
+                if let Some(y) = year {
+                    card.metadata.year = Some(y);
+                }
+                for t in tag {
+                    if !card.organization.tags.contains(&t) {
+                        card.organization.tags.push(t);
+                    }
+                }
+                if let Some(lib) = library {
+                    card.organization.libraries.push(lib);
+                }
+                omniscope_core::storage::json_cards::save_card(&cards_dir, &card)?;
+                db.upsert_book(&card)?;
+            }
+            let dur = start.elapsed().as_millis();
+
+            if json_output {
+                print_json(&serde_json::json!({
+                    "status": "ok",
+                    "data": {
+                        "card": card,
+                        "existing": existing
+                    },
+                    "meta": { "duration_ms": dur }
+                }))?;
+            } else if existing {
+                println!("Already in library: {} ({})", card.metadata.title, card.id);
+            } else {
+                println!("Added: {} ({})", card.metadata.title, card.id);
+                match (&card.file, pdf) {
+                    (Some(file), _) => println!("  PDF: {}", file.path),
+                    (None, true) => println!("  No open-access PDF could be downloaded."),
+                    (None, false) => {}
+                }
+            }
+        }
+
         Some(Commands::Add {
             file,
             title,
@@ -611,6 +988,7 @@ fn main() -> Result<()> {
             year,
             tag,
             library,
+            ..
         }) => {
             let mut enrichment_report = None;
             let mut card = if let Some(ref file_path) = file {
@@ -647,93 +1025,963 @@ fn main() -> Result<()> {
                         "fields_updated": report.fields_updated.len(),
                         "warnings": report.errors.len(),
                     })
-                });
-                print_json(
-                    &serde_json::json!({"status":"ok","data":card,"enrichment":enrichment_json,"meta":{"duration_ms":dur}}),
-                )?;
+                });
+                print_json(
+                    &serde_json::json!({"status":"ok","data":card,"enrichment":enrichment_json,"meta":{"duration_ms":dur}}),
+                )?;
+            } else {
+                println!("Added: {} ({})", card.metadata.title, card.id);
+                if let Some(report) = enrichment_report {
+                    println!(
+                        "  Metadata: {} field(s) updated, {} warning(s)",
+                        report.fields_updated.len(),
+                        report.errors.len()
+                    );
+                }
+            }
+        }
+
+        Some(Commands::Import { dir, recursive }) => {
+            let cards = omniscope_core::file_import::scan_directory(Path::new(&dir), recursive)?;
+            let db = resolve_db(&library_root, &config)?;
+            let cards_dir = resolve_cards_dir(&library_root, &config);
+            let mut count = 0;
+            let mut created_count = 0usize;
+            let mut updated_existing_count = 0usize;
+            let mut cards_with_metadata_updates = 0usize;
+            let mut updated_fields_total = 0usize;
+            let mut warnings_total = 0usize;
+
+            let mut existing_by_path = std::collections::HashMap::new();
+            if let Ok(existing_cards) = omniscope_core::storage::json_cards::list_cards(&cards_dir)
+            {
+                for existing_card in existing_cards {
+                    if let Some(file) = existing_card.file.as_ref() {
+                        existing_by_path.insert(file.path.clone(), existing_card);
+                    }
+                }
+            }
+
+            for scanned_card in cards {
+                let (mut card, is_update) =
+                    if let Some(path) = scanned_card.file.as_ref().map(|file| file.path.clone()) {
+                        if let Some(existing_card) = existing_by_path.remove(&path) {
+                            updated_existing_count += 1;
+                            (existing_card, true)
+                        } else {
+                            created_count += 1;
+                            (scanned_card, false)
+                        }
+                    } else {
+                        created_count += 1;
+                        (scanned_card, false)
+                    };
+
+                let report = enrich_card_metadata(&mut card);
+                if !report.fields_updated.is_empty() {
+                    cards_with_metadata_updates += 1;
+                    updated_fields_total += report.fields_updated.len();
+                }
+                warnings_total += report.errors.len();
+
+                omniscope_core::storage::json_cards::save_card(&cards_dir, &card)?;
+                db.upsert_book(&card)?;
+                count += 1;
+                if !json_output {
+                    if is_update {
+                        println!("  Updated: {}", card.metadata.title);
+                    } else {
+                        println!("  Imported: {}", card.metadata.title);
+                    }
+                }
+            }
+            let dur = start.elapsed().as_millis();
+
+            if json_output {
+                print_json(&serde_json::json!({
+                    "status":"ok","data":{"processed":count,"created":created_count,"updated":updated_existing_count,"total":db.count_books()?,"metadata":{"cards_updated":cards_with_metadata_updates,"fields_updated":updated_fields_total,"warnings":warnings_total}},
+                    "meta":{"duration_ms":dur}
+                }))?;
+            } else {
+                println!(
+                    "Processed {count} book(s): created {created_count}, updated {updated_existing_count}."
+                );
+                println!(
+                    "Metadata enriched: {} card(s), {} field(s), {} warning(s).",
+                    cards_with_metadata_updates, updated_fields_total, warnings_total
+                );
+            }
+        }
+
+        Some(Commands::ImportBib { file, dry_run }) => {
+            use omniscope_science::bib_import::{self, ImportDecision};
+
+            let lr = require_library(&library_root, json_output)?;
+            let cards_dir = lr.cards_dir();
+            let candidates = bib_import::read_candidates(Path::new(&file), lr.root())?;
+            let existing = omniscope_core::storage::json_cards::list_cards(&cards_dir)?;
+            let plan = bib_import::plan_import(candidates, &existing);
+
+            let created = if dry_run {
+                0
+            } else {
+                let db = open_db_from_root(&lr)?;
+                bib_import::apply_import(&plan, &db, &cards_dir)?.len()
+            };
+            let to_create = plan.cards_to_create().count();
+            let unattached: usize = plan
+                .entries
+                .iter()
+                .map(|(candidate, _)| candidate.unresolved_files.len())
+                .sum();
+            let dur = start.elapsed().as_millis();
+
+            if json_output {
+                let entries: Vec<serde_json::Value> = plan
+                    .entries
+                    .iter()
+                    .map(|(candidate, decision)| {
+                        let mut item = serde_json::json!({
+                            "key": candidate.key,
+                            "title": candidate.card.metadata.title,
+                            "file": candidate.card.file.as_ref().map(|f| &f.path),
+                            "unresolved_files": candidate.unresolved_files,
+                        });
+                        match decision {
+                            ImportDecision::Create => {
+                                item["action"] = "create".into();
+                                item["id"] = candidate.card.id.to_string().into();
+                            }
+                            ImportDecision::Existing { id, matched_by } => {
+                                item["action"] = "skip".into();
+                                item["duplicate_of"] = id.to_string().into();
+                                item["matched_by"] = matched_by.as_str().into();
+                            }
+                            ImportDecision::Repeated { key, matched_by } => {
+                                item["action"] = "skip".into();
+                                item["repeats"] = key.as_str().into();
+                                item["matched_by"] = matched_by.as_str().into();
+                            }
+                        }
+                        item
+                    })
+                    .collect();
+                print_json(&serde_json::json!({
+                    "status": "ok",
+                    "data": {
+                        "file": file,
+                        "dry_run": dry_run,
+                        "entries": entries.len(),
+                        "to_create": to_create,
+                        "created": created,
+                        "duplicates": plan.duplicate_count(),
+                        "unattached_files": unattached,
+                        "items": entries,
+                    },
+                    "meta": { "duration_ms": dur }
+                }))?;
+            } else {
+                for (candidate, decision) in &plan.entries {
+                    let title = &candidate.card.metadata.title;
+                    match decision {
+                        ImportDecision::Create => {
+                            let attached = if candidate.card.file.is_some() {
+                                " (file attached)"
+                            } else {
+                                ""
+                            };
+                            println!("  + [{}] {title}{attached}", candidate.key);
+                        }
+                        ImportDecision::Existing { id, matched_by } => println!(
+                            "  = [{}] {title} — already in library ({} match, {id})",
+                            candidate.key,
+                            matched_by.as_str()
+                        ),
+                        ImportDecision::Repeated { key, matched_by } => println!(
+                            "  = [{}] {title} — repeats [{key}] ({} match)",
+                            candidate.key,
+                            matched_by.as_str()
+                        ),
+                    }
+                    for missing in &candidate.unresolved_files {
+                        println!("      file not attached: {missing}");
+                    }
+                }
+                if dry_run {
+                    println!(
+                        "Dry run: would import {to_create} of {} entries, skipping {} duplicate(s); {unattached} file(s) not attached.",
+                        plan.entries.len(),
+                        plan.duplicate_count()
+                    );
+                } else {
+                    println!(
+                        "Imported {created} of {} entries, skipped {} duplicate(s); {unattached} file(s) not attached.",
+                        plan.entries.len(),
+                        plan.duplicate_count()
+                    );
+                }
+            }
+        }
+
+        Some(Commands::Enrich {
+            query,
+            jobs,
+            restart,
+        }) => {
+            use omniscope_science::enrichment::{BatchEnricher, EnrichCheckpoint};
+
+            let lr = require_library(&library_root, json_output)?;
+            let parsed = omniscope_core::SearchQuery::parse(&query);
+            if parsed.semantic_text().is_some() {
+                anyhow::bail!(
+                    "`~` terms are not supported by enrich; narrow the set with DSL filters"
+                );
+            }
+            let cards_dir = lr.cards_dir();
+            let cards = omniscope_science::export::select_cards(
+                omniscope_core::storage::json_cards::list_cards(&cards_dir)?,
+                &parsed,
+            );
+            let checkpoint_path = EnrichCheckpoint::path(&lr);
+            if restart {
+                EnrichCheckpoint::clear(&checkpoint_path)?;
+            }
+            let mut checkpoint = EnrichCheckpoint::for_query(&checkpoint_path, &query)?;
+            let db = open_db_from_root(&lr)?;
+
+            let total = cards.len();
+            let mut done = cards
+                .iter()
+                .filter(|card| checkpoint.completed.contains(&card.id))
+                .count();
+            if !json_output && done > 0 {
+                println!("Resuming: {done} of {total} book(s) already enriched.");
+            }
+
+            let enricher = BatchEnricher::new(EnrichmentPipeline::from_env(), jobs);
+            let runtime = tokio::runtime::Runtime::new()?;
+            let outcome = runtime.block_on(async {
+                let run = enricher.run(
+                    cards,
+                    &mut checkpoint,
+                    &checkpoint_path,
+                    |card, report| -> Result<()> {
+                        if !report.fields_updated.is_empty() {
+                            omniscope_core::storage::json_cards::save_card(&cards_dir, card)?;
+                            db.upsert_book(card)?;
+                        }
+                        done += 1;
+                        if !json_output {
+                            println!(
+                                "  [{done}/{total}] {} field(s), {} warning(s)  {}",
+                                report.fields_updated.len(),
+                                report.errors.len(),
+                                card.metadata.title
+                            );
+                        }
+                        Ok(())
+                    },
+                );
+                tokio::select! {
+                    result = run => Some(result),
+                    _ = tokio::signal::ctrl_c() => None,
+                }
+            });
+            checkpoint.save(&checkpoint_path)?;
+            let dur = start.elapsed().as_millis();
+
+            let Some(result) = outcome else {
+                let completed = checkpoint.completed.len();
+                if json_output {
+                    print_json(&serde_json::json!({
+                        "status": "interrupted",
+                        "data": {
+                            "query": query,
+                            "selected": total,
+                            "completed": completed,
+                            "checkpoint": checkpoint_path,
+                        },
+                        "meta": { "duration_ms": dur }
+                    }))?;
+                } else {
+                    eprintln!(
+                        "\nInterrupted after {completed} of {total} book(s); run the same command again to resume."
+                    );
+                }
+                std::process::exit(130);
+            };
+            let summary = result?;
+            if summary.failed == 0 {
+                EnrichCheckpoint::clear(&checkpoint_path)?;
+            }
+
+            if json_output {
+                print_json(&serde_json::json!({
+                    "status": "ok",
+                    "data": {
+                        "query": query,
+                        "selected": summary.selected,
+                        "resumed": summary.resumed,
+                        "enriched": summary.enriched,
+                        "updated": summary.updated,
+                        "failed": summary.failed,
+                        "fields": summary.field_counts,
+                        "sources": summary.report.sources_used,
+                        "warnings": summary.report.errors,
+                    },
+                    "meta": { "duration_ms": dur }
+                }))?;
+            } else {
+                println!(
+                    "Enriched {} of {} book(s): {} updated, {} failed, {} done in an earlier run.",
+                    summary.enriched,
+                    summary.selected,
+                    summary.updated,
+                    summary.failed,
+                    summary.resumed
+                );
+                if !summary.field_counts.is_empty() {
+                    let fields = summary
+                        .field_counts
+                        .iter()
+                        .map(|(field, count)| format!("{field} {count}"))
+                        .collect::<Vec<_>>();
+                    println!("Fields: {}", fields.join(", "));
+                }
+                if !summary.report.sources_used.is_empty() {
+                    println!("Sources: {}", summary.report.sources_used.join(", "));
+                }
+                if !summary.report.errors.is_empty() {
+                    println!(
+                        "{} warning(s); use --json to list them.",
+                        summary.report.errors.len()
+                    );
+                }
+                if summary.failed > 0 {
+                    println!(
+                        "{} book(s) were not saved or could not reach their sources; run the same command again to retry them.",
+                        summary.failed
+                    );
+                }
+            }
+        }
+
+        // ── Export ─────────────────────────────────────────────────────────
+        Some(Commands::Export {
+            format,
+            query,
+            folder,
+            out,
+        }) => {
+            use omniscope_science::export::{self, ExportFormat};
+
+            let science = omniscope_science::ScienceConfig::load()?;
+            let export_format = ExportFormat::resolve(&format, &science.export)?;
+            let parsed = omniscope_core::SearchQuery::parse(&query);
+            if parsed.semantic_text().is_some() {
+                anyhow::bail!(
+                    "`~` terms are not supported by export; narrow the set with DSL filters"
+                );
+            }
+            let cards_dir = resolve_cards_dir(&library_root, &config);
+            let mut cards = omniscope_core::storage::json_cards::list_cards(&cards_dir)?;
+            if let Some(folder) = &folder {
+                let db = resolve_db(&library_root, &config)?;
+                let root = library_root
+                    .as_ref()
+                    .map(|lr| lr.root().to_path_buf())
+                    .unwrap_or_else(|| config.library_path());
+                let (ids, dirs) = resolve_folder_subtree(&db, &root, folder)?;
+                cards.retain(|card| {
+                    card.organization.folders.iter().any(|id| ids.contains(id))
+                        || card.file.as_ref().is_some_and(|file| {
+                            dirs.iter()
+                                .any(|dir| Path::new(&file.path).starts_with(dir))
+                        })
+                });
+            }
+            let selected = export::select_cards(cards, &parsed);
+            let refs: Vec<&BookCard> = selected.iter().collect();
+            let rendered = export::render(&refs, &export_format, &science.export)?;
+            let dur = start.elapsed().as_millis();
+
+            if let Some(path) = &out {
+                std::fs::write(path, &rendered)?;
+                if json_output {
+                    print_json(&serde_json::json!({
+                        "status": "ok",
+                        "data": {
+                            "format": format,
+                            "query": query,
+                            "count": refs.len(),
+                            "path": path,
+                        },
+                        "meta": { "duration_ms": dur }
+                    }))?;
+                } else {
+                    eprintln!("Exported {} book(s) to {path}", refs.len());
+                }
+            } else if json_output {
+                print_json(&serde_json::json!({
+                    "status": "ok",
+                    "data": {
+                        "format": format,
+                        "query": query,
+                        "count": refs.len(),
+                        "content": rendered,
+                    },
+                    "meta": { "duration_ms": dur }
+                }))?;
+            } else {
+                print!("{rendered}");
+            }
+        }
+
+        // ── Bib sync ───────────────────────────────────────────────────────
+        Some(Commands::BibSync {
+            query,
+            out,
+            save,
+            remove,
+            watch,
+        }) => {
+            use omniscope_core::models::BibSyncTarget;
+
+            let lr = require_library(&library_root, json_output)?;
+            let mut manifest = lr.load_manifest()?;
+
+            if remove {
+                let Some(out) = out else {
+                    anyhow::bail!("--remove needs the --out of the target to drop");
+                };
+                let before = manifest.bib_sync.len();
+                manifest.bib_sync.retain(|target| target.out != out);
+                let removed = before - manifest.bib_sync.len();
+                lr.save_manifest(&manifest)?;
+                let dur = start.elapsed().as_millis();
+                if json_output {
+                    print_json(&serde_json::json!({
+                        "status": "ok",
+                        "data": { "out": out, "removed": removed },
+                        "meta": { "duration_ms": dur }
+                    }))?;
+                } else if removed == 0 {
+                    println!("No bib-sync target writes to {out}");
+                } else {
+                    println!("Stopped syncing {out}");
+                }
+            } else {
+                let targets = match (query, out) {
+                    (Some(query), Some(out)) => {
+                        let target = BibSyncTarget { query, out };
+                        if save {
+                            manifest.bib_sync.retain(|t| t.out != target.out);
+                            manifest.bib_sync.push(target.clone());
+                            lr.save_manifest(&manifest)?;
+                        }
+                        vec![target]
+                    }
+                    (None, None) => manifest.bib_sync.clone(),
+                    _ => anyhow::bail!("--query and --out must be given together"),
+                };
+                if targets.is_empty() {
+                    anyhow::bail!(
+                        "no bib-sync targets in library.toml; add one with --query, --out and --save"
+                    );
+                }
+
+                let export = omniscope_science::ScienceConfig::load()?.export;
+                let results = run_bib_sync(&lr, &targets, &export);
+                let dur = start.elapsed().as_millis();
+                if json_output {
+                    let items: Vec<serde_json::Value> = results
+                        .iter()
+                        .map(|(target, result)| match result {
+                            Ok(report) => serde_json::json!({
+                                "query": target.query,
+                                "out": report.path,
+                                "entries": report.entries,
+                                "new_keys": report.keyed.len(),
+                                "written": report.written,
+                            }),
+                            Err(err) => serde_json::json!({
+                                "query": target.query,
+                                "out": target.out,
+                                "error": err.to_string(),
+                            }),
+                        })
+                        .collect();
+                    print_json(&serde_json::json!({
+                        "status": "ok",
+                        "data": { "items": items, "saved": save },
+                        "meta": { "duration_ms": dur }
+                    }))?;
+                } else {
+                    print_bib_sync(&results, true);
+                }
+
+                if watch {
+                    let (_watcher, events) = omniscope_core::sync::CardsWatcher::start(
+                        &lr.cards_dir(),
+                        std::time::Duration::from_millis(500),
+                    )?;
+                    if !json_output {
+                        eprintln!("Watching for card changes (Ctrl-C to stop)…");
+                    }
+                    while events.recv().is_ok() {
+                        // One regeneration per burst of card writes.
+                        while events.try_recv().is_ok() {}
+                        let results = run_bib_sync(&lr, &targets, &export);
+                        if !json_output {
+                            print_bib_sync(&results, false);
+                        }
+                    }
+                }
+            }
+        }
+
+        // ── Dedup ──────────────────────────────────────────────────────────
+        Some(Commands::Dedup { strategy, apply }) => {
+            use omniscope_science::dedup::{self, DedupStrategy, DuplicateFinder};
+
+            let lr = require_library(&library_root, json_output)?;
+            let cards_dir = lr.cards_dir();
+            let cards = omniscope_core::storage::json_cards::list_cards(&cards_dir)?;
+            let finder = DuplicateFinder::new();
+            let groups = match strategy.as_deref() {
+                Some(name) => finder.find(&cards, DedupStrategy::parse(name)?),
+                None => finder.find_all(&cards),
+            };
+            let by_id: std::collections::HashMap<uuid::Uuid, &BookCard> =
+                cards.iter().map(|card| (card.id, card)).collect();
+            let members = |group: &dedup::DuplicateGroup| -> Vec<&BookCard> {
+                std::iter::once(&group.canonical)
+                    .chain(&group.duplicates)
+                    .filter_map(|id| by_id.get(id).copied())
+                    .collect()
+            };
+
+            let mut log_id = None;
+            if apply && !groups.is_empty() {
+                // Journal first, so a merge that fails halfway can be reversed.
+                let before: Vec<BookCard> = groups.iter().flat_map(&members).cloned().collect();
+                let groups_json: Vec<serde_json::Value> = groups
+                    .iter()
+                    .map(|group| {
+                        serde_json::json!({
+                            "canonical": group.canonical,
+                            "duplicates": group.duplicates,
+                        })
+                    })
+                    .collect();
+                let db = open_db_from_root(&lr)?;
+                log_id = Some(
+                    omniscope_ai::actions::ActionExecutor::new(&db, &cards_dir).journal(
+                        "merge_duplicates",
+                        serde_json::json!({ "groups": groups_json }),
+                        &before,
+                    )?,
+                );
+
+                for group in &groups {
+                    dedup::merge_group(&group.canonical, &group.duplicates, &db, &cards_dir)?;
+                }
+            }
+            let dur = start.elapsed().as_millis();
+
+            if json_output {
+                let items: Vec<serde_json::Value> = groups
+                    .iter()
+                    .map(|group| {
+                        let cards: Vec<serde_json::Value> = members(group)
+                            .into_iter()
+                            .map(|card| {
+                                serde_json::json!({
+                                    "id": card.id,
+                                    "title": card.metadata.title,
+                                    "authors": card.metadata.authors,
+                                    "year": card.metadata.year,
+                                    "doi": card.identifiers.as_ref().and_then(|ids| ids.doi.as_ref()),
+                                    "file": card.file.as_ref().map(|file| &file.path),
+                                    "canonical": card.id == group.canonical,
+                                })
+                            })
+                            .collect();
+                        serde_json::json!({
+                            "strategy": group.strategy.as_str(),
+                            "canonical": group.canonical,
+                            "cards": cards,
+                        })
+                    })
+                    .collect();
+                print_json(&serde_json::json!({
+                    "status": "ok",
+                    "data": { "groups": items, "applied": apply, "log_id": log_id },
+                    "meta": { "duration_ms": dur }
+                }))?;
+            } else if groups.is_empty() {
+                println!("No duplicates found.");
             } else {
-                println!("Added: {} ({})", card.metadata.title, card.id);
-                if let Some(report) = enrichment_report {
-                    println!(
-                        "  Metadata: {} field(s) updated, {} warning(s)",
-                        report.fields_updated.len(),
-                        report.errors.len()
-                    );
+                for group in &groups {
+                    println!("[{}]", group.strategy.as_str());
+                    for card in members(group) {
+                        let role = if card.id == group.canonical {
+                            "keep "
+                        } else {
+                            "merge"
+                        };
+                        let mut line = format!(
+                            "  {role} {}  {}",
+                            &card.id.to_string()[..8],
+                            card.metadata.title
+                        );
+                        if !card.metadata.authors.is_empty() {
+                            line.push_str(&format!(" — {}", card.metadata.authors.join(", ")));
+                        }
+                        if let Some(year) = card.metadata.year {
+                            line.push_str(&format!(" ({year})"));
+                        }
+                        println!("{line}");
+                    }
+                }
+                match &log_id {
+                    Some(id) => println!(
+                        "\nMerged {} group(s); journal entry {id} holds the previous cards",
+                        groups.len()
+                    ),
+                    None => println!(
+                        "\n{} group(s) found. Run with --apply to merge them.",
+                        groups.len()
+                    ),
                 }
             }
         }
 
-        Some(Commands::Import { dir, recursive }) => {
-            let cards = omniscope_core::file_import::scan_directory(Path::new(&dir), recursive)?;
-            let db = resolve_db(&library_root, &config)?;
-            let cards_dir = resolve_cards_dir(&library_root, &config);
-            let mut count = 0;
-            let mut created_count = 0usize;
-            let mut updated_existing_count = 0usize;
-            let mut cards_with_metadata_updates = 0usize;
-            let mut updated_fields_total = 0usize;
-            let mut warnings_total = 0usize;
+        // ── arXiv ──────────────────────────────────────────────────────────
+        Some(Commands::Arxiv { action }) => match action {
+            ArxivAction::CheckUpdates {
+                apply,
+                download_pdf,
+                force,
+            } => {
+                use omniscope_science::arxiv::{ArxivApplyOptions, ArxivUpdateState, ArxivUpdater};
 
-            let mut existing_by_path = std::collections::HashMap::new();
-            if let Ok(existing_cards) = omniscope_core::storage::json_cards::list_cards(&cards_dir)
-            {
-                for existing_card in existing_cards {
-                    if let Some(file) = existing_card.file.as_ref() {
-                        existing_by_path.insert(file.path.clone(), existing_card);
+                let lr = require_library(&library_root, json_output)?;
+                let cards_dir = lr.cards_dir();
+                let science = omniscope_science::ScienceConfig::load().unwrap_or_default();
+                let interval = if force {
+                    chrono::Duration::zero()
+                } else {
+                    science.arxiv.update_check_interval()
+                };
+                let state_path = ArxivUpdateState::path(&lr);
+                let mut state = ArxivUpdateState::load(&state_path)?;
+                let updater = ArxivUpdater::from_db(std::sync::Arc::new(open_db_from_root(&lr)?));
+                let runtime = tokio::runtime::Runtime::new()?;
+
+                // Save even on failure: the checks that did finish still count.
+                let checked = runtime.block_on(updater.check_due_updates(&mut state, interval));
+                state.save(&state_path)?;
+                let found = checked?;
+
+                let mut applied = Vec::new();
+                if apply {
+                    let opts = ArxivApplyOptions {
+                        download_pdf,
+                        download_dir: science.download_directory.clone(),
+                    };
+                    for update in state.pending.clone() {
+                        let result =
+                            runtime.block_on(updater.apply_to_library(&update, &cards_dir, &opts));
+                        match result {
+                            Ok(outcome) => {
+                                state.take(&update.book_id);
+                                applied.push((update, outcome));
+                            }
+                            Err(err) => {
+                                state.save(&state_path)?;
+                                return Err(err.into());
+                            }
+                        }
+                    }
+                    state.save(&state_path)?;
+                }
+                let dur = start.elapsed().as_millis();
+
+                let cards = omniscope_core::storage::json_cards::list_cards(&cards_dir)?;
+                let card_for = |id: &uuid::Uuid| cards.iter().find(|card| card.id == *id);
+                if json_output {
+                    let describe = |update: &omniscope_science::arxiv::ArxivUpdateResult,
+                                    card: Option<&BookCard>| {
+                        serde_json::json!({
+                            "id": update.book_id,
+                            "arxiv_id": update.arxiv_id.id,
+                            "old_version": update.old_version,
+                            "new_version": update.new_version,
+                            "title": update.new_metadata.title,
+                            "changes": card.map(|card| update.diff(card)),
+                        })
+                    };
+                    let pending: Vec<serde_json::Value> = state
+                        .pending
+                        .iter()
+                        .map(|update| describe(update, card_for(&update.book_id)))
+                        .collect();
+                    let applied: Vec<serde_json::Value> = applied
+                        .iter()
+                        .map(|(update, outcome)| {
+                            let mut item = describe(update, Some(&outcome.previous));
+                            item["file"] = outcome
+                                .card
+                                .file
+                                .as_ref()
+                                .map(|file| file.path.clone())
+                                .into();
+                            item
+                        })
+                        .collect();
+                    print_json(&serde_json::json!({
+                        "status": "ok",
+                        "data": {
+                            "found": found.len(),
+                            "pending": pending,
+                            "applied": applied,
+                            "last_run": state.last_run,
+                        },
+                        "meta": { "duration_ms": dur }
+                    }))?;
+                } else {
+                    let print_update =
+                        |update: &omniscope_science::arxiv::ArxivUpdateResult,
+                         card: Option<&BookCard>| {
+                            println!(
+                                "  {}  arXiv:{} {}  {}",
+                                &update.book_id.to_string()[..8],
+                                update.arxiv_id.id,
+                                update.version_label(),
+                                update.new_metadata.title
+                            );
+                            let Some(diff) = card.map(|card| update.diff(card)) else {
+                                return;
+                            };
+                            if let Some((old, new)) = &diff.title {
+                                println!("      title:    {old}\n             → {new}");
+                            }
+                            if let Some((old, new)) = &diff.authors {
+                                println!(
+                                    "      authors:  {}\n             → {}",
+                                    old.join(", "),
+                                    new.join(", ")
+                                );
+                            }
+                            if diff.abstract_text.is_some() {
+                                println!("      abstract: changed");
+                            }
+                        };
+
+                    println!("Found {} new version(s).", found.len());
+                    if !applied.is_empty() {
+                        println!("Applied {} update(s):", applied.len());
+                        for (update, outcome) in &applied {
+                            print_update(update, Some(&outcome.previous));
+                            if download_pdf && let Some(file) = &outcome.card.file {
+                                println!("      pdf:      {}", file.path);
+                            }
+                        }
+                    }
+                    if !state.pending.is_empty() {
+                        println!("Pending update(s):");
+                        for update in &state.pending {
+                            print_update(update, card_for(&update.book_id));
+                        }
+                        println!("Run with --apply to update the cards.");
                     }
                 }
             }
 
-            for scanned_card in cards {
-                let (mut card, is_update) =
-                    if let Some(path) = scanned_card.file.as_ref().map(|file| file.path.clone()) {
-                        if let Some(existing_card) = existing_by_path.remove(&path) {
-                            updated_existing_count += 1;
-                            (existing_card, true)
+            ArxivAction::Feed { action } => {
+                use omniscope_core::models::ArxivFeed;
+                use omniscope_science::arxiv::feeds::fetch_feed;
+                use omniscope_science::arxiv::{ArxivFeedState, feed_terms};
+
+                let lr = require_library(&library_root, json_output)?;
+                let mut manifest = lr.load_manifest()?;
+                let state_path = ArxivFeedState::path(&lr);
+                let mut state = ArxivFeedState::load(&state_path)?;
+
+                match action {
+                    FeedAction::Add {
+                        name,
+                        categories,
+                        authors,
+                        keywords,
+                    } => {
+                        let feed = ArxivFeed {
+                            name: name.clone(),
+                            categories,
+                            authors,
+                            keywords,
+                        };
+                        if feed.is_empty() {
+                            anyhow::bail!(
+                                "a feed needs at least one --category, --author or --keyword"
+                            );
+                        }
+                        let replaced = manifest.arxiv_feeds.iter().any(|f| f.name == name);
+                        manifest.arxiv_feeds.retain(|f| f.name != name);
+                        manifest.arxiv_feeds.push(feed.clone());
+                        lr.save_manifest(&manifest)?;
+                        let dur = start.elapsed().as_millis();
+                        if json_output {
+                            print_json(&serde_json::json!({
+                                "status": "ok",
+                                "data": { "feed": feed, "replaced": replaced },
+                                "meta": { "duration_ms": dur }
+                            }))?;
                         } else {
-                            created_count += 1;
-                            (scanned_card, false)
+                            let verb = if replaced { "Updated" } else { "Saved" };
+                            println!("{verb} feed '{name}': {}", feed_terms(&feed));
                         }
-                    } else {
-                        created_count += 1;
-                        (scanned_card, false)
-                    };
+                    }
 
-                let report = enrich_card_metadata(&mut card);
-                if !report.fields_updated.is_empty() {
-                    cards_with_metadata_updates += 1;
-                    updated_fields_total += report.fields_updated.len();
-                }
-                warnings_total += report.errors.len();
+                    FeedAction::List => {
+                        let dur = start.elapsed().as_millis();
+                        let new_count = |feed: &ArxivFeed| {
+                            state
+                                .history(&feed.name)
+                                .map(|history| history.new_count())
+                                .unwrap_or(0)
+                        };
+                        if json_output {
+                            let items: Vec<serde_json::Value> = manifest
+                                .arxiv_feeds
+                                .iter()
+                                .map(|feed| {
+                                    let history = state.history(&feed.name);
+                                    serde_json::json!({
+                                        "name": feed.name,
+                                        "query": feed_terms(feed),
+                                        "new": new_count(feed),
+                                        "fetched_at": history.and_then(|h| h.fetched_at),
+                                        "last_visit": history.and_then(|h| h.last_visit),
+                                    })
+                                })
+                                .collect();
+                            print_json(&serde_json::json!({
+                                "status": "ok",
+                                "data": items,
+                                "meta": { "duration_ms": dur }
+                            }))?;
+                        } else if manifest.arxiv_feeds.is_empty() {
+                            println!("No saved feeds. Add one with `arxiv feed add`.");
+                        } else {
+                            for feed in &manifest.arxiv_feeds {
+                                println!(
+                                    "  {:<20} {:>4} new  {}",
+                                    feed.name,
+                                    new_count(feed),
+                                    feed_terms(feed)
+                                );
+                            }
+                        }
+                    }
 
-                omniscope_core::storage::json_cards::save_card(&cards_dir, &card)?;
-                db.upsert_book(&card)?;
-                count += 1;
-                if !json_output {
-                    if is_update {
-                        println!("  Updated: {}", card.metadata.title);
-                    } else {
-                        println!("  Imported: {}", card.metadata.title);
+                    FeedAction::Remove { name } => {
+                        let before = manifest.arxiv_feeds.len();
+                        manifest.arxiv_feeds.retain(|feed| feed.name != name);
+                        let removed = before - manifest.arxiv_feeds.len();
+                        lr.save_manifest(&manifest)?;
+                        state.retain_feeds(&manifest.arxiv_feeds);
+                        state.save(&state_path)?;
+                        let dur = start.elapsed().as_millis();
+                        if json_output {
+                            print_json(&serde_json::json!({
+                                "status": "ok",
+                                "data": { "name": name, "removed": removed },
+                                "meta": { "duration_ms": dur }
+                            }))?;
+                        } else if removed == 0 {
+                            println!("No feed named '{name}'");
+                        } else {
+                            println!("Removed feed '{name}'");
+                        }
                     }
-                }
-            }
-            let dur = start.elapsed().as_millis();
 
-            if json_output {
-                print_json(&serde_json::json!({
-                    "status":"ok","data":{"processed":count,"created":created_count,"updated":updated_existing_count,"total":db.count_books()?,"metadata":{"cards_updated":cards_with_metadata_updates,"fields_updated":updated_fields_total,"warnings":warnings_total}},
-                    "meta":{"duration_ms":dur}
-                }))?;
-            } else {
-                println!(
-                    "Processed {count} book(s): created {created_count}, updated {updated_existing_count}."
-                );
-                println!(
-                    "Metadata enriched: {} card(s), {} field(s), {} warning(s).",
-                    cards_with_metadata_updates, updated_fields_total, warnings_total
-                );
+                    FeedAction::Show { name, refresh } => {
+                        let Some(feed) = manifest.arxiv_feeds.iter().find(|f| f.name == name)
+                        else {
+                            anyhow::bail!("no feed named '{name}'");
+                        };
+                        let science = omniscope_science::ScienceConfig::load().unwrap_or_default();
+                        let now = chrono::Utc::now();
+                        let stale = state.history(&name).is_none_or(|history| {
+                            history.is_stale(now, science.arxiv.feed_refresh_interval())
+                        });
+                        if refresh || stale {
+                            let client = omniscope_science::arxiv::client::ArxivClient::new();
+                            let runtime = tokio::runtime::Runtime::new()?;
+                            let entries = runtime.block_on(fetch_feed(
+                                &client,
+                                feed,
+                                science.arxiv.feed_max_results,
+                            ))?;
+                            state.store(&name, entries, now);
+                        }
+                        let history = state.history(&name).cloned().unwrap_or_default();
+                        state.mark_visited(&name, now);
+                        state.save(&state_path)?;
+                        let dur = start.elapsed().as_millis();
+
+                        if json_output {
+                            let items: Vec<serde_json::Value> = history
+                                .entries
+                                .iter()
+                                .map(|entry| {
+                                    serde_json::json!({
+                                        "arxiv_id": entry.arxiv_id.id,
+                                        "title": entry.title,
+                                        "authors": entry.authors.iter().map(|a| &a.name).collect::<Vec<_>>(),
+                                        "published": entry.published,
+                                        "primary_category": entry.primary_category,
+                                        "new": history.is_new(entry),
+                                    })
+                                })
+                                .collect();
+                            print_json(&serde_json::json!({
+                                "status": "ok",
+                                "data": {
+                                    "name": name,
+                                    "fetched_at": history.fetched_at,
+                                    "entries": items,
+                                },
+                                "meta": { "duration_ms": dur }
+                            }))?;
+                        } else if history.entries.is_empty() {
+                            println!("No submissions match '{name}'.");
+                        } else {
+                            println!(
+                                "{} — {} new of {}",
+                                name,
+                                history.new_count(),
+                                history.entries.len()
+                            );
+                            for entry in &history.entries {
+                                let marker = if history.is_new(entry) { "*" } else { " " };
+                                let authors: Vec<&str> =
+                                    entry.authors.iter().map(|a| a.name.as_str()).collect();
+                                println!(
+                                    "{marker} {}  {}  {}\n      {}",
+                                    entry.published.format("%Y-%m-%d"),
+                                    entry.arxiv_id.id,
+                                    entry.title,
+                                    authors.join(", ")
+                                );
+                            }
+                        }
+                    }
+                }
             }
-        }
+        },
 
         // ── Tag ────────────────────────────────────────────────────────────
         Some(Commands::Tag { action }) => match action {
@@ -1345,6 +2593,183 @@ fn main() -> Result<()> {
             }
         }
 
+        // ── Serve ──────────────────────────────────────────────────────────
+        Some(Commands::Serve { bind }) => {
+            let lr = require_library(&library_root, json_output)?;
+            let library = std::sync::Arc::new(
+                omniscope_server::LibraryHandle::open(lr)?.with_ai_config(&global_config.ai),
+            );
+            let _watchers = omniscope_server::feed::start_watchers(library.clone())?;
+            let state =
+                omniscope_server::api::ApiState::from_config(library, &global_config.server)?;
+            let addr = bind.unwrap_or_else(|| {
+                format!(
+                    "{}:{}",
+                    global_config.server.host, global_config.server.port
+                )
+            });
+            let addr: std::net::SocketAddr = addr.parse()?;
+            println!("Serving REST API on http://{addr}/api");
+            tokio::runtime::Runtime::new()?.block_on(omniscope_server::api::serve(state, addr))?;
+        }
+
+        // ── MCP ────────────────────────────────────────────────────────────
+        Some(Commands::Mcp { http, bind }) => {
+            let lr = require_library(&library_root, json_output)?;
+            let library = std::sync::Arc::new(
+                omniscope_server::LibraryHandle::open(lr)?.with_ai_config(&global_config.ai),
+            );
+            let server = omniscope_server::mcp::McpServer::new(library);
+            let runtime = tokio::runtime::Runtime::new()?;
+            if http {
+                let addr = bind.unwrap_or_else(|| {
+                    format!(
+                        "{}:{}",
+                        global_config.server.host, global_config.server.port
+                    )
+                });
+                let addr: std::net::SocketAddr = addr.parse()?;
+                let token = omniscope_server::api::BearerToken::from_env(&global_config.server)
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "set {} to the bearer token clients must send",
+                            global_config.server.auth_token_env
+                        )
+                    })?;
+                eprintln!("MCP server listening on http://{addr}/mcp");
+                runtime.block_on(omniscope_server::mcp::serve_http(server, token, addr))?;
+            } else {
+                runtime.block_on(omniscope_server::mcp::serve_stdio(server))?;
+            }
+        }
+
+        // ── AI ─────────────────────────────────────────────────────────────
+        Some(Commands::Ai { action }) => match action {
+            AiAction::Usage { daily, monthly } => {
+                let budget = omniscope_ai::budget::TokenBudget::workstation();
+                if daily.is_some() || monthly.is_some() {
+                    budget.update(|profile| {
+                        profile.daily_token_budget = daily.unwrap_or(profile.daily_token_budget);
+                        profile.monthly_token_budget =
+                            monthly.unwrap_or(profile.monthly_token_budget);
+                    })?;
+                }
+                let profile = budget.profile()?;
+                let dur = start.elapsed().as_millis();
+
+                if json_output {
+                    print_json(&serde_json::json!({
+                        "status": "ok",
+                        "data": {
+                            "profile": budget.path(),
+                            "date": profile.usage_date,
+                            "daily": {
+                                "used": profile.tokens_used_today,
+                                "budget": profile.daily_token_budget,
+                                "remaining": profile.remaining_today(),
+                            },
+                            "monthly": {
+                                "used": profile.tokens_used_month,
+                                "budget": profile.monthly_token_budget,
+                                "remaining": profile.remaining_month(),
+                            },
+                            "fallback": global_config.ai.budget_fallback,
+                        },
+                        "meta": { "duration_ms": dur }
+                    }))?;
+                } else {
+                    let line = |used: u64, budget: u64| {
+                        if budget == 0 {
+                            format!("{used} tokens (no limit)")
+                        } else {
+                            let pct = used as f64 * 100.0 / budget as f64;
+                            format!("{used} / {budget} tokens ({pct:.1}%)")
+                        }
+                    };
+                    println!(
+                        "Today:      {}",
+                        line(profile.tokens_used_today, profile.daily_token_budget)
+                    );
+                    println!(
+                        "This month: {}",
+                        line(profile.tokens_used_month, profile.monthly_token_budget)
+                    );
+                    match &global_config.ai.budget_fallback {
+                        Some(fallback) => println!("When spent: falls back to {fallback}"),
+                        None => println!("When spent: AI calls are refused"),
+                    }
+                    println!("Profile:    {}", budget.path().display());
+                }
+            }
+            AiAction::Index { all, id, force } => {
+                let db = resolve_db(&library_root, &config)?;
+                let cards_dir = resolve_cards_dir(&library_root, &config);
+                let ids: Vec<uuid::Uuid> = if !id.is_empty() {
+                    id.iter()
+                        .map(|raw| uuid::Uuid::parse_str(raw))
+                        .collect::<std::result::Result<_, _>>()?
+                } else if all && force {
+                    omniscope_core::storage::json_cards::list_cards(&cards_dir)?
+                        .into_iter()
+                        .filter(|card| card.file.is_some())
+                        .map(|card| card.id)
+                        .collect()
+                } else if all {
+                    omniscope_ai::index::cards_needing_index(&cards_dir)?
+                } else {
+                    anyhow::bail!("pass --all or --id <ID>");
+                };
+
+                let provider = omniscope_ai::provider::from_config(&global_config.ai)?;
+                let total = ids.len();
+                let report =
+                    tokio::runtime::Runtime::new()?.block_on(omniscope_ai::index::index_cards(
+                        &db,
+                        &cards_dir,
+                        provider.as_ref(),
+                        &ids,
+                        |position, title| {
+                            if !json_output {
+                                eprintln!("[{}/{total}] {title}", position + 1);
+                            }
+                        },
+                    ))?;
+                let dur = start.elapsed().as_millis();
+
+                let describe = |items: &[(uuid::Uuid, String)]| -> Vec<serde_json::Value> {
+                    items
+                        .iter()
+                        .map(|(id, reason)| serde_json::json!({"id": id, "reason": reason}))
+                        .collect()
+                };
+                if json_output {
+                    print_json(&serde_json::json!({
+                        "status": "ok",
+                        "data": {
+                            "indexed": report.indexed,
+                            "skipped": describe(&report.skipped),
+                            "failed": describe(&report.failed),
+                            "stopped": report.stopped,
+                        },
+                        "meta": { "duration_ms": dur }
+                    }))?;
+                } else {
+                    println!(
+                        "Indexed {} of {total} book(s); {} skipped, {} failed",
+                        report.indexed.len(),
+                        report.skipped.len(),
+                        report.failed.len()
+                    );
+                    for (id, reason) in &report.failed {
+                        println!("  {} — {reason}", &id.to_string()[..8]);
+                    }
+                    if let Some(reason) = &report.stopped {
+                        println!("Stopped early: {reason}");
+                    }
+                }
+            }
+        },
+
         // ── Libraries ──────────────────────────────────────────────────────
         Some(Commands::Libraries { action }) => match action {
             LibrariesAction::List => {
@@ -1464,6 +2889,238 @@ fn require_library(library_root: &Option<LibraryRoot>, json_output: bool) -> Res
     }
 }
 
+type BibSyncResult = (
+    omniscope_core::models::BibSyncTarget,
+    omniscope_science::Result<omniscope_science::bib_sync::BibSyncReport>,
+);
+
+fn run_bib_sync(
+    lr: &LibraryRoot,
+    targets: &[omniscope_core::models::BibSyncTarget],
+    export: &omniscope_science::config::ExportConfig,
+) -> Vec<BibSyncResult> {
+    let cards_dir = lr.cards_dir();
+    targets
+        .iter()
+        .map(|target| {
+            let report =
+                omniscope_science::bib_sync::sync_target(target, lr.root(), &cards_dir, export);
+            (target.clone(), report)
+        })
+        .collect()
+}
+
+/// Print one line per target; with `verbose` off, unchanged files are skipped.
+fn print_bib_sync(results: &[BibSyncResult], verbose: bool) {
+    for (target, result) in results {
+        match result {
+            Ok(report) if report.written || verbose => {
+                let state = if report.written {
+                    "updated"
+                } else {
+                    "unchanged"
+                };
+                println!(
+                    "{}: {} entries, {} new cite key(s), {state}",
+                    report.path.display(),
+                    report.entries,
+                    report.keyed.len()
+                );
+            }
+            Ok(_) => {}
+            Err(err) => eprintln!("{} ({}): {err}", target.out, target.query),
+        }
+    }
+}
+
+/// Folder ids and on-disk directories for `spec` (an id, id prefix, name or
+/// path) and everything below it.
+fn resolve_folder_subtree(
+    db: &Database,
+    library_root: &Path,
+    spec: &str,
+) -> Result<(std::collections::HashSet<String>, Vec<PathBuf>)> {
+    let folders = db.list_all_folders()?;
+    let spec = spec.trim().trim_end_matches('/');
+    let start = folders
+        .iter()
+        .find(|f| f.id == spec || f.disk_path.as_deref() == Some(spec))
+        .or_else(|| folders.iter().find(|f| f.name == spec))
+        .or_else(|| {
+            folders
+                .iter()
+                .find(|f| spec.len() >= 4 && f.id.starts_with(spec))
+        })
+        .ok_or_else(|| anyhow::anyhow!("no folder matches `{spec}`"))?;
+
+    let mut ids = std::collections::HashSet::from([start.id.clone()]);
+    loop {
+        let before = ids.len();
+        for folder in &folders {
+            if folder
+                .parent_id
+                .as_ref()
+                .is_some_and(|parent| ids.contains(parent))
+            {
+                ids.insert(folder.id.clone());
+            }
+        }
+        if ids.len() == before {
+            break;
+        }
+    }
+    let dirs = folders
+        .iter()
+        .filter(|f| ids.contains(&f.id))
+        .filter_map(|f| f.disk_path.as_deref())
+        .map(|path| library_root.join(path))
+        .collect();
+    Ok((ids, dirs))
+}
+
+/// Embed new or edited cards, then rank the library against `concept`.
+fn run_semantic_search(
+    db: &Database,
+    cards_dir: &Path,
+    ai: &omniscope_core::config::AiConfig,
+    concept: &str,
+    filter: Option<&omniscope_core::SearchQuery>,
+    limit: usize,
+    quiet: bool,
+) -> Result<Vec<(omniscope_core::BookSummaryView, f32)>> {
+    use omniscope_ai::semantic::{refresh_embeddings, semantic_search};
+
+    let provider = omniscope_ai::provider::from_config(ai)?;
+    let runtime = tokio::runtime::Runtime::new()?;
+    let hits = runtime.block_on(async {
+        let report = refresh_embeddings(db, cards_dir, provider.as_ref()).await?;
+        if !quiet && report.embedded > 0 {
+            eprintln!("Embedded {} new or changed book(s)", report.embedded);
+        }
+        let candidates = match filter {
+            Some(_) => db.count_embeddings(provider.embedding_model())?,
+            None => limit,
+        };
+        semantic_search(db, provider.as_ref(), concept, candidates).await
+    })?;
+
+    let mut results = Vec::new();
+    for (id, score) in hits {
+        let Ok(book) = db.get_book_summary(&id.to_string()) else {
+            continue;
+        };
+        let matches = filter.is_none_or(|query| {
+            query.matches(&book)
+                && (!query.has_source_filter()
+                    || omniscope_core::storage::json_cards::load_card_by_id(cards_dir, &id)
+                        .is_ok_and(|card| omniscope_science::export::matches_card(query, &card)))
+        });
+        if !matches {
+            continue;
+        }
+        results.push((book, score));
+        if results.len() == limit {
+            break;
+        }
+    }
+    Ok(results)
+}
+
+fn print_provenance(card: &BookCard) {
+    let provenance = omniscope_science::enrichment::field_provenance(card);
+    if provenance.is_empty() {
+        return;
+    }
+    println!();
+    println!("Provenance:");
+    for entry in provenance {
+        println!(
+            "  {:<34} {}{}",
+            entry.field,
+            entry.source.as_deref().unwrap_or("—"),
+            if entry.locked { "  [locked]" } else { "" }
+        );
+    }
+}
+
+fn set_field_lock(
+    config: &AppConfig,
+    id: &str,
+    field: &str,
+    lock: bool,
+    json_output: bool,
+    start: Instant,
+) -> Result<()> {
+    use omniscope_science::enrichment::provenance::{
+        lock_current_value, resolve_field, unlock_field,
+    };
+
+    let Some(field) = resolve_field(field) else {
+        eprintln!("Unknown field: {field} (try metadata.title, year, doi, journal, ...)");
+        std::process::exit(2);
+    };
+    let cards_dir = config.cards_dir();
+    let uuid = uuid::Uuid::parse_str(id)?;
+    let mut card = match omniscope_core::storage::json_cards::load_card_by_id(&cards_dir, &uuid) {
+        Ok(card) => card,
+        Err(_) => {
+            eprintln!("Book not found: {id}");
+            std::process::exit(2);
+        }
+    };
+
+    let before = card.clone();
+    let changed = if lock {
+        if !card.is_field_locked(field)
+            && omniscope_science::enrichment::review::field_value(&card, field).is_none()
+        {
+            eprintln!("{field} is empty; there is no value to lock");
+            std::process::exit(2);
+        }
+        lock_current_value(&mut card, field)
+    } else {
+        unlock_field(&mut card, field)
+    };
+
+    let mut log_id = None;
+    if changed {
+        let db = open_db(config)?;
+        log_id = Some(
+            omniscope_ai::actions::ActionExecutor::new(&db, &cards_dir).journal(
+                if lock { "lock_field" } else { "unlock_field" },
+                serde_json::json!({ "book_id": card.id, "field": field }),
+                &[before],
+            )?,
+        );
+
+        omniscope_core::storage::json_cards::save_card(&cards_dir, &card)?;
+        db.upsert_book(&card)?;
+    }
+    let dur = start.elapsed().as_millis();
+
+    if json_output {
+        print_json(&serde_json::json!({
+            "status": "ok",
+            "data": {
+                "field": field,
+                "locked": lock,
+                "changed": changed,
+                "locked_fields": card.locked_fields,
+                "log_id": log_id,
+            },
+            "meta": { "duration_ms": dur }
+        }))?;
+    } else {
+        let state = if lock { "locked" } else { "unlocked" };
+        if changed {
+            println!("{field} {state}: {}", card.metadata.title);
+        } else {
+            println!("{field} already {state}");
+        }
+    }
+    Ok(())
+}
+
 fn enrich_card_metadata(card: &mut BookCard) -> omniscope_science::enrichment::EnrichmentReport {
     EnrichmentPipeline::enrich_full_metadata_blocking(card)
 }
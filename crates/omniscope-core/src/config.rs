@@ -198,11 +198,22 @@ pub struct AiConfig {
     pub provider: String,
     pub model: String,
     pub api_key_env: String,
+    /// Endpoint override for OpenAI-compatible servers (llama.cpp, vLLM) or Ollama.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding_model: Option<String>,
+    /// `provider:model` used instead of refusing once the token budget is spent,
+    /// e.g. `ollama:llama3.1`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget_fallback: Option<String>,
     pub max_tokens: u32,
     pub temperature: f64,
     pub auto_index: bool,
     pub auto_summary: bool,
     pub library_map_cache: String,
+    /// Tokens set aside for the library map in a prompt.
+    pub library_map_tokens: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -287,14 +298,56 @@ impl Default for UiConfig {
 impl Default for AiConfig {
     fn default() -> Self {
         Self {
-            provider: "anthropic".to_string(),
-            model: "claude-sonnet-4-20250514".to_string(),
-            api_key_env: "ANTHROPIC_API_KEY".to_string(),
+            // A local model: works without an account or API key.
+            provider: "ollama".to_string(),
+            model: "llama3.1".to_string(),
+            api_key_env: "OPENAI_API_KEY".to_string(),
+            base_url: None,
+            embedding_model: None,
+            budget_fallback: None,
             max_tokens: 4096,
             temperature: 0.1,
-            auto_index: true,
+            // Indexing sends book text to the provider: opt in.
+            auto_index: false,
             auto_summary: false,
             library_map_cache: "1h".to_string(),
+            library_map_tokens: 4_000,
+        }
+    }
+}
+
+/// `[ai]` provider, model and key variable that releases before the provider
+/// layer wrote to config.toml. No backend was ever built for them.
+const LEGACY_AI_PROVIDER: (&str, &str, &str) =
+    ("anthropic", "claude-sonnet-4-20250514", "ANTHROPIC_API_KEY");
+
+impl AiConfig {
+    /// Whether provider, model and key variable are still the untouched
+    /// defaults an older release saved, rather than a choice the user made.
+    pub fn has_legacy_provider(&self) -> bool {
+        let (provider, model, api_key_env) = LEGACY_AI_PROVIDER;
+        self.provider == provider && self.model == model && self.api_key_env == api_key_env
+    }
+
+    /// `auto_index` as an opt-in. Older releases saved `auto_index = true`
+    /// by default next to the legacy provider, so it only counts once the
+    /// user has picked a provider.
+    pub fn auto_index_enabled(&self) -> bool {
+        self.auto_index && !self.has_legacy_provider()
+    }
+
+    /// This config with a legacy provider triple replaced by the current
+    /// defaults; other settings are kept.
+    pub fn without_legacy_provider(&self) -> Self {
+        if !self.has_legacy_provider() {
+            return self.clone();
+        }
+        let defaults = Self::default();
+        Self {
+            provider: defaults.provider,
+            model: defaults.model,
+            api_key_env: defaults.api_key_env,
+            ..self.clone()
         }
     }
 }
@@ -442,6 +495,40 @@ mod tests {
         assert!(!cfg.core.library_path.is_empty());
     }
 
+    #[test]
+    fn test_background_ai_jobs_are_opt_in() {
+        let ai = AiConfig::default();
+        assert!(!ai.auto_index);
+        assert!(!ai.auto_summary);
+    }
+
+    #[test]
+    fn test_legacy_ai_defaults_fall_back_to_current() {
+        let legacy: GlobalConfig = toml::from_str(
+            r#"
+[ai]
+provider = "anthropic"
+model = "claude-sonnet-4-20250514"
+api_key_env = "ANTHROPIC_API_KEY"
+max_tokens = 2048
+auto_index = true
+"#,
+        )
+        .unwrap();
+        assert!(legacy.ai.has_legacy_provider());
+        let resolved = legacy.ai.without_legacy_provider();
+        assert_eq!(resolved.provider, AiConfig::default().provider);
+        assert_eq!(resolved.max_tokens, 2048);
+        assert!(!legacy.ai.auto_index_enabled());
+
+        let chosen = AiConfig {
+            model: "claude-opus".to_string(),
+            ..legacy.ai
+        };
+        assert!(!chosen.has_legacy_provider());
+        assert!(chosen.auto_index_enabled());
+    }
+
     #[test]
     fn test_config_toml_roundtrip() {
         let dir = TempDir::new().unwrap();
@@ -6,6 +6,7 @@ pub mod models;
 pub mod search;
 pub mod search_dsl;
 pub mod storage;
+pub mod sync;
 pub mod undo;
 pub mod viewer;
 
@@ -27,8 +28,10 @@ pub use storage::library_root::LibraryRoot;
 pub use storage::scan::{ScanOptions, ScanResult, scan_library};
 
 pub use storage::repositories::{
-    BookRepository, FolderRepository, LibraryRepository, Repository, SqliteBookRepository,
-    SqliteFolderRepository, SqliteLibraryRepository, SqliteTagRepository, TagRepository,
+    ActionLogRepository, BookRepository, EmbeddingRepository, FolderRepository,
+    LibraryRepository, Repository, SqliteActionLogRepository, SqliteBookRepository,
+    SqliteEmbeddingRepository, SqliteFolderRepository, SqliteLibraryRepository,
+    SqliteTagRepository, TagRepository,
 };
 
 pub use storage::queries::{BookSearchQuery, FrecencyService, LibraryStatsQuery};
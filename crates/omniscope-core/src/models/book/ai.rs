@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct BookAi {
@@ -42,3 +43,17 @@ pub struct TocEntry {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub page: Option<u32>,
 }
+
+/// Stored embedding of a card's text, used for semantic search.
+///
+/// Vectors are unit length, so cosine similarity is a dot product.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookEmbedding {
+    pub book_id: Uuid,
+    pub model: String,
+    pub vector: Vec<f32>,
+    /// Hash of the embedded text; unchanged text is not embedded again.
+    pub content_hash: String,
+    /// `updated_at` of the card when it was embedded.
+    pub source_updated_at: DateTime<Utc>,
+}
@@ -55,6 +55,10 @@ pub struct BookCard {
 
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub metadata_sources: HashMap<String, String>,
+
+    /// Fields (named as in `metadata_sources`) that enrichment must not touch.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locked_fields: Vec<String>,
 }
 
 impl BookCard {
@@ -76,12 +80,31 @@ impl BookCard {
             web: BookWeb::default(),
             notes: Vec::new(),
             metadata_sources: HashMap::new(),
+            locked_fields: Vec::new(),
         }
     }
 
     pub fn touch(&mut self) {
         self.updated_at = Utc::now();
     }
+
+    pub fn is_field_locked(&self, field: &str) -> bool {
+        self.locked_fields.iter().any(|locked| locked == field)
+    }
+
+    /// Lock or unlock `field`. Returns `false` if it was already in that state.
+    pub fn set_field_locked(&mut self, field: &str, locked: bool) -> bool {
+        if self.is_field_locked(field) == locked {
+            return false;
+        }
+        if locked {
+            self.locked_fields.push(field.to_string());
+            self.locked_fields.sort();
+        } else {
+            self.locked_fields.retain(|existing| existing != field);
+        }
+        true
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -149,4 +172,23 @@ mod tests {
         assert_eq!(restored.organization.rating, Some(5));
         assert_eq!(restored.organization.read_status, ReadStatus::Read);
     }
+
+    #[test]
+    fn test_field_locks_roundtrip() {
+        let mut card = BookCard::new("Locked");
+        assert!(card.set_field_locked("metadata.title", true));
+        assert!(!card.set_field_locked("metadata.title", true));
+        assert!(card.set_field_locked("metadata.year", true));
+
+        let json = serde_json::to_string(&card).unwrap();
+        let mut restored: BookCard = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            restored.locked_fields,
+            vec!["metadata.title", "metadata.year"]
+        );
+
+        assert!(restored.set_field_locked("metadata.title", false));
+        assert!(!restored.is_field_locked("metadata.title"));
+        assert!(restored.is_field_locked("metadata.year"));
+    }
 }
@@ -31,6 +31,11 @@ pub struct ScientificIdentifiers {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub dblp_key: Option<String>,
+
+    /// BibTeX cite key assigned on first export or import; kept stable so
+    /// documents citing it don't break when metadata changes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cite_key: Option<String>,
 }
 
 impl ScientificIdentifiers {
@@ -45,6 +50,7 @@ impl ScientificIdentifiers {
             && self.semantic_scholar_id.is_none()
             && self.mag_id.is_none()
             && self.dblp_key.is_none()
+            && self.cite_key.is_none()
     }
 }
 
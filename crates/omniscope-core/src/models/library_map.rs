@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::{BookCard, FileFormat};
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct LibraryMap {
     pub generated_at: String,
@@ -44,3 +46,40 @@ pub struct BookSummaryCompact {
     pub status: String,
     pub frecency: f64,
 }
+
+/// Per-book facts a [`LibraryMap`] is aggregated from.
+///
+/// Caches keep one entry per card so the map can be updated when a single
+/// card changes instead of being rebuilt from the whole library.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibraryMapEntry {
+    pub book: BookSummaryCompact,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub libraries: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub format: Option<FileFormat>,
+    #[serde(default)]
+    pub has_file: bool,
+    #[serde(default)]
+    pub has_summary: bool,
+}
+
+impl From<&BookCard> for LibraryMapEntry {
+    fn from(card: &BookCard) -> Self {
+        Self {
+            book: BookSummaryCompact {
+                id: card.id,
+                title: card.metadata.title.clone(),
+                authors: card.metadata.authors.clone(),
+                year: card.metadata.year,
+                tags: card.organization.tags.clone(),
+                status: card.organization.read_status.to_string(),
+                frecency: 0.0,
+            },
+            libraries: card.organization.libraries.clone(),
+            format: card.file.as_ref().map(|f| f.format),
+            has_file: card.file.is_some(),
+            has_summary: card.ai.summary.as_deref().is_some_and(|s| !s.is_empty()),
+        }
+    }
+}
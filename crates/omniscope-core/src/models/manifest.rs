@@ -15,6 +15,14 @@ pub struct LibraryManifest {
 
     #[serde(default)]
     pub settings: LibrarySettings,
+
+    /// `.bib` files regenerated whenever matching cards change.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bib_sync: Vec<BibSyncTarget>,
+
+    /// Saved arXiv searches, listed under FEEDS in the TUI sidebar.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arxiv_feeds: Vec<ArxivFeed>,
 }
 
 /// Core identity fields for a library.
@@ -66,6 +74,42 @@ pub struct LibrarySettings {
     pub watcher: WatcherConfig,
 }
 
+/// A `.bib` file kept in sync with a search (`omniscope bib-sync`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BibSyncTarget {
+    /// Search DSL selecting the entries, e.g. `#thesis`.
+    pub query: String,
+
+    /// Output file; relative paths are resolved against the library root.
+    pub out: String,
+}
+
+/// A saved arXiv search (`omniscope arxiv feed add`). A paper matches when
+/// it is in any of the categories, by any of the authors and mentions any of
+/// the keywords; empty lists match everything.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArxivFeed {
+    pub name: String,
+
+    /// arXiv categories, e.g. `cs.LG` or `stat.ML`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+
+    /// Words or phrases searched anywhere in the entry.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+}
+
+impl ArxivFeed {
+    /// Whether the feed has no criteria at all.
+    pub fn is_empty(&self) -> bool {
+        self.categories.is_empty() && self.authors.is_empty() && self.keywords.is_empty()
+    }
+}
+
 /// Configuration for the automatic filesystem watcher
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WatcherConfig {
@@ -113,6 +157,8 @@ impl LibraryManifest {
                 roots: ExtraRoots::default(),
             },
             settings: LibrarySettings::default(),
+            bib_sync: Vec::new(),
+            arxiv_feeds: Vec::new(),
         }
     }
 
@@ -176,4 +222,35 @@ mod tests {
         assert_eq!(restored.library.roots.extra.len(), 2);
         assert_eq!(restored.library.roots.extra[0], "/media/external/Books");
     }
+
+    #[test]
+    fn test_manifest_with_bib_sync_targets() {
+        let mut m = LibraryManifest::new("Thesis");
+        m.bib_sync.push(BibSyncTarget {
+            query: "#thesis".to_string(),
+            out: "thesis/refs.bib".to_string(),
+        });
+
+        let toml_str = m.to_toml().unwrap();
+        assert!(toml_str.contains("[[bib_sync]]"));
+        let restored = LibraryManifest::from_toml(&toml_str).unwrap();
+        assert_eq!(restored.bib_sync, m.bib_sync);
+    }
+
+    #[test]
+    fn test_manifest_with_arxiv_feeds() {
+        let mut m = LibraryManifest::new("Group");
+        m.arxiv_feeds.push(ArxivFeed {
+            name: "ml".to_string(),
+            categories: vec!["cs.LG".to_string(), "stat.ML".to_string()],
+            authors: Vec::new(),
+            keywords: vec!["diffusion".to_string()],
+        });
+
+        let toml_str = m.to_toml().unwrap();
+        assert!(toml_str.contains("[[arxiv_feeds]]"));
+        assert!(!toml_str.contains("authors"));
+        let restored = LibraryManifest::from_toml(&toml_str).unwrap();
+        assert_eq!(restored.arxiv_feeds, m.arxiv_feeds);
+    }
 }
@@ -1,6 +1,12 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{Datelike, NaiveDate};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::config::GlobalConfig;
+use crate::error::Result;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserProfile {
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -26,6 +32,11 @@ pub struct UserProfile {
 
     #[serde(default)]
     pub tokens_used_month: u64,
+
+    /// Local date the usage counters were last touched; they reset when the
+    /// day or month changes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage_date: Option<NaiveDate>,
 }
 
 fn default_daily_budget() -> u64 {
@@ -47,6 +58,86 @@ impl Default for UserProfile {
             reading_history: Vec::new(),
             tokens_used_today: 0,
             tokens_used_month: 0,
+            usage_date: None,
+        }
+    }
+}
+
+impl UserProfile {
+    /// Per-workstation profile, next to the global config:
+    /// `~/.config/omniscope/profile.json`.
+    pub fn profile_path() -> PathBuf {
+        GlobalConfig::config_path().with_file_name("profile.json")
+    }
+
+    pub fn load() -> Result<Self> {
+        Self::load_from(&Self::profile_path())
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Reset the counters whose period ended before `today`.
+    pub fn roll_over(&mut self, today: NaiveDate) {
+        let Some(last) = self.usage_date else {
+            self.usage_date = Some(today);
+            return;
+        };
+        if last != today {
+            self.tokens_used_today = 0;
+        }
+        if (last.year(), last.month()) != (today.year(), today.month()) {
+            self.tokens_used_month = 0;
+        }
+        self.usage_date = Some(today);
+    }
+
+    pub fn record_tokens(&mut self, tokens: u64, today: NaiveDate) {
+        self.roll_over(today);
+        self.tokens_used_today += tokens;
+        self.tokens_used_month += tokens;
+    }
+
+    /// Tokens left today; `None` when the daily budget is 0 (unlimited).
+    pub fn remaining_today(&self) -> Option<u64> {
+        if self.daily_token_budget == 0 {
+            return None;
+        }
+        Some(
+            self.daily_token_budget
+                .saturating_sub(self.tokens_used_today),
+        )
+    }
+
+    /// Tokens left this month; `None` when the monthly budget is 0 (unlimited).
+    pub fn remaining_month(&self) -> Option<u64> {
+        if self.monthly_token_budget == 0 {
+            return None;
+        }
+        Some(
+            self.monthly_token_budget
+                .saturating_sub(self.tokens_used_month),
+        )
+    }
+
+    /// The tighter of the two remaining budgets.
+    pub fn remaining_tokens(&self) -> Option<u64> {
+        match (self.remaining_today(), self.remaining_month()) {
+            (Some(day), Some(month)) => Some(day.min(month)),
+            (day, month) => day.or(month),
         }
     }
 }
@@ -61,4 +152,38 @@ mod tests {
         assert_eq!(profile.daily_token_budget, 100_000);
         assert_eq!(profile.monthly_token_budget, 2_000_000);
     }
+
+    #[test]
+    fn test_usage_rolls_over_by_day_and_month() {
+        let date = |m, d| NaiveDate::from_ymd_opt(2025, m, d).unwrap();
+        let mut profile = UserProfile::default();
+
+        profile.record_tokens(1_000, date(3, 30));
+        profile.record_tokens(500, date(3, 30));
+        assert_eq!(profile.tokens_used_today, 1_500);
+
+        profile.record_tokens(200, date(3, 31));
+        assert_eq!(profile.tokens_used_today, 200);
+        assert_eq!(profile.tokens_used_month, 1_700);
+
+        profile.roll_over(date(4, 1));
+        assert_eq!(profile.tokens_used_today, 0);
+        assert_eq!(profile.tokens_used_month, 0);
+    }
+
+    #[test]
+    fn test_remaining_tokens_uses_tighter_budget() {
+        let mut profile = UserProfile {
+            daily_token_budget: 1_000,
+            monthly_token_budget: 5_000,
+            tokens_used_today: 400,
+            tokens_used_month: 4_800,
+            ..UserProfile::default()
+        };
+        assert_eq!(profile.remaining_tokens(), Some(200));
+
+        profile.monthly_token_budget = 0;
+        assert_eq!(profile.remaining_month(), None);
+        assert_eq!(profile.remaining_tokens(), Some(600));
+    }
 }
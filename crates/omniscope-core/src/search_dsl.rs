@@ -12,6 +12,7 @@
 /// f:pdf  f:epub          → format filter
 /// lib:programming        → library filter
 /// has:file  has:summary  → existence filter
+/// ~"attention mechanisms" → semantic match (embeddings)
 /// NOT #python            → negate next token
 /// ```
 use crate::models::{BookSummaryView, ReadStatus};
@@ -39,6 +40,10 @@ pub enum SearchFilter {
     HasFile,
     HasSummary,
     HasTags,
+    /// Cards where some field came from this source (`src:crossref`).
+    Source(String),
+    /// Concept query answered by nearest-neighbour search over embeddings.
+    Semantic(String),
     Not(Box<SearchFilter>),
 }
 
@@ -105,9 +110,14 @@ impl SearchQuery {
         query
     }
 
-    /// Check whether a book matches all filters in this query.
+    /// Check whether a book matches all filters in this query. `src:`
+    /// filters, negated or not, need the full card and are skipped here;
+    /// see [`Self::has_source_filter`].
     pub fn matches(&self, book: &BookSummaryView) -> bool {
-        self.filters.iter().all(|f| filter_matches(f, book))
+        self.filters
+            .iter()
+            .filter(|f| !is_source_filter(f))
+            .all(|f| filter_matches(f, book))
     }
 
     /// Is this a pure fuzzy query (no DSL filters)?
@@ -119,6 +129,33 @@ impl SearchQuery {
     pub fn fuzzy_text(&self) -> String {
         self.fuzzy_terms.join(" ")
     }
+
+    /// Whether any filter needs the full card's provenance to evaluate.
+    pub fn has_source_filter(&self) -> bool {
+        self.filters.iter().any(is_source_filter)
+    }
+
+    /// Check the `src:` filters, negated or not, against the source tags a
+    /// card's `metadata_sources` records. Other filters are ignored.
+    pub fn matches_sources(&self, sources: &[String]) -> bool {
+        self.filters
+            .iter()
+            .filter(|f| is_source_filter(f))
+            .all(|f| source_filter_matches(f, sources))
+    }
+
+    /// The `~` terms joined into one concept query, if there are any.
+    pub fn semantic_text(&self) -> Option<String> {
+        let terms: Vec<&str> = self
+            .filters
+            .iter()
+            .filter_map(|f| match f {
+                SearchFilter::Semantic(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        (!terms.is_empty()).then(|| terms.join(" "))
+    }
 }
 
 fn tokenize(input: &str) -> Vec<String> {
@@ -157,6 +194,14 @@ fn parse_token(token: &str) -> Option<SearchFilter> {
         return Some(SearchFilter::Author(rest.to_string()));
     }
 
+    // ~concept or ~"multi word concept"
+    if let Some(rest) = token.strip_prefix('~') {
+        if rest.is_empty() {
+            return None;
+        }
+        return Some(SearchFilter::Semantic(rest.to_string()));
+    }
+
     // #tag
     if let Some(rest) = token.strip_prefix('#') {
         // Handle #tag:name syntax too
@@ -205,6 +250,17 @@ fn parse_token(token: &str) -> Option<SearchFilter> {
         return Some(SearchFilter::Library(rest.to_string()));
     }
 
+    // src:SOURCE
+    if let Some(rest) = token
+        .strip_prefix("src:")
+        .or_else(|| token.strip_prefix("source:"))
+    {
+        if rest.is_empty() {
+            return None;
+        }
+        return Some(SearchFilter::Source(rest.to_lowercase()));
+    }
+
     // has:file, has:summary, has:tags
     if let Some(rest) = token.strip_prefix("has:") {
         return match rest {
@@ -277,6 +333,22 @@ fn parse_rating_filter(s: &str) -> Option<CompareOp> {
     })
 }
 
+fn is_source_filter(filter: &SearchFilter) -> bool {
+    match filter {
+        SearchFilter::Source(_) => true,
+        SearchFilter::Not(inner) => is_source_filter(inner),
+        _ => false,
+    }
+}
+
+fn source_filter_matches(filter: &SearchFilter, sources: &[String]) -> bool {
+    match filter {
+        SearchFilter::Source(source) => sources.iter().any(|tag| tag.eq_ignore_ascii_case(source)),
+        SearchFilter::Not(inner) => !source_filter_matches(inner, sources),
+        _ => true,
+    }
+}
+
 fn filter_matches(filter: &SearchFilter, book: &BookSummaryView) -> bool {
     match filter {
         SearchFilter::Author(name) => {
@@ -330,6 +402,14 @@ fn filter_matches(filter: &SearchFilter, book: &BookSummaryView) -> bool {
         SearchFilter::HasFile => book.has_file,
         SearchFilter::HasSummary => false, // Not available in summary view
         SearchFilter::HasTags => !book.tags.is_empty(),
+        SearchFilter::Source(_) => {
+            // Provenance is only on the full card, checked by card-level matchers
+            true
+        }
+        SearchFilter::Semantic(_) => {
+            // Ranked by the caller against stored embeddings, always true here
+            true
+        }
         SearchFilter::Not(inner) => !filter_matches(inner, book),
     }
 }
@@ -438,6 +518,32 @@ mod tests {
         assert!(matches!(&q.filters[0], SearchFilter::Not(_)));
     }
 
+    #[test]
+    fn test_parse_semantic() {
+        let q = SearchQuery::parse("~\"attention mechanisms\" #ml");
+        assert!(matches!(&q.filters[0], SearchFilter::Semantic(t) if t == "attention mechanisms"));
+        assert_eq!(q.semantic_text().as_deref(), Some("attention mechanisms"));
+        assert!(SearchQuery::parse("rust").semantic_text().is_none());
+    }
+
+    #[test]
+    fn test_parse_source() {
+        let q = SearchQuery::parse("src:CrossRef NOT source:openalex");
+        assert!(matches!(&q.filters[0], SearchFilter::Source(s) if s == "crossref"));
+        assert!(matches!(&q.filters[1], SearchFilter::Not(_)));
+        assert!(q.has_source_filter());
+        assert!(!SearchQuery::parse("#ml").has_source_filter());
+    }
+
+    #[test]
+    fn test_negated_source_filter_is_left_to_the_card() {
+        let q = SearchQuery::parse("NOT src:crossref");
+        let book = make_book("Paper", &[], &[], None, None);
+        assert!(q.matches(&book));
+        assert!(q.matches_sources(&["openalex".to_string()]));
+        assert!(!q.matches_sources(&["CrossRef".to_string()]));
+    }
+
     #[test]
     fn test_parse_complex() {
         let q = SearchQuery::parse("rust @author:klabnik #systems y:2020-2023 r:>=4");
@@ -1,6 +1,7 @@
 mod v1_initial;
 mod v2_doi_arxiv;
 mod v3_disk_path;
+mod v4_embeddings;
 
 use chrono::Utc;
 use rusqlite::Connection;
@@ -41,6 +42,7 @@ pub fn run_migrations(conn: &Connection) -> Result<()> {
         Box::new(v1_initial::V1Initial),
         Box::new(v2_doi_arxiv::V2DoiArxiv),
         Box::new(v3_disk_path::V3DiskPath),
+        Box::new(v4_embeddings::V4Embeddings),
     ];
 
     for migration in migrations {
@@ -0,0 +1,33 @@
+use rusqlite::Connection;
+
+use super::Migration;
+use crate::error::Result;
+
+pub struct V4Embeddings;
+
+impl Migration for V4Embeddings {
+    fn version(&self) -> u32 {
+        4
+    }
+
+    fn description(&self) -> &'static str {
+        "Add book_embeddings table for semantic search"
+    }
+
+    fn up(&self, conn: &Connection) -> Result<()> {
+        conn.execute_batch(
+            "
+            CREATE TABLE IF NOT EXISTS book_embeddings (
+                book_id           TEXT PRIMARY KEY,
+                model             TEXT NOT NULL,
+                dims              INTEGER NOT NULL,
+                vector            BLOB NOT NULL,
+                content_hash      TEXT NOT NULL,
+                source_updated_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_book_embeddings_model ON book_embeddings(model);
+            ",
+        )?;
+        Ok(())
+    }
+}
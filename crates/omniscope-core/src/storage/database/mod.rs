@@ -13,11 +13,14 @@ use std::path::Path;
 use rusqlite::params;
 
 use crate::error::{OmniscopeError, Result};
-use crate::models::{BookCard, BookSummaryView, Folder};
+use crate::models::{
+    ActionLogEntry, BookCard, BookEmbedding, BookSummaryView, Folder, LibraryMapEntry, LibraryStats,
+};
 use uuid::Uuid;
 
 use super::repositories::{
-    BookRepository, FolderRepository, LibraryRepository, Repository, TagRepository,
+    ActionLogRepository, BookRepository, EmbeddingRepository, FolderRepository, LibraryRepository,
+    Repository, TagRepository,
 };
 
 pub struct DatabaseConfig {
@@ -102,11 +105,15 @@ impl Database {
 
     pub fn delete_book(&self, id: &str) -> Result<()> {
         let uuid = Uuid::parse_str(id).map_err(|_| OmniscopeError::BookNotFound(id.to_string()))?;
-        let conn = self.pool.get_connection();
-        let repo = super::repositories::SqliteBookRepository::new(conn);
-        if !repo.delete(&uuid)? {
+        let deleted = {
+            let conn = self.pool.get_connection();
+            let repo = super::repositories::SqliteBookRepository::new(conn);
+            repo.delete(&uuid)?
+        };
+        if !deleted {
             return Err(OmniscopeError::BookNotFound(id.to_string()));
         }
+        self.delete_embedding(&uuid)?;
         Ok(())
     }
 
@@ -123,6 +130,24 @@ impl Database {
         repo.count()
     }
 
+    pub fn library_stats(&self) -> Result<LibraryStats> {
+        let conn = self.pool.get_connection();
+        let stats = super::queries::LibraryStatsQuery::new(conn);
+        stats.get_stats()
+    }
+
+    pub fn library_map_entries(&self) -> Result<Vec<LibraryMapEntry>> {
+        let conn = self.pool.get_connection();
+        let stats = super::queries::LibraryStatsQuery::new(conn);
+        stats.map_entries()
+    }
+
+    pub fn library_fingerprint(&self) -> Result<String> {
+        let conn = self.pool.get_connection();
+        let stats = super::queries::LibraryStatsQuery::new(conn);
+        stats.fingerprint()
+    }
+
     pub fn search_fts(&self, query: &str, limit: usize) -> Result<Vec<BookSummaryView>> {
         let conn = self.pool.get_connection();
         let search = super::queries::BookSearchQuery::new(conn);
@@ -360,6 +385,74 @@ impl Database {
         let repo = super::repositories::SqliteBookRepository::new(conn);
         repo.find_books_by_path_prefix(prefix)
     }
+
+    pub fn save_embedding(&self, embedding: &BookEmbedding) -> Result<()> {
+        let conn = self.pool.get_connection();
+        let repo = super::repositories::SqliteEmbeddingRepository::new(conn);
+        repo.save(embedding)
+    }
+
+    pub fn get_embedding(&self, book_id: &Uuid) -> Result<Option<BookEmbedding>> {
+        let conn = self.pool.get_connection();
+        let repo = super::repositories::SqliteEmbeddingRepository::new(conn);
+        repo.find_by_id(book_id)
+    }
+
+    pub fn delete_embedding(&self, book_id: &Uuid) -> Result<bool> {
+        let conn = self.pool.get_connection();
+        let repo = super::repositories::SqliteEmbeddingRepository::new(conn);
+        repo.delete(book_id)
+    }
+
+    /// Books whose embedding from `model` is missing or older than the card.
+    pub fn list_stale_embeddings(&self, model: &str) -> Result<Vec<Uuid>> {
+        let conn = self.pool.get_connection();
+        let repo = super::repositories::SqliteEmbeddingRepository::new(conn);
+        repo.list_stale(model)
+    }
+
+    pub fn count_embeddings(&self, model: &str) -> Result<usize> {
+        let conn = self.pool.get_connection();
+        let repo = super::repositories::SqliteEmbeddingRepository::new(conn);
+        repo.count(model)
+    }
+
+    /// Nearest neighbours of a unit-length `query` vector, best match first,
+    /// with their cosine similarity.
+    pub fn nearest_books(
+        &self,
+        query: &[f32],
+        model: &str,
+        limit: usize,
+    ) -> Result<Vec<(Uuid, f32)>> {
+        let conn = self.pool.get_connection();
+        let repo = super::repositories::SqliteEmbeddingRepository::new(conn);
+        repo.nearest(query, model, limit)
+    }
+
+    pub fn log_action(&self, entry: &ActionLogEntry) -> Result<()> {
+        let conn = self.pool.get_connection();
+        let repo = super::repositories::SqliteActionLogRepository::new(conn);
+        repo.save(entry)
+    }
+
+    pub fn get_action_log_entry(&self, id: &Uuid) -> Result<Option<ActionLogEntry>> {
+        let conn = self.pool.get_connection();
+        let repo = super::repositories::SqliteActionLogRepository::new(conn);
+        repo.find_by_id(id)
+    }
+
+    pub fn list_action_log(&self, limit: usize) -> Result<Vec<ActionLogEntry>> {
+        let conn = self.pool.get_connection();
+        let repo = super::repositories::SqliteActionLogRepository::new(conn);
+        repo.list_recent(limit)
+    }
+
+    pub fn mark_action_reversed(&self, id: &Uuid) -> Result<bool> {
+        let conn = self.pool.get_connection();
+        let repo = super::repositories::SqliteActionLogRepository::new(conn);
+        repo.mark_reversed(id)
+    }
 }
 
 #[cfg(feature = "async")]
@@ -19,6 +19,19 @@ pub fn save_card(cards_dir: &Path, card: &BookCard) -> Result<PathBuf> {
     Ok(final_path)
 }
 
+/// Save a BookCard unless its file already holds exactly this content, so
+/// file watchers don't see a change that isn't one. Returns whether the
+/// file was written.
+pub fn save_card_if_changed(cards_dir: &Path, card: &BookCard) -> Result<bool> {
+    let path = cards_dir.join(format!("{}.json", card.id));
+    let json = serde_json::to_string_pretty(card)?;
+    if fs::read_to_string(&path).is_ok_and(|existing| existing == json) {
+        return Ok(false);
+    }
+    save_card(cards_dir, card)?;
+    Ok(true)
+}
+
 /// Load a single BookCard from a JSON file.
 pub fn load_card(path: &Path) -> Result<BookCard> {
     let contents = fs::read_to_string(path)?;
@@ -86,6 +99,19 @@ mod tests {
         assert_eq!(loaded.metadata.title, "Test Book");
     }
 
+    #[test]
+    fn test_save_card_if_changed_skips_identical_content() {
+        let dir = TempDir::new().unwrap();
+        let cards_dir = dir.path().join("cards");
+
+        let mut card = BookCard::new("Stable");
+        assert!(save_card_if_changed(&cards_dir, &card).unwrap());
+        assert!(!save_card_if_changed(&cards_dir, &card).unwrap());
+
+        card.metadata.title = "Edited".to_string();
+        assert!(save_card_if_changed(&cards_dir, &card).unwrap());
+    }
+
     #[test]
     fn test_list_cards() {
         let dir = TempDir::new().unwrap();
@@ -73,11 +73,21 @@ impl LibraryRoot {
         self.libr_dir().join("cache").join("covers")
     }
 
+    /// Path to the cached library map sent to AI models.
+    pub fn library_map_path(&self) -> PathBuf {
+        self.libr_dir().join("cache").join("library_map.json")
+    }
+
     /// Path to the undo log directory.
     pub fn undo_dir(&self) -> PathBuf {
         self.libr_dir().join("undo")
     }
 
+    /// Path to the per-book AI chat histories.
+    pub fn chats_dir(&self) -> PathBuf {
+        self.libr_dir().join("ai").join("chats")
+    }
+
     /// Path to the backups directory.
     pub fn backups_dir(&self) -> PathBuf {
         self.libr_dir().join("backups")
@@ -172,6 +182,12 @@ impl LibraryRoot {
         let contents = std::fs::read_to_string(self.manifest_path())?;
         LibraryManifest::from_toml(&contents)
     }
+
+    /// Write the library manifest back to `.libr/library.toml`.
+    pub fn save_manifest(&self, manifest: &LibraryManifest) -> Result<()> {
+        std::fs::write(self.manifest_path(), manifest.to_toml()?)?;
+        Ok(())
+    }
 }
 
 // ─── Tests ─────────────────────────────────────────────────
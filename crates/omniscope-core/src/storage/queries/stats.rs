@@ -1,8 +1,9 @@
 use rusqlite::Connection;
 use std::sync::MutexGuard;
+use uuid::Uuid;
 
 use crate::error::Result;
-use crate::models::{LibraryStats, ReadStatus};
+use crate::models::{BookSummaryCompact, LibraryMapEntry, LibraryStats, ReadStatus};
 
 pub struct LibraryStatsQuery<'a> {
     conn: MutexGuard<'a, Connection>,
@@ -110,4 +111,48 @@ impl<'a> LibraryStatsQuery<'a> {
         )?;
         Ok(count as usize)
     }
+
+    /// One row per book with just what the library map aggregates.
+    pub fn map_entries(&self) -> Result<Vec<LibraryMapEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, title, authors, year, tags, read_status, frecency_score,
+                    libraries, file_format, file_path IS NOT NULL,
+                    summary IS NOT NULL AND summary != ''
+             FROM books",
+        )?;
+        let entries = stmt
+            .query_map([], |row| {
+                let authors: String = row.get(2)?;
+                let tags: String = row.get(4)?;
+                let libraries: String = row.get(7)?;
+                let format: Option<String> = row.get(8)?;
+                Ok(LibraryMapEntry {
+                    book: BookSummaryCompact {
+                        id: Uuid::parse_str(&row.get::<_, String>(0)?).unwrap_or_default(),
+                        title: row.get(1)?,
+                        authors: serde_json::from_str(&authors).unwrap_or_default(),
+                        year: row.get(3)?,
+                        tags: serde_json::from_str(&tags).unwrap_or_default(),
+                        status: row.get(5)?,
+                        frecency: row.get(6)?,
+                    },
+                    libraries: serde_json::from_str(&libraries).unwrap_or_default(),
+                    format: format.and_then(|s| serde_json::from_str(&format!("\"{s}\"")).ok()),
+                    has_file: row.get(9)?,
+                    has_summary: row.get(10)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(entries)
+    }
+
+    /// Cheap value that changes whenever books are added, removed or saved.
+    pub fn fingerprint(&self) -> Result<String> {
+        let (count, latest): (i64, Option<String>) =
+            self.conn
+                .query_row("SELECT COUNT(*), MAX(updated_at) FROM books", [], |row| {
+                    Ok((row.get(0)?, row.get(1)?))
+                })?;
+        Ok(format!("{count}:{}", latest.unwrap_or_default()))
+    }
 }
@@ -0,0 +1,114 @@
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OptionalExtension, params};
+use std::sync::MutexGuard;
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::models::ActionLogEntry;
+
+use super::Repository;
+
+pub trait ActionLogRepository: Repository<Entity = ActionLogEntry, Id = Uuid> {
+    /// Newest entries first.
+    fn list_recent(&self, limit: usize) -> Result<Vec<ActionLogEntry>>;
+    fn mark_reversed(&self, id: &Uuid) -> Result<bool>;
+}
+
+pub struct SqliteActionLogRepository<'a> {
+    conn: MutexGuard<'a, Connection>,
+}
+
+impl<'a> SqliteActionLogRepository<'a> {
+    pub fn new(conn: MutexGuard<'a, Connection>) -> Self {
+        Self { conn }
+    }
+
+    fn row_to_entry(row: &rusqlite::Row) -> rusqlite::Result<ActionLogEntry> {
+        let id: String = row.get(0)?;
+        let payload: String = row.get(2)?;
+        let snapshot: Option<String> = row.get(3)?;
+        let created_at: String = row.get(4)?;
+
+        Ok(ActionLogEntry {
+            id: Uuid::parse_str(&id).unwrap_or_default(),
+            action_type: row.get(1)?,
+            payload: serde_json::from_str(&payload).unwrap_or_default(),
+            snapshot_before: snapshot.and_then(|s| serde_json::from_str(&s).ok()),
+            created_at: DateTime::parse_from_rfc3339(&created_at)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_default(),
+            reversed: row.get(5)?,
+        })
+    }
+}
+
+impl<'a> Repository for SqliteActionLogRepository<'a> {
+    type Entity = ActionLogEntry;
+    type Id = Uuid;
+
+    fn find_by_id(&self, id: &Self::Id) -> Result<Option<Self::Entity>> {
+        let entry = self
+            .conn
+            .query_row(
+                "SELECT id, action_type, payload, snapshot_before, created_at, reversed
+                 FROM action_log WHERE id = ?1",
+                params![id.to_string()],
+                Self::row_to_entry,
+            )
+            .optional()?;
+        Ok(entry)
+    }
+
+    fn save(&self, entry: &Self::Entity) -> Result<()> {
+        let payload = serde_json::to_string(&entry.payload)?;
+        let snapshot = entry
+            .snapshot_before
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+
+        self.conn.execute(
+            "INSERT OR REPLACE INTO action_log
+                (id, action_type, payload, snapshot_before, created_at, reversed)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                entry.id.to_string(),
+                entry.action_type,
+                payload,
+                snapshot,
+                entry.created_at.to_rfc3339(),
+                entry.reversed,
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn delete(&self, id: &Self::Id) -> Result<bool> {
+        let rows = self.conn.execute(
+            "DELETE FROM action_log WHERE id = ?1",
+            params![id.to_string()],
+        )?;
+        Ok(rows > 0)
+    }
+}
+
+impl<'a> ActionLogRepository for SqliteActionLogRepository<'a> {
+    fn list_recent(&self, limit: usize) -> Result<Vec<ActionLogEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, action_type, payload, snapshot_before, created_at, reversed
+             FROM action_log ORDER BY created_at DESC, id DESC LIMIT ?1",
+        )?;
+        let entries = stmt
+            .query_map(params![limit as i64], Self::row_to_entry)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(entries)
+    }
+
+    fn mark_reversed(&self, id: &Uuid) -> Result<bool> {
+        let rows = self.conn.execute(
+            "UPDATE action_log SET reversed = 1 WHERE id = ?1",
+            params![id.to_string()],
+        )?;
+        Ok(rows > 0)
+    }
+}
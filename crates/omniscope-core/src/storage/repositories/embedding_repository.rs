@@ -0,0 +1,151 @@
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OptionalExtension, params};
+use std::cmp::Ordering;
+use std::sync::MutexGuard;
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::models::BookEmbedding;
+
+use super::Repository;
+
+pub trait EmbeddingRepository: Repository<Entity = BookEmbedding, Id = Uuid> {
+    /// Books with no embedding from `model`, or edited since they were embedded.
+    fn list_stale(&self, model: &str) -> Result<Vec<Uuid>>;
+    /// The `limit` books closest to `query` (a unit vector), best first.
+    fn nearest(&self, query: &[f32], model: &str, limit: usize) -> Result<Vec<(Uuid, f32)>>;
+    fn count(&self, model: &str) -> Result<usize>;
+}
+
+pub struct SqliteEmbeddingRepository<'a> {
+    conn: MutexGuard<'a, Connection>,
+}
+
+impl<'a> SqliteEmbeddingRepository<'a> {
+    pub fn new(conn: MutexGuard<'a, Connection>) -> Self {
+        Self { conn }
+    }
+
+    fn row_to_embedding(row: &rusqlite::Row) -> rusqlite::Result<BookEmbedding> {
+        let id: String = row.get(0)?;
+        let vector: Vec<u8> = row.get(2)?;
+        let updated_at: String = row.get(4)?;
+
+        Ok(BookEmbedding {
+            book_id: Uuid::parse_str(&id).unwrap_or_default(),
+            model: row.get(1)?,
+            vector: decode_vector(&vector),
+            content_hash: row.get(3)?,
+            source_updated_at: DateTime::parse_from_rfc3339(&updated_at)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_default(),
+        })
+    }
+}
+
+fn encode_vector(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+fn decode_vector(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect()
+}
+
+impl<'a> Repository for SqliteEmbeddingRepository<'a> {
+    type Entity = BookEmbedding;
+    type Id = Uuid;
+
+    fn find_by_id(&self, id: &Self::Id) -> Result<Option<Self::Entity>> {
+        let embedding = self
+            .conn
+            .query_row(
+                "SELECT book_id, model, vector, content_hash, source_updated_at
+                 FROM book_embeddings WHERE book_id = ?1",
+                params![id.to_string()],
+                Self::row_to_embedding,
+            )
+            .optional()?;
+        Ok(embedding)
+    }
+
+    fn save(&self, embedding: &Self::Entity) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO book_embeddings
+                (book_id, model, dims, vector, content_hash, source_updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                embedding.book_id.to_string(),
+                embedding.model,
+                embedding.vector.len() as i64,
+                encode_vector(&embedding.vector),
+                embedding.content_hash,
+                embedding.source_updated_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn delete(&self, id: &Self::Id) -> Result<bool> {
+        let rows = self.conn.execute(
+            "DELETE FROM book_embeddings WHERE book_id = ?1",
+            params![id.to_string()],
+        )?;
+        Ok(rows > 0)
+    }
+}
+
+impl<'a> EmbeddingRepository for SqliteEmbeddingRepository<'a> {
+    fn list_stale(&self, model: &str) -> Result<Vec<Uuid>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT b.id FROM books b
+             LEFT JOIN book_embeddings e ON e.book_id = b.id
+             WHERE e.book_id IS NULL OR e.model != ?1 OR e.source_updated_at < b.updated_at",
+        )?;
+        let ids = stmt
+            .query_map(params![model], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(ids
+            .iter()
+            .filter_map(|id| Uuid::parse_str(id).ok())
+            .collect())
+    }
+
+    fn nearest(&self, query: &[f32], model: &str, limit: usize) -> Result<Vec<(Uuid, f32)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT book_id, vector FROM book_embeddings WHERE model = ?1 AND dims = ?2",
+        )?;
+        let mut rows = stmt.query(params![model, query.len() as i64])?;
+
+        // Exhaustive scan: at personal-library sizes this is fast enough that
+        // an approximate index isn't worth maintaining.
+        let mut scored: Vec<(Uuid, f32)> = Vec::new();
+        while let Some(row) = rows.next()? {
+            let id: String = row.get(0)?;
+            let bytes: Vec<u8> = row.get(1)?;
+            let score = bytes
+                .chunks_exact(4)
+                .zip(query)
+                .map(|(chunk, q)| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]) * q)
+                .sum::<f32>();
+            if let Ok(id) = Uuid::parse_str(&id) {
+                scored.push((id, score));
+            }
+        }
+
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+        scored.truncate(limit);
+        Ok(scored)
+    }
+
+    fn count(&self, model: &str) -> Result<usize> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM book_embeddings WHERE model = ?1",
+            params![model],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+}
@@ -1,9 +1,13 @@
+mod action_log_repository;
 mod book_repository;
+mod embedding_repository;
 mod folder_repository;
 mod library_repository;
 mod tag_repository;
 
+pub use action_log_repository::{ActionLogRepository, SqliteActionLogRepository};
 pub use book_repository::{BookRepository, SqliteBookRepository};
+pub use embedding_repository::{EmbeddingRepository, SqliteEmbeddingRepository};
 pub use folder_repository::{FolderRepository, SqliteFolderRepository};
 pub use library_repository::{LibraryRepository, SqliteLibraryRepository};
 pub use tag_repository::{SqliteTagRepository, TagRepository};
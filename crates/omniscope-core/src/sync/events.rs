@@ -0,0 +1,123 @@
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use notify_debouncer_mini::{DebounceEventResult, new_debouncer, notify::RecursiveMode};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::models::BookSummaryView;
+use crate::storage::json_cards;
+
+use super::watcher::WatcherEvent;
+
+/// A change to the library, broadcast to every connected session.
+///
+/// Upserts carry the full summary so subscribers can patch their book list
+/// without a round-trip; they are idempotent and may arrive more than once.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LibraryEvent {
+    CardUpserted { book: BookSummaryView },
+    CardDeleted { id: Uuid },
+    /// The folder tree changed; subscribers should reload it.
+    FoldersChanged,
+    /// A raw filesystem event from [`LibraryWatcher`](super::LibraryWatcher).
+    Watcher { event: WatcherEvent },
+}
+
+impl From<WatcherEvent> for LibraryEvent {
+    fn from(event: WatcherEvent) -> Self {
+        Self::Watcher { event }
+    }
+}
+
+/// Watches `.libr/cards/` so card writes from any process become events.
+pub struct CardsWatcher {
+    _debouncer: notify_debouncer_mini::Debouncer<notify::RecommendedWatcher>,
+}
+
+impl CardsWatcher {
+    pub fn start(cards_dir: &Path, debounce: Duration) -> Result<(Self, mpsc::Receiver<LibraryEvent>)> {
+        std::fs::create_dir_all(cards_dir)?;
+        let (event_tx, event_rx) = mpsc::channel::<LibraryEvent>();
+
+        let mut debouncer = new_debouncer(debounce, move |res: DebounceEventResult| {
+            let Ok(events) = res else {
+                return;
+            };
+            for event in events {
+                if let Some(library_event) = card_event(&event.path) {
+                    let _ = event_tx.send(library_event);
+                }
+            }
+        })?;
+        debouncer
+            .watcher()
+            .watch(cards_dir, RecursiveMode::NonRecursive)?;
+
+        Ok((
+            Self {
+                _debouncer: debouncer,
+            },
+            event_rx,
+        ))
+    }
+}
+
+/// Translate a touched card file into an upsert or delete event.
+fn card_event(path: &Path) -> Option<LibraryEvent> {
+    if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+        return None;
+    }
+    let id = Uuid::parse_str(path.file_stem()?.to_str()?).ok()?;
+
+    if path.exists() {
+        let card = json_cards::load_card(path).ok()?;
+        Some(LibraryEvent::CardUpserted {
+            book: BookSummaryView::from(&card),
+        })
+    } else {
+        Some(LibraryEvent::CardDeleted { id })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::BookCard;
+
+    #[test]
+    fn test_card_event_for_saved_and_removed_card() {
+        let dir = tempfile::tempdir().unwrap();
+        let card = BookCard::new("Watched");
+        let path = json_cards::save_card(dir.path(), &card).unwrap();
+
+        match card_event(&path) {
+            Some(LibraryEvent::CardUpserted { book }) => assert_eq!(book.id, card.id),
+            other => panic!("unexpected event: {other:?}"),
+        }
+
+        std::fs::remove_file(&path).unwrap();
+        match card_event(&path) {
+            Some(LibraryEvent::CardDeleted { id }) => assert_eq!(id, card.id),
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_card_event_ignores_temp_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let tmp = dir.path().join(format!("{}.json.tmp", Uuid::new_v4()));
+        std::fs::write(&tmp, "{}").unwrap();
+        assert!(card_event(&tmp).is_none());
+    }
+
+    #[test]
+    fn test_event_json_is_tagged() {
+        let event = LibraryEvent::CardDeleted { id: Uuid::nil() };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "card_deleted");
+    }
+}
@@ -1,7 +1,9 @@
+pub mod events;
 pub mod folder_sync;
 pub mod folder_ops;
 pub mod watcher;
 
+pub use events::*;
 pub use folder_sync::*;
 pub use folder_ops::*;
 pub use watcher::*;
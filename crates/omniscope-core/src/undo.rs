@@ -8,6 +8,9 @@ pub enum UndoAction {
     UpsertCards(Vec<BookCard>),
     /// Delete these cards (revert an addition)
     DeleteCards(Vec<BookCard>),
+    /// Put each card back as it was: `Some` is saved, `None` is deleted
+    /// (revert a change that both edited and added or removed cards)
+    RestoreCards(Vec<(uuid::Uuid, Option<BookCard>)>),
 }
 
 /// An undoable book-modification snapshot.
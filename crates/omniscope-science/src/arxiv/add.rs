@@ -10,12 +10,16 @@ use omniscope_core::storage::database::Database;
 use crate::arxiv::client::ArxivClient;
 use crate::arxiv::types::ArxivMetadata;
 use crate::enrichment::merge::{BookCardMergeExt, MetadataSource, PartialMetadata};
+use crate::enrichment::pipeline::{partial_from_openlibrary, partial_from_pubmed};
 use crate::error::{Result, ScienceError};
 use crate::http::RateLimitedClient;
 use crate::identifiers::arxiv::ArxivId;
 use crate::identifiers::doi::Doi;
+use crate::identifiers::extract::{Identifier, detect_identifier};
 use crate::identifiers::isbn::Isbn;
 use crate::sources::crossref::{CrossRefAuthor, CrossRefSource, CrossRefWork};
+use crate::sources::openlibrary::OpenLibrarySource;
+use crate::sources::pubmed::{PubMedSource, normalize_pmcid};
 use crate::sources::semantic_scholar::{S2Paper, S2PaperId, SemanticScholarSource};
 use crate::sources::unpaywall::{UnpaywallResult, UnpaywallSource};
 use crate::types::DocumentType as ScienceDocumentType;
@@ -23,6 +27,7 @@ use crate::types::DocumentType as ScienceDocumentType;
 const DEFAULT_USER_AGENT: &str = "omniscope-science/0.1";
 const ENV_POLITE_EMAIL: &str = "OMNISCOPE_POLITE_EMAIL";
 const ENV_SEMANTIC_SCHOLAR_API_KEY: &str = "OMNISCOPE_SEMANTIC_SCHOLAR_API_KEY";
+const ENV_NCBI_API_KEY: &str = "OMNISCOPE_NCBI_API_KEY";
 const DUPLICATE_SCAN_PAGE_SIZE: usize = 200;
 
 #[derive(Debug, Clone, Default)]
@@ -32,6 +37,14 @@ pub struct ArxivAddOptions {
     pub auto_index: bool,
 }
 
+/// A lookup's result: the matching card already in the library, or a new
+/// card built from the sources that has not been stored yet.
+#[derive(Debug, Clone)]
+pub enum FetchedCard {
+    Existing(BookCard),
+    New(BookCard),
+}
+
 #[async_trait]
 pub trait ScienceIndexer: Send + Sync {
     async fn index_book(&self, card: &mut BookCard) -> Result<()>;
@@ -42,6 +55,8 @@ pub struct ArxivAddService {
     crossref: Arc<CrossRefSource>,
     semantic_scholar: Arc<SemanticScholarSource>,
     unpaywall: Option<Arc<UnpaywallSource>>,
+    openlibrary: Arc<OpenLibrarySource>,
+    pubmed: Arc<PubMedSource>,
     downloader: RateLimitedClient,
     indexer: Option<Arc<dyn ScienceIndexer>>,
 }
@@ -56,7 +71,8 @@ impl ArxivAddService {
     pub fn from_env() -> Self {
         let polite_email = env_var_non_empty(ENV_POLITE_EMAIL);
         let semantic_scholar_api_key = env_var_non_empty(ENV_SEMANTIC_SCHOLAR_API_KEY);
-        Self::new(polite_email, semantic_scholar_api_key)
+        let pubmed = PubMedSource::new(env_var_non_empty(ENV_NCBI_API_KEY), polite_email.clone());
+        Self::new(polite_email, semantic_scholar_api_key).with_pubmed(Arc::new(pubmed))
     }
 
     pub fn new(polite_email: Option<String>, semantic_scholar_api_key: Option<String>) -> Self {
@@ -67,9 +83,11 @@ impl ArxivAddService {
 
         Self {
             arxiv_client: Arc::new(ArxivClient::new()),
-            crossref: Arc::new(CrossRefSource::new(polite_email)),
+            crossref: Arc::new(CrossRefSource::new(polite_email.clone())),
             semantic_scholar: Arc::new(SemanticScholarSource::new(semantic_scholar_api_key)),
             unpaywall,
+            openlibrary: Arc::new(OpenLibrarySource::new()),
+            pubmed: Arc::new(PubMedSource::new(None, polite_email)),
             downloader: RateLimitedClient::new(Duration::from_millis(300), 3, DEFAULT_USER_AGENT),
             indexer: None,
         }
@@ -80,6 +98,11 @@ impl ArxivAddService {
         self
     }
 
+    pub fn with_pubmed(mut self, pubmed: Arc<PubMedSource>) -> Self {
+        self.pubmed = pubmed;
+        self
+    }
+
     #[cfg(test)]
     pub(crate) fn with_clients(
         arxiv_client: Arc<ArxivClient>,
@@ -92,28 +115,48 @@ impl ArxivAddService {
             crossref,
             semantic_scholar,
             unpaywall,
+            openlibrary: Arc::new(OpenLibrarySource::new()),
+            pubmed: Arc::new(PubMedSource::default()),
             downloader: RateLimitedClient::new(Duration::from_millis(1), 1, DEFAULT_USER_AGENT),
             indexer: None,
         }
     }
 
+    #[cfg(test)]
+    pub(crate) fn with_openlibrary(mut self, openlibrary: Arc<OpenLibrarySource>) -> Self {
+        self.openlibrary = openlibrary;
+        self
+    }
+
     pub async fn add_from_arxiv(
         &self,
         id: &str,
         opts: ArxivAddOptions,
         db: &Database,
     ) -> Result<BookCard> {
+        let fetched = self.fetch_arxiv(id, &opts, db).await?;
+        self.store_fetched(fetched, &opts, db).await
+    }
+
+    /// Like [`Self::add_from_arxiv`], but leaves a new card for the caller
+    /// to store. `opts.auto_index` is not applied.
+    pub async fn fetch_arxiv(
+        &self,
+        id: &str,
+        opts: &ArxivAddOptions,
+        db: &Database,
+    ) -> Result<FetchedCard> {
         let arxiv_id = ArxivId::parse(id)?;
         if let Some(existing) = find_existing_card(db, |card| card_matches_arxiv(card, &arxiv_id))?
         {
-            return Ok(existing);
+            return Ok(FetchedCard::Existing(existing));
         }
 
         let metadata = self.arxiv_client.fetch_metadata(&arxiv_id).await?;
         if let Some(doi) = metadata.doi.as_ref()
             && let Some(existing) = find_existing_card(db, |card| card_matches_doi(card, doi))?
         {
-            return Ok(existing);
+            return Ok(FetchedCard::Existing(existing));
         }
 
         let s2_paper = self
@@ -128,40 +171,256 @@ impl ArxivAddService {
 
         if opts.download_pdf {
             card.file = Some(
-                self.download_arxiv_pdf(&arxiv_id, &pdf_url, opts.download_dir.as_deref())
-                    .await?,
+                self.download_pdf(
+                    &pdf_url,
+                    &download_file_name(&arxiv_id),
+                    opts.download_dir.as_deref(),
+                )
+                .await?,
             );
         }
 
-        if opts.auto_index {
-            self.run_auto_index(&mut card).await?;
-        }
-
-        card.touch();
-        db.upsert_book(&card).map_err(map_db_error)?;
-        Ok(card)
+        Ok(FetchedCard::New(card))
     }
 
+    /// Add the work behind a DOI from CrossRef, Semantic Scholar and
+    /// Unpaywall. With `download_pdf`, Unpaywall's best open-access PDF is
+    /// fetched when there is one; a failed download leaves the card without
+    /// a file rather than failing the add.
     pub async fn add_from_doi(
         &self,
         doi: &str,
         opts: ArxivAddOptions,
         db: &Database,
     ) -> Result<BookCard> {
+        let fetched = self.fetch_doi(doi, &opts, db).await?;
+        self.store_fetched(fetched, &opts, db).await
+    }
+
+    /// Like [`Self::add_from_doi`], but leaves a new card for the caller to
+    /// store. `opts.auto_index` is not applied.
+    pub async fn fetch_doi(
+        &self,
+        doi: &str,
+        opts: &ArxivAddOptions,
+        db: &Database,
+    ) -> Result<FetchedCard> {
         let doi = Doi::parse(doi)?;
         if let Some(existing) = find_existing_card(db, |card| card_matches_doi(card, &doi))? {
-            return Ok(existing);
+            return Ok(FetchedCard::Existing(existing));
         }
 
-        let crossref_work = self.crossref.fetch_by_doi(&doi).await?;
         let s2_paper = self
             .semantic_scholar
             .fetch_paper(&S2PaperId::from_doi(&doi))
             .await
             .ok();
-        let unpaywall = self.fetch_unpaywall(Some(&doi)).await;
+        let card = self.card_from_doi(&doi, s2_paper, opts).await?;
+        Ok(FetchedCard::New(card))
+    }
+
+    /// Add a book by ISBN from Open Library. Open Library has no PDFs, so
+    /// `download_pdf` is refused.
+    pub async fn add_from_isbn(
+        &self,
+        isbn: &str,
+        opts: ArxivAddOptions,
+        db: &Database,
+    ) -> Result<BookCard> {
+        let isbn = Isbn::parse(isbn)?;
+        if opts.download_pdf {
+            return Err(ScienceError::SourceUnavailable(format!(
+                "no PDF source for ISBN {}",
+                isbn.isbn13
+            )));
+        }
+        if let Some(existing) = find_existing_card(db, |card| card_matches_isbn(card, &isbn))? {
+            return Ok(existing);
+        }
+
+        let work = self.openlibrary.fetch_by_isbn(&isbn).await?;
+        let mut card = BookCard::new(work.title.clone());
+        card.merge_metadata(
+            PartialMetadata {
+                doc_type: Some(DocumentType::Book),
+                ..partial_from_openlibrary(work, isbn)
+            },
+            MetadataSource::OpenLibrary,
+        );
+        self.store_new_card(card, &opts, db).await
+    }
+
+    /// Add a paper by PubMed id. PubMed resolves the id, with Semantic
+    /// Scholar as a fallback for records PubMed cannot serve; when either
+    /// knows a DOI the paper is added as with [`Self::add_from_doi`]. With
+    /// `download_pdf`, the PMC open-access PDF is preferred, then Unpaywall's;
+    /// with neither a PMC copy nor a DOI, `download_pdf` fails the add.
+    pub async fn add_from_pmid(
+        &self,
+        pmid: &str,
+        opts: ArxivAddOptions,
+        db: &Database,
+    ) -> Result<BookCard> {
+        let pmid = pmid.trim();
+        if pmid.is_empty() || !pmid.chars().all(|ch| ch.is_ascii_digit()) {
+            return Err(ScienceError::Parse(format!("invalid PMID: {pmid}")));
+        }
+        if let Some(existing) = find_existing_card(db, |card| card_matches_pmid(card, pmid))? {
+            return Ok(existing);
+        }
+
+        let article = self.pubmed.fetch_by_pmid(pmid).await;
+        let s2_paper = self
+            .semantic_scholar
+            .fetch_paper(&S2PaperId::new(format!("PMID:{pmid}")))
+            .await;
+        let (article, s2_paper) = match (article, s2_paper) {
+            (Err(err), Err(_)) => return Err(err),
+            (article, s2_paper) => (article.ok(), s2_paper.ok()),
+        };
+
+        let converted = self.pubmed.convert_ids(pmid).await.ok().flatten();
+        let pmcid = article
+            .as_ref()
+            .and_then(|article| article.pmcid.clone())
+            .or_else(|| converted.as_ref().and_then(|ids| ids.pmcid.clone()))
+            .and_then(|pmcid| normalize_pmcid(&pmcid));
+        let doi = article
+            .as_ref()
+            .and_then(|article| article.doi.as_deref())
+            .or_else(|| converted.as_ref().and_then(|ids| ids.doi.as_deref()))
+            .and_then(parse_doi)
+            .or_else(|| {
+                s2_paper
+                    .as_ref()
+                    .and_then(|paper| lookup_external_id(&paper.external_ids, "DOI"))
+                    .and_then(parse_doi)
+            });
+        if let Some(doi) = &doi
+            && let Some(existing) = find_existing_card(db, |card| card_matches_doi(card, doi))?
+        {
+            return Ok(existing);
+        }
+
+        let pmc_pdf = match (&pmcid, opts.download_pdf) {
+            (Some(pmcid), true) => self.pmc_pdf_url(pmcid).await,
+            _ => None,
+        };
+        if opts.download_pdf && pmc_pdf.is_none() && doi.is_none() {
+            return Err(ScienceError::SourceUnavailable(format!(
+                "no DOI or PMC open-access copy for PMID {pmid}, so no PDF to download"
+            )));
+        }
+
+        let mut card = match doi {
+            Some(doi) => {
+                // The PMC copy, when there is one, replaces Unpaywall's.
+                let doi_opts = ArxivAddOptions {
+                    download_pdf: opts.download_pdf && pmc_pdf.is_none(),
+                    ..opts.clone()
+                };
+                self.card_from_doi(&doi, s2_paper, &doi_opts).await?
+            }
+            None => {
+                let title = match (&article, &s2_paper) {
+                    (Some(article), _) => article.title.clone(),
+                    (None, Some(paper)) => paper.title.clone(),
+                    (None, None) => pmid.to_string(),
+                };
+                let mut card = BookCard::new(title);
+                if let Some(paper) = s2_paper {
+                    merge_semantic_scholar_data(&mut card, paper);
+                }
+                card
+            }
+        };
+        if let Some(article) = article {
+            card.merge_metadata(partial_from_pubmed(article), MetadataSource::PubMed);
+        }
+        if let (Some(pdf_url), Some(pmcid)) = (pmc_pdf, &pmcid) {
+            card.file = self
+                .download_pdf(
+                    &pdf_url,
+                    &format!("{pmcid}.pdf"),
+                    opts.download_dir.as_deref(),
+                )
+                .await
+                .ok();
+        }
+
+        let identifiers = card.identifiers.get_or_insert_with(Default::default);
+        if identifiers.pmid.is_none() {
+            identifiers.pmid = Some(pmid.to_string());
+        }
+        if identifiers.pmcid.is_none() {
+            identifiers.pmcid = pmcid;
+        }
+        self.store_new_card(card, &opts, db).await
+    }
+
+    /// Add whatever `input` identifies — a DOI, arXiv id, ISBN or PMID, bare
+    /// or as a URL; see [`detect_identifier`].
+    pub async fn add_from_identifier(
+        &self,
+        input: &str,
+        opts: ArxivAddOptions,
+        db: &Database,
+    ) -> Result<BookCard> {
+        match detect_identifier(input) {
+            Some(Identifier::Doi(doi)) => self.add_from_doi(&doi.normalized, opts, db).await,
+            Some(Identifier::Arxiv(id)) => self.add_from_arxiv(&id.raw, opts, db).await,
+            Some(Identifier::Isbn(isbn)) => self.add_from_isbn(&isbn.isbn13, opts, db).await,
+            Some(Identifier::Pmid(pmid)) => self.add_from_pmid(&pmid, opts, db).await,
+            None => Err(ScienceError::Parse(format!(
+                "no DOI, arXiv id, ISBN or PMID found in '{}'",
+                input.trim()
+            ))),
+        }
+    }
+
+    async fn card_from_doi(
+        &self,
+        doi: &Doi,
+        s2_paper: Option<S2Paper>,
+        opts: &ArxivAddOptions,
+    ) -> Result<BookCard> {
+        let crossref_work = self.crossref.fetch_by_doi(doi).await?;
+        let unpaywall = self.fetch_unpaywall(Some(doi)).await;
+        let pdf_url = unpaywall
+            .as_ref()
+            .and_then(UnpaywallResult::best_pdf_url)
+            .map(ToOwned::to_owned);
 
         let mut card = build_card_from_crossref(crossref_work, s2_paper, unpaywall);
+        if opts.download_pdf
+            && let Some(pdf_url) = pdf_url
+        {
+            card.file = self
+                .download_pdf(&pdf_url, &doi_file_name(doi), opts.download_dir.as_deref())
+                .await
+                .ok();
+        }
+        Ok(card)
+    }
+
+    async fn store_fetched(
+        &self,
+        fetched: FetchedCard,
+        opts: &ArxivAddOptions,
+        db: &Database,
+    ) -> Result<BookCard> {
+        match fetched {
+            FetchedCard::Existing(card) => Ok(card),
+            FetchedCard::New(card) => self.store_new_card(card, opts, db).await,
+        }
+    }
+
+    async fn store_new_card(
+        &self,
+        mut card: BookCard,
+        opts: &ArxivAddOptions,
+        db: &Database,
+    ) -> Result<BookCard> {
         if opts.auto_index {
             self.run_auto_index(&mut card).await?;
         }
@@ -171,6 +430,16 @@ impl ArxivAddService {
         Ok(card)
     }
 
+    /// The PDF link of `pmcid`'s PMC open-access copy, if it has one.
+    async fn pmc_pdf_url(&self, pmcid: &str) -> Option<String> {
+        self.pubmed
+            .find_open_access(pmcid)
+            .await
+            .ok()
+            .flatten()
+            .and_then(|record| record.pdf_url)
+    }
+
     async fn fetch_unpaywall(&self, doi: Option<&Doi>) -> Option<UnpaywallResult> {
         let doi = doi?;
         let client = self.unpaywall.as_ref()?;
@@ -184,10 +453,10 @@ impl ArxivAddService {
         Ok(())
     }
 
-    async fn download_arxiv_pdf(
+    async fn download_pdf(
         &self,
-        arxiv_id: &ArxivId,
         pdf_url: &str,
+        file_name: &str,
         download_dir: Option<&Path>,
     ) -> Result<BookFile> {
         let dir = resolve_download_dir(download_dir)?;
@@ -196,7 +465,7 @@ impl ArxivAddService {
         })?;
 
         let pdf_bytes = self.downloader.get_bytes(pdf_url).await?;
-        let file_path = dir.join(download_file_name(arxiv_id));
+        let file_path = dir.join(file_name);
         tokio::fs::write(&file_path, &pdf_bytes)
             .await
             .map_err(|e| ScienceError::Parse(format!("failed to write downloaded PDF: {e}")))?;
@@ -539,6 +808,24 @@ fn card_matches_arxiv(card: &BookCard, target: &ArxivId) -> bool {
     }
 }
 
+fn card_matches_isbn(card: &BookCard, target: &Isbn) -> bool {
+    let identifiers = card.identifiers.as_ref();
+    identifiers
+        .and_then(|ids| ids.isbn13.as_deref())
+        .into_iter()
+        .chain(identifiers.and_then(|ids| ids.isbn10.as_deref()))
+        .chain(card.metadata.isbn.iter().map(String::as_str))
+        .filter_map(|raw| Isbn::parse(raw).ok())
+        .any(|isbn| isbn.isbn13 == target.isbn13)
+}
+
+fn card_matches_pmid(card: &BookCard, pmid: &str) -> bool {
+    card.identifiers
+        .as_ref()
+        .and_then(|identifiers| identifiers.pmid.as_deref())
+        .is_some_and(|raw| raw.trim() == pmid)
+}
+
 fn card_matches_doi(card: &BookCard, target: &Doi) -> bool {
     let Some(raw) = card
         .identifiers
@@ -557,7 +844,7 @@ fn map_db_error(err: omniscope_core::error::OmniscopeError) -> ScienceError {
     ScienceError::Parse(format!("database error: {err}"))
 }
 
-fn resolve_download_dir(explicit: Option<&Path>) -> Result<PathBuf> {
+pub(crate) fn resolve_download_dir(explicit: Option<&Path>) -> Result<PathBuf> {
     if let Some(path) = explicit {
         return Ok(path.to_path_buf());
     }
@@ -570,7 +857,7 @@ fn resolve_download_dir(explicit: Option<&Path>) -> Result<PathBuf> {
         .map_err(|e| ScienceError::Parse(format!("failed to resolve download directory: {e}")))
 }
 
-fn download_file_name(arxiv_id: &ArxivId) -> String {
+pub(crate) fn download_file_name(arxiv_id: &ArxivId) -> String {
     let raw = match arxiv_id.version {
         Some(version) => format!("{}v{version}", arxiv_id.id),
         None => arxiv_id.id.clone(),
@@ -585,6 +872,18 @@ fn download_file_name(arxiv_id: &ArxivId) -> String {
     format!("{sanitized}.pdf")
 }
 
+fn doi_file_name(doi: &Doi) -> String {
+    let sanitized = doi
+        .normalized
+        .chars()
+        .map(|ch| match ch {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' | '_' => ch,
+            _ => '_',
+        })
+        .collect::<String>();
+    format!("{sanitized}.pdf")
+}
+
 fn env_var_non_empty(key: &str) -> Option<String> {
     std::env::var(key)
         .ok()
@@ -626,6 +925,7 @@ mod tests {
             ))),
             unpaywall,
         )
+        .with_pubmed(Arc::new(PubMedSource::new_for_tests(server.url())))
     }
 
     fn make_temp_download_dir() -> PathBuf {
@@ -815,6 +1115,40 @@ mod tests {
         assert_eq!(db.count_books().unwrap(), 1);
     }
 
+    #[tokio::test]
+    async fn fetch_doi_leaves_new_card_unstored() {
+        let mut server = Server::new_async().await;
+        let _crossref = server
+            .mock("GET", "/works/10.1000%2Ffetch")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "message": {
+                        "DOI": "10.1000/fetch",
+                        "title": ["Fetched Title"],
+                        "type": "journal-article"
+                    }
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let service = build_service(&server, false);
+        let db = Database::open_in_memory().unwrap();
+
+        let fetched = service
+            .fetch_doi("10.1000/fetch", &ArxivAddOptions::default(), &db)
+            .await
+            .unwrap();
+
+        let FetchedCard::New(card) = fetched else {
+            panic!("expected a new card");
+        };
+        assert_eq!(card.metadata.title, "Fetched Title");
+        assert_eq!(db.count_books().unwrap(), 0);
+    }
+
     #[tokio::test]
     async fn add_from_doi_enriches_from_crossref_semantic_scholar_and_unpaywall() {
         let mut server = Server::new_async().await;
@@ -984,6 +1318,301 @@ mod tests {
         assert!(card.open_access.is_none());
     }
 
+    #[tokio::test]
+    async fn add_from_identifier_resolves_pmid_and_downloads_open_access_pdf() {
+        let mut server = Server::new_async().await;
+        let pdf_url = format!("{}/oa/paper.pdf", server.url());
+
+        let s2_mock = server
+            .mock("GET", "/graph/v1/paper/PMID:31452104")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "paperId": "s2-pm",
+                    "externalIds": {"DOI": "10.1000/pm", "PubMed": "31452104"},
+                    "title": "PubMed Paper",
+                    "year": 2019,
+                    "authors": [{"name":"Grace Hopper"}],
+                    "citationCount": 5,
+                    "referenceCount": 3,
+                    "influentialCitationCount": 1
+                })
+                .to_string(),
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let crossref_mock = server
+            .mock("GET", "/works/10.1000%2Fpm")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "message": {
+                        "DOI": "10.1000/pm",
+                        "title": ["PubMed Paper"],
+                        "author": [{"given":"Grace","family":"Hopper"}],
+                        "type": "journal-article"
+                    }
+                })
+                .to_string(),
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let unpaywall_mock = server
+            .mock("GET", "/v2/10.1000%2Fpm")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "doi": "10.1000/pm",
+                    "is_oa": true,
+                    "oa_status": "gold",
+                    "best_oa_location": {"url": pdf_url, "url_for_pdf": pdf_url},
+                    "oa_locations": []
+                })
+                .to_string(),
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let pdf_mock = server
+            .mock("GET", "/oa/paper.pdf")
+            .with_status(200)
+            .with_body(vec![0x25, 0x50, 0x44, 0x46])
+            .expect(1)
+            .create_async()
+            .await;
+
+        let service = build_service(&server, true);
+        let db = Database::open_in_memory().unwrap();
+        let download_dir = make_temp_download_dir();
+        let opts = ArxivAddOptions {
+            download_pdf: true,
+            download_dir: Some(download_dir.clone()),
+            auto_index: false,
+        };
+
+        let card = service
+            .add_from_identifier("PMID: 31452104", opts.clone(), &db)
+            .await
+            .unwrap();
+
+        s2_mock.assert_async().await;
+        crossref_mock.assert_async().await;
+        unpaywall_mock.assert_async().await;
+        pdf_mock.assert_async().await;
+
+        let ids = card.identifiers.as_ref().unwrap();
+        assert_eq!(ids.pmid.as_deref(), Some("31452104"));
+        assert_eq!(ids.doi.as_deref(), Some("10.1000/pm"));
+        assert!(
+            card.file
+                .as_ref()
+                .is_some_and(|file| file.path.ends_with("10.1000_pm.pdf"))
+        );
+
+        let again = service
+            .add_from_identifier("https://pubmed.ncbi.nlm.nih.gov/31452104/", opts, &db)
+            .await
+            .unwrap();
+        assert_eq!(again.id, card.id);
+        assert_eq!(db.count_books().unwrap(), 1);
+
+        let _ = std::fs::remove_dir_all(download_dir);
+    }
+
+    #[tokio::test]
+    async fn add_from_isbn_builds_book_from_open_library() {
+        let mut server = Server::new_async().await;
+        let openlibrary_mock = server
+            .mock("GET", "/api/books")
+            .match_query(Matcher::UrlEncoded(
+                "bibkeys".to_string(),
+                "ISBN:9780306406157".to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "ISBN:9780306406157": {
+                        "title": "Open Library Book",
+                        "authors": [{"name": "Ada Lovelace"}],
+                        "publish_date": "1992"
+                    }
+                })
+                .to_string(),
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let service = build_service(&server, false)
+            .with_openlibrary(Arc::new(OpenLibrarySource::new_for_tests(server.url())));
+        let db = Database::open_in_memory().unwrap();
+        let card = service
+            .add_from_identifier("978-0-306-40615-7", ArxivAddOptions::default(), &db)
+            .await
+            .unwrap();
+
+        openlibrary_mock.assert_async().await;
+        assert_eq!(card.metadata.title, "Open Library Book");
+        assert_eq!(card.metadata.authors, vec!["Ada Lovelace"]);
+        assert_eq!(
+            card.publication.as_ref().map(|p| p.doc_type),
+            Some(DocumentType::Book)
+        );
+    }
+
+    #[tokio::test]
+    async fn add_from_pmid_resolves_through_pubmed_and_downloads_the_pmc_pdf() {
+        let mut server = Server::new_async().await;
+        let s2_mock = server
+            .mock("GET", "/graph/v1/paper/PMID:31452104")
+            .match_query(Matcher::Any)
+            .with_status(404)
+            .with_body(r#"{"error":"Paper not found"}"#)
+            .create_async()
+            .await;
+        let efetch_mock = server
+            .mock("GET", "/eutils/efetch.fcgi")
+            .match_query(Matcher::UrlEncoded(
+                "id".to_string(),
+                "31452104".to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "text/xml")
+            .with_body(
+                r#"<?xml version="1.0" ?>
+<PubmedArticleSet>
+  <PubmedArticle>
+    <MedlineCitation>
+      <PMID Version="1">31452104</PMID>
+      <Article>
+        <Journal>
+          <JournalIssue><PubDate><Year>2019</Year></PubDate></JournalIssue>
+          <Title>Nature</Title>
+        </Journal>
+        <ArticleTitle>PubMed Only Paper</ArticleTitle>
+        <AuthorList>
+          <Author><LastName>Hopper</LastName><ForeName>Grace</ForeName></Author>
+        </AuthorList>
+      </Article>
+    </MedlineCitation>
+    <PubmedData>
+      <ArticleIdList>
+        <ArticleId IdType="pubmed">31452104</ArticleId>
+        <ArticleId IdType="pmc">PMC6789012</ArticleId>
+      </ArticleIdList>
+    </PubmedData>
+  </PubmedArticle>
+</PubmedArticleSet>"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let oa_mock = server
+            .mock("GET", "/oa")
+            .match_query(Matcher::UrlEncoded(
+                "id".to_string(),
+                "PMC6789012".to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "text/xml")
+            .with_body(format!(
+                r#"<OA><records returned-count="1" total-count="1">
+<record id="PMC6789012" license="CC BY">
+<link format="pdf" href="{}/pmc/paper.pdf" />
+</record></records></OA>"#,
+                server.url()
+            ))
+            .expect(1)
+            .create_async()
+            .await;
+        let pdf_mock = server
+            .mock("GET", "/pmc/paper.pdf")
+            .with_status(200)
+            .with_body(vec![0x25, 0x50, 0x44, 0x46])
+            .expect(1)
+            .create_async()
+            .await;
+
+        let service = build_service(&server, false);
+        let db = Database::open_in_memory().unwrap();
+        let download_dir = make_temp_download_dir();
+        let opts = ArxivAddOptions {
+            download_pdf: true,
+            download_dir: Some(download_dir.clone()),
+            auto_index: false,
+        };
+
+        let card = service.add_from_pmid("31452104", opts, &db).await.unwrap();
+
+        s2_mock.assert_async().await;
+        efetch_mock.assert_async().await;
+        oa_mock.assert_async().await;
+        pdf_mock.assert_async().await;
+
+        assert_eq!(card.metadata.title, "PubMed Only Paper");
+        assert_eq!(card.metadata.authors, vec!["Grace Hopper"]);
+        let ids = card.identifiers.as_ref().unwrap();
+        assert_eq!(ids.pmid.as_deref(), Some("31452104"));
+        assert_eq!(ids.pmcid.as_deref(), Some("PMC6789012"));
+        assert!(
+            card.file
+                .as_ref()
+                .is_some_and(|file| file.path.ends_with("PMC6789012.pdf"))
+        );
+        assert_eq!(db.count_books().unwrap(), 1);
+
+        let _ = std::fs::remove_dir_all(download_dir);
+    }
+
+    #[tokio::test]
+    async fn download_pdf_is_refused_when_there_is_no_pdf_source() {
+        let mut server = Server::new_async().await;
+        let s2_mock = server
+            .mock("GET", "/graph/v1/paper/PMID:31452104")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "paperId": "s2-pm",
+                    "externalIds": {"PubMed": "31452104"},
+                    "title": "PubMed Paper"
+                })
+                .to_string(),
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let service = build_service(&server, false);
+        let db = Database::open_in_memory().unwrap();
+        let opts = ArxivAddOptions {
+            download_pdf: true,
+            ..ArxivAddOptions::default()
+        };
+
+        let isbn = service
+            .add_from_isbn("978-0-306-40615-7", opts.clone(), &db)
+            .await;
+        assert!(matches!(isbn, Err(ScienceError::SourceUnavailable(_))));
+
+        let pmid = service.add_from_pmid("31452104", opts, &db).await;
+        s2_mock.assert_async().await;
+        assert!(matches!(pmid, Err(ScienceError::SourceUnavailable(_))));
+        assert_eq!(db.count_books().unwrap(), 0);
+    }
+
     #[tokio::test]
     async fn integration_add_from_arxiv_real_api_when_enabled() {
         if std::env::var("CI_INTEGRATION").ok().as_deref() != Some("1") {
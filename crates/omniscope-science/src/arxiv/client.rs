@@ -40,7 +40,13 @@ impl ArxivClient {
         if let Some(cached) = self.cache.get::<ArxivMetadata>(&cache_key).await {
             return Ok(cached);
         }
+        self.fetch_fresh(id).await
+    }
 
+    /// Like [`fetch_metadata`](Self::fetch_metadata), but always asks arXiv,
+    /// refreshing the cached entry.
+    async fn fetch_fresh(&self, id: &ArxivId) -> Result<ArxivMetadata> {
+        let cache_key = format!("metadata:{}", id.id);
         let url = format!("{}?id_list={}", self.base_url, id.id);
         let xml = self.http.get(&url).await?;
         let mut parsed = parse_atom_response(&xml)?;
@@ -53,6 +59,20 @@ impl ArxivClient {
     }
 
     pub async fn search(&self, query: &ArxivSearchQuery) -> Result<Vec<ArxivMetadata>> {
+        self.run_search(query, true).await
+    }
+
+    /// Like [`search`](Self::search), but skips the cache; feeds use this so
+    /// today's submissions show up today.
+    pub async fn search_latest(&self, query: &ArxivSearchQuery) -> Result<Vec<ArxivMetadata>> {
+        self.run_search(query, false).await
+    }
+
+    async fn run_search(
+        &self,
+        query: &ArxivSearchQuery,
+        use_cache: bool,
+    ) -> Result<Vec<ArxivMetadata>> {
         let search_query = query.to_query_string();
         let max_results = query.max_results.unwrap_or(DEFAULT_MAX_RESULTS);
         let start = query.start.unwrap_or(0);
@@ -65,7 +85,7 @@ impl ArxivClient {
 
         let cache_key =
             format!("search:{search_query}:start={start}:max={max_results}:sort={sort_by}");
-        if let Some(cached) = self.cache.get::<Vec<ArxivMetadata>>(&cache_key).await {
+        if use_cache && let Some(cached) = self.cache.get::<Vec<ArxivMetadata>>(&cache_key).await {
             return Ok(cached);
         }
 
@@ -90,7 +110,8 @@ impl ArxivClient {
         id: &ArxivId,
         current_version: Option<u8>,
     ) -> Result<Option<ArxivMetadata>> {
-        let latest = self.fetch_metadata(id).await?;
+        // A week-old cached entry would hide any version released since.
+        let latest = self.fetch_fresh(id).await?;
         let latest_version = latest.arxiv_id.version;
 
         let has_update = match (current_version, latest_version) {
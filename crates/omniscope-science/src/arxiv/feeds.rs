@@ -0,0 +1,282 @@
+//! Saved arXiv searches ("feeds") and what has been seen of them.
+//!
+//! Feeds themselves live in the library manifest; the entries last fetched
+//! and the time of the last visit are kept in `.libr/arxiv_feeds.json`, so
+//! the sidebar can count new submissions without asking arXiv on every start.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use omniscope_core::LibraryRoot;
+use omniscope_core::models::ArxivFeed;
+use serde::{Deserialize, Serialize};
+
+use crate::arxiv::client::ArxivClient;
+use crate::arxiv::types::{ArxivMetadata, ArxivSearchQuery};
+use crate::error::{Result, ScienceError};
+
+const STATE_FILE_NAME: &str = "arxiv_feeds.json";
+
+/// The search behind `feed`, newest submissions first.
+pub fn feed_query(feed: &ArxivFeed, max_results: u32) -> ArxivSearchQuery {
+    let groups: Vec<String> = [
+        ("cat", &feed.categories),
+        ("au", &feed.authors),
+        ("all", &feed.keywords),
+    ]
+    .into_iter()
+    .filter_map(|(field, values)| any_of(field, values))
+    .collect();
+
+    ArxivSearchQuery {
+        raw: (!groups.is_empty()).then(|| groups.join("+AND+")),
+        sort_by: Some("submittedDate".to_string()),
+        max_results: Some(max_results),
+        ..Default::default()
+    }
+}
+
+/// `%28cat:cs.LG+OR+cat:stat.ML%29`; phrases are quoted.
+fn any_of(field: &str, values: &[String]) -> Option<String> {
+    let terms: Vec<String> = values
+        .iter()
+        .map(|value| value.split_whitespace().collect::<Vec<_>>())
+        .filter(|words| !words.is_empty())
+        .map(|words| match words.as_slice() {
+            [word] => format!("{field}:{word}"),
+            _ => format!("{field}:%22{}%22", words.join("+")),
+        })
+        .collect();
+    match terms.len() {
+        0 => None,
+        1 => terms.into_iter().next(),
+        _ => Some(format!("%28{}%29", terms.join("+OR+"))),
+    }
+}
+
+/// Build a feed from search terms: `cat:cs.LG`, `au:Bengio` or
+/// `au:"Yoshua Bengio"`; anything else is a keyword.
+pub fn feed_from_terms(name: &str, terms: &str) -> ArxivFeed {
+    let mut feed = ArxivFeed {
+        name: name.to_string(),
+        categories: Vec::new(),
+        authors: Vec::new(),
+        keywords: Vec::new(),
+    };
+    for term in split_terms(terms) {
+        if let Some(category) = term.strip_prefix("cat:") {
+            feed.categories.push(category.to_string());
+        } else if let Some(author) = term.strip_prefix("au:") {
+            feed.authors.push(author.to_string());
+        } else {
+            feed.keywords.push(term);
+        }
+    }
+    feed
+}
+
+/// The inverse of [`feed_from_terms`], for display and editing.
+pub fn feed_terms(feed: &ArxivFeed) -> String {
+    let quote = |value: &String| {
+        if value.contains(char::is_whitespace) {
+            format!("\"{value}\"")
+        } else {
+            value.clone()
+        }
+    };
+    let categories = feed.categories.iter().map(|c| format!("cat:{}", quote(c)));
+    let authors = feed.authors.iter().map(|a| format!("au:{}", quote(a)));
+    let keywords = feed.keywords.iter().map(quote);
+    categories
+        .chain(authors)
+        .chain(keywords)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Whitespace-separated terms; double quotes keep a phrase together and are
+/// dropped.
+fn split_terms(input: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for ch in input.chars() {
+        match ch {
+            '"' => quoted = !quoted,
+            ch if ch.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    terms.push(std::mem::take(&mut current));
+                }
+            }
+            ch => current.push(ch),
+        }
+    }
+    if !current.is_empty() {
+        terms.push(current);
+    }
+    terms
+}
+
+/// Fetch the newest submissions matching `feed`, bypassing the search cache.
+pub async fn fetch_feed(
+    client: &ArxivClient,
+    feed: &ArxivFeed,
+    max_results: u32,
+) -> Result<Vec<ArxivMetadata>> {
+    client.search_latest(&feed_query(feed, max_results)).await
+}
+
+/// One feed's last fetch and last visit.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FeedHistory {
+    pub last_visit: Option<DateTime<Utc>>,
+    pub fetched_at: Option<DateTime<Utc>>,
+    pub entries: Vec<ArxivMetadata>,
+}
+
+impl FeedHistory {
+    /// Whether `entry` was submitted after the last visit.
+    pub fn is_new(&self, entry: &ArxivMetadata) -> bool {
+        self.last_visit.is_none_or(|visit| entry.published > visit)
+    }
+
+    pub fn new_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| self.is_new(entry))
+            .count()
+    }
+
+    pub fn is_stale(&self, now: DateTime<Utc>, interval: chrono::Duration) -> bool {
+        self.fetched_at.is_none_or(|at| now - at >= interval)
+    }
+}
+
+/// Feed histories by feed name, kept in `.libr/arxiv_feeds.json`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArxivFeedState {
+    pub feeds: HashMap<String, FeedHistory>,
+}
+
+impl ArxivFeedState {
+    pub fn path(root: &LibraryRoot) -> PathBuf {
+        root.libr_dir().join(STATE_FILE_NAME)
+    }
+
+    /// Load the state, treating a missing file as "never fetched".
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| ScienceError::Parse(format!("{}: {err}", path.display())))?;
+        serde_json::from_str(&contents)
+            .map_err(|err| ScienceError::Parse(format!("{}: {err}", path.display())))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|err| ScienceError::Parse(format!("{}: {err}", parent.display())))?;
+        }
+        let json = serde_json::to_string(self)
+            .map_err(|err| ScienceError::Parse(format!("failed to encode feed state: {err}")))?;
+        std::fs::write(path, json)
+            .map_err(|err| ScienceError::Parse(format!("{}: {err}", path.display())))
+    }
+
+    pub fn history(&self, name: &str) -> Option<&FeedHistory> {
+        self.feeds.get(name)
+    }
+
+    /// Replace the fetched entries of `name`.
+    pub fn store(&mut self, name: &str, entries: Vec<ArxivMetadata>, now: DateTime<Utc>) {
+        let history = self.feeds.entry(name.to_string()).or_default();
+        history.entries = entries;
+        history.fetched_at = Some(now);
+    }
+
+    /// Record a visit; entries submitted before `now` stop counting as new.
+    pub fn mark_visited(&mut self, name: &str, now: DateTime<Utc>) {
+        self.feeds.entry(name.to_string()).or_default().last_visit = Some(now);
+    }
+
+    /// Forget feeds that were removed from the manifest.
+    pub fn retain_feeds(&mut self, feeds: &[ArxivFeed]) {
+        self.feeds
+            .retain(|name, _| feeds.iter().any(|feed| feed.name == *name));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::identifiers::arxiv::ArxivId;
+
+    #[test]
+    fn feed_query_groups_alternatives() {
+        let feed = feed_from_terms(
+            "ml",
+            r#"cat:cs.LG cat:stat.ML au:"Yoshua Bengio" diffusion"#,
+        );
+        assert_eq!(feed.categories, vec!["cs.LG", "stat.ML"]);
+        assert_eq!(feed.authors, vec!["Yoshua Bengio"]);
+        assert_eq!(feed.keywords, vec!["diffusion"]);
+        assert_eq!(
+            feed_terms(&feed),
+            r#"cat:cs.LG cat:stat.ML au:"Yoshua Bengio" diffusion"#
+        );
+
+        let query = feed_query(&feed, 50);
+        assert_eq!(
+            query.to_query_string(),
+            "%28cat:cs.LG+OR+cat:stat.ML%29+AND+au:%22Yoshua+Bengio%22+AND+all:diffusion"
+        );
+        assert_eq!(query.sort_by.as_deref(), Some("submittedDate"));
+    }
+
+    #[test]
+    fn entries_after_the_last_visit_are_new() {
+        let at = |day: u32| Utc.with_ymd_and_hms(2026, 3, day, 12, 0, 0).unwrap();
+        let entry = |id: &str, day: u32| {
+            let arxiv_id = ArxivId::parse(id).unwrap();
+            ArxivMetadata {
+                arxiv_id: arxiv_id.clone(),
+                doi: None,
+                title: id.to_string(),
+                authors: Vec::new(),
+                abstract_text: String::new(),
+                published: at(day),
+                updated: at(day),
+                categories: vec!["cs.LG".to_string()],
+                primary_category: "cs.LG".to_string(),
+                comment: None,
+                journal_ref: None,
+                pdf_url: String::new(),
+                abs_url: arxiv_id.abs_url,
+            }
+        };
+
+        let mut state = ArxivFeedState::default();
+        state.store(
+            "ml",
+            vec![entry("2603.00001", 1), entry("2603.00002", 3)],
+            at(3),
+        );
+        assert_eq!(state.history("ml").unwrap().new_count(), 2);
+
+        state.mark_visited("ml", at(2));
+        let history = state.history("ml").unwrap();
+        assert_eq!(history.new_count(), 1);
+        assert!(!history.is_stale(at(3), chrono::Duration::hours(1)));
+        assert!(history.is_stale(at(4), chrono::Duration::hours(1)));
+
+        state.retain_feeds(&[]);
+        assert!(state.feeds.is_empty());
+    }
+}
@@ -1,8 +1,13 @@
 pub mod add;
 pub mod client;
+pub mod feeds;
 pub mod parser;
 pub mod types;
 pub mod updater;
 
 pub use add::{ArxivAddOptions, ArxivAddService, ScienceIndexer, add_from_arxiv, add_from_doi};
-pub use updater::{ArxivUpdateResult, ArxivUpdater};
+pub use feeds::{ArxivFeedState, FeedHistory, feed_from_terms, feed_terms};
+pub use updater::{
+    AppliedArxivUpdate, ArxivApplyOptions, ArxivUpdateDiff, ArxivUpdateResult, ArxivUpdateState,
+    ArxivUpdater,
+};
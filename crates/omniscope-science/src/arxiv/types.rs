@@ -32,6 +32,9 @@ pub struct ArxivSearchQuery {
     pub abstract_text: Option<String>,
     pub category: Option<String>,
     pub journal: Option<String>,
+    /// A `search_query` expression that is already encoded, ANDed with the
+    /// fields above. Lets callers group alternatives, e.g. several categories.
+    pub raw: Option<String>,
     pub id_list: Vec<String>,
     pub sort_by: Option<String>,
     pub max_results: Option<u32>,
@@ -62,6 +65,9 @@ impl ArxivSearchQuery {
         if let Some(value) = non_empty(&self.journal) {
             parts.push(format!("jr:{}", encode_value(value)));
         }
+        if let Some(value) = non_empty(&self.raw) {
+            parts.push(value.to_string());
+        }
 
         if !self.id_list.is_empty() {
             let ids = self
@@ -1,18 +1,34 @@
+//! Checks tracked arXiv papers for newer versions (`omniscope arxiv
+//! check-updates`, the TUI's update inbox).
+//!
+//! Each check is recorded in an [`ArxivUpdateState`] under `.libr/`, so runs
+//! only ask arXiv about papers not looked at within the configured interval,
+//! and updates that were found but not yet accepted survive restarts.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
-use chrono::Datelike;
-use omniscope_core::models::{BookCard, DocumentType, WebSource};
+use chrono::{DateTime, Datelike, Utc};
+use omniscope_core::LibraryRoot;
+use omniscope_core::models::{BookCard, BookFile, DocumentType, FileFormat, WebSource};
 use omniscope_core::storage::database::Database;
+use omniscope_core::storage::json_cards;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::arxiv::add::{download_file_name, resolve_download_dir};
 use crate::arxiv::client::ArxivClient;
 use crate::arxiv::types::ArxivMetadata;
 use crate::enrichment::merge::{BookCardMergeExt, MetadataSource, PartialMetadata};
 use crate::error::{Result, ScienceError};
+use crate::http::RateLimitedClient;
 use crate::identifiers::arxiv::ArxivId;
 
 const SCAN_PAGE_SIZE: usize = 200;
+const STATE_FILE_NAME: &str = "arxiv_updates.json";
+const USER_AGENT: &str = "omniscope-science/0.1";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArxivUpdateResult {
@@ -23,56 +39,242 @@ pub struct ArxivUpdateResult {
     pub new_metadata: ArxivMetadata,
 }
 
+impl ArxivUpdateResult {
+    /// `v1 → v7`, or `→ v7` when the card had no version.
+    pub fn version_label(&self) -> String {
+        match self.old_version {
+            Some(old) => format!("v{old} → v{}", self.new_version),
+            None => format!("→ v{}", self.new_version),
+        }
+    }
+
+    /// The title, authors and abstract that the new version changes on `card`.
+    pub fn diff(&self, card: &BookCard) -> ArxivUpdateDiff {
+        let metadata = &self.new_metadata;
+        let new_authors: Vec<String> = metadata
+            .authors
+            .iter()
+            .map(|author| author.name.clone())
+            .collect();
+
+        let title = (normalize_space(&card.metadata.title) != normalize_space(&metadata.title))
+            .then(|| (card.metadata.title.clone(), metadata.title.clone()));
+        let authors = (card.metadata.authors != new_authors)
+            .then(|| (card.metadata.authors.clone(), new_authors));
+        let abstract_text = (card.ai.summary.as_deref().map(normalize_space)
+            != Some(normalize_space(&metadata.abstract_text)))
+        .then(|| (card.ai.summary.clone(), metadata.abstract_text.clone()));
+
+        ArxivUpdateDiff {
+            title,
+            authors,
+            abstract_text,
+        }
+    }
+}
+
+/// Old and new values of the fields a new arXiv version changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ArxivUpdateDiff {
+    pub title: Option<(String, String)>,
+    pub authors: Option<(Vec<String>, Vec<String>)>,
+    pub abstract_text: Option<(Option<String>, String)>,
+}
+
+impl ArxivUpdateDiff {
+    /// True when only the version number moved.
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none() && self.authors.is_none() && self.abstract_text.is_none()
+    }
+}
+
+/// Last-check times and not-yet-accepted updates, kept in
+/// `.libr/arxiv_updates.json`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArxivUpdateState {
+    pub last_run: Option<DateTime<Utc>>,
+    pub checked: HashMap<Uuid, DateTime<Utc>>,
+    pub pending: Vec<ArxivUpdateResult>,
+}
+
+impl ArxivUpdateState {
+    pub fn path(root: &LibraryRoot) -> PathBuf {
+        root.libr_dir().join(STATE_FILE_NAME)
+    }
+
+    /// Load the state, treating a missing file as "never checked".
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| ScienceError::Parse(format!("{}: {err}", path.display())))?;
+        serde_json::from_str(&contents)
+            .map_err(|err| ScienceError::Parse(format!("{}: {err}", path.display())))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|err| ScienceError::Parse(format!("{}: {err}", parent.display())))?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|err| ScienceError::Parse(format!("failed to encode update state: {err}")))?;
+        std::fs::write(path, json)
+            .map_err(|err| ScienceError::Parse(format!("{}: {err}", path.display())))
+    }
+
+    /// Whether `book_id` was last checked more than `interval` before `now`.
+    pub fn is_due(&self, book_id: &Uuid, now: DateTime<Utc>, interval: chrono::Duration) -> bool {
+        self.checked
+            .get(book_id)
+            .is_none_or(|checked| now - *checked >= interval)
+    }
+
+    /// Whether any paper would be checked now; lets callers skip opening a
+    /// runtime when nothing is due.
+    pub fn run_due(&self, now: DateTime<Utc>, interval: chrono::Duration) -> bool {
+        self.last_run.is_none_or(|last| now - last >= interval)
+    }
+
+    /// Add `update`, replacing an older pending update for the same book.
+    pub fn record(&mut self, update: ArxivUpdateResult) {
+        self.pending
+            .retain(|pending| pending.book_id != update.book_id);
+        self.pending.push(update);
+        self.pending
+            .sort_by_key(|pending| pending.book_id.as_u128());
+    }
+
+    /// Remove and return the pending update for `book_id`.
+    pub fn take(&mut self, book_id: &Uuid) -> Option<ArxivUpdateResult> {
+        let idx = self
+            .pending
+            .iter()
+            .position(|pending| pending.book_id == *book_id)?;
+        Some(self.pending.remove(idx))
+    }
+}
+
+/// How [`ArxivUpdater::apply_to_library`] treats the new version's PDF.
+#[derive(Debug, Clone, Default)]
+pub struct ArxivApplyOptions {
+    /// Download the new PDF and point the card at it. It is saved next to
+    /// the current file, or in `download_dir` when the card has none.
+    pub download_pdf: bool,
+    pub download_dir: Option<PathBuf>,
+}
+
+/// A card before and after an update was applied.
+#[derive(Debug, Clone)]
+pub struct AppliedArxivUpdate {
+    pub previous: BookCard,
+    pub card: BookCard,
+}
+
 pub struct ArxivUpdater {
     pub client: Arc<ArxivClient>,
     pub db: Arc<Database>,
+    downloader: RateLimitedClient,
 }
 
 impl ArxivUpdater {
     pub fn new(client: Arc<ArxivClient>, db: Arc<Database>) -> Self {
-        Self { client, db }
-    }
-
-    pub fn from_db(db: Arc<Database>) -> Self {
         Self {
-            client: Arc::new(ArxivClient::new()),
+            client,
             db,
+            downloader: RateLimitedClient::new(Duration::from_secs(3), 3, USER_AGENT),
         }
     }
 
+    pub fn from_db(db: Arc<Database>) -> Self {
+        Self::new(Arc::new(ArxivClient::new()), db)
+    }
+
     pub async fn check_all_updates(&self) -> Result<Vec<ArxivUpdateResult>> {
         let tracked = tracked_arxiv_books(self.db.as_ref())?;
         let mut updates = Vec::new();
 
         for tracked_book in tracked {
-            let maybe_updated = self
-                .client
-                .check_for_updates(&tracked_book.arxiv_id, tracked_book.old_version)
-                .await?;
-            let Some(new_metadata) = maybe_updated else {
-                continue;
-            };
-
-            let new_version = new_metadata.arxiv_id.version.ok_or_else(|| {
-                ScienceError::Parse(format!(
-                    "arXiv API returned update without version: {}",
-                    new_metadata.arxiv_id.id
-                ))
-            })?;
-
-            updates.push(ArxivUpdateResult {
-                book_id: tracked_book.book_id,
-                arxiv_id: tracked_book.arxiv_id,
-                old_version: tracked_book.old_version,
-                new_version,
-                new_metadata,
-            });
+            if let Some(update) = self.check_book(tracked_book).await? {
+                updates.push(update);
+            }
         }
 
         updates.sort_by_key(|item| item.book_id.as_u128());
         Ok(updates)
     }
 
+    /// Check the papers not looked at within `interval`, recording each
+    /// check and every update found in `state` as it goes, so a failure
+    /// part-way through keeps the progress made. Pending updates for books
+    /// that are gone or already at the new version are dropped.
+    ///
+    /// Returns the updates found by this run.
+    pub async fn check_due_updates(
+        &self,
+        state: &mut ArxivUpdateState,
+        interval: chrono::Duration,
+    ) -> Result<Vec<ArxivUpdateResult>> {
+        let tracked = tracked_arxiv_books(self.db.as_ref())?;
+        let versions: HashMap<Uuid, Option<u8>> = tracked
+            .iter()
+            .map(|book| (book.book_id, book.old_version))
+            .collect();
+        state.pending.retain(|pending| {
+            versions
+                .get(&pending.book_id)
+                .is_some_and(|version| version.is_none_or(|old| old < pending.new_version))
+        });
+        state.checked.retain(|id, _| versions.contains_key(id));
+
+        let now = Utc::now();
+        let mut found = Vec::new();
+        for tracked_book in tracked {
+            if !state.is_due(&tracked_book.book_id, now, interval) {
+                continue;
+            }
+            let book_id = tracked_book.book_id;
+            if let Some(update) = self.check_book(tracked_book).await? {
+                state.record(update.clone());
+                found.push(update);
+            }
+            state.checked.insert(book_id, Utc::now());
+        }
+
+        state.last_run = Some(now);
+        Ok(found)
+    }
+
+    async fn check_book(
+        &self,
+        tracked_book: TrackedArxivBook,
+    ) -> Result<Option<ArxivUpdateResult>> {
+        let maybe_updated = self
+            .client
+            .check_for_updates(&tracked_book.arxiv_id, tracked_book.old_version)
+            .await?;
+        let Some(new_metadata) = maybe_updated else {
+            return Ok(None);
+        };
+
+        let new_version = new_metadata.arxiv_id.version.ok_or_else(|| {
+            ScienceError::Parse(format!(
+                "arXiv API returned update without version: {}",
+                new_metadata.arxiv_id.id
+            ))
+        })?;
+
+        Ok(Some(ArxivUpdateResult {
+            book_id: tracked_book.book_id,
+            arxiv_id: tracked_book.arxiv_id,
+            old_version: tracked_book.old_version,
+            new_version,
+            new_metadata,
+        }))
+    }
+
     pub fn apply_updates(&self, results: &[ArxivUpdateResult]) -> Result<()> {
         for result in results {
             let mut card = self
@@ -80,17 +282,80 @@ impl ArxivUpdater {
                 .get_book(&result.book_id.to_string())
                 .map_err(map_db_error)?;
 
-            let metadata = result.new_metadata.clone();
-            upsert_arxiv_web_source(&mut card, "arxiv_abs", &metadata.abs_url);
-            upsert_arxiv_web_source(&mut card, "arxiv_pdf", &metadata.pdf_url);
-            card.merge_metadata(partial_from_arxiv(metadata), MetadataSource::ArxivApi);
-            card.touch();
-
+            apply_metadata(&mut card, &result.new_metadata);
             self.db.upsert_book(&card).map_err(map_db_error)?;
         }
 
         Ok(())
     }
+
+    /// Apply `update` to the card in `cards_dir` and the index, optionally
+    /// fetching the new PDF. The old file stays on disk.
+    ///
+    /// Fails when the card's JSON can't be read: the index row lacks notes,
+    /// files and other fields, and saving it as the card would lose them.
+    pub async fn apply_to_library(
+        &self,
+        update: &ArxivUpdateResult,
+        cards_dir: &Path,
+        opts: &ArxivApplyOptions,
+    ) -> Result<AppliedArxivUpdate> {
+        let previous = json_cards::load_card_by_id(cards_dir, &update.book_id).map_err(|err| {
+            ScienceError::Parse(format!("failed to load card {}: {err}", update.book_id))
+        })?;
+
+        let mut card = previous.clone();
+        if opts.download_pdf {
+            let dir = match previous.file.as_ref().and_then(|file| {
+                Path::new(&file.path)
+                    .parent()
+                    .filter(|parent| !parent.as_os_str().is_empty())
+                    .map(Path::to_path_buf)
+            }) {
+                Some(dir) => dir,
+                None => resolve_download_dir(opts.download_dir.as_deref())?,
+            };
+            card.file = Some(self.download_pdf(&update.new_metadata, &dir).await?);
+        }
+        apply_metadata(&mut card, &update.new_metadata);
+
+        json_cards::save_card(cards_dir, &card)
+            .map_err(|err| ScienceError::Parse(format!("failed to save card: {err}")))?;
+        self.db.upsert_book(&card).map_err(map_db_error)?;
+        Ok(AppliedArxivUpdate { previous, card })
+    }
+
+    async fn download_pdf(&self, metadata: &ArxivMetadata, dir: &Path) -> Result<BookFile> {
+        tokio::fs::create_dir_all(dir).await.map_err(|e| {
+            ScienceError::Parse(format!("failed to create download directory: {e}"))
+        })?;
+
+        let pdf_bytes = self.downloader.get_bytes(&metadata.pdf_url).await?;
+        let file_path = dir.join(download_file_name(&metadata.arxiv_id));
+        tokio::fs::write(&file_path, &pdf_bytes)
+            .await
+            .map_err(|e| ScienceError::Parse(format!("failed to write downloaded PDF: {e}")))?;
+
+        Ok(BookFile {
+            path: file_path.to_string_lossy().to_string(),
+            format: FileFormat::Pdf,
+            size_bytes: pdf_bytes.len() as u64,
+            hash_sha256: None,
+            added_at: Utc::now(),
+        })
+    }
+}
+
+fn apply_metadata(card: &mut BookCard, metadata: &ArxivMetadata) {
+    let metadata = metadata.clone();
+    upsert_arxiv_web_source(card, "arxiv_abs", &metadata.abs_url);
+    upsert_arxiv_web_source(card, "arxiv_pdf", &metadata.pdf_url);
+    card.merge_metadata(partial_from_arxiv(metadata), MetadataSource::ArxivApi);
+    card.touch();
+}
+
+fn normalize_space(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
 }
 
 #[derive(Debug, Clone)]
@@ -329,6 +594,130 @@ mod tests {
         assert!(err.to_string().contains("database error"));
     }
 
+    #[tokio::test]
+    async fn apply_to_library_refuses_cards_without_json() {
+        let cards_dir =
+            std::env::temp_dir().join(format!("omniscope_arxiv_apply_test_{}", std::process::id()));
+        let db = Arc::new(Database::open_in_memory().unwrap());
+        let card = BookCard::new("Index Only");
+        db.upsert_book(&card).unwrap();
+        let updater = ArxivUpdater::new(
+            Arc::new(ArxivClient::new_for_tests(
+                "http://127.0.0.1:9/api/query".to_string(),
+            )),
+            Arc::clone(&db),
+        );
+
+        let update = ArxivUpdateResult {
+            book_id: card.id,
+            arxiv_id: ArxivId::parse("1706.03762v1").unwrap(),
+            old_version: Some(1),
+            new_version: 7,
+            new_metadata: sample_metadata("1706.03762v7", "Updated Title"),
+        };
+
+        let result = updater
+            .apply_to_library(&update, &cards_dir, &ArxivApplyOptions::default())
+            .await;
+        assert!(result.is_err());
+        assert!(json_cards::load_card_by_id(&cards_dir, &card.id).is_err());
+        assert_eq!(
+            db.get_book(&card.id.to_string()).unwrap().metadata.title,
+            "Index Only"
+        );
+    }
+
+    #[tokio::test]
+    async fn check_due_updates_skips_recent_checks_and_keeps_pending() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/query")
+            .match_query(Matcher::UrlEncoded(
+                "id_list".to_string(),
+                "1706.03762".to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/atom+xml")
+            .with_body(atom_feed_for("1706.03762v7", "Updated Attention"))
+            .expect(1)
+            .create_async()
+            .await;
+
+        let db = Arc::new(Database::open_in_memory().unwrap());
+        let mut card = BookCard::new("Old Attention");
+        card.identifiers = Some(ScientificIdentifiers {
+            arxiv_id: Some("1706.03762v1".to_string()),
+            ..Default::default()
+        });
+        db.upsert_book(&card).unwrap();
+
+        let updater = ArxivUpdater::new(
+            Arc::new(ArxivClient::new_for_tests(format!(
+                "{}/api/query",
+                server.url()
+            ))),
+            Arc::clone(&db),
+        );
+        let interval = chrono::Duration::hours(24);
+        let mut state = ArxivUpdateState::default();
+
+        let found = updater
+            .check_due_updates(&mut state, interval)
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        assert!(state.checked.contains_key(&card.id));
+
+        // Checked a moment ago, so the second run doesn't ask arXiv again.
+        let found = updater
+            .check_due_updates(&mut state, interval)
+            .await
+            .unwrap();
+        assert!(found.is_empty());
+        mock.assert_async().await;
+        assert_eq!(state.pending.len(), 1);
+        assert_eq!(state.pending[0].version_label(), "v1 → v7");
+
+        // Once the card carries the new version the pending entry goes away.
+        card.identifiers = Some(ScientificIdentifiers {
+            arxiv_id: Some("1706.03762v7".to_string()),
+            ..Default::default()
+        });
+        db.upsert_book(&card).unwrap();
+        updater
+            .check_due_updates(&mut state, interval)
+            .await
+            .unwrap();
+        assert!(state.pending.is_empty());
+    }
+
+    #[test]
+    fn diff_lists_only_changed_fields() {
+        let mut card = BookCard::new("Updated   Title");
+        card.metadata.authors = vec!["Someone Else".to_string()];
+        card.ai.summary = Some("Updated abstract".to_string());
+
+        let update = ArxivUpdateResult {
+            book_id: card.id,
+            arxiv_id: ArxivId::parse("1706.03762v1").unwrap(),
+            old_version: Some(1),
+            new_version: 2,
+            new_metadata: sample_metadata("1706.03762v2", "Updated Title"),
+        };
+
+        let diff = update.diff(&card);
+        assert_eq!(diff.title, None);
+        assert_eq!(diff.abstract_text, None);
+        assert_eq!(
+            diff.authors,
+            Some((
+                vec!["Someone Else".to_string()],
+                vec!["Example Author".to_string()]
+            ))
+        );
+        assert!(!diff.is_empty());
+    }
+
     fn atom_feed_for(id_with_version: &str, title: &str) -> String {
         format!(
             r#"<?xml version="1.0" encoding="UTF-8"?>
@@ -0,0 +1,435 @@
+//! Bulk import of BibTeX, RIS and CSL-JSON exports (Zotero, JabRef,
+//! Mendeley, Pandoc).
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use omniscope_core::file_import;
+use omniscope_core::storage::json_cards;
+use omniscope_core::{BookCard, Database};
+use uuid::Uuid;
+
+use crate::dedup::{DedupStrategy, DuplicateFinder};
+use crate::error::{Result, ScienceError};
+use crate::formats::{bibtex, csl_json, ris};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BibFormat {
+    BibTeX,
+    Ris,
+    CslJson,
+}
+
+impl BibFormat {
+    /// Format implied by the file extension (`.bib`, `.bibtex`, `.ris`,
+    /// `.json`).
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_string_lossy().to_ascii_lowercase();
+        match ext.as_str() {
+            "bib" | "bibtex" => Some(Self::BibTeX),
+            "ris" => Some(Self::Ris),
+            "json" => Some(Self::CslJson),
+            _ => None,
+        }
+    }
+}
+
+/// One entry of the export, mapped to a card.
+#[derive(Debug, Clone)]
+pub struct ImportCandidate {
+    /// Cite key for BibTeX, item `id` for CSL-JSON, `#n` for RIS records.
+    pub key: String,
+    pub card: BookCard,
+    /// Listed files that were not attached: missing, or outside the library.
+    pub unresolved_files: Vec<String>,
+}
+
+/// Why an entry was recognised as a duplicate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchKind {
+    Doi,
+    Isbn,
+    Title,
+    /// The attached file already belongs to a card.
+    File,
+}
+
+impl MatchKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Doi => "doi",
+            Self::Isbn => "isbn",
+            Self::Title => "title",
+            Self::File => "file",
+        }
+    }
+}
+
+impl From<DedupStrategy> for MatchKind {
+    fn from(strategy: DedupStrategy) -> Self {
+        match strategy {
+            DedupStrategy::Doi => Self::Doi,
+            DedupStrategy::Isbn => Self::Isbn,
+            DedupStrategy::TitleFuzzy => Self::Title,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportDecision {
+    Create,
+    /// Already in the library as card `id`.
+    Existing {
+        id: Uuid,
+        matched_by: MatchKind,
+    },
+    /// Repeats the earlier entry `key` of the same export.
+    Repeated {
+        key: String,
+        matched_by: MatchKind,
+    },
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportPlan {
+    pub entries: Vec<(ImportCandidate, ImportDecision)>,
+}
+
+impl ImportPlan {
+    /// Cards that [`apply_import`] will write.
+    pub fn cards_to_create(&self) -> impl Iterator<Item = &BookCard> {
+        self.entries
+            .iter()
+            .filter(|(_, decision)| *decision == ImportDecision::Create)
+            .map(|(candidate, _)| &candidate.card)
+    }
+
+    pub fn duplicate_count(&self) -> usize {
+        self.entries.len() - self.cards_to_create().count()
+    }
+}
+
+/// Read and map every entry of `path`.
+///
+/// Relative `file` paths are resolved against the export's directory and
+/// only attached when they point inside `library_root`.
+pub fn read_candidates(path: &Path, library_root: &Path) -> Result<Vec<ImportCandidate>> {
+    let format = BibFormat::from_path(path).ok_or_else(|| {
+        ScienceError::Parse(format!(
+            "{} is not a .bib, .ris or CSL-JSON .json file",
+            path.display()
+        ))
+    })?;
+    let content = fs::read_to_string(path)
+        .map_err(|e| ScienceError::Parse(format!("failed to read {}: {e}", path.display())))?;
+    let base_dir = path.parent().unwrap_or(Path::new("."));
+    parse_candidates(&content, format, base_dir, library_root)
+}
+
+pub fn parse_candidates(
+    content: &str,
+    format: BibFormat,
+    base_dir: &Path,
+    library_root: &Path,
+) -> Result<Vec<ImportCandidate>> {
+    let mapped: Vec<(String, BookCard, Vec<String>)> = match format {
+        BibFormat::BibTeX => bibtex::parse_bibtex(content)?
+            .iter()
+            .map(|entry| {
+                (
+                    entry.cite_key.clone(),
+                    bibtex::to_book_card(entry),
+                    bibtex::file_paths(entry),
+                )
+            })
+            .collect(),
+        BibFormat::Ris => ris::parse_ris(content)?
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                (
+                    format!("#{}", index + 1),
+                    ris::to_book_card(entry),
+                    ris::file_paths(entry),
+                )
+            })
+            .collect(),
+        BibFormat::CslJson => csl_json::parse(content)?
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let key = if item.id.trim().is_empty() {
+                    format!("#{}", index + 1)
+                } else {
+                    item.id.clone()
+                };
+                (key, csl_json::to_book_card(item), Vec::new())
+            })
+            .collect(),
+    };
+
+    let library_root = library_root
+        .canonicalize()
+        .unwrap_or_else(|_| library_root.to_path_buf());
+    Ok(mapped
+        .into_iter()
+        .map(|(key, mut card, files)| {
+            // The first file that resolves is attached; the rest are ignored.
+            let mut unresolved_files = Vec::new();
+            for raw in files {
+                if card.file.is_some() {
+                    break;
+                }
+                let file = resolve_attachment(&raw, base_dir, &library_root)
+                    .and_then(|path| file_import::import_file(&path).ok())
+                    .and_then(|imported| imported.file);
+                match file {
+                    Some(file) => card.file = Some(file),
+                    None => unresolved_files.push(raw),
+                }
+            }
+            ImportCandidate {
+                key,
+                card,
+                unresolved_files,
+            }
+        })
+        .collect())
+}
+
+fn resolve_attachment(raw: &str, base_dir: &Path, library_root: &Path) -> Option<PathBuf> {
+    let path = Path::new(raw);
+    let path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        base_dir.join(path)
+    };
+    let path = path.canonicalize().ok()?;
+    (path.is_file() && path.starts_with(library_root)).then_some(path)
+}
+
+/// Decide, for each candidate, whether it is new to the library.
+///
+/// Runs the [`DuplicateFinder`] strategies over the library and the export
+/// together, so entries repeated within the export are caught as well.
+pub fn plan_import(candidates: Vec<ImportCandidate>, existing: &[BookCard]) -> ImportPlan {
+    let mut decisions = vec![ImportDecision::Create; candidates.len()];
+
+    let tracked_files: HashMap<&str, Uuid> = existing
+        .iter()
+        .filter_map(|card| card.file.as_ref().map(|file| (file.path.as_str(), card.id)))
+        .collect();
+    for (decision, candidate) in decisions.iter_mut().zip(&candidates) {
+        let Some(file) = candidate.card.file.as_ref() else {
+            continue;
+        };
+        if let Some(id) = tracked_files.get(file.path.as_str()) {
+            *decision = ImportDecision::Existing {
+                id: *id,
+                matched_by: MatchKind::File,
+            };
+        }
+    }
+
+    let existing_ids: HashSet<Uuid> = existing.iter().map(|card| card.id).collect();
+    let positions: HashMap<Uuid, usize> = candidates
+        .iter()
+        .enumerate()
+        .map(|(index, candidate)| (candidate.card.id, index))
+        .collect();
+    let mut books = existing.to_vec();
+    books.extend(candidates.iter().map(|candidate| candidate.card.clone()));
+
+    let finder = DuplicateFinder::new();
+    let groups = finder
+        .find_by_doi(&books)
+        .into_iter()
+        .chain(finder.find_by_isbn(&books))
+        .chain(finder.find_by_title_fuzzy(&books));
+    for group in groups {
+        let matched_by = MatchKind::from(group.strategy);
+        let members: Vec<Uuid> = std::iter::once(group.canonical)
+            .chain(group.duplicates)
+            .collect();
+        let mut in_export: Vec<usize> = members
+            .iter()
+            .filter_map(|id| positions.get(id).copied())
+            .collect();
+        in_export.sort_unstable();
+
+        let known = members.iter().find(|id| existing_ids.contains(id));
+        let (first, repeats) = match known {
+            Some(_) => (None, in_export.as_slice()),
+            None => match in_export.split_first() {
+                Some((first, rest)) => (Some(*first), rest),
+                None => continue,
+            },
+        };
+        for &index in repeats {
+            if decisions[index] != ImportDecision::Create {
+                continue;
+            }
+            decisions[index] = match (known, first) {
+                (Some(id), _) => ImportDecision::Existing {
+                    id: *id,
+                    matched_by,
+                },
+                (None, Some(first)) => ImportDecision::Repeated {
+                    key: candidates[first].key.clone(),
+                    matched_by,
+                },
+                (None, None) => continue,
+            };
+        }
+    }
+
+    ImportPlan {
+        entries: candidates.into_iter().zip(decisions).collect(),
+    }
+}
+
+/// Write the new cards of `plan`; returns their ids.
+pub fn apply_import(plan: &ImportPlan, db: &Database, cards_dir: &Path) -> Result<Vec<Uuid>> {
+    let mut created = Vec::new();
+    for card in plan.cards_to_create() {
+        json_cards::save_card(cards_dir, card)
+            .map_err(|e| ScienceError::Parse(format!("failed to save card: {e}")))?;
+        db.upsert_book(card)
+            .map_err(|e| ScienceError::Parse(format!("database error: {e}")))?;
+        created.push(card.id);
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use omniscope_core::models::DocumentType;
+
+    use super::*;
+
+    static TMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    fn temp_library() -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "omniscope_bib_import_test_{}_{}",
+            std::process::id(),
+            TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(path.join("papers")).unwrap();
+        path
+    }
+
+    const EXPORT: &str = r#"
+@Comment{jabref-meta: databaseType:bibtex;}
+
+@article{vaswani2017,
+  title = {Attention Is All You Need},
+  author = {Vaswani, Ashish and Shazeer, Noam},
+  journal = {Advances in Neural Information Processing Systems},
+  year = {2017},
+  doi = {10.48550/arXiv.1706.03762},
+  file = {:papers/attention.pdf:PDF}
+}
+
+@inproceedings{lamport1978,
+  title = {Time, Clocks, and the Ordering of Events},
+  author = {Lamport, Leslie},
+  booktitle = {Communications of the ACM},
+  year = {1978},
+  file = {Full Text:/nowhere/lamport.pdf:application/pdf}
+}
+
+@misc{vaswani2017dup,
+  title = {Attention is all you need},
+  author = {Vaswani, A.},
+  doi = {10.48550/arxiv.1706.03762}
+}
+"#;
+
+    #[test]
+    fn parse_candidates_attaches_files_inside_the_library() {
+        let root = temp_library();
+        fs::write(root.join("papers/attention.pdf"), b"%PDF-1.4").unwrap();
+
+        let candidates = parse_candidates(EXPORT, BibFormat::BibTeX, &root, &root).unwrap();
+        assert_eq!(candidates.len(), 3);
+
+        let attention = &candidates[0];
+        assert_eq!(attention.key, "vaswani2017");
+        let file = attention.card.file.as_ref().expect("pdf attached");
+        assert!(file.path.ends_with("attention.pdf"));
+        assert!(attention.unresolved_files.is_empty());
+
+        let lamport = &candidates[1];
+        assert!(lamport.card.file.is_none());
+        assert_eq!(lamport.unresolved_files, vec!["/nowhere/lamport.pdf"]);
+        assert_eq!(
+            lamport.card.publication.as_ref().map(|p| p.doc_type),
+            Some(DocumentType::ConferencePaper)
+        );
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn plan_import_skips_library_and_in_file_duplicates() {
+        let root = temp_library();
+        let candidates = parse_candidates(EXPORT, BibFormat::BibTeX, &root, &root).unwrap();
+
+        let known = BookCard::new("Time, clocks and the ordering of events");
+        let mut unrelated = BookCard::new("Dune");
+        unrelated.metadata.year = Some(1965);
+
+        let plan = plan_import(candidates, &[known.clone(), unrelated]);
+        let decisions: Vec<&ImportDecision> = plan.entries.iter().map(|(_, d)| d).collect();
+        assert_eq!(*decisions[0], ImportDecision::Create);
+        assert_eq!(
+            *decisions[1],
+            ImportDecision::Existing {
+                id: known.id,
+                matched_by: MatchKind::Title
+            }
+        );
+        assert_eq!(
+            *decisions[2],
+            ImportDecision::Repeated {
+                key: "vaswani2017".to_string(),
+                matched_by: MatchKind::Doi
+            }
+        );
+        assert_eq!(plan.cards_to_create().count(), 1);
+        assert_eq!(plan.duplicate_count(), 2);
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn csl_json_items_are_planned_like_bibtex_entries() {
+        let root = temp_library();
+        let json = r#"[
+            {"id": "vaswani2017", "type": "article-journal", "title": "Attention Is All You Need",
+             "DOI": "10.48550/arXiv.1706.03762"},
+            {"type": "book", "title": "Dune", "issued": {"date-parts": [[1965]]}}
+        ]"#;
+        let candidates = parse_candidates(json, BibFormat::CslJson, &root, &root).unwrap();
+        assert_eq!(candidates[0].key, "vaswani2017");
+        assert_eq!(candidates[1].key, "#2");
+
+        let bib = parse_candidates(EXPORT, BibFormat::BibTeX, &root, &root).unwrap();
+        let existing = vec![bib[0].card.clone()];
+        let plan = plan_import(candidates, &existing);
+        assert_eq!(
+            plan.entries[0].1,
+            ImportDecision::Existing {
+                id: existing[0].id,
+                matched_by: MatchKind::Doi
+            }
+        );
+        assert_eq!(plan.entries[1].1, ImportDecision::Create);
+
+        fs::remove_dir_all(root).ok();
+    }
+}
@@ -0,0 +1,253 @@
+//! `.bib` files kept in sync with a library search (`omniscope bib-sync`).
+//!
+//! Every entry written gets its cite key pinned on the card, so later
+//! metadata changes don't rename keys that LaTeX documents already cite.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use omniscope_core::models::BibSyncTarget;
+use omniscope_core::search_dsl::SearchQuery;
+use omniscope_core::storage::json_cards;
+use omniscope_core::{BookCard, LibraryRoot};
+use uuid::Uuid;
+
+use crate::config::ExportConfig;
+use crate::error::{Result, ScienceError};
+use crate::export;
+use crate::formats::bibtex::{
+    CiteKeyScheme, disambiguate_cite_key, generate_cite_key, render_bibliography, stored_cite_key,
+};
+
+/// Outcome of regenerating one target.
+#[derive(Debug)]
+pub struct BibSyncReport {
+    pub path: PathBuf,
+    pub entries: usize,
+    /// Cards that were given a cite key on this run; already saved.
+    pub keyed: Vec<BookCard>,
+    /// Whether the file on disk changed.
+    pub written: bool,
+}
+
+/// Where `target` is written; relative paths hang off the library root.
+pub fn target_path(target: &BibSyncTarget, library_root: &Path) -> PathBuf {
+    let out = Path::new(&target.out);
+    if out.is_absolute() {
+        out.to_path_buf()
+    } else {
+        library_root.join(out)
+    }
+}
+
+/// Give every card in `selected` a pinned cite key, unique across `library`.
+///
+/// A card keeps its stored key unless an older card already holds the same
+/// one. Returns the ids of cards whose key was assigned or changed.
+pub fn pin_cite_keys(
+    library: &[BookCard],
+    selected: &mut [BookCard],
+    scheme: &CiteKeyScheme,
+) -> Vec<Uuid> {
+    let mut by_age: Vec<&BookCard> = library.iter().collect();
+    by_age.sort_by_key(|card| card.created_at);
+    let mut owners: HashMap<String, Uuid> = HashMap::new();
+    for card in by_age {
+        if let Some(key) = stored_cite_key(card) {
+            owners.entry(key.to_string()).or_insert(card.id);
+        }
+    }
+    let mut used: HashSet<String> = owners.keys().cloned().collect();
+
+    let mut changed = Vec::new();
+    for card in selected.iter_mut() {
+        let owned = stored_cite_key(card).is_some_and(|key| owners.get(key) == Some(&card.id));
+        if owned {
+            continue;
+        }
+        let key = disambiguate_cite_key(&generate_cite_key(card, scheme), &mut used);
+        owners.insert(key.clone(), card.id);
+        card.identifiers
+            .get_or_insert_with(Default::default)
+            .cite_key = Some(key);
+        changed.push(card.id);
+    }
+    changed
+}
+
+/// Regenerate the `.bib` file for `target`. The file is only rewritten when
+/// its content changes.
+pub fn sync_target(
+    target: &BibSyncTarget,
+    library_root: &Path,
+    cards_dir: &Path,
+    config: &ExportConfig,
+) -> Result<BibSyncReport> {
+    let query = SearchQuery::parse(&target.query);
+    if query.semantic_text().is_some() {
+        return Err(ScienceError::Parse(
+            "`~` terms can't be used in a synced selection".to_string(),
+        ));
+    }
+
+    let library = json_cards::list_cards(cards_dir)
+        .map_err(|e| ScienceError::Parse(format!("failed to read cards: {e}")))?;
+    let mut selected = export::select_cards(library.clone(), &query);
+    let changed = pin_cite_keys(&library, &mut selected, &config.cite_key_scheme());
+
+    // Cards are only rewritten when their content changes: the server's
+    // card watcher runs this on every card write.
+    let mut keyed = Vec::new();
+    for card in selected.iter().filter(|card| changed.contains(&card.id)) {
+        let written = json_cards::save_card_if_changed(cards_dir, card)
+            .map_err(|e| ScienceError::Parse(format!("failed to save card: {e}")))?;
+        if written {
+            keyed.push(card.clone());
+        }
+    }
+
+    let refs: Vec<&BookCard> = selected.iter().collect();
+    let keys: Vec<String> = selected
+        .iter()
+        .map(|card| stored_cite_key(card).unwrap_or_default().to_string())
+        .collect();
+    let content = format!(
+        "% Generated by omniscope bib-sync from `{}`; edits will be overwritten.\n\n{}",
+        target.query,
+        render_bibliography(&refs, &keys, &config.bibtex_options())
+    );
+
+    let path = target_path(target, library_root);
+    let written = fs::read_to_string(&path).ok().as_deref() != Some(content.as_str());
+    if written {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| ScienceError::Parse(format!("{}: {e}", parent.display())))?;
+        }
+        fs::write(&path, content)
+            .map_err(|e| ScienceError::Parse(format!("{}: {e}", path.display())))?;
+    }
+
+    Ok(BibSyncReport {
+        path,
+        entries: selected.len(),
+        keyed,
+        written,
+    })
+}
+
+/// Regenerate every target registered in the library's `library.toml`.
+pub fn sync_library(
+    root: &LibraryRoot,
+    config: &ExportConfig,
+) -> Result<Vec<(BibSyncTarget, Result<BibSyncReport>)>> {
+    let manifest = root
+        .load_manifest()
+        .map_err(|e| ScienceError::Parse(format!("failed to read library.toml: {e}")))?;
+    let cards_dir = root.cards_dir();
+    Ok(manifest
+        .bib_sync
+        .into_iter()
+        .map(|target| {
+            let report = sync_target(&target, root.root(), &cards_dir, config);
+            (target, report)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    static TMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    fn temp_dir() -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "omniscope_bib_sync_test_{}_{}",
+            std::process::id(),
+            TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(path.join("cards")).unwrap();
+        path
+    }
+
+    fn card(title: &str, author: &str, year: i32, tags: &[&str]) -> BookCard {
+        let mut card = BookCard::new(title);
+        card.metadata.authors = vec![author.to_string()];
+        card.metadata.year = Some(year);
+        card.organization.tags = tags.iter().map(|tag| tag.to_string()).collect();
+        card
+    }
+
+    #[test]
+    fn pinned_keys_survive_metadata_changes() {
+        let root = temp_dir();
+        let cards_dir = root.join("cards");
+        let paxos = card("Paxos Made Simple", "Leslie Lamport", 2001, &["thesis"]);
+        let live = card("Paxos Made Live", "Leslie Lamport", 2001, &["thesis"]);
+        let other = card("The Rust Book", "Steve Klabnik", 2018, &["rust"]);
+        for card in [&paxos, &live, &other] {
+            json_cards::save_card(&cards_dir, card).unwrap();
+        }
+        let target = BibSyncTarget {
+            query: "#thesis".to_string(),
+            out: "refs.bib".to_string(),
+        };
+        let config = ExportConfig {
+            cite_key_scheme: "author_year".to_string(),
+            ..Default::default()
+        };
+
+        let report = sync_target(&target, &root, &cards_dir, &config).unwrap();
+        assert_eq!(report.entries, 2);
+        assert_eq!(report.keyed.len(), 2);
+        assert!(report.written);
+        let bib = fs::read_to_string(root.join("refs.bib")).unwrap();
+        assert!(bib.contains("{Lamport2001,"));
+        assert!(bib.contains("{Lamport2001a,"));
+        assert!(!bib.contains("Rust Book"));
+
+        let again = sync_target(&target, &root, &cards_dir, &config).unwrap();
+        assert!(again.keyed.is_empty());
+        assert!(!again.written);
+
+        // Enriching the year would change a generated key, but not a pinned one.
+        let mut enriched = json_cards::load_card_by_id(&cards_dir, &paxos.id).unwrap();
+        let key = stored_cite_key(&enriched).unwrap().to_string();
+        enriched.metadata.year = Some(2002);
+        json_cards::save_card(&cards_dir, &enriched).unwrap();
+
+        sync_target(&target, &root, &cards_dir, &config).unwrap();
+        let bib = fs::read_to_string(root.join("refs.bib")).unwrap();
+        assert!(bib.contains(&format!("{{{key},")));
+        assert!(bib.contains("year = {2002}"));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn older_card_keeps_a_shared_key() {
+        let mut first = card("Paxos Made Simple", "Leslie Lamport", 2001, &[]);
+        first
+            .identifiers
+            .get_or_insert_with(Default::default)
+            .cite_key = Some("lamport".to_string());
+        let mut second = card("Paxos Made Live", "Leslie Lamport", 2001, &[]);
+        second.created_at = first.created_at + chrono::Duration::seconds(1);
+        second
+            .identifiers
+            .get_or_insert_with(Default::default)
+            .cite_key = Some("lamport".to_string());
+
+        let library = vec![first.clone(), second.clone()];
+        let mut selected = library.clone();
+        let changed = pin_cite_keys(&library, &mut selected, &CiteKeyScheme::AuthorYear);
+
+        assert_eq!(changed, vec![second.id]);
+        assert_eq!(stored_cite_key(&selected[0]), Some("lamport"));
+        assert_eq!(stored_cite_key(&selected[1]), Some("Lamport2001"));
+    }
+}
@@ -1,6 +1,13 @@
+use std::path::{Path, PathBuf};
+
+use omniscope_core::config::GlobalConfig;
 use serde::{Deserialize, Serialize};
 
+use crate::error::{Result, ScienceError};
+use crate::formats::bibtex::{BibTeXOptions, CiteKeyScheme};
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
 pub struct ScienceConfig {
     pub polite_pool_email: Option<String>,
     pub semantic_scholar_api_key: Option<String>,
@@ -13,9 +20,11 @@ pub struct ScienceConfig {
     pub annas_archive: AnnasArchiveConfig,
     pub export: ExportConfig,
     pub citation_graph: CitationGraphConfig,
+    pub arxiv: ArxivConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
 pub struct SciHubConfig {
     pub enabled: bool,
     pub mirror_check_on_startup: bool,
@@ -23,28 +32,166 @@ pub struct SciHubConfig {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
 pub struct AnnasArchiveConfig {
     pub enabled: bool,
     pub preferred_formats: Vec<String>,
     pub preferred_languages: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ExportConfig {
     pub default_cite_style: String,
     pub cite_key_scheme: String,
     pub bibtex_utf8: bool,
+    /// Locale for CSL terms and dates, e.g. `en-US` or `de-DE`.
+    pub csl_locale: String,
+    /// Directory with `.csl` styles and `locales-*.xml` files; defaults to
+    /// `csl/` next to `science.toml`.
+    pub csl_styles_dir: Option<PathBuf>,
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self {
+            default_cite_style: "ieee".to_string(),
+            cite_key_scheme: "author_year_title".to_string(),
+            bibtex_utf8: true,
+            csl_locale: "en-US".to_string(),
+            csl_styles_dir: None,
+        }
+    }
+}
+
+impl ExportConfig {
+    /// Parse `cite_key_scheme`: `author_year`, `author_year_title`, `doi`,
+    /// `dblp`, or a template such as `{first_author}{year}`.
+    pub fn cite_key_scheme(&self) -> CiteKeyScheme {
+        let scheme = self.cite_key_scheme.trim();
+        match scheme.to_ascii_lowercase().replace('-', "_").as_str() {
+            "author_year" | "authoryear" => CiteKeyScheme::AuthorYear,
+            "" | "author_year_title" | "authoryeartitle" => CiteKeyScheme::AuthorYearTitle,
+            "doi" | "doi_based" => CiteKeyScheme::DoiBased,
+            "dblp" => CiteKeyScheme::Dblp,
+            _ if scheme.contains('{') => CiteKeyScheme::Custom(scheme.to_string()),
+            _ => CiteKeyScheme::AuthorYearTitle,
+        }
+    }
+
+    pub fn styles_dir(&self) -> PathBuf {
+        self.csl_styles_dir
+            .clone()
+            .unwrap_or_else(|| ScienceConfig::config_path().with_file_name("csl"))
+    }
+
+    pub fn bibtex_options(&self) -> BibTeXOptions {
+        BibTeXOptions {
+            cite_key_scheme: self.cite_key_scheme(),
+            utf8: self.bibtex_utf8,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
 pub struct CitationGraphConfig {
     pub fetch_on_add: bool,
     pub fetch_depth: u32,
     pub max_citations_to_store: u32,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArxivConfig {
+    /// Minimum time between version checks of the same paper.
+    pub update_check_interval_hours: u32,
+    /// Check for new versions in the background when the TUI starts.
+    pub check_updates_on_startup: bool,
+    /// Feeds are fetched again once their entries are older than this.
+    pub feed_refresh_minutes: u32,
+    /// Newest submissions fetched per feed; busy categories such as `cs.LG`
+    /// see a few hundred a day.
+    pub feed_max_results: u32,
+}
+
+impl Default for ArxivConfig {
+    fn default() -> Self {
+        Self {
+            update_check_interval_hours: 24,
+            check_updates_on_startup: true,
+            feed_refresh_minutes: 60,
+            feed_max_results: 300,
+        }
+    }
+}
+
+impl ArxivConfig {
+    pub fn update_check_interval(&self) -> chrono::Duration {
+        chrono::Duration::hours(i64::from(self.update_check_interval_hours))
+    }
+
+    pub fn feed_refresh_interval(&self) -> chrono::Duration {
+        chrono::Duration::minutes(i64::from(self.feed_refresh_minutes))
+    }
+}
+
 impl ScienceConfig {
-    pub fn load() -> crate::error::Result<Self> {
-        todo!()
+    /// `science.toml`, next to the global `config.toml`.
+    pub fn config_path() -> PathBuf {
+        GlobalConfig::config_path().with_file_name("science.toml")
+    }
+
+    /// Load from the standard path, falling back to defaults.
+    pub fn load() -> Result<Self> {
+        Self::load_from(&Self::config_path())
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| ScienceError::Parse(format!("{}: {err}", path.display())))?;
+        toml::from_str(&contents)
+            .map_err(|err| ScienceError::Parse(format!("{}: {err}", path.display())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cite_key_scheme_names_and_templates() {
+        let mut export = ExportConfig::default();
+        assert_eq!(export.cite_key_scheme(), CiteKeyScheme::AuthorYearTitle);
+        export.cite_key_scheme = "author_year".to_string();
+        assert_eq!(export.cite_key_scheme(), CiteKeyScheme::AuthorYear);
+        export.cite_key_scheme = "doi".to_string();
+        assert_eq!(export.cite_key_scheme(), CiteKeyScheme::DoiBased);
+        export.cite_key_scheme = "dblp".to_string();
+        assert_eq!(export.cite_key_scheme(), CiteKeyScheme::Dblp);
+        export.cite_key_scheme = "{first_author}:{year}".to_string();
+        assert_eq!(
+            export.cite_key_scheme(),
+            CiteKeyScheme::Custom("{first_author}:{year}".to_string())
+        );
+    }
+
+    #[test]
+    fn load_from_partial_file_keeps_defaults() {
+        let dir =
+            std::env::temp_dir().join(format!("omniscope-science-cfg-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("science.toml");
+        std::fs::write(&path, "[export]\ncite_key_scheme = \"author_year\"\n").unwrap();
+
+        let config = ScienceConfig::load_from(&path).unwrap();
+        assert_eq!(config.export.cite_key_scheme(), CiteKeyScheme::AuthorYear);
+        assert!(config.export.bibtex_utf8);
+        assert_eq!(config.export.default_cite_style, "ieee");
+
+        let _ = std::fs::remove_dir_all(&dir);
     }
 }
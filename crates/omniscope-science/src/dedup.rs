@@ -1,5 +1,7 @@
 use std::collections::{HashMap, HashSet};
+use std::path::Path;
 
+use omniscope_core::storage::json_cards;
 use omniscope_core::{BookCard, Database, ReadStatus};
 use uuid::Uuid;
 
@@ -15,6 +17,31 @@ pub enum DedupStrategy {
     TitleFuzzy,
 }
 
+impl DedupStrategy {
+    /// Strongest evidence first.
+    pub const ALL: [Self; 3] = [Self::Doi, Self::Isbn, Self::TitleFuzzy];
+
+    /// Accepts `doi`, `isbn` and `title`.
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "doi" => Ok(Self::Doi),
+            "isbn" => Ok(Self::Isbn),
+            "title" => Ok(Self::TitleFuzzy),
+            other => Err(ScienceError::Parse(format!(
+                "unknown dedup strategy `{other}` (expected doi, isbn or title)"
+            ))),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Doi => "doi",
+            Self::Isbn => "isbn",
+            Self::TitleFuzzy => "title",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DuplicateGroup {
     pub canonical: BookId,
@@ -45,6 +72,44 @@ impl DuplicateFinder {
         self
     }
 
+    pub fn find(&self, books: &[BookCard], strategy: DedupStrategy) -> Vec<DuplicateGroup> {
+        match strategy {
+            DedupStrategy::Doi => self.find_by_doi(books),
+            DedupStrategy::Isbn => self.find_by_isbn(books),
+            DedupStrategy::TitleFuzzy => self.find_by_title_fuzzy(books),
+        }
+    }
+
+    /// Groups from every strategy, strongest first. A card joins at most one
+    /// group, so merging every group never touches a card twice.
+    pub fn find_all(&self, books: &[BookCard]) -> Vec<DuplicateGroup> {
+        let positions: HashMap<BookId, usize> = books
+            .iter()
+            .enumerate()
+            .map(|(idx, card)| (card.id, idx))
+            .collect();
+        let mut claimed: HashSet<BookId> = HashSet::new();
+        let mut groups = Vec::new();
+
+        for strategy in DedupStrategy::ALL {
+            for group in self.find(books, strategy) {
+                let indexes: Vec<usize> = std::iter::once(group.canonical)
+                    .chain(group.duplicates)
+                    .filter(|id| !claimed.contains(id))
+                    .filter_map(|id| positions.get(&id).copied())
+                    .collect();
+                if indexes.len() < 2 {
+                    continue;
+                }
+                let group = self.build_group(indexes, books, strategy);
+                claimed.insert(group.canonical);
+                claimed.extend(group.duplicates.iter().copied());
+                groups.push(group);
+            }
+        }
+        groups
+    }
+
     pub fn find_by_doi(&self, books: &[BookCard]) -> Vec<DuplicateGroup> {
         let mut doi_buckets: HashMap<String, Vec<usize>> = HashMap::new();
         for (idx, card) in books.iter().enumerate() {
@@ -135,9 +200,24 @@ impl DuplicateFinder {
             .map(|card| normalize_title(&card.metadata.title))
             .collect();
 
+        // Titles whose lengths differ by more than the threshold allows can
+        // never be similar; comparing in length order lets each row stop early.
+        let lengths: Vec<usize> = normalized_titles
+            .iter()
+            .map(|title| title.chars().count())
+            .collect();
+        let mut by_length: Vec<usize> = (0..normalized_titles.len()).collect();
+        by_length.sort_by_key(|idx| lengths[*idx]);
+
         let mut dsu = DisjointSet::new(books.len());
-        for i in 0..normalized_titles.len() {
-            for j in (i + 1)..normalized_titles.len() {
+        for (pos, &i) in by_length.iter().enumerate() {
+            for &j in &by_length[pos + 1..] {
+                let longer = lengths[j] as f64;
+                if (lengths[j] - lengths[i]) as f64
+                    > (1.0 - self.title_similarity_threshold) * longer
+                {
+                    break;
+                }
                 if similar_titles(
                     &normalized_titles[i],
                     &normalized_titles[j],
@@ -239,6 +319,64 @@ pub fn merge_duplicates(canonical: &BookId, to_merge: &[BookId], db: &Database)
     Ok(())
 }
 
+/// A merged duplicate group, with the cards as they were before.
+#[derive(Debug, Clone)]
+pub struct MergeOutcome {
+    pub merged: BookCard,
+    /// The canonical card and every merged-away card, unmodified; saving
+    /// them back undoes the merge.
+    pub originals: Vec<BookCard>,
+}
+
+/// Fold `others` into `canonical`. The canonical card keeps its id and file;
+/// missing fields are filled from the others (the longer text wins), and
+/// tags, notes, folders and libraries are unioned.
+pub fn merge_cards(canonical: &BookCard, others: &[BookCard]) -> BookCard {
+    let mut merged = canonical.clone();
+    for card in others.iter().filter(|card| card.id != canonical.id) {
+        merge_card_data(&mut merged, card);
+    }
+    merged.updated_at = chrono::Utc::now();
+    merged
+}
+
+/// Merge `to_merge` into `canonical` in both the JSON cards and the index,
+/// deleting the merged-away cards.
+pub fn merge_group(
+    canonical: &BookId,
+    to_merge: &[BookId],
+    db: &Database,
+    cards_dir: &Path,
+) -> Result<MergeOutcome> {
+    let load = |id: &BookId| {
+        json_cards::load_card_by_id(cards_dir, id)
+            .map_err(|e| ScienceError::Parse(format!("failed to load card {id}: {e}")))
+    };
+    let target = load(canonical)?;
+    let mut others = Vec::with_capacity(to_merge.len());
+    for id in to_merge.iter().filter(|id| *id != canonical) {
+        if !others.iter().any(|card: &BookCard| card.id == *id) {
+            others.push(load(id)?);
+        }
+    }
+
+    let merged = merge_cards(&target, &others);
+    json_cards::save_card(cards_dir, &merged)
+        .map_err(|e| ScienceError::Parse(format!("failed to save card: {e}")))?;
+    db.upsert_book(&merged)
+        .map_err(|e| ScienceError::Parse(format!("database error: {e}")))?;
+    for card in &others {
+        json_cards::delete_card(cards_dir, &card.id)
+            .map_err(|e| ScienceError::Parse(format!("failed to delete card: {e}")))?;
+        db.delete_book(&card.id.to_string())
+            .map_err(|e| ScienceError::Parse(format!("database error: {e}")))?;
+    }
+
+    let mut originals = vec![target];
+    originals.extend(others);
+    Ok(MergeOutcome { merged, originals })
+}
+
 fn normalized_doi(card: &BookCard) -> Option<String> {
     card.identifiers
         .as_ref()
@@ -505,6 +643,7 @@ fn merge_card_data(target: &mut BookCard, incoming: &BookCard) {
             );
             merge_option_string(&mut target_ids.mag_id, &incoming_ids.mag_id);
             merge_option_string(&mut target_ids.dblp_key, &incoming_ids.dblp_key);
+            merge_option_string(&mut target_ids.cite_key, &incoming_ids.cite_key);
         }
         _ => {}
     }
@@ -956,4 +1095,64 @@ mod tests {
         );
         assert!(db.get_book(&rich.id.to_string()).is_err());
     }
+
+    #[test]
+    fn find_all_puts_each_card_in_one_group() {
+        let mut a = build_card(41, "Attention Is All You Need");
+        a.identifiers = Some(ScientificIdentifiers {
+            doi: Some("10.1000/attention".to_string()),
+            ..Default::default()
+        });
+        let mut b = build_card(42, "Attention is all you need");
+        b.identifiers = a.identifiers.clone();
+        let c = build_card(43, "Attention Is All You Need!");
+
+        let groups = DuplicateFinder::new().find_all(&[a, b, c]);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].strategy, DedupStrategy::Doi);
+        assert_eq!(groups[0].duplicates.len(), 1);
+        assert_eq!(
+            DedupStrategy::parse("Title").unwrap(),
+            DedupStrategy::TitleFuzzy
+        );
+        assert!(DedupStrategy::parse("author").is_err());
+    }
+
+    #[test]
+    fn merge_group_unions_cards_on_disk_and_returns_originals() {
+        let cards_dir = std::env::temp_dir().join(format!(
+            "omniscope_dedup_test_{}_{}",
+            std::process::id(),
+            Uuid::new_v4()
+        ));
+        let db = Database::open_in_memory().unwrap();
+
+        let mut keep = build_card(51, "Dune");
+        keep.organization.tags = vec!["scifi".to_string()];
+        keep.organization.folders = vec!["novels".to_string()];
+        let mut other = build_card(52, "Dune");
+        other.metadata.year = Some(1965);
+        other.organization.tags = vec!["classic".to_string(), "scifi".to_string()];
+        other.organization.folders = vec!["to-read".to_string()];
+        other.notes = vec![omniscope_core::BookNote::new("Reread the appendix")];
+        for card in [&keep, &other] {
+            json_cards::save_card(&cards_dir, card).unwrap();
+            db.upsert_book(card).unwrap();
+        }
+
+        let outcome = merge_group(&keep.id, &[other.id], &db, &cards_dir).unwrap();
+        let original_ids: Vec<BookId> = outcome.originals.iter().map(|card| card.id).collect();
+        assert_eq!(original_ids, vec![keep.id, other.id]);
+        assert_eq!(outcome.originals[1].metadata.year, Some(1965));
+
+        let merged = json_cards::load_card_by_id(&cards_dir, &keep.id).unwrap();
+        assert_eq!(merged.metadata.year, Some(1965));
+        assert_eq!(merged.organization.tags, vec!["scifi", "classic"]);
+        assert_eq!(merged.organization.folders, vec!["novels", "to-read"]);
+        assert_eq!(merged.notes.len(), 1);
+        assert!(json_cards::load_card_by_id(&cards_dir, &other.id).is_err());
+        assert!(db.get_book(&other.id.to_string()).is_err());
+
+        std::fs::remove_dir_all(cards_dir).ok();
+    }
 }
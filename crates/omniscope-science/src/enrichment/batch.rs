@@ -0,0 +1,398 @@
+//! Enriches many cards at once (`omniscope enrich --jobs N`).
+//!
+//! Every worker shares one [`EnrichmentPipeline`], and with it each
+//! source's rate-limited client, so `--jobs` adds overlap between sources
+//! without sending any single API more than its limit allows. Finished
+//! cards are recorded in an [`EnrichCheckpoint`] under `.libr/`, and a run
+//! that is interrupted picks up where it stopped.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use omniscope_core::LibraryRoot;
+use omniscope_core::models::BookCard;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::enrichment::pipeline::{EnrichmentPipeline, EnrichmentReport};
+use crate::error::{Result, ScienceError};
+
+const CHECKPOINT_FILE_NAME: &str = "enrich_checkpoint.json";
+
+/// Cards written to disk between checkpoint saves. Interrupting with
+/// Ctrl-C saves straight away; a crash redoes at most this many cards.
+const CHECKPOINT_EVERY: usize = 25;
+
+/// Cards finished by an `omniscope enrich` run, kept in
+/// `.libr/enrich_checkpoint.json` until the run completes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnrichCheckpoint {
+    /// The query the run was started with.
+    pub query: String,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed: HashSet<Uuid>,
+}
+
+impl EnrichCheckpoint {
+    pub fn path(root: &LibraryRoot) -> PathBuf {
+        root.libr_dir().join(CHECKPOINT_FILE_NAME)
+    }
+
+    /// The checkpoint to resume for `query`. A missing file, or one left by
+    /// a run with a different query, starts from scratch.
+    pub fn for_query(path: &Path, query: &str) -> Result<Self> {
+        let resumed = if path.exists() {
+            let contents = std::fs::read_to_string(path)
+                .map_err(|err| ScienceError::Io(format!("{}: {err}", path.display())))?;
+            serde_json::from_str::<Self>(&contents)
+                .map_err(|err| ScienceError::Parse(format!("{}: {err}", path.display())))?
+        } else {
+            Self::default()
+        };
+
+        if resumed.started_at.is_some() && resumed.query == query {
+            return Ok(resumed);
+        }
+        Ok(Self {
+            query: query.to_string(),
+            started_at: Some(Utc::now()),
+            completed: HashSet::new(),
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|err| ScienceError::Io(format!("{}: {err}", parent.display())))?;
+        }
+        let json = serde_json::to_string(self)
+            .map_err(|err| ScienceError::Parse(format!("failed to encode checkpoint: {err}")))?;
+        // Written aside and renamed, so a crash mid-write leaves the old
+        // checkpoint rather than a torn one `for_query` cannot parse.
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json)
+            .map_err(|err| ScienceError::Io(format!("{}: {err}", tmp.display())))?;
+        std::fs::rename(&tmp, path)
+            .map_err(|err| ScienceError::Io(format!("{}: {err}", path.display())))
+    }
+
+    /// Remove the checkpoint once a run has gone through every card.
+    pub fn clear(path: &Path) -> Result<()> {
+        match std::fs::remove_file(path) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(ScienceError::Io(format!("{}: {err}", path.display()))),
+        }
+    }
+}
+
+/// Totals over a batch run.
+#[derive(Debug, Clone, Default)]
+pub struct BatchEnrichmentReport {
+    /// Cards the query selected.
+    pub selected: usize,
+    /// Cards skipped because an earlier run already finished them.
+    pub resumed: usize,
+    /// Cards enriched and saved by this run.
+    pub enriched: usize,
+    /// Of those, cards where at least one field changed.
+    pub updated: usize,
+    /// Cards not saved, or left unchanged because a source could not be
+    /// reached; they stay out of the checkpoint.
+    pub failed: usize,
+    /// How many cards had each field updated.
+    pub field_counts: BTreeMap<String, usize>,
+    /// Sources and warnings across all cards, each warning prefixed with
+    /// the card's title. Candidates are not kept.
+    pub report: EnrichmentReport,
+}
+
+impl BatchEnrichmentReport {
+    fn absorb(&mut self, card: &BookCard, report: EnrichmentReport) {
+        self.enriched += 1;
+        if !report.fields_updated.is_empty() {
+            self.updated += 1;
+        }
+        for field in &report.fields_updated {
+            *self.field_counts.entry(field.clone()).or_default() += 1;
+        }
+        self.report.fields_updated = self.field_counts.keys().cloned().collect();
+        for source in report.sources_used {
+            if !self.report.sources_used.contains(&source) {
+                self.report.sources_used.push(source);
+            }
+        }
+        self.add_errors(card, report.errors);
+    }
+
+    fn add_errors(&mut self, card: &BookCard, errors: Vec<String>) {
+        let title = &card.metadata.title;
+        self.report
+            .errors
+            .extend(errors.into_iter().map(|error| format!("{title}: {error}")));
+    }
+}
+
+pub struct BatchEnricher {
+    pipeline: EnrichmentPipeline,
+    jobs: usize,
+}
+
+impl BatchEnricher {
+    /// `jobs` cards are enriched at a time; zero is treated as one.
+    pub fn new(pipeline: EnrichmentPipeline, jobs: usize) -> Self {
+        Self {
+            pipeline,
+            jobs: jobs.max(1),
+        }
+    }
+
+    /// Enrich the `cards` not yet in `checkpoint`. Each finished card is
+    /// handed to `on_card`, in completion order, to be saved; cards it
+    /// accepts are added to `checkpoint`. A card that gained nothing
+    /// because a source was unreachable or rate-limited is left for the
+    /// next run instead. `checkpoint` is written to `checkpoint_path` every
+    /// few cards and once more at the end.
+    ///
+    /// Dropping the future part-way leaves `checkpoint` holding every card
+    /// saved so far, so callers can save it and resume later.
+    pub async fn run<F, E>(
+        &self,
+        cards: Vec<BookCard>,
+        checkpoint: &mut EnrichCheckpoint,
+        checkpoint_path: &Path,
+        mut on_card: F,
+    ) -> Result<BatchEnrichmentReport>
+    where
+        F: FnMut(&BookCard, &EnrichmentReport) -> std::result::Result<(), E>,
+        E: Display,
+    {
+        let mut summary = BatchEnrichmentReport {
+            selected: cards.len(),
+            ..Default::default()
+        };
+        let pending = cards
+            .into_iter()
+            .filter(|card| !checkpoint.completed.contains(&card.id))
+            .collect::<Vec<_>>();
+        summary.resumed = summary.selected - pending.len();
+
+        let pipeline = &self.pipeline;
+        let mut finished = stream::iter(pending)
+            .map(|mut card| async move {
+                let report = pipeline.enrich(&mut card).await;
+                (card, report)
+            })
+            .buffer_unordered(self.jobs);
+
+        let mut unsaved = 0;
+        while let Some((card, report)) = finished.next().await {
+            if report.transient_errors > 0 && report.fields_updated.is_empty() {
+                summary.failed += 1;
+                summary.add_errors(&card, report.errors);
+                continue;
+            }
+            if let Err(err) = on_card(&card, &report) {
+                summary.failed += 1;
+                summary.add_errors(&card, vec![format!("not saved: {err}")]);
+                continue;
+            }
+            checkpoint.completed.insert(card.id);
+            summary.absorb(&card, report);
+
+            unsaved += 1;
+            if unsaved >= CHECKPOINT_EVERY {
+                checkpoint.save(checkpoint_path)?;
+                unsaved = 0;
+            }
+        }
+
+        checkpoint.save(checkpoint_path)?;
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use mockito::Server;
+    use omniscope_core::models::ScientificIdentifiers;
+    use serde_json::json;
+
+    use super::*;
+
+    static TMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    fn checkpoint_path() -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "omniscope_enrich_batch_test_{}_{}.json",
+            std::process::id(),
+            TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ))
+    }
+
+    fn card_with_doi(doi: &str) -> BookCard {
+        let mut card = BookCard::new("Seed");
+        card.identifiers = Some(ScientificIdentifiers {
+            doi: Some(doi.to_string()),
+            ..Default::default()
+        });
+        card
+    }
+
+    #[tokio::test]
+    async fn batch_skips_checkpointed_cards_and_records_saved_ones() {
+        let mut server = Server::new_async().await;
+        let mut mocks = Vec::new();
+        for (suffix, title) in [("a", "First Paper"), ("b", "Second Paper")] {
+            let mock = server
+                .mock("GET", format!("/works/10.1000%2F{suffix}").as_str())
+                .with_status(200)
+                .with_header("content-type", "application/json")
+                .with_body(
+                    json!({
+                        "message": {
+                            "DOI": format!("10.1000/{suffix}"),
+                            "title": [title],
+                            "type": "journal-article"
+                        }
+                    })
+                    .to_string(),
+                )
+                .expect(1)
+                .create_async()
+                .await;
+            mocks.push(mock);
+        }
+
+        let first = card_with_doi("10.1000/a");
+        let second = card_with_doi("10.1000/b");
+        let done = card_with_doi("10.1000/c");
+        let path = checkpoint_path();
+        let mut checkpoint = EnrichCheckpoint::for_query(&path, "#ml").unwrap();
+        checkpoint.completed.insert(done.id);
+
+        let enricher = BatchEnricher::new(EnrichmentPipeline::new_for_tests(&server.url()), 2);
+        let mut saved = Vec::new();
+        let summary = enricher
+            .run(
+                vec![first.clone(), second.clone(), done.clone()],
+                &mut checkpoint,
+                &path,
+                |card, _report| {
+                    if card.id == second.id {
+                        return Err("disk full");
+                    }
+                    saved.push(card.metadata.title.clone());
+                    Ok(())
+                },
+            )
+            .await
+            .unwrap();
+
+        for mock in mocks {
+            mock.assert_async().await;
+        }
+        assert_eq!(saved, vec!["First Paper"]);
+        assert_eq!(summary.selected, 3);
+        assert_eq!(summary.resumed, 1);
+        assert_eq!(summary.enriched, 1);
+        assert_eq!(summary.updated, 1);
+        assert_eq!(summary.failed, 1);
+        assert_eq!(summary.field_counts.get("metadata.title"), Some(&1));
+        assert!(
+            summary
+                .report
+                .sources_used
+                .contains(&"crossref".to_string())
+        );
+        assert!(
+            summary
+                .report
+                .errors
+                .iter()
+                .any(|error| error == "Second Paper: not saved: disk full")
+        );
+
+        let resumed = EnrichCheckpoint::for_query(&path, "#ml").unwrap();
+        assert!(resumed.completed.contains(&first.id));
+        assert!(resumed.completed.contains(&done.id));
+        assert!(!resumed.completed.contains(&second.id));
+
+        let other_query = EnrichCheckpoint::for_query(&path, "#physics").unwrap();
+        assert!(other_query.completed.is_empty());
+
+        EnrichCheckpoint::clear(&path).unwrap();
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn batch_leaves_rate_limited_cards_for_the_next_run() {
+        let mut server = Server::new_async().await;
+        let _ok = server
+            .mock("GET", "/works/10.1000%2Fok")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "message": {
+                        "DOI": "10.1000/ok",
+                        "title": ["Reachable Paper"],
+                        "type": "journal-article"
+                    }
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let _busy = server
+            .mock("GET", "/works/10.1000%2Fbusy")
+            .with_status(429)
+            .with_header("retry-after", "0")
+            .create_async()
+            .await;
+
+        let ok = card_with_doi("10.1000/ok");
+        let busy = card_with_doi("10.1000/busy");
+        let path = checkpoint_path();
+        let mut checkpoint = EnrichCheckpoint::for_query(&path, "#ml").unwrap();
+
+        let enricher = BatchEnricher::new(EnrichmentPipeline::new_for_tests(&server.url()), 2);
+        let mut saved = Vec::new();
+        let summary = enricher
+            .run(
+                vec![ok.clone(), busy.clone()],
+                &mut checkpoint,
+                &path,
+                |card, _report| {
+                    saved.push(card.id);
+                    Ok::<(), String>(())
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(saved, vec![ok.id]);
+        assert_eq!(summary.enriched, 1);
+        assert_eq!(summary.failed, 1);
+        assert!(
+            summary
+                .report
+                .errors
+                .iter()
+                .any(|error| { error.starts_with("Seed: crossref enrichment failed: rate limit") })
+        );
+
+        let resumed = EnrichCheckpoint::for_query(&path, "#ml").unwrap();
+        assert!(resumed.completed.contains(&ok.id));
+        assert!(!resumed.completed.contains(&busy.id));
+        assert!(!path.with_extension("json.tmp").exists());
+
+        EnrichCheckpoint::clear(&path).unwrap();
+    }
+}
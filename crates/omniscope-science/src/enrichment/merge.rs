@@ -4,20 +4,20 @@ use omniscope_core::models::{BookCard, DocumentType};
 
 use crate::identifiers::{arxiv::ArxivId, doi::Doi, isbn::Isbn};
 
-const FIELD_TITLE: &str = "metadata.title";
-const FIELD_SUBTITLE: &str = "metadata.subtitle";
-const FIELD_AUTHORS: &str = "metadata.authors";
-const FIELD_YEAR: &str = "metadata.year";
-const FIELD_PUBLISHER: &str = "metadata.publisher";
-const FIELD_LANGUAGE: &str = "metadata.language";
-const FIELD_PAGES: &str = "metadata.pages";
+pub(crate) const FIELD_TITLE: &str = "metadata.title";
+pub(crate) const FIELD_SUBTITLE: &str = "metadata.subtitle";
+pub(crate) const FIELD_AUTHORS: &str = "metadata.authors";
+pub(crate) const FIELD_YEAR: &str = "metadata.year";
+pub(crate) const FIELD_PUBLISHER: &str = "metadata.publisher";
+pub(crate) const FIELD_LANGUAGE: &str = "metadata.language";
+pub(crate) const FIELD_PAGES: &str = "metadata.pages";
 const FIELD_EDITION: &str = "metadata.edition";
 const FIELD_SERIES: &str = "metadata.series";
 const FIELD_SERIES_INDEX: &str = "metadata.series_index";
 const FIELD_TAGS: &str = "organization.tags";
-const FIELD_ABSTRACT: &str = "ai.summary";
+pub(crate) const FIELD_ABSTRACT: &str = "ai.summary";
 const FIELD_TLDR: &str = "ai.tldr";
-const FIELD_DOI: &str = "identifiers.doi";
+pub(crate) const FIELD_DOI: &str = "identifiers.doi";
 const FIELD_ARXIV: &str = "identifiers.arxiv_id";
 const FIELD_ISBN: &str = "metadata.isbn";
 const FIELD_ISBN13: &str = "identifiers.isbn13";
@@ -29,18 +29,21 @@ const FIELD_OPENALEX: &str = "identifiers.openalex_id";
 const FIELD_MAG: &str = "identifiers.mag_id";
 const FIELD_DBLP: &str = "identifiers.dblp_key";
 const FIELD_OPENLIBRARY: &str = "web.openlibrary_id";
+const FIELD_COVER: &str = "web.cover_url";
 const FIELD_DOC_TYPE: &str = "publication.doc_type";
-const FIELD_JOURNAL: &str = "publication.journal";
-const FIELD_CONFERENCE: &str = "publication.conference";
-const FIELD_VENUE: &str = "publication.venue";
-const FIELD_VOLUME: &str = "publication.volume";
-const FIELD_ISSUE: &str = "publication.issue";
-const FIELD_PUBLICATION_PAGES: &str = "publication.pages";
+pub(crate) const FIELD_JOURNAL: &str = "publication.journal";
+pub(crate) const FIELD_CONFERENCE: &str = "publication.conference";
+pub(crate) const FIELD_VENUE: &str = "publication.venue";
+pub(crate) const FIELD_VOLUME: &str = "publication.volume";
+pub(crate) const FIELD_ISSUE: &str = "publication.issue";
+pub(crate) const FIELD_PUBLICATION_PAGES: &str = "publication.pages";
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MetadataSource {
     UserManual,
     CrossRef,
+    PubMed,
+    Dblp,
     ArxivApi,
     PdfInternal,
     EpubOpf,
@@ -48,6 +51,7 @@ pub enum MetadataSource {
     SemanticScholar,
     OpenAlex,
     GoogleBooks,
+    BibImport,
     AiInferred,
     AnnasArchive,
     Unknown,
@@ -58,6 +62,8 @@ impl MetadataSource {
         match self {
             Self::UserManual => "user_manual",
             Self::CrossRef => "crossref",
+            Self::PubMed => "pubmed",
+            Self::Dblp => "dblp",
             Self::ArxivApi => "arxiv_api",
             Self::PdfInternal => "pdf_internal",
             Self::EpubOpf => "epub_opf",
@@ -65,6 +71,7 @@ impl MetadataSource {
             Self::SemanticScholar => "semantic_scholar",
             Self::OpenAlex => "openalex",
             Self::GoogleBooks => "google_books",
+            Self::BibImport => "bib_import",
             Self::AiInferred => "ai_inferred",
             Self::AnnasArchive => "annas_archive",
             Self::Unknown => "unknown",
@@ -75,6 +82,8 @@ impl MetadataSource {
         match value {
             "user_manual" => Self::UserManual,
             "crossref" => Self::CrossRef,
+            "pubmed" => Self::PubMed,
+            "dblp" => Self::Dblp,
             "arxiv_api" => Self::ArxivApi,
             "pdf_internal" => Self::PdfInternal,
             "epub_opf" => Self::EpubOpf,
@@ -82,6 +91,7 @@ impl MetadataSource {
             "semantic_scholar" => Self::SemanticScholar,
             "openalex" => Self::OpenAlex,
             "google_books" => Self::GoogleBooks,
+            "bib_import" => Self::BibImport,
             "ai_inferred" => Self::AiInferred,
             "annas_archive" => Self::AnnasArchive,
             _ => Self::Unknown,
@@ -93,6 +103,8 @@ pub fn source_priority(source: MetadataSource) -> u8 {
     match source {
         MetadataSource::UserManual => 100,
         MetadataSource::CrossRef => 90,
+        MetadataSource::PubMed => 88,
+        MetadataSource::Dblp => 86,
         MetadataSource::ArxivApi => 85,
         MetadataSource::PdfInternal => 80,
         MetadataSource::EpubOpf => 75,
@@ -100,6 +112,7 @@ pub fn source_priority(source: MetadataSource) -> u8 {
         MetadataSource::SemanticScholar => 65,
         MetadataSource::OpenAlex => 60,
         MetadataSource::GoogleBooks => 55,
+        MetadataSource::BibImport => 50,
         MetadataSource::AiInferred => 40,
         MetadataSource::AnnasArchive => 30,
         MetadataSource::Unknown => 10,
@@ -139,6 +152,7 @@ pub struct PartialMetadata {
     pub mag_id: Option<String>,
     pub dblp_key: Option<String>,
     pub openlibrary_id: Option<String>,
+    pub cover_url: Option<String>,
     pub doc_type: Option<DocumentType>,
     pub journal: Option<String>,
     pub conference: Option<String>,
@@ -169,7 +183,9 @@ impl BookCardMergeExt for BookCard {
     ) -> Vec<String> {
         let mut updated = Vec::new();
 
-        if let Some(value) = clean_text_option(new_data.title) {
+        if let Some(value) = clean_text_option(new_data.title)
+            && !locked_against(self, FIELD_TITLE, source)
+        {
             let existing_source = field_source(self, FIELD_TITLE);
             let should_set = self.metadata.title.trim().is_empty()
                 || can_update(existing_source, source, MergeStrategy::HighestPriority);
@@ -182,7 +198,9 @@ impl BookCardMergeExt for BookCard {
             }
         }
 
-        if let Some(value) = clean_text_option(new_data.subtitle) {
+        if let Some(value) = clean_text_option(new_data.subtitle)
+            && !locked_against(self, FIELD_SUBTITLE, source)
+        {
             let existing_source = field_source(self, FIELD_SUBTITLE);
             let should_set = self.metadata.subtitle.is_none()
                 || can_update(existing_source, source, MergeStrategy::HighestPriority);
@@ -195,7 +213,11 @@ impl BookCardMergeExt for BookCard {
             }
         }
 
-        if !new_data.authors.is_empty() {
+        // Authors are concatenated regardless of priority, but never into a
+        // list the user picked.
+        let authors_locked = field_source(self, FIELD_AUTHORS) == MetadataSource::UserManual
+            && source != MetadataSource::UserManual;
+        if !new_data.authors.is_empty() && !authors_locked {
             let added = concat_unique(&mut self.metadata.authors, new_data.authors);
             if added {
                 push_unique(&mut updated, FIELD_AUTHORS);
@@ -203,7 +225,9 @@ impl BookCardMergeExt for BookCard {
             }
         }
 
-        if let Some(value) = new_data.year {
+        if let Some(value) = new_data.year
+            && !locked_against(self, FIELD_YEAR, source)
+        {
             let existing_source = field_source(self, FIELD_YEAR);
             let should_set = self.metadata.year.is_none()
                 || can_update(existing_source, source, MergeStrategy::HighestPriority);
@@ -216,7 +240,9 @@ impl BookCardMergeExt for BookCard {
             }
         }
 
-        if let Some(value) = clean_text_option(new_data.publisher) {
+        if let Some(value) = clean_text_option(new_data.publisher)
+            && !locked_against(self, FIELD_PUBLISHER, source)
+        {
             let existing_source = field_source(self, FIELD_PUBLISHER);
             let should_set = self.metadata.publisher.is_none()
                 || can_update(existing_source, source, MergeStrategy::HighestPriority);
@@ -229,7 +255,9 @@ impl BookCardMergeExt for BookCard {
             }
         }
 
-        if let Some(value) = clean_text_option(new_data.language) {
+        if let Some(value) = clean_text_option(new_data.language)
+            && !locked_against(self, FIELD_LANGUAGE, source)
+        {
             let existing_source = field_source(self, FIELD_LANGUAGE);
             let should_set = self.metadata.language.is_none()
                 || can_update(existing_source, source, MergeStrategy::HighestPriority);
@@ -242,7 +270,9 @@ impl BookCardMergeExt for BookCard {
             }
         }
 
-        if let Some(value) = new_data.pages {
+        if let Some(value) = new_data.pages
+            && !locked_against(self, FIELD_PAGES, source)
+        {
             let existing_source = field_source(self, FIELD_PAGES);
             let should_set = self.metadata.pages.is_none()
                 || can_update(existing_source, source, MergeStrategy::HighestPriority);
@@ -255,7 +285,9 @@ impl BookCardMergeExt for BookCard {
             }
         }
 
-        if let Some(value) = new_data.edition {
+        if let Some(value) = new_data.edition
+            && !locked_against(self, FIELD_EDITION, source)
+        {
             let existing_source = field_source(self, FIELD_EDITION);
             let should_set = self.metadata.edition.is_none()
                 || can_update(existing_source, source, MergeStrategy::HighestPriority);
@@ -268,7 +300,9 @@ impl BookCardMergeExt for BookCard {
             }
         }
 
-        if let Some(value) = clean_text_option(new_data.series) {
+        if let Some(value) = clean_text_option(new_data.series)
+            && !locked_against(self, FIELD_SERIES, source)
+        {
             let existing_source = field_source(self, FIELD_SERIES);
             let should_set = self.metadata.series.is_none()
                 || can_update(existing_source, source, MergeStrategy::HighestPriority);
@@ -281,7 +315,9 @@ impl BookCardMergeExt for BookCard {
             }
         }
 
-        if let Some(value) = new_data.series_index {
+        if let Some(value) = new_data.series_index
+            && !locked_against(self, FIELD_SERIES_INDEX, source)
+        {
             let existing_source = field_source(self, FIELD_SERIES_INDEX);
             let should_set = self.metadata.series_index.is_none()
                 || can_update(existing_source, source, MergeStrategy::HighestPriority);
@@ -294,7 +330,7 @@ impl BookCardMergeExt for BookCard {
             }
         }
 
-        if !new_data.tags.is_empty() {
+        if !new_data.tags.is_empty() && !locked_against(self, FIELD_TAGS, source) {
             let added = concat_unique(&mut self.organization.tags, new_data.tags);
             if added {
                 push_unique(&mut updated, FIELD_TAGS);
@@ -302,7 +338,9 @@ impl BookCardMergeExt for BookCard {
             }
         }
 
-        if let Some(value) = clean_text_option(new_data.abstract_text) {
+        if let Some(value) = clean_text_option(new_data.abstract_text)
+            && !locked_against(self, FIELD_ABSTRACT, source)
+        {
             let existing_source = field_source(self, FIELD_ABSTRACT);
             let current_len = self
                 .ai
@@ -323,7 +361,9 @@ impl BookCardMergeExt for BookCard {
             }
         }
 
-        if let Some(value) = clean_text_option(new_data.tldr) {
+        if let Some(value) = clean_text_option(new_data.tldr)
+            && !locked_against(self, FIELD_TLDR, source)
+        {
             let existing_source = field_source(self, FIELD_TLDR);
             let should_set = self.ai.tldr.is_none()
                 || can_update(existing_source, source, MergeStrategy::HighestPriority);
@@ -336,7 +376,9 @@ impl BookCardMergeExt for BookCard {
             }
         }
 
-        if let Some(value) = new_data.doi {
+        if let Some(value) = new_data.doi
+            && !locked_against(self, FIELD_DOI, source)
+        {
             let existing_source = field_source(self, FIELD_DOI);
             let identifiers = self.identifiers.get_or_insert_with(Default::default);
             let should_set = identifiers.doi.is_none()
@@ -350,7 +392,9 @@ impl BookCardMergeExt for BookCard {
             }
         }
 
-        if let Some(value) = new_data.arxiv_id {
+        if let Some(value) = new_data.arxiv_id
+            && !locked_against(self, FIELD_ARXIV, source)
+        {
             let existing_source = field_source(self, FIELD_ARXIV);
             let normalized = format_arxiv_id(&value);
             let identifiers = self.identifiers.get_or_insert_with(Default::default);
@@ -371,19 +415,28 @@ impl BookCardMergeExt for BookCard {
             let mut isbn10_updated = false;
             let existing_isbn_source = field_source(self, FIELD_ISBN);
             let identifiers = self.identifiers.get_or_insert_with(Default::default);
+            let isbn_locked = locked_against(self, FIELD_ISBN, source);
+            let isbn13_locked = locked_against(self, FIELD_ISBN13, source);
+            let isbn10_locked = locked_against(self, FIELD_ISBN10, source);
             for isbn in new_data.isbn {
-                if !self.metadata.isbn.iter().any(|value| value == &isbn.isbn13) {
+                if !isbn_locked && !self.metadata.isbn.iter().any(|value| value == &isbn.isbn13) {
                     self.metadata.isbn.push(isbn.isbn13.clone());
                     isbn_added = true;
                 }
-                let can_update_isbn13 = identifiers.isbn13.is_none()
-                    || can_update(existing_isbn_source, source, MergeStrategy::HighestPriority);
+                let can_update_isbn13 = !isbn13_locked
+                    && (identifiers.isbn13.is_none()
+                        || can_update(
+                            existing_isbn_source,
+                            source,
+                            MergeStrategy::HighestPriority,
+                        ));
                 if can_update_isbn13 && identifiers.isbn13.as_deref() != Some(isbn.isbn13.as_str())
                 {
                     identifiers.isbn13 = Some(isbn.isbn13.clone());
                     isbn13_updated = true;
                 }
                 if let Some(isbn10) = isbn.isbn10
+                    && !isbn10_locked
                     && (identifiers.isbn10.is_none()
                         || can_update(existing_isbn_source, source, MergeStrategy::HighestPriority))
                     && identifiers.isbn10.as_deref() != Some(isbn10.as_str())
@@ -456,7 +509,9 @@ impl BookCardMergeExt for BookCard {
             &mut updated,
         );
 
-        if let Some(value) = clean_text_option(new_data.openlibrary_id) {
+        if let Some(value) = clean_text_option(new_data.openlibrary_id)
+            && !locked_against(self, FIELD_OPENLIBRARY, source)
+        {
             let existing_source = field_source(self, FIELD_OPENLIBRARY);
             let should_set = self.web.openlibrary_id.is_none()
                 || can_update(existing_source, source, MergeStrategy::HighestPriority);
@@ -469,7 +524,24 @@ impl BookCardMergeExt for BookCard {
             }
         }
 
-        if let Some(value) = new_data.doc_type {
+        if let Some(value) = clean_text_option(new_data.cover_url)
+            && !locked_against(self, FIELD_COVER, source)
+        {
+            let existing_source = field_source(self, FIELD_COVER);
+            let should_set = self.web.cover_url.is_none()
+                || can_update(existing_source, source, MergeStrategy::HighestPriority);
+            if should_set {
+                if self.web.cover_url.as_deref() != Some(value.as_str()) {
+                    self.web.cover_url = Some(value);
+                    push_unique(&mut updated, FIELD_COVER);
+                }
+                set_field_source(self, FIELD_COVER, source);
+            }
+        }
+
+        if let Some(value) = new_data.doc_type
+            && !locked_against(self, FIELD_DOC_TYPE, source)
+        {
             let existing_source = field_source(self, FIELD_DOC_TYPE);
             let publication = self.publication.get_or_insert_with(Default::default);
             let should_set = publication.doc_type == DocumentType::default()
@@ -548,6 +620,9 @@ fn merge_identifier_field(
     slot: impl FnOnce(&mut omniscope_core::models::ScientificIdentifiers) -> &mut Option<String>,
     updated: &mut Vec<String>,
 ) {
+    if locked_against(card, field_name, source) {
+        return;
+    }
     let Some(value) = clean_text_option(incoming) else {
         return;
     };
@@ -574,6 +649,9 @@ fn merge_publication_field(
     slot: impl FnOnce(&mut omniscope_core::models::BookPublication) -> &mut Option<String>,
     updated: &mut Vec<String>,
 ) {
+    if locked_against(card, field_name, source) {
+        return;
+    }
     let Some(value) = clean_text_option(incoming) else {
         return;
     };
@@ -592,7 +670,18 @@ fn merge_publication_field(
     }
 }
 
-fn field_source(card: &BookCard, field: &str) -> MetadataSource {
+/// Whether `field` is locked against `source`. Checked before the
+/// empty-field shortcut, so a locked field the user cleared stays empty.
+fn locked_against(card: &BookCard, field: &str, source: MetadataSource) -> bool {
+    card.is_field_locked(field) && source != MetadataSource::UserManual
+}
+
+/// Locked fields report `UserManual`, so `can_update` turns every other
+/// source away.
+pub fn field_source(card: &BookCard, field: &str) -> MetadataSource {
+    if card.is_field_locked(field) {
+        return MetadataSource::UserManual;
+    }
     card.metadata_sources
         .get(field)
         .map(String::as_str)
@@ -600,7 +689,7 @@ fn field_source(card: &BookCard, field: &str) -> MetadataSource {
         .unwrap_or(MetadataSource::Unknown)
 }
 
-fn set_field_source(card: &mut BookCard, field: &str, source: MetadataSource) {
+pub(crate) fn set_field_source(card: &mut BookCard, field: &str, source: MetadataSource) {
     card.metadata_sources
         .insert(field.to_string(), source.as_tag().to_string());
 }
@@ -674,6 +763,8 @@ mod tests {
     fn source_priority_matches_spec() {
         assert_eq!(source_priority(MetadataSource::UserManual), 100);
         assert_eq!(source_priority(MetadataSource::CrossRef), 90);
+        assert_eq!(source_priority(MetadataSource::PubMed), 88);
+        assert_eq!(source_priority(MetadataSource::Dblp), 86);
         assert_eq!(source_priority(MetadataSource::ArxivApi), 85);
         assert_eq!(source_priority(MetadataSource::PdfInternal), 80);
         assert_eq!(source_priority(MetadataSource::EpubOpf), 75);
@@ -794,4 +885,63 @@ mod tests {
             Some("user_manual")
         );
     }
+
+    #[test]
+    fn locked_field_keeps_value_and_provenance() {
+        let mut card = BookCard::new("Paper");
+        card.merge_metadata(
+            PartialMetadata {
+                year: Some(2019),
+                authors: vec!["Alice".to_string()],
+                ..Default::default()
+            },
+            MetadataSource::OpenAlex,
+        );
+        card.set_field_locked(FIELD_YEAR, true);
+        card.set_field_locked(FIELD_AUTHORS, true);
+
+        let updated = card.merge_metadata_with_trace(
+            PartialMetadata {
+                year: Some(2020),
+                authors: vec!["Bob".to_string()],
+                ..Default::default()
+            },
+            MetadataSource::CrossRef,
+        );
+
+        assert!(updated.is_empty());
+        assert_eq!(card.metadata.year, Some(2019));
+        assert_eq!(card.metadata.authors, vec!["Alice"]);
+        assert_eq!(
+            card.metadata_sources.get(FIELD_YEAR).map(String::as_str),
+            Some("openalex")
+        );
+    }
+
+    #[test]
+    fn locked_field_cleared_by_the_user_stays_empty() {
+        let mut card = BookCard::new("Paper");
+        card.merge_metadata(
+            PartialMetadata {
+                subtitle: Some("A first subtitle".to_string()),
+                ..Default::default()
+            },
+            MetadataSource::OpenAlex,
+        );
+        card.set_field_locked(FIELD_SUBTITLE, true);
+        card.metadata.subtitle = None;
+        let sources = card.metadata_sources.clone();
+
+        let updated = card.merge_metadata_with_trace(
+            PartialMetadata {
+                subtitle: Some("A second subtitle".to_string()),
+                ..Default::default()
+            },
+            MetadataSource::CrossRef,
+        );
+
+        assert!(updated.is_empty());
+        assert_eq!(card.metadata.subtitle, None);
+        assert_eq!(card.metadata_sources, sources);
+    }
 }
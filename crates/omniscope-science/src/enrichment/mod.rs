@@ -1,7 +1,13 @@
+pub mod batch;
 pub mod merge;
 pub mod pipeline;
+pub mod provenance;
+pub mod review;
 
+pub use batch::{BatchEnricher, BatchEnrichmentReport, EnrichCheckpoint};
 pub use merge::{
     BookCardMergeExt, MergeStrategy, MetadataSource, PartialMetadata, source_priority,
 };
 pub use pipeline::{EnrichmentPipeline, EnrichmentReport, FileMetadataExtractor};
+pub use provenance::{FieldProvenance, field_provenance, resolve_field};
+pub use review::{FieldCandidate, FieldReview, lock_field, review_fields};
@@ -17,6 +17,7 @@ use zip::ZipArchive;
 use crate::arxiv::client::ArxivClient;
 use crate::arxiv::types::ArxivMetadata;
 use crate::enrichment::merge::{BookCardMergeExt, MetadataSource, PartialMetadata};
+use crate::enrichment::review::{FieldCandidate, candidates_from_partial};
 use crate::error::{Result, ScienceError};
 use crate::identifiers::arxiv::ArxivId;
 use crate::identifiers::doi::Doi;
@@ -26,8 +27,13 @@ use crate::identifiers::extract::{
 use crate::identifiers::isbn::Isbn;
 use crate::references::{ExtractedReference, ReferenceExtractor};
 use crate::sources::crossref::{CrossRefAuthor, CrossRefSource, CrossRefWork};
+use crate::sources::dblp::{DblpEntryType, DblpPublication, DblpSource, normalize_key};
+use crate::sources::google_books::{GoogleBooksSource, GoogleBooksVolume};
 use crate::sources::openalex::OpenAlexSource;
 use crate::sources::openlibrary::{OpenLibrarySource, OpenLibraryWork};
+use crate::sources::pubmed::{
+    PubMedArticle, PubMedIds, PubMedSource, normalize_pmcid, normalize_pmid, pmc_article_url,
+};
 use crate::sources::semantic_scholar::{S2Paper, S2PaperId, S2Reference, SemanticScholarSource};
 use crate::sources::unpaywall::{UnpaywallResult, UnpaywallSource};
 use crate::types::DocumentType as ScienceDocumentType;
@@ -46,6 +52,11 @@ pub struct EnrichmentReport {
     pub fields_updated: Vec<String>,
     pub sources_used: Vec<String>,
     pub errors: Vec<String>,
+    /// How many of `errors` were network or rate-limit failures, which a
+    /// later run may get past.
+    pub transient_errors: usize,
+    /// Every reviewable value a source offered, whether or not it won.
+    pub candidates: Vec<FieldCandidate>,
 }
 
 impl EnrichmentReport {
@@ -61,6 +72,13 @@ impl EnrichmentReport {
         self.errors.push(error.into());
     }
 
+    fn add_source_error(&mut self, what: &str, err: &ScienceError) {
+        if err.is_transient() {
+            self.transient_errors += 1;
+        }
+        self.add_error(format!("{what} failed: {err}"));
+    }
+
     fn add_fields<I>(&mut self, fields: I)
     where
         I: IntoIterator<Item = String>,
@@ -69,6 +87,17 @@ impl EnrichmentReport {
             push_unique(&mut self.fields_updated, field);
         }
     }
+
+    fn merge(
+        &mut self,
+        card: &mut BookCard,
+        partial: PartialMetadata,
+        source: MetadataSource,
+    ) -> Vec<String> {
+        self.candidates
+            .extend(candidates_from_partial(&partial, source));
+        card.merge_metadata_with_trace(partial, source)
+    }
 }
 
 pub trait FileMetadataExtractor: Send + Sync {
@@ -196,6 +225,9 @@ pub struct EnrichmentPipeline {
     pub openalex: Arc<OpenAlexSource>,
     pub unpaywall: Arc<UnpaywallSource>,
     pub openlibrary: Arc<OpenLibrarySource>,
+    pub google_books: Arc<GoogleBooksSource>,
+    pub pubmed: Arc<PubMedSource>,
+    pub dblp: Arc<DblpSource>,
     pub arxiv_client: Arc<ArxivClient>,
     file_metadata_extractor: Arc<dyn FileMetadataExtractor>,
 }
@@ -215,11 +247,53 @@ impl EnrichmentPipeline {
             openalex,
             unpaywall,
             openlibrary,
+            google_books: Arc::new(GoogleBooksSource::default()),
+            pubmed: Arc::new(PubMedSource::default()),
+            dblp: Arc::new(DblpSource::new()),
             arxiv_client,
             file_metadata_extractor: Arc::new(DefaultFileMetadataExtractor),
         }
     }
 
+    /// Every source pointed at one mock server, as the sources' own
+    /// `new_for_tests` lay out their endpoints.
+    #[cfg(test)]
+    pub(crate) fn new_for_tests(base_url: &str) -> Self {
+        Self::new(
+            Arc::new(CrossRefSource::new_for_tests(base_url.to_string())),
+            Arc::new(SemanticScholarSource::new_for_tests(format!(
+                "{base_url}/graph/v1"
+            ))),
+            Arc::new(OpenAlexSource::new_for_tests(base_url.to_string())),
+            Arc::new(UnpaywallSource::new_for_tests(
+                format!("{base_url}/v2"),
+                "test@example.com".to_string(),
+            )),
+            Arc::new(OpenLibrarySource::new_for_tests(base_url.to_string())),
+            Arc::new(ArxivClient::new_for_tests(format!("{base_url}/api/query"))),
+        )
+        .with_google_books(Arc::new(GoogleBooksSource::new_for_tests(
+            base_url.to_string(),
+        )))
+        .with_pubmed(Arc::new(PubMedSource::new_for_tests(base_url.to_string())))
+        .with_dblp(Arc::new(DblpSource::new_for_tests(base_url.to_string())))
+    }
+
+    pub fn with_google_books(mut self, google_books: Arc<GoogleBooksSource>) -> Self {
+        self.google_books = google_books;
+        self
+    }
+
+    pub fn with_pubmed(mut self, pubmed: Arc<PubMedSource>) -> Self {
+        self.pubmed = pubmed;
+        self
+    }
+
+    pub fn with_dblp(mut self, dblp: Arc<DblpSource>) -> Self {
+        self.dblp = dblp;
+        self
+    }
+
     pub fn with_file_extractor(mut self, extractor: Arc<dyn FileMetadataExtractor>) -> Self {
         self.file_metadata_extractor = extractor;
         self
@@ -249,6 +323,10 @@ impl EnrichmentPipeline {
         let unpaywall_email = env_first(["OMNISCOPE_UNPAYWALL_EMAIL", "UNPAYWALL_EMAIL"])
             .or_else(|| polite_email.clone())
             .unwrap_or_else(|| "noreply@example.com".to_string());
+        let google_books_key =
+            env_first(["OMNISCOPE_GOOGLE_BOOKS_API_KEY", "GOOGLE_BOOKS_API_KEY"]);
+        let ncbi_api_key = env_first(["OMNISCOPE_NCBI_API_KEY", "NCBI_API_KEY"]);
+        let pubmed = PubMedSource::new(ncbi_api_key, polite_email.clone());
 
         Self::new(
             Arc::new(CrossRefSource::new(polite_email)),
@@ -258,6 +336,8 @@ impl EnrichmentPipeline {
             Arc::new(OpenLibrarySource::new()),
             Arc::new(ArxivClient::new()),
         )
+        .with_google_books(Arc::new(GoogleBooksSource::new(google_books_key)))
+        .with_pubmed(Arc::new(pubmed))
     }
 
     /// Blocking helper for sync callers (TUI/CLI): runs full file + online enrichment.
@@ -280,10 +360,12 @@ impl EnrichmentPipeline {
         let mut report = EnrichmentReport::default();
 
         self.run_file_stage(card, &mut report);
-        self.run_identifier_stage(card, &mut report).await;
+        let categories = self.run_identifier_stage(card, &mut report).await;
         self.run_semantic_scholar_stage(card, &mut report).await;
+        self.run_dblp_stage(card, &categories, &mut report).await;
         self.run_references_stage(card, &mut report).await;
         self.run_open_access_stage(card, &mut report).await;
+        self.run_pmc_stage(card, &mut report).await;
 
         report
     }
@@ -292,7 +374,13 @@ impl EnrichmentPipeline {
         run_file_stage_with_extractor(self.file_metadata_extractor.as_ref(), card, report);
     }
 
-    async fn run_identifier_stage(&self, card: &mut BookCard, report: &mut EnrichmentReport) {
+    /// Returns the paper's arXiv categories, which the card has no field for.
+    async fn run_identifier_stage(
+        &self,
+        card: &mut BookCard,
+        report: &mut EnrichmentReport,
+    ) -> Vec<String> {
+        let mut arxiv_categories = Vec::new();
         if let Some(doi) = card
             .identifiers
             .as_ref()
@@ -301,17 +389,15 @@ impl EnrichmentPipeline {
         {
             match self.crossref.fetch_by_doi(&doi).await {
                 Ok(work) => {
-                    let fields = card.merge_metadata_with_trace(
-                        partial_from_crossref(work),
-                        MetadataSource::CrossRef,
-                    );
+                    let fields =
+                        report.merge(card, partial_from_crossref(work), MetadataSource::CrossRef);
                     if !fields.is_empty() {
                         report.add_fields(fields);
                     }
                     report.add_step("Enriched from CrossRef via DOI");
                     report.add_source("crossref");
                 }
-                Err(err) => report.add_error(format!("crossref enrichment failed: {err}")),
+                Err(err) => report.add_source_error("crossref enrichment", &err),
             }
         }
 
@@ -323,25 +409,25 @@ impl EnrichmentPipeline {
         {
             match self.arxiv_client.fetch_metadata(&arxiv_id).await {
                 Ok(metadata) => {
-                    let fields = card.merge_metadata_with_trace(
-                        partial_from_arxiv(metadata),
-                        MetadataSource::ArxivApi,
-                    );
+                    arxiv_categories = metadata.categories.clone();
+                    let fields =
+                        report.merge(card, partial_from_arxiv(metadata), MetadataSource::ArxivApi);
                     if !fields.is_empty() {
                         report.add_fields(fields);
                     }
                     report.add_step("Enriched from arXiv API");
                     report.add_source("arxiv_api");
                 }
-                Err(err) => report.add_error(format!("arxiv enrichment failed: {err}")),
+                Err(err) => report.add_source_error("arxiv enrichment", &err),
             }
         }
 
         if let Some(isbn) = first_isbn(card) {
             match self.openlibrary.fetch_by_isbn(&isbn).await {
                 Ok(work) => {
-                    let fields = card.merge_metadata_with_trace(
-                        partial_from_openlibrary(work, isbn),
+                    let fields = report.merge(
+                        card,
+                        partial_from_openlibrary(work, isbn.clone()),
                         MetadataSource::OpenLibrary,
                     );
                     if !fields.is_empty() {
@@ -350,7 +436,70 @@ impl EnrichmentPipeline {
                     report.add_step("Enriched from Open Library via ISBN");
                     report.add_source("openlibrary");
                 }
-                Err(err) => report.add_error(format!("openlibrary enrichment failed: {err}")),
+                Err(err) => report.add_source_error("openlibrary enrichment", &err),
+            }
+
+            // Fills what Open Library lacks (descriptions, page counts, covers);
+            // its lower priority keeps it from overriding anything else.
+            match self.google_books.fetch_by_isbn(&isbn).await {
+                Ok(volume) => {
+                    let fields = report.merge(
+                        card,
+                        partial_from_google_books(volume, isbn),
+                        MetadataSource::GoogleBooks,
+                    );
+                    if !fields.is_empty() {
+                        report.add_fields(fields);
+                    }
+                    report.add_step("Enriched from Google Books via ISBN");
+                    report.add_source("google_books");
+                }
+                Err(err) => report.add_source_error("google books enrichment", &err),
+            }
+        }
+
+        if let Some(pmid) = self.pubmed_id(card, report).await {
+            match self.pubmed.fetch_by_pmid(&pmid).await {
+                Ok(article) => {
+                    let fields =
+                        report.merge(card, partial_from_pubmed(article), MetadataSource::PubMed);
+                    if !fields.is_empty() {
+                        report.add_fields(fields);
+                    }
+                    report.add_step("Enriched from PubMed via PMID");
+                    report.add_source("pubmed");
+                }
+                Err(err) => report.add_source_error("pubmed enrichment", &err),
+            }
+        }
+
+        arxiv_categories
+    }
+
+    /// The card's PMID, or the one PubMed knows for its PMCID or DOI. An
+    /// article PubMed has never heard of is not an error.
+    async fn pubmed_id(&self, card: &BookCard, report: &mut EnrichmentReport) -> Option<String> {
+        let identifiers = card.identifiers.as_ref()?;
+        if let Some(pmid) = identifiers.pmid.as_deref().and_then(normalize_pmid) {
+            return Some(pmid);
+        }
+
+        if let Some(pmcid) = identifiers.pmcid.as_deref().and_then(normalize_pmcid) {
+            match self.pubmed.convert_ids(&pmcid).await {
+                Ok(Some(PubMedIds {
+                    pmid: Some(pmid), ..
+                })) => return Some(pmid),
+                Ok(_) => {}
+                Err(err) => report.add_source_error("pmc id conversion", &err),
+            }
+        }
+
+        let doi = identifiers.doi.as_deref().and_then(parse_doi)?;
+        match self.pubmed.pmid_for_doi(&doi.normalized).await {
+            Ok(pmid) => pmid,
+            Err(err) => {
+                report.add_source_error("pubmed doi lookup", &err);
+                None
             }
         }
     }
@@ -385,7 +534,8 @@ impl EnrichmentPipeline {
                 card.citation_graph.last_updated = Some(now);
                 report.add_fields(vec!["citation_graph.last_updated".to_string()]);
 
-                let fields = card.merge_metadata_with_trace(
+                let fields = report.merge(
+                    card,
                     partial_from_semantic_scholar(paper),
                     MetadataSource::SemanticScholar,
                 );
@@ -415,8 +565,9 @@ impl EnrichmentPipeline {
                                 }
                             }
                         }
-                        Err(err) => report
-                            .add_error(format!("semantic scholar references fetch failed: {err}")),
+                        Err(err) => {
+                            report.add_source_error("semantic scholar references fetch", &err)
+                        }
                     }
                 }
 
@@ -443,15 +594,65 @@ impl EnrichmentPipeline {
                                 }
                             }
                         }
-                        Err(err) => report
-                            .add_error(format!("semantic scholar citations fetch failed: {err}")),
+                        Err(err) => {
+                            report.add_source_error("semantic scholar citations fetch", &err)
+                        }
                     }
                 }
 
                 report.add_step("Enriched from Semantic Scholar");
                 report.add_source("semantic_scholar");
             }
-            Err(err) => report.add_error(format!("semantic scholar enrichment failed: {err}")),
+            Err(err) => report.add_source_error("semantic scholar enrichment", &err),
+        }
+    }
+
+    /// Semantic Scholar usually supplies the DBLP key; otherwise DBLP is
+    /// searched for the DOI.
+    /// DBLP only indexes computer science, so a card without a DBLP key is
+    /// looked up by DOI only when it looks like a CS paper.
+    async fn run_dblp_stage(
+        &self,
+        card: &mut BookCard,
+        arxiv_categories: &[String],
+        report: &mut EnrichmentReport,
+    ) {
+        let identifiers = card.identifiers.as_ref();
+        let key = match identifiers
+            .and_then(|ids| ids.dblp_key.as_deref())
+            .and_then(normalize_key)
+        {
+            Some(key) => key,
+            None => {
+                let Some(doi) = identifiers
+                    .and_then(|ids| ids.doi.as_deref())
+                    .and_then(parse_doi)
+                    .filter(|doi| is_computer_science(card, doi, arxiv_categories))
+                else {
+                    return;
+                };
+                match self.dblp.find_by_doi(&doi).await {
+                    Ok(Some(hit)) => hit.key,
+                    Ok(None) => return,
+                    Err(err) => {
+                        report.add_source_error("dblp lookup", &err);
+                        return;
+                    }
+                }
+            }
+        };
+
+        match self.dblp.fetch_by_key(&key).await {
+            Ok(publication) => {
+                let fields =
+                    report.merge(card, partial_from_dblp(publication), MetadataSource::Dblp);
+                if !fields.is_empty() {
+                    report.add_fields(fields);
+                }
+                report.add_step("Enriched from DBLP");
+                report.add_source("dblp");
+            }
+            Err(err) => report.add_source_error("dblp enrichment", &err),
         }
     }
 
@@ -502,7 +703,7 @@ impl EnrichmentPipeline {
                 report.add_step("References extracted from available sources");
                 report.add_source("reference_extractor");
             }
-            Err(err) => report.add_error(format!("reference extraction failed: {err}")),
+            Err(err) => report.add_source_error("reference extraction", &err),
         }
     }
 
@@ -528,11 +729,100 @@ impl EnrichmentPipeline {
                 report.add_step("Open Access status checked");
                 report.add_source("unpaywall");
             }
-            Err(err) => report.add_error(format!("unpaywall check failed: {err}")),
+            Err(err) => report.add_source_error("unpaywall check", &err),
+        }
+    }
+
+    /// Biomedical papers without a DOI, or that Unpaywall has no PDF for,
+    /// often have an open-access copy in PubMed Central.
+    async fn run_pmc_stage(&self, card: &mut BookCard, report: &mut EnrichmentReport) {
+        if card
+            .open_access
+            .as_ref()
+            .is_some_and(|oa| !oa.pdf_urls.is_empty())
+        {
+            return;
+        }
+        let Some(pmcid) = card
+            .identifiers
+            .as_ref()
+            .and_then(|ids| ids.pmcid.as_deref())
+            .and_then(normalize_pmcid)
+        else {
+            return;
+        };
+
+        match self.pubmed.find_open_access(&pmcid).await {
+            Ok(Some(record)) => {
+                let mut mapped = card.open_access.clone().unwrap_or_default();
+                mapped.is_open = true;
+                if mapped.license.is_none() {
+                    mapped.license = record.license;
+                }
+                if mapped.oa_url.is_none() {
+                    mapped.oa_url = Some(pmc_article_url(&pmcid));
+                }
+                if let Some(pdf_url) = record.pdf_url {
+                    push_unique(&mut mapped.pdf_urls, pdf_url);
+                }
+
+                if card.open_access.as_ref() != Some(&mapped) {
+                    card.open_access = Some(mapped);
+                    card.touch();
+                    report.add_fields(vec!["open_access".to_string()]);
+                }
+                report.add_step("Open Access copy found in PubMed Central");
+                report.add_source("pmc");
+            }
+            Ok(None) => {}
+            Err(err) => report.add_source_error("pmc open access check", &err),
         }
     }
 }
 
+/// DOI prefixes of the ACM, IEEE and LIPIcs, whose papers DBLP covers.
+const CS_DOI_PREFIXES: [&str; 3] = ["10.1145/", "10.1109/", "10.4230/"];
+
+/// Venue name fragments of computer science journals and conferences.
+const CS_VENUE_MARKERS: [&str; 8] = [
+    "acm",
+    "ieee",
+    "comput",
+    "usenix",
+    "lecture notes in computer science",
+    "neural information processing",
+    "machine learning",
+    "artificial intelligence",
+];
+
+fn is_computer_science(card: &BookCard, doi: &Doi, arxiv_categories: &[String]) -> bool {
+    if arxiv_categories
+        .iter()
+        .any(|category| category.starts_with("cs."))
+    {
+        return true;
+    }
+    if CS_DOI_PREFIXES
+        .iter()
+        .any(|prefix| doi.normalized.starts_with(prefix))
+    {
+        return true;
+    }
+    card.publication.as_ref().is_some_and(|publication| {
+        [
+            &publication.journal,
+            &publication.conference,
+            &publication.venue,
+        ]
+        .into_iter()
+        .flatten()
+        .any(|name| {
+            let name = name.to_lowercase();
+            CS_VENUE_MARKERS.iter().any(|marker| name.contains(marker))
+        })
+    })
+}
+
 fn partial_from_crossref(work: CrossRefWork) -> PartialMetadata {
     let authors = work
         .author
@@ -578,7 +868,7 @@ fn partial_from_arxiv(metadata: ArxivMetadata) -> PartialMetadata {
     }
 }
 
-fn partial_from_openlibrary(work: OpenLibraryWork, isbn: Isbn) -> PartialMetadata {
+pub(crate) fn partial_from_openlibrary(work: OpenLibraryWork, isbn: Isbn) -> PartialMetadata {
     PartialMetadata {
         title: Some(work.title),
         authors: work.authors,
@@ -591,6 +881,90 @@ fn partial_from_openlibrary(work: OpenLibraryWork, isbn: Isbn) -> PartialMetadat
     }
 }
 
+fn partial_from_google_books(volume: GoogleBooksVolume, isbn: Isbn) -> PartialMetadata {
+    PartialMetadata {
+        year: volume.year(),
+        title: Some(volume.title),
+        subtitle: volume.subtitle,
+        authors: volume.authors,
+        publisher: volume.publisher,
+        language: volume.language,
+        pages: volume.page_count,
+        abstract_text: volume.description,
+        isbn: vec![isbn],
+        cover_url: volume.cover_url,
+        ..Default::default()
+    }
+}
+
+pub(crate) fn partial_from_pubmed(article: PubMedArticle) -> PartialMetadata {
+    let doc_type = if article.is_preprint() {
+        DocumentType::Preprint
+    } else {
+        DocumentType::Article
+    };
+
+    PartialMetadata {
+        title: Some(article.title),
+        authors: article.authors,
+        year: article.year,
+        abstract_text: article.abstract_text,
+        doi: article.doi.as_deref().and_then(parse_doi),
+        pmid: Some(article.pmid),
+        pmcid: article.pmcid,
+        doc_type: Some(doc_type),
+        journal: article.journal,
+        volume: article.volume,
+        issue: article.issue,
+        publication_pages: article.pages,
+        ..Default::default()
+    }
+}
+
+fn partial_from_dblp(publication: DblpPublication) -> PartialMetadata {
+    let doc_type = match publication.entry_type {
+        DblpEntryType::Conference => DocumentType::ConferencePaper,
+        DblpEntryType::Journal => DocumentType::Article,
+        DblpEntryType::Informal => DocumentType::Preprint,
+        DblpEntryType::Book | DblpEntryType::Editorship => DocumentType::Book,
+        DblpEntryType::Chapter => DocumentType::Chapter,
+        DblpEntryType::Thesis => DocumentType::Thesis,
+        DblpEntryType::Other => DocumentType::Other,
+    };
+    // CoRR's "journal" and "volume" are just the arXiv listing.
+    let (journal, conference, venue) = match publication.entry_type {
+        DblpEntryType::Informal => (None, None, None),
+        DblpEntryType::Conference => (
+            None,
+            publication
+                .proceedings_title
+                .or_else(|| publication.venue.clone()),
+            publication.venue,
+        ),
+        DblpEntryType::Journal => (publication.venue.clone(), None, publication.venue),
+        _ => (None, None, publication.venue),
+    };
+    let volume = publication
+        .volume
+        .filter(|_| publication.entry_type != DblpEntryType::Informal);
+
+    PartialMetadata {
+        title: Some(publication.title),
+        authors: publication.authors,
+        year: publication.year,
+        doi: publication.doi.as_deref().and_then(parse_doi),
+        dblp_key: Some(publication.key),
+        doc_type: Some(doc_type),
+        journal,
+        conference,
+        venue,
+        volume,
+        issue: publication.number,
+        publication_pages: publication.pages,
+        ..Default::default()
+    }
+}
+
 fn partial_from_semantic_scholar(paper: S2Paper) -> PartialMetadata {
     let doi = lookup_external_id(&paper.external_ids, "DOI").and_then(parse_doi);
     let arxiv_id = lookup_external_id(&paper.external_ids, "ArXiv").and_then(parse_arxiv);
@@ -669,11 +1043,15 @@ fn semantic_scholar_paper_id(card: &BookCard) -> Option<S2PaperId> {
         return Some(S2PaperId::new(format!("DOI:{}", doi.normalized)));
     }
 
+    if let Some(arxiv_id) = identifiers.arxiv_id.as_deref().and_then(parse_arxiv) {
+        return Some(S2PaperId::new(format!("ArXiv:{}", arxiv_id.id)));
+    }
+
     identifiers
-        .arxiv_id
+        .pmid
         .as_deref()
-        .and_then(parse_arxiv)
-        .map(|arxiv_id| S2PaperId::new(format!("ArXiv:{}", arxiv_id.id)))
+        .and_then(normalize_pmid)
+        .map(|pmid| S2PaperId::new(format!("PMID:{pmid}")))
 }
 
 fn s2_references_to_graph_text(references: &[S2Reference]) -> Vec<String> {
@@ -848,8 +1226,7 @@ fn run_file_stage_with_extractor(
         FileFormat::Pdf => {
             match extractor.extract_pdf_metadata(file_path) {
                 Ok(partial) => {
-                    let fields =
-                        card.merge_metadata_with_trace(partial, MetadataSource::PdfInternal);
+                    let fields = report.merge(card, partial, MetadataSource::PdfInternal);
                     if !fields.is_empty() {
                         report.add_fields(fields);
                         report.add_step("PDF metadata extracted");
@@ -867,7 +1244,8 @@ fn run_file_stage_with_extractor(
             {
                 match find_doi_in_first_page(file_path) {
                     Ok(doi) => {
-                        let fields = card.merge_metadata_with_trace(
+                        let fields = report.merge(
+                            card,
                             PartialMetadata {
                                 doi: Some(doi),
                                 ..Default::default()
@@ -892,7 +1270,8 @@ fn run_file_stage_with_extractor(
             {
                 match find_arxiv_id_in_pdf(file_path) {
                     Ok(arxiv_id) => {
-                        let fields = card.merge_metadata_with_trace(
+                        let fields = report.merge(
+                            card,
                             PartialMetadata {
                                 arxiv_id: Some(arxiv_id),
                                 ..Default::default()
@@ -913,7 +1292,7 @@ fn run_file_stage_with_extractor(
         }
         FileFormat::Epub => match extractor.extract_epub_metadata(file_path) {
             Ok(partial) => {
-                let fields = card.merge_metadata_with_trace(partial, MetadataSource::EpubOpf);
+                let fields = report.merge(card, partial, MetadataSource::EpubOpf);
                 if !fields.is_empty() {
                     report.add_fields(fields);
                     report.add_step("EPUB OPF metadata extracted");
@@ -1503,7 +1882,7 @@ fn env_first<const N: usize>(keys: [&str; N]) -> Option<String> {
         .filter(|value| !value.is_empty())
 }
 
-fn parse_container_full_path(container_xml: &str) -> Option<String> {
+pub(crate) fn parse_container_full_path(container_xml: &str) -> Option<String> {
     let regex = Regex::new(r#"full-path\s*=\s*["']([^"']+)["']"#).ok()?;
     regex
         .captures(container_xml)
@@ -1511,7 +1890,7 @@ fn parse_container_full_path(container_xml: &str) -> Option<String> {
         .map(|m| m.as_str().to_string())
 }
 
-fn read_zip_entry_to_string(archive: &mut ZipArchive<File>, path: &str) -> Result<String> {
+pub(crate) fn read_zip_entry_to_string(archive: &mut ZipArchive<File>, path: &str) -> Result<String> {
     let mut entry = archive
         .by_name(path)
         .map_err(|e| ScienceError::Parse(format!("missing EPUB entry {path}: {e}")))?;
@@ -1702,6 +2081,36 @@ mod tests {
             .create_async()
             .await;
 
+        let google_books_mock = server
+            .mock("GET", "/volumes")
+            .match_query(Matcher::UrlEncoded(
+                "q".to_string(),
+                "isbn:9780306406157".to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "totalItems": 1,
+                    "items": [{
+                        "id": "gb-volume",
+                        "volumeInfo": {
+                            "title": "Google Books Title",
+                            "authors": ["Ada Lovelace"],
+                            "publishedDate": "2020-03-01",
+                            "pageCount": 321,
+                            "imageLinks": {
+                                "thumbnail": "http://books.google.com/books/content?id=gb-volume&img=1&zoom=1&edge=curl"
+                            }
+                        }
+                    }]
+                })
+                .to_string(),
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
         let s2_mock = server
             .mock("GET", "/graph/v1/paper/DOI:10.1000%2Ftest")
             .match_query(Matcher::Any)
@@ -1822,23 +2231,47 @@ mod tests {
             .create_async()
             .await;
 
-        let pipeline = EnrichmentPipeline::new(
-            Arc::new(CrossRefSource::new_for_tests(server.url())),
-            Arc::new(SemanticScholarSource::new_for_tests(format!(
-                "{}/graph/v1",
-                server.url()
-            ))),
-            Arc::new(OpenAlexSource::new_for_tests(server.url())),
-            Arc::new(UnpaywallSource::new_for_tests(
-                format!("{}/v2", server.url()),
-                "test@example.com".to_string(),
-            )),
-            Arc::new(OpenLibrarySource::new_for_tests(server.url())),
-            Arc::new(ArxivClient::new_for_tests(format!(
-                "{}/api/query",
-                server.url()
-            ))),
-        );
+        let dblp_mock = server
+            .mock("GET", "/rec/conf/nips/VaswaniSPUJGKP17.xml")
+            .with_status(200)
+            .with_header("content-type", "application/xml")
+            .with_body(
+                r#"<dblp><inproceedings key="conf/nips/VaswaniSPUJGKP17">
+<author>Ashish Vaswani</author><title>Attention is All you Need.</title>
+<pages>5998-6008</pages><year>2017</year><booktitle>NIPS</booktitle>
+<crossref>conf/nips/2017</crossref></inproceedings></dblp>"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let dblp_proceedings_mock = server
+            .mock("GET", "/rec/conf/nips/2017.xml")
+            .with_status(200)
+            .with_header("content-type", "application/xml")
+            .with_body(
+                r#"<dblp><proceedings key="conf/nips/2017">
+<title>Advances in Neural Information Processing Systems 30</title>
+<booktitle>NIPS</booktitle><year>2017</year></proceedings></dblp>"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let esearch_mock = server
+            .mock("GET", "/eutils/esearch.fcgi")
+            .match_query(Matcher::UrlEncoded(
+                "term".to_string(),
+                "\"10.1000/test\"[doi]".to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({"esearchresult": {"idlist": []}}).to_string())
+            .expect(1)
+            .create_async()
+            .await;
+
+        let pipeline = EnrichmentPipeline::new_for_tests(&server.url());
 
         let mut card = BookCard::new("Seed");
         card.identifiers = Some(ScientificIdentifiers {
@@ -1853,10 +2286,14 @@ mod tests {
         crossref_mock.assert_async().await;
         arxiv_mock.assert_async().await;
         openlibrary_mock.assert_async().await;
+        google_books_mock.assert_async().await;
         s2_mock.assert_async().await;
         s2_references_mock.assert_async().await;
         s2_citations_mock.assert_async().await;
         unpaywall_mock.assert_async().await;
+        dblp_mock.assert_async().await;
+        dblp_proceedings_mock.assert_async().await;
+        esearch_mock.assert_async().await;
 
         assert_eq!(card.metadata.title, "CrossRef Title");
         assert_eq!(card.citation_graph.citation_count, 12000);
@@ -1882,6 +2319,23 @@ mod tests {
             Some("conf/nips/VaswaniSPUJGKP17")
         );
         assert_eq!(card.web.openlibrary_id.as_deref(), Some("OL123M"));
+        assert_eq!(card.metadata.pages, Some(321));
+        assert_eq!(
+            card.web.cover_url.as_deref(),
+            Some("https://books.google.com/books/content?id=gb-volume&img=1&zoom=1")
+        );
+        assert_eq!(
+            card.publication
+                .as_ref()
+                .and_then(|publication| publication.conference.as_deref()),
+            Some("Advances in Neural Information Processing Systems 30")
+        );
+        assert_eq!(
+            card.publication
+                .as_ref()
+                .and_then(|publication| publication.venue.as_deref()),
+            Some("NIPS")
+        );
         assert!(card.open_access.as_ref().is_some_and(|oa| oa.is_open));
         assert_eq!(
             card.open_access
@@ -1892,15 +2346,367 @@ mod tests {
         assert!(report.sources_used.contains(&"crossref".to_string()));
         assert!(report.sources_used.contains(&"arxiv_api".to_string()));
         assert!(report.sources_used.contains(&"openlibrary".to_string()));
+        assert!(report.sources_used.contains(&"google_books".to_string()));
         assert!(
             report
                 .sources_used
                 .contains(&"semantic_scholar".to_string())
         );
         assert!(report.sources_used.contains(&"unpaywall".to_string()));
+        assert!(report.sources_used.contains(&"dblp".to_string()));
         assert!(report.errors.is_empty());
     }
 
+    #[tokio::test]
+    async fn pipeline_reports_candidates_from_every_source() {
+        let mut server = Server::new_async().await;
+        let openlibrary_mock = server
+            .mock("GET", "/api/books")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "ISBN:9780306406157": {
+                        "title": "Open Library Title",
+                        "authors": [{"name":"Ada Lovelace"}]
+                    }
+                })
+                .to_string(),
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let google_books_mock = server
+            .mock("GET", "/volumes")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "totalItems": 1,
+                    "items": [{
+                        "id": "gb-volume",
+                        "volumeInfo": {"title": "Google Books Title", "authors": ["Ada Lovelace"]}
+                    }]
+                })
+                .to_string(),
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let pipeline = EnrichmentPipeline::new_for_tests(&server.url());
+        let mut card = BookCard::new("Seed");
+        card.identifiers = Some(ScientificIdentifiers {
+            isbn13: Some("9780306406157".to_string()),
+            ..Default::default()
+        });
+        let report = pipeline.enrich(&mut card).await;
+
+        openlibrary_mock.assert_async().await;
+        google_books_mock.assert_async().await;
+        assert_eq!(card.metadata.title, "Open Library Title");
+        for (source, title) in [
+            (MetadataSource::OpenLibrary, "Open Library Title"),
+            (MetadataSource::GoogleBooks, "Google Books Title"),
+        ] {
+            assert!(
+                report
+                    .candidates
+                    .iter()
+                    .any(|candidate| candidate.source == source && candidate.value == title)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn pipeline_enriches_pmc_card_from_pubmed() {
+        let mut server = Server::new_async().await;
+
+        let idconv_mock = server
+            .mock("GET", "/idconv")
+            .match_query(Matcher::UrlEncoded(
+                "ids".to_string(),
+                "PMC6789012".to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "status": "ok",
+                    "records": [{"pmcid": "PMC6789012", "pmid": "31452104"}]
+                })
+                .to_string(),
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let efetch_mock = server
+            .mock("GET", "/eutils/efetch.fcgi")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("db".to_string(), "pubmed".to_string()),
+                Matcher::UrlEncoded("id".to_string(), "31452104".to_string()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "text/xml")
+            .with_body(
+                r#"<?xml version="1.0" ?>
+<PubmedArticleSet>
+  <PubmedArticle>
+    <MedlineCitation>
+      <PMID Version="1">31452104</PMID>
+      <Article>
+        <Journal>
+          <JournalIssue><Volume>12</Volume><PubDate><Year>2019</Year></PubDate></JournalIssue>
+          <Title>Nature Communications</Title>
+        </Journal>
+        <ArticleTitle>PubMed Title</ArticleTitle>
+        <Abstract><AbstractText>A biomedical abstract.</AbstractText></Abstract>
+        <AuthorList><Author><LastName>Curie</LastName><ForeName>Marie</ForeName></Author></AuthorList>
+      </Article>
+    </MedlineCitation>
+    <PubmedData>
+      <ArticleIdList><ArticleId IdType="pmc">PMC6789012</ArticleId></ArticleIdList>
+    </PubmedData>
+  </PubmedArticle>
+</PubmedArticleSet>"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let s2_mock = server
+            .mock("GET", "/graph/v1/paper/PMID:31452104")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "paperId": "s2-pubmed",
+                    "externalIds": {"PubMed": "31452104"},
+                    "title": "Semantic Scholar Title",
+                    "year": 2019,
+                    "authors": [{"name":"Marie Curie"}],
+                    "citationCount": 0,
+                    "referenceCount": 0,
+                    "influentialCitationCount": 0
+                })
+                .to_string(),
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let oa_mock = server
+            .mock("GET", "/oa")
+            .match_query(Matcher::UrlEncoded(
+                "id".to_string(),
+                "PMC6789012".to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "text/xml")
+            .with_body(
+                r#"<OA><records returned-count="1" total-count="1">
+<record id="PMC6789012" license="CC BY">
+<link format="pdf" href="ftp://ftp.ncbi.nlm.nih.gov/pub/pmc/oa_pdf/paper.pdf" />
+</record></records></OA>"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let pipeline = EnrichmentPipeline::new_for_tests(&server.url());
+
+        let mut card = BookCard::new("Seed");
+        card.identifiers = Some(ScientificIdentifiers {
+            pmcid: Some("PMC6789012".to_string()),
+            ..Default::default()
+        });
+
+        let report = pipeline.enrich(&mut card).await;
+
+        idconv_mock.assert_async().await;
+        efetch_mock.assert_async().await;
+        s2_mock.assert_async().await;
+        oa_mock.assert_async().await;
+
+        assert_eq!(card.metadata.title, "PubMed Title");
+        assert_eq!(card.metadata.authors, vec!["Marie Curie"]);
+        assert_eq!(
+            card.metadata_sources
+                .get("metadata.title")
+                .map(String::as_str),
+            Some("pubmed")
+        );
+        assert_eq!(
+            card.identifiers
+                .as_ref()
+                .and_then(|ids| ids.pmid.as_deref()),
+            Some("31452104")
+        );
+        assert_eq!(
+            card.publication
+                .as_ref()
+                .and_then(|publication| publication.journal.as_deref()),
+            Some("Nature Communications")
+        );
+        let open_access = card.open_access.as_ref().expect("PMC copy recorded");
+        assert!(open_access.is_open);
+        assert_eq!(open_access.license.as_deref(), Some("CC BY"));
+        assert_eq!(
+            open_access.pdf_urls,
+            vec!["https://ftp.ncbi.nlm.nih.gov/pub/pmc/oa_pdf/paper.pdf"]
+        );
+        assert!(report.sources_used.contains(&"pubmed".to_string()));
+        assert!(report.sources_used.contains(&"pmc".to_string()));
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+    }
+
+    #[tokio::test]
+    async fn pipeline_finds_pubmed_record_by_doi() {
+        let mut server = Server::new_async().await;
+
+        let idconv_mock = server
+            .mock("GET", "/idconv")
+            .match_query(Matcher::UrlEncoded(
+                "ids".to_string(),
+                "PMC0000001".to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "status": "ok",
+                    "records": [{"pmcid": "PMC0000001", "status": "error", "errmsg": "invalid article id"}]
+                })
+                .to_string(),
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let esearch_mock = server
+            .mock("GET", "/eutils/esearch.fcgi")
+            .match_query(Matcher::UrlEncoded(
+                "term".to_string(),
+                "\"10.1000/pm\"[doi]".to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({"esearchresult": {"idlist": ["31452104"]}}).to_string())
+            .expect(1)
+            .create_async()
+            .await;
+
+        let efetch_mock = server
+            .mock("GET", "/eutils/efetch.fcgi")
+            .match_query(Matcher::UrlEncoded(
+                "id".to_string(),
+                "31452104".to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "text/xml")
+            .with_body(
+                r#"<PubmedArticleSet><PubmedArticle><MedlineCitation>
+<PMID Version="1">31452104</PMID>
+<Article><ArticleTitle>PubMed Title</ArticleTitle></Article>
+</MedlineCitation></PubmedArticle></PubmedArticleSet>"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let oa_mock = server
+            .mock("GET", "/oa")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "text/xml")
+            .with_body(r#"<OA><error code="idIsNotOpenAccess">not Open Access</error></OA>"#)
+            .create_async()
+            .await;
+
+        let pipeline = EnrichmentPipeline::new_for_tests(&server.url());
+
+        let mut card = BookCard::new("Seed");
+        card.identifiers = Some(ScientificIdentifiers {
+            doi: Some("10.1000/pm".to_string()),
+            pmcid: Some("PMC0000001".to_string()),
+            ..Default::default()
+        });
+
+        let report = pipeline.enrich(&mut card).await;
+
+        idconv_mock.assert_async().await;
+        esearch_mock.assert_async().await;
+        efetch_mock.assert_async().await;
+        oa_mock.assert_async().await;
+
+        assert_eq!(
+            card.identifiers
+                .as_ref()
+                .and_then(|ids| ids.pmid.as_deref()),
+            Some("31452104")
+        );
+        assert!(report.sources_used.contains(&"pubmed".to_string()));
+        assert!(
+            !report
+                .errors
+                .iter()
+                .any(|err| err.starts_with("pmc") || err.starts_with("pubmed")),
+            "{:?}",
+            report.errors
+        );
+    }
+
+    #[tokio::test]
+    async fn pipeline_searches_dblp_only_for_computer_science() {
+        let mut server = Server::new_async().await;
+        let crossref_mock = server
+            .mock("GET", "/works/10.1000%2Fbio")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "message": {
+                        "DOI": "10.1000/bio",
+                        "title": ["A Clinical Trial"],
+                        "type": "journal-article",
+                        "container-title": ["Nature Medicine"]
+                    }
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let search_mock = server
+            .mock("GET", "/search/publ/api")
+            .match_query(Matcher::UrlEncoded(
+                "q".to_string(),
+                "10.1145/3292500.3330701".to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({"result": {"hits": {"@total": "0"}}}).to_string())
+            .expect(1)
+            .create_async()
+            .await;
+
+        let pipeline = EnrichmentPipeline::new_for_tests(&server.url());
+        for doi in ["10.1000/bio", "10.1145/3292500.3330701"] {
+            let mut card = BookCard::new("Seed");
+            card.identifiers = Some(ScientificIdentifiers {
+                doi: Some(doi.to_string()),
+                ..Default::default()
+            });
+            pipeline.enrich(&mut card).await;
+        }
+
+        crossref_mock.assert_async().await;
+        search_mock.assert_async().await;
+    }
+
     #[test]
     fn parse_epub_opf_extracts_core_fields() {
         let opf = r#"
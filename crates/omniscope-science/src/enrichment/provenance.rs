@@ -0,0 +1,167 @@
+//! Where each field of a card came from, and the user's locks on fields.
+
+use omniscope_core::models::BookCard;
+
+use crate::enrichment::merge::MetadataSource;
+use crate::enrichment::review::{REVIEW_FIELDS, field_value};
+
+/// One field's recorded source and lock state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldProvenance {
+    /// Field path as stored in `metadata_sources`, e.g. `metadata.title`.
+    pub field: String,
+    /// Display label for the bibliographic fields a user can lock.
+    pub label: Option<&'static str>,
+    /// Source tag (`crossref`, `user_manual`, ...); `None` if unrecorded.
+    pub source: Option<String>,
+    pub locked: bool,
+}
+
+/// Every field with a recorded source or a lock. Lockable fields come
+/// first, in review order; identifiers and the rest follow by name.
+pub fn field_provenance(card: &BookCard) -> Vec<FieldProvenance> {
+    let mut fields = card
+        .metadata_sources
+        .keys()
+        .chain(&card.locked_fields)
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+    fields.sort_by_key(|field| (display_rank(field), *field));
+    fields.dedup();
+
+    fields
+        .into_iter()
+        .map(|field| FieldProvenance {
+            field: field.to_string(),
+            label: field_label(field),
+            source: card.metadata_sources.get(field).cloned(),
+            locked: card.is_field_locked(field),
+        })
+        .collect()
+}
+
+pub fn field_label(field: &str) -> Option<&'static str> {
+    REVIEW_FIELDS
+        .iter()
+        .find(|(name, _)| *name == field)
+        .map(|&(_, label)| label)
+}
+
+/// A lockable field by path (`metadata.year`), label (`year`) or last
+/// path segment (`pages` is `metadata.pages`; use `page range` or
+/// `publication.pages` for the article's pages).
+pub fn resolve_field(name: &str) -> Option<&'static str> {
+    let name = name.trim();
+    REVIEW_FIELDS
+        .iter()
+        .find(|(field, label)| {
+            field.eq_ignore_ascii_case(name)
+                || label.eq_ignore_ascii_case(name)
+                || field
+                    .rsplit('.')
+                    .next()
+                    .is_some_and(|tail| tail.eq_ignore_ascii_case(name))
+        })
+        .map(|&(field, _)| field)
+}
+
+fn display_rank(field: &str) -> usize {
+    REVIEW_FIELDS
+        .iter()
+        .position(|(name, _)| *name == field)
+        .unwrap_or(REVIEW_FIELDS.len())
+}
+
+/// Lock `field` at its current value. Returns `false` if the field is
+/// empty, since there is no value to protect, or is already locked.
+pub fn lock_current_value(card: &mut BookCard, field: &str) -> bool {
+    if field_value(card, field).is_none() || !card.set_field_locked(field, true) {
+        return false;
+    }
+    card.touch();
+    true
+}
+
+/// Unlock `field`. A value the user typed or picked would still outrank
+/// every source, so its `user_manual` provenance is dropped as well.
+pub fn unlock_field(card: &mut BookCard, field: &str) -> bool {
+    if !card.set_field_locked(field, false) {
+        return false;
+    }
+    let user_manual = MetadataSource::UserManual.as_tag();
+    if card.metadata_sources.get(field).map(String::as_str) == Some(user_manual) {
+        card.metadata_sources.remove(field);
+    }
+    card.touch();
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::enrichment::review::lock_field;
+    use crate::enrichment::{BookCardMergeExt, PartialMetadata};
+
+    use super::*;
+
+    #[test]
+    fn provenance_lists_lockable_fields_first() {
+        let mut card = BookCard::new("Paper");
+        card.merge_metadata(
+            PartialMetadata {
+                title: Some("Crossref Title".to_string()),
+                year: Some(2020),
+                semantic_scholar_id: Some("s2-1".to_string()),
+                ..Default::default()
+            },
+            MetadataSource::CrossRef,
+        );
+        assert!(lock_current_value(&mut card, "metadata.year"));
+        assert!(!lock_current_value(&mut card, "metadata.year"));
+        assert!(!lock_current_value(&mut card, "metadata.subtitle"));
+
+        let provenance = field_provenance(&card);
+        let fields = provenance
+            .iter()
+            .map(|entry| entry.field.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            vec![
+                "metadata.title",
+                "metadata.year",
+                "identifiers.semantic_scholar_id"
+            ]
+        );
+        assert_eq!(provenance[1].label, Some("Year"));
+        assert_eq!(provenance[1].source.as_deref(), Some("crossref"));
+        assert!(provenance[1].locked);
+        assert!(!provenance[0].locked);
+    }
+
+    #[test]
+    fn fields_resolve_by_path_label_or_tail() {
+        assert_eq!(resolve_field("metadata.title"), Some("metadata.title"));
+        assert_eq!(resolve_field("Year"), Some("metadata.year"));
+        assert_eq!(resolve_field("doi"), Some("identifiers.doi"));
+        assert_eq!(resolve_field("pages"), Some("metadata.pages"));
+        assert_eq!(resolve_field("page range"), Some("publication.pages"));
+        assert_eq!(resolve_field("cover"), None);
+    }
+
+    #[test]
+    fn unlocking_a_picked_value_lets_sources_replace_it() {
+        let mut card = BookCard::new("Seed");
+        assert!(lock_field(&mut card, "metadata.title", "Picked Title"));
+        assert!(unlock_field(&mut card, "metadata.title"));
+        assert!(!unlock_field(&mut card, "metadata.title"));
+
+        card.merge_metadata(
+            PartialMetadata {
+                title: Some("Crossref Title".to_string()),
+                ..Default::default()
+            },
+            MetadataSource::CrossRef,
+        );
+        assert_eq!(card.metadata.title, "Crossref Title");
+    }
+}
@@ -0,0 +1,296 @@
+//! Field-level review of enrichment results: what each source offered
+//! against what the card held, and locking the value the user picks.
+
+use omniscope_core::models::BookCard;
+
+use crate::enrichment::merge::{
+    FIELD_ABSTRACT, FIELD_AUTHORS, FIELD_CONFERENCE, FIELD_DOI, FIELD_ISSUE, FIELD_JOURNAL,
+    FIELD_LANGUAGE, FIELD_PAGES, FIELD_PUBLICATION_PAGES, FIELD_PUBLISHER, FIELD_SUBTITLE,
+    FIELD_TITLE, FIELD_VENUE, FIELD_VOLUME, FIELD_YEAR, MetadataSource, PartialMetadata,
+    field_source, set_field_source, source_priority,
+};
+use crate::identifiers::doi::Doi;
+
+/// Fields shown in a merge review, with their display labels.
+pub const REVIEW_FIELDS: &[(&str, &str)] = &[
+    (FIELD_TITLE, "Title"),
+    (FIELD_SUBTITLE, "Subtitle"),
+    (FIELD_AUTHORS, "Authors"),
+    (FIELD_YEAR, "Year"),
+    (FIELD_PUBLISHER, "Publisher"),
+    (FIELD_LANGUAGE, "Language"),
+    (FIELD_PAGES, "Pages"),
+    (FIELD_ABSTRACT, "Abstract"),
+    (FIELD_DOI, "DOI"),
+    (FIELD_JOURNAL, "Journal"),
+    (FIELD_CONFERENCE, "Conference"),
+    (FIELD_VENUE, "Venue"),
+    (FIELD_VOLUME, "Volume"),
+    (FIELD_ISSUE, "Issue"),
+    (FIELD_PUBLICATION_PAGES, "Page range"),
+];
+
+const AUTHOR_SEPARATOR: &str = "; ";
+
+/// A value one source offered for a reviewable field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldCandidate {
+    pub field: &'static str,
+    pub value: String,
+    pub source: MetadataSource,
+}
+
+/// A field where sources disagree with the value the card had.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldReview {
+    pub field: &'static str,
+    pub label: &'static str,
+    pub current: String,
+    /// `None` when the card does not record where the value came from.
+    pub current_source: Option<MetadataSource>,
+    /// Distinct values other than `current`, highest-priority source first.
+    pub candidates: Vec<FieldCandidate>,
+}
+
+/// The reviewable values in `partial`, as `source` offered them.
+pub fn candidates_from_partial(
+    partial: &PartialMetadata,
+    source: MetadataSource,
+) -> Vec<FieldCandidate> {
+    let authors = (!partial.authors.is_empty()).then(|| partial.authors.join(AUTHOR_SEPARATOR));
+    let values = [
+        (FIELD_TITLE, partial.title.clone()),
+        (FIELD_SUBTITLE, partial.subtitle.clone()),
+        (FIELD_AUTHORS, authors),
+        (FIELD_YEAR, partial.year.map(|year| year.to_string())),
+        (FIELD_PUBLISHER, partial.publisher.clone()),
+        (FIELD_LANGUAGE, partial.language.clone()),
+        (FIELD_PAGES, partial.pages.map(|pages| pages.to_string())),
+        (FIELD_ABSTRACT, partial.abstract_text.clone()),
+        (
+            FIELD_DOI,
+            partial.doi.as_ref().map(|doi| doi.normalized.clone()),
+        ),
+        (FIELD_JOURNAL, partial.journal.clone()),
+        (FIELD_CONFERENCE, partial.conference.clone()),
+        (FIELD_VENUE, partial.venue.clone()),
+        (FIELD_VOLUME, partial.volume.clone()),
+        (FIELD_ISSUE, partial.issue.clone()),
+        (FIELD_PUBLICATION_PAGES, partial.publication_pages.clone()),
+    ];
+
+    values
+        .into_iter()
+        .filter_map(|(field, value)| {
+            let value = clean(value?)?;
+            Some(FieldCandidate {
+                field,
+                value,
+                source,
+            })
+        })
+        .collect()
+}
+
+/// The card's value for a reviewable field, in the form candidates use.
+pub fn field_value(card: &BookCard, field: &str) -> Option<String> {
+    let publication = card.publication.as_ref();
+    let value = match field {
+        FIELD_TITLE => Some(card.metadata.title.clone()),
+        FIELD_SUBTITLE => card.metadata.subtitle.clone(),
+        FIELD_AUTHORS => Some(card.metadata.authors.join(AUTHOR_SEPARATOR)),
+        FIELD_YEAR => card.metadata.year.map(|year| year.to_string()),
+        FIELD_PUBLISHER => card.metadata.publisher.clone(),
+        FIELD_LANGUAGE => card.metadata.language.clone(),
+        FIELD_PAGES => card.metadata.pages.map(|pages| pages.to_string()),
+        FIELD_ABSTRACT => card.ai.summary.clone(),
+        FIELD_DOI => card.identifiers.as_ref().and_then(|ids| ids.doi.clone()),
+        FIELD_JOURNAL => publication.and_then(|p| p.journal.clone()),
+        FIELD_CONFERENCE => publication.and_then(|p| p.conference.clone()),
+        FIELD_VENUE => publication.and_then(|p| p.venue.clone()),
+        FIELD_VOLUME => publication.and_then(|p| p.volume.clone()),
+        FIELD_ISSUE => publication.and_then(|p| p.issue.clone()),
+        FIELD_PUBLICATION_PAGES => publication.and_then(|p| p.pages.clone()),
+        _ => None,
+    };
+    value.and_then(clean)
+}
+
+/// Fields of `card` that some candidate disagrees with. Empty fields are
+/// left to the automatic merge, and fields the user already chose are
+/// not asked about again.
+pub fn review_fields(card: &BookCard, candidates: &[FieldCandidate]) -> Vec<FieldReview> {
+    REVIEW_FIELDS
+        .iter()
+        .filter_map(|&(field, label)| {
+            let current = field_value(card, field)?;
+            if card.is_field_locked(field) {
+                return None;
+            }
+            let current_source = card
+                .metadata_sources
+                .contains_key(field)
+                .then(|| field_source(card, field));
+            if current_source == Some(MetadataSource::UserManual) {
+                return None;
+            }
+
+            let mut offered = candidates
+                .iter()
+                .filter(|candidate| candidate.field == field && candidate.value != current)
+                .cloned()
+                .collect::<Vec<_>>();
+            offered.sort_by_key(|candidate| std::cmp::Reverse(source_priority(candidate.source)));
+            let mut seen = Vec::new();
+            offered.retain(|candidate| {
+                let fresh = !seen.contains(&candidate.value);
+                seen.push(candidate.value.clone());
+                fresh
+            });
+            if offered.is_empty() {
+                return None;
+            }
+
+            Some(FieldReview {
+                field,
+                label,
+                current,
+                current_source,
+                candidates: offered,
+            })
+        })
+        .collect()
+}
+
+/// Set `field` to `value`, record it as the user's choice and lock it, so
+/// later enrichments leave it alone. Returns `false` for fields a review does
+/// not cover and values that do not parse (a non-numeric year, say).
+pub fn lock_field(card: &mut BookCard, field: &str, value: &str) -> bool {
+    let Some(value) = clean(value.to_string()) else {
+        return false;
+    };
+    let Some(&(field, _)) = REVIEW_FIELDS.iter().find(|(name, _)| *name == field) else {
+        return false;
+    };
+
+    match field {
+        FIELD_TITLE => card.metadata.title = value,
+        FIELD_SUBTITLE => card.metadata.subtitle = Some(value),
+        FIELD_AUTHORS => {
+            card.metadata.authors = value
+                .split(AUTHOR_SEPARATOR.trim())
+                .map(str::trim)
+                .filter(|author| !author.is_empty())
+                .map(ToOwned::to_owned)
+                .collect();
+        }
+        FIELD_YEAR => match value.parse() {
+            Ok(year) => card.metadata.year = Some(year),
+            Err(_) => return false,
+        },
+        FIELD_PUBLISHER => card.metadata.publisher = Some(value),
+        FIELD_LANGUAGE => card.metadata.language = Some(value),
+        FIELD_PAGES => match value.parse() {
+            Ok(pages) => card.metadata.pages = Some(pages),
+            Err(_) => return false,
+        },
+        FIELD_ABSTRACT => card.ai.summary = Some(value),
+        FIELD_DOI => {
+            let Ok(doi) = Doi::parse(&value) else {
+                return false;
+            };
+            card.identifiers.get_or_insert_with(Default::default).doi = Some(doi.normalized);
+        }
+        _ => {
+            let publication = card.publication.get_or_insert_with(Default::default);
+            let slot = match field {
+                FIELD_JOURNAL => &mut publication.journal,
+                FIELD_CONFERENCE => &mut publication.conference,
+                FIELD_VENUE => &mut publication.venue,
+                FIELD_VOLUME => &mut publication.volume,
+                FIELD_ISSUE => &mut publication.issue,
+                _ => &mut publication.pages,
+            };
+            *slot = Some(value);
+        }
+    }
+
+    set_field_source(card, field, MetadataSource::UserManual);
+    card.set_field_locked(field, true);
+    card.touch();
+    true
+}
+
+fn clean(value: String) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::enrichment::BookCardMergeExt;
+
+    use super::*;
+
+    fn offer(title: &str, year: i32) -> PartialMetadata {
+        PartialMetadata {
+            title: Some(title.to_string()),
+            year: Some(year),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn review_lists_disagreeing_sources_by_priority() {
+        let mut card = BookCard::new("My typed title");
+        card.metadata.year = Some(2017);
+
+        let mut candidates = candidates_from_partial(
+            &offer("Crawled Title", 2017),
+            MetadataSource::SemanticScholar,
+        );
+        candidates.extend(candidates_from_partial(
+            &offer("Crawled Title", 2018),
+            MetadataSource::CrossRef,
+        ));
+
+        let reviews = review_fields(&card, &candidates);
+        assert_eq!(reviews.len(), 2);
+        assert_eq!(reviews[0].label, "Title");
+        assert_eq!(reviews[0].current, "My typed title");
+        assert_eq!(reviews[0].current_source, None);
+        assert_eq!(reviews[0].candidates.len(), 1);
+        assert_eq!(reviews[0].candidates[0].source, MetadataSource::CrossRef);
+        assert_eq!(reviews[1].label, "Year");
+        assert_eq!(reviews[1].candidates[0].value, "2018");
+    }
+
+    #[test]
+    fn locked_field_survives_enrichment_and_later_reviews() {
+        let mut card = BookCard::new("Seed");
+        card.metadata.authors = vec!["Crawled Author".to_string()];
+
+        assert!(lock_field(
+            &mut card,
+            FIELD_AUTHORS,
+            "Ada Lovelace; Charles Babbage"
+        ));
+        assert!(lock_field(&mut card, FIELD_TITLE, "Chosen Title"));
+        assert!(!lock_field(&mut card, FIELD_YEAR, "soon"));
+
+        let incoming = PartialMetadata {
+            title: Some("CrossRef Title".to_string()),
+            authors: vec!["Someone Else".to_string()],
+            ..Default::default()
+        };
+        let candidates = candidates_from_partial(&incoming, MetadataSource::CrossRef);
+        card.merge_metadata(incoming, MetadataSource::CrossRef);
+
+        assert_eq!(card.metadata.title, "Chosen Title");
+        assert_eq!(
+            card.metadata.authors,
+            vec!["Ada Lovelace", "Charles Babbage"]
+        );
+        assert!(review_fields(&card, &candidates).is_empty());
+    }
+}
@@ -34,6 +34,16 @@ pub enum ScienceError {
 
     #[error("cache error: {0}")]
     Cache(String),
+
+    #[error("I/O error: {0}")]
+    Io(String),
 }
 
 pub type Result<T> = std::result::Result<T, ScienceError>;
+
+impl ScienceError {
+    /// A network or rate-limit failure, where asking again later may work.
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Self::Http(_) | Self::RateLimit(_, _))
+    }
+}
@@ -0,0 +1,256 @@
+//! Bulk export of a filtered set of cards (`omniscope export`).
+
+use std::collections::HashSet;
+
+use omniscope_core::FuzzySearcher;
+use omniscope_core::models::{BookCard, BookSummaryView};
+use omniscope_core::search_dsl::{SearchFilter, SearchQuery};
+use uuid::Uuid;
+
+use crate::config::ExportConfig;
+use crate::error::{Result, ScienceError};
+use crate::formats::bibtex::{generate_bibliography, unique_cite_keys};
+use crate::formats::csl::{BUNDLED_STYLES, CslProcessor, split_name};
+use crate::formats::{csl_json, ris};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExportFormat {
+    BibTeX,
+    Ris,
+    CslJson,
+    /// A formatted bibliography in a CSL style: a bundled name, a style
+    /// installed in the styles directory, or a path to a `.csl` file.
+    Citation(String),
+}
+
+impl ExportFormat {
+    /// Accepts `bibtex`, `ris`, `csl-json`, `apa`, `ieee`, `gost`, or any
+    /// bundled style name.
+    pub fn parse(name: &str) -> Result<Self> {
+        let name = name.trim().to_ascii_lowercase();
+        match name.as_str() {
+            "bibtex" | "bib" => Ok(Self::BibTeX),
+            "ris" => Ok(Self::Ris),
+            "csl-json" | "csljson" | "csl_json" => Ok(Self::CslJson),
+            "gost" => Ok(Self::Citation("gost-r-7-0-5-2008".to_string())),
+            style if BUNDLED_STYLES.contains(&style) => Ok(Self::Citation(style.to_string())),
+            _ => Err(ScienceError::Parse(format!(
+                "unknown export format `{name}` (expected bibtex, ris, csl-json, apa, ieee or gost)"
+            ))),
+        }
+    }
+
+    /// Like [`ExportFormat::parse`], but also accepts any `.csl` style the
+    /// configured styles directory provides.
+    pub fn resolve(name: &str, config: &ExportConfig) -> Result<Self> {
+        Self::parse(name).or_else(|err| {
+            if CslProcessor::from_config(config).has_style(name) {
+                Ok(Self::Citation(name.trim().to_string()))
+            } else {
+                Err(err)
+            }
+        })
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::BibTeX => "bib",
+            Self::Ris => "ris",
+            Self::CslJson => "json",
+            Self::Citation(_) => "txt",
+        }
+    }
+}
+
+/// Whether `card` passes every DSL filter. Unlike [`SearchQuery::matches`],
+/// which only sees a summary, this also checks `lib:`, `has:summary` and
+/// `src:`.
+pub fn matches_card(query: &SearchQuery, card: &BookCard) -> bool {
+    let summary = BookSummaryView::from(card);
+    query
+        .filters
+        .iter()
+        .all(|filter| filter_matches_card(filter, card, &summary))
+}
+
+/// Cards matching `query`, ordered by first author's family name, year and
+/// title. Free-text terms are matched the same way as the TUI search bar.
+pub fn select_cards(cards: Vec<BookCard>, query: &SearchQuery) -> Vec<BookCard> {
+    let mut selected: Vec<BookCard> = cards
+        .into_iter()
+        .filter(|card| matches_card(query, card))
+        .collect();
+
+    let fuzzy = query.fuzzy_text();
+    if !fuzzy.is_empty() {
+        let summaries: Vec<BookSummaryView> = selected.iter().map(BookSummaryView::from).collect();
+        let hits: HashSet<Uuid> = FuzzySearcher::new()
+            .search(&fuzzy, &summaries)
+            .into_iter()
+            .map(|result| result.book.id)
+            .collect();
+        selected.retain(|card| hits.contains(&card.id));
+    }
+
+    selected.sort_by_cached_key(|card| {
+        (
+            card.metadata
+                .authors
+                .first()
+                .and_then(|author| split_name(author))
+                .map(|name| name.family.to_lowercase())
+                .unwrap_or_default(),
+            card.metadata.year,
+            card.metadata.title.to_lowercase(),
+        )
+    });
+    selected
+}
+
+/// Render `cards` as one document. Cite keys follow `config` and are unique
+/// across the set.
+pub fn render(cards: &[&BookCard], format: &ExportFormat, config: &ExportConfig) -> Result<String> {
+    match format {
+        ExportFormat::BibTeX => Ok(generate_bibliography(cards, &config.bibtex_options())),
+        ExportFormat::Ris => {
+            let keys = unique_cite_keys(cards, &config.cite_key_scheme());
+            Ok(ris::generate_ris_set(cards, &keys))
+        }
+        ExportFormat::CslJson => {
+            let keys = unique_cite_keys(cards, &config.cite_key_scheme());
+            let items: Vec<_> = cards
+                .iter()
+                .zip(&keys)
+                .map(|(card, key)| csl_json::from_book_card(card, key))
+                .collect();
+            let mut out = csl_json::to_string(&items)?;
+            out.push('\n');
+            Ok(out)
+        }
+        ExportFormat::Citation(style) => {
+            let processor = CslProcessor::from_config(config);
+            let entries = processor.format_bibliography(cards, style)?;
+            // A numeric `.csl` style prints its own labels.
+            let numbered = style == "ieee" && processor.is_builtin(style);
+            let mut out = String::new();
+            for (index, entry) in entries.iter().enumerate() {
+                if numbered {
+                    out.push_str(&format!("[{}] ", index + 1));
+                }
+                out.push_str(entry);
+                out.push('\n');
+            }
+            Ok(out)
+        }
+    }
+}
+
+fn filter_matches_card(filter: &SearchFilter, card: &BookCard, summary: &BookSummaryView) -> bool {
+    match filter {
+        SearchFilter::Library(name) => card
+            .organization
+            .libraries
+            .iter()
+            .any(|library| library.eq_ignore_ascii_case(name)),
+        SearchFilter::HasSummary => card.ai.summary.is_some(),
+        SearchFilter::Source(source) => card
+            .metadata_sources
+            .values()
+            .any(|tag| tag.eq_ignore_ascii_case(source)),
+        SearchFilter::Not(inner) => !filter_matches_card(inner, card, summary),
+        other => SearchQuery {
+            fuzzy_terms: Vec::new(),
+            filters: vec![other.clone()],
+        }
+        .matches(summary),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(title: &str, author: &str, year: i32, tags: &[&str]) -> BookCard {
+        let mut card = BookCard::new(title);
+        card.metadata.authors = vec![author.to_string()];
+        card.metadata.year = Some(year);
+        card.organization.tags = tags.iter().map(|tag| tag.to_string()).collect();
+        card
+    }
+
+    #[test]
+    fn select_filters_by_tag_and_text_in_stable_order() {
+        let cards = vec![
+            card(
+                "Paxos Made Simple",
+                "Leslie Lamport",
+                2001,
+                &["distributed"],
+            ),
+            card("Raft Consensus", "Diego Ongaro", 2014, &["distributed"]),
+            card("The Rust Book", "Steve Klabnik", 2018, &["rust"]),
+            card("Time, Clocks", "Leslie Lamport", 1978, &["distributed"]),
+        ];
+
+        let selected = select_cards(cards.clone(), &SearchQuery::parse("#distributed"));
+        let titles: Vec<_> = selected.iter().map(|c| c.metadata.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec!["Time, Clocks", "Paxos Made Simple", "Raft Consensus"]
+        );
+
+        let selected = select_cards(cards, &SearchQuery::parse("#distributed raft"));
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].metadata.title, "Raft Consensus");
+    }
+
+    #[test]
+    fn source_filter_reads_card_provenance() {
+        let mut from_crossref = card("Raft Consensus", "Diego Ongaro", 2014, &[]);
+        from_crossref
+            .metadata_sources
+            .insert("metadata.title".to_string(), "crossref".to_string());
+        let typed = card("The Rust Book", "Steve Klabnik", 2018, &[]);
+
+        let query = SearchQuery::parse("src:crossref");
+        assert!(matches_card(&query, &from_crossref));
+        assert!(!matches_card(&query, &typed));
+        assert!(matches_card(
+            &SearchQuery::parse("NOT src:crossref"),
+            &typed
+        ));
+    }
+
+    #[test]
+    fn ris_and_csl_json_share_unique_keys() {
+        let first = card("Paxos Made Simple", "Leslie Lamport", 2001, &[]);
+        let second = card("Paxos Made Live", "Leslie Lamport", 2001, &[]);
+        let config = ExportConfig {
+            cite_key_scheme: "author_year".to_string(),
+            ..Default::default()
+        };
+        let cards = [&first, &second];
+
+        let ris = render(&cards, &ExportFormat::Ris, &config).unwrap();
+        assert!(ris.contains("ID  - Lamport2001\n"));
+        assert!(ris.contains("ID  - Lamport2001a\n"));
+
+        let json = render(&cards, &ExportFormat::CslJson, &config).unwrap();
+        let items: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(items[1]["id"], "Lamport2001a");
+    }
+
+    #[test]
+    fn parse_format_names() {
+        assert_eq!(ExportFormat::parse("BibTeX").unwrap(), ExportFormat::BibTeX);
+        assert_eq!(
+            ExportFormat::parse("csl-json").unwrap(),
+            ExportFormat::CslJson
+        );
+        assert_eq!(
+            ExportFormat::parse("gost").unwrap(),
+            ExportFormat::Citation("gost-r-7-0-5-2008".to_string())
+        );
+        assert!(ExportFormat::parse("docx").is_err());
+    }
+}
@@ -1,9 +1,10 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 
-use omniscope_core::models::{BookCard, DocumentType};
+use omniscope_core::models::{BookCard, DocumentType, WebSource};
 
+use crate::enrichment::{BookCardMergeExt, MetadataSource, PartialMetadata};
 use crate::error::{Result, ScienceError};
-use crate::identifiers::{arxiv::ArxivId, doi::Doi};
+use crate::identifiers::{arxiv::ArxivId, doi::Doi, isbn::Isbn};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BibEntry {
@@ -32,6 +33,9 @@ pub enum CiteKeyScheme {
     AuthorYear,
     AuthorYearTitle,
     DoiBased,
+    /// DBLP's own key, `DBLP:conf/nips/VaswaniSPUJGKP17`, for cards DBLP
+    /// knows; author-year-title for the rest.
+    Dblp,
     Custom(String),
 }
 
@@ -40,8 +44,67 @@ pub fn generate_bibtex(card: &BookCard, opts: &BibTeXOptions) -> String {
     render_bib_entry(&entry, opts.utf8)
 }
 
+/// Render a set of cards as one `.bib` file, with cite keys unique across it.
+pub fn generate_bibliography(cards: &[&BookCard], opts: &BibTeXOptions) -> String {
+    let keys = unique_cite_keys(cards, &opts.cite_key_scheme);
+    render_bibliography(cards, &keys, opts)
+}
+
+/// Render `cards` with the given cite keys, one per card.
+pub fn render_bibliography(cards: &[&BookCard], keys: &[String], opts: &BibTeXOptions) -> String {
+    cards
+        .iter()
+        .zip(keys)
+        .map(|(card, key)| {
+            let mut entry = from_book_card(card, opts);
+            entry.cite_key = key.clone();
+            render_bib_entry(&entry, opts.utf8)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Cite keys for `cards` in order: the key stored on the card if it has one,
+/// otherwise one generated from `scheme`. Colliding keys get a letter suffix
+/// (`Knuth1984`, `Knuth1984a`, `Knuth1984b`, …).
+pub fn unique_cite_keys(cards: &[&BookCard], scheme: &CiteKeyScheme) -> Vec<String> {
+    let mut used = HashSet::new();
+    cards
+        .iter()
+        .map(|card| {
+            let base = stored_cite_key(card)
+                .map(str::to_string)
+                .unwrap_or_else(|| generate_cite_key(card, scheme));
+            disambiguate_cite_key(&base, &mut used)
+        })
+        .collect()
+}
+
+/// The cite key pinned on the card by an earlier import or `bib-sync`.
+pub fn stored_cite_key(card: &BookCard) -> Option<&str> {
+    card.identifiers
+        .as_ref()
+        .and_then(|identifiers| identifiers.cite_key.as_deref())
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+}
+
+/// `base`, or `base` plus the first letter suffix not yet in `used`; the
+/// returned key is added to `used`.
+pub fn disambiguate_cite_key(base: &str, used: &mut HashSet<String>) -> String {
+    let mut key = base.to_string();
+    let mut n = 0;
+    while !used.insert(key.clone()) {
+        key = format!("{base}{}", suffix_letters(n));
+        n += 1;
+    }
+    key
+}
+
 pub fn from_book_card(card: &BookCard, opts: &BibTeXOptions) -> BibEntry {
-    let cite_key = generate_cite_key(card, &opts.cite_key_scheme);
+    let cite_key = stored_cite_key(card)
+        .map(str::to_string)
+        .unwrap_or_else(|| generate_cite_key(card, &opts.cite_key_scheme));
     let entry_type = document_type_to_bibtex(
         card.publication
             .as_ref()
@@ -163,17 +226,24 @@ pub fn generate_cite_key(card: &BookCard, scheme: &CiteKeyScheme) -> String {
         CiteKeyScheme::DoiBased => {
             doi_based_key(card).unwrap_or_else(|| format!("{first_author}{year}{title_word}"))
         }
+        CiteKeyScheme::Dblp => dblp_key(card)
+            .map(|key| format!("DBLP:{key}"))
+            .unwrap_or_else(|| format!("{first_author}{year}{title_word}")),
         CiteKeyScheme::Custom(template) => template
             .replace("{first_author}", &first_author)
             .replace("{year}", &year)
             .replace("{title_word}", &title_word)
-            .replace("{doi}", &doi_based_key(card).unwrap_or_default()),
+            .replace("{doi}", &doi_based_key(card).unwrap_or_default())
+            .replace("{dblp_key}", dblp_key(card).unwrap_or_default()),
     };
 
     if key.is_empty() {
         key = "untitled0".to_string();
     }
-    sanitize_cite_key(key)
+    match scheme {
+        CiteKeyScheme::Dblp => sanitize_dblp_cite_key(key),
+        _ => sanitize_cite_key(key),
+    }
 }
 
 pub fn parse_bibtex(content: &str) -> Result<Vec<BibEntry>> {
@@ -181,6 +251,129 @@ pub fn parse_bibtex(content: &str) -> Result<Vec<BibEntry>> {
     parser.parse_entries()
 }
 
+/// Card for a parsed entry (Zotero, JabRef, Mendeley exports). Attached
+/// files are left to the caller, see [`file_paths`].
+pub fn to_book_card(entry: &BibEntry) -> BookCard {
+    let field = |name: &str| {
+        entry
+            .fields
+            .get(name)
+            .map(|value| clean_latex(value))
+            .filter(|value| !value.is_empty())
+    };
+
+    let title = field("title").unwrap_or_else(|| entry.cite_key.clone());
+    let doc_type = bibtex_to_document_type(entry);
+    let venue = field("journal").or_else(|| field("journaltitle"));
+    let booktitle = field("booktitle");
+    let (journal, conference, venue) = match doc_type {
+        DocumentType::ConferencePaper => (None, booktitle.or(venue), None),
+        DocumentType::Chapter => (venue, None, booktitle),
+        _ => (venue, None, None),
+    };
+
+    let arxiv_id = field("arxivid")
+        .or_else(|| {
+            field("eprint").filter(|_| {
+                field("archiveprefix")
+                    .or_else(|| field("eprinttype"))
+                    .is_some_and(|prefix| prefix.eq_ignore_ascii_case("arxiv"))
+            })
+        })
+        .and_then(|raw| ArxivId::parse(&raw).ok());
+
+    let mut card = BookCard::new(title.clone());
+    card.merge_metadata(
+        PartialMetadata {
+            title: Some(title),
+            subtitle: field("subtitle"),
+            authors: entry
+                .fields
+                .get("author")
+                .map(|raw| split_bibtex_authors(raw))
+                .unwrap_or_default(),
+            year: field("year")
+                .or_else(|| field("date"))
+                .and_then(|raw| parse_year(&raw)),
+            publisher: field("publisher")
+                .or_else(|| field("institution"))
+                .or_else(|| field("school"))
+                .or_else(|| field("organization")),
+            language: field("language").or_else(|| field("langid")),
+            pages: field("pagetotal").and_then(|raw| leading_number(&raw)),
+            edition: field("edition").and_then(|raw| leading_number(&raw)),
+            series: field("series"),
+            tags: field("keywords")
+                .map(|raw| split_keywords(&raw))
+                .unwrap_or_default(),
+            abstract_text: field("abstract"),
+            doi: field("doi").and_then(|raw| Doi::parse(&raw).ok()),
+            arxiv_id,
+            isbn: field("isbn")
+                .map(|raw| parse_isbn_list(&raw))
+                .unwrap_or_default(),
+            pmid: field("pmid"),
+            pmcid: field("pmcid"),
+            doc_type: Some(doc_type),
+            journal,
+            conference,
+            venue,
+            volume: field("volume"),
+            issue: field("number").or_else(|| field("issue")),
+            publication_pages: field("pages").map(|pages| pages.replace("--", "-")),
+            ..Default::default()
+        },
+        MetadataSource::BibImport,
+    );
+
+    let url = entry
+        .fields
+        .get("url")
+        .map(|raw| unescape_file_path(raw.trim()));
+    if let Some(url) = url.filter(|url| !url.is_empty()) {
+        card.web.sources.push(WebSource {
+            name: "bibtex".to_string(),
+            url,
+        });
+    }
+    let cite_key = entry.cite_key.trim();
+    if !cite_key.is_empty() {
+        card.identifiers
+            .get_or_insert_with(Default::default)
+            .cite_key = Some(cite_key.to_string());
+    }
+    card
+}
+
+/// Paths listed in the entry's `file` field.
+///
+/// Understands plain paths (Better BibTeX) as well as the
+/// `description:path:type;…` lists written by JabRef, Zotero and Mendeley,
+/// including JabRef's `\:` escapes.
+pub fn file_paths(entry: &BibEntry) -> Vec<String> {
+    let Some(raw) = entry.fields.get("file") else {
+        return Vec::new();
+    };
+
+    split_unescaped(raw, ';')
+        .into_iter()
+        .filter(|item| !item.trim().is_empty())
+        .filter_map(|item| {
+            let parts = split_unescaped(&item, ':');
+            let path = match parts.as_slice() {
+                [] => return None,
+                [path] => path.clone(),
+                // `C:\dir\file.pdf` without escapes, or `description:path`.
+                [drive, rest] if drive.len() == 1 => format!("{drive}:{rest}"),
+                [_, path] => path.clone(),
+                [_, middle @ .., _] => middle.join(":"),
+            };
+            let path = unescape_file_path(path.trim());
+            (!path.is_empty()).then_some(path)
+        })
+        .collect()
+}
+
 fn document_type_to_bibtex(doc_type: DocumentType) -> &'static str {
     match doc_type {
         DocumentType::Article | DocumentType::MagazineArticle => "article",
@@ -412,6 +605,14 @@ fn doi_based_key(card: &BookCard) -> Option<String> {
     }
 }
 
+fn dblp_key(card: &BookCard) -> Option<&str> {
+    card.identifiers
+        .as_ref()
+        .and_then(|identifiers| identifiers.dblp_key.as_deref())
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+}
+
 fn sanitize_cite_key(input: String) -> String {
     input
         .chars()
@@ -419,6 +620,29 @@ fn sanitize_cite_key(input: String) -> String {
         .collect::<String>()
 }
 
+/// Like [`sanitize_cite_key`], but keeps the `:` and `/` of DBLP keys,
+/// which BibTeX accepts.
+fn sanitize_dblp_cite_key(input: String) -> String {
+    input
+        .chars()
+        .filter(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-' | '.' | ':' | '/'))
+        .collect::<String>()
+}
+
+/// 0 → `a`, 25 → `z`, 26 → `aa`, …
+pub(crate) fn suffix_letters(mut n: usize) -> String {
+    let mut letters = Vec::new();
+    loop {
+        letters.push(b'a' + (n % 26) as u8);
+        if n < 26 {
+            break;
+        }
+        n = n / 26 - 1;
+    }
+    letters.reverse();
+    String::from_utf8(letters).unwrap_or_default()
+}
+
 fn capitalize_word(value: &str) -> String {
     let mut chars = value.chars();
     let Some(first) = chars.next() else {
@@ -461,6 +685,272 @@ fn escape_bibtex_value(value: &str, utf8: bool) -> String {
     escaped.trim().to_string()
 }
 
+fn bibtex_to_document_type(entry: &BibEntry) -> DocumentType {
+    match entry.entry_type.as_str() {
+        "article" => DocumentType::Article,
+        "book" | "mvbook" | "booklet" => DocumentType::Book,
+        "inproceedings" | "conference" | "proceedings" => DocumentType::ConferencePaper,
+        "incollection" | "inbook" | "bookinbook" => DocumentType::Chapter,
+        "phdthesis" | "mastersthesis" | "thesis" => DocumentType::Thesis,
+        "techreport" | "report" => DocumentType::Report,
+        "manual" => DocumentType::Standard,
+        "online" | "electronic" | "www" => DocumentType::WebPage,
+        "dataset" => DocumentType::Dataset,
+        "software" => DocumentType::Software,
+        "patent" => DocumentType::Patent,
+        "unpublished" => DocumentType::Preprint,
+        _ if entry.fields.contains_key("eprint") || entry.fields.contains_key("arxivid") => {
+            DocumentType::Preprint
+        }
+        _ => DocumentType::Other,
+    }
+}
+
+/// `Vaswani, Ashish and {Google Brain}` → `["Ashish Vaswani", "Google Brain"]`.
+fn split_bibtex_authors(raw: &str) -> Vec<String> {
+    let raw = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    let raw = raw.as_str();
+    let mut names = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0usize;
+    let lower = raw.to_ascii_lowercase();
+    for (index, ch) in raw.char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            _ if depth == 0 && index >= start && lower[index..].starts_with(" and ") => {
+                names.push(&raw[start..index]);
+                start = index + " and ".len();
+            }
+            _ => {}
+        }
+    }
+    names.push(&raw[start..]);
+
+    names
+        .into_iter()
+        .map(|name| {
+            // A comma inside braces belongs to a corporate name.
+            let comma = top_level_comma(name);
+            let name = match comma {
+                Some(at) => {
+                    let (family, given) = (&name[..at], &name[at + 1..]);
+                    match given.split_once(',') {
+                        // `von Last, Jr, First`
+                        Some((suffix, first)) => format!("{first} {family} {suffix}"),
+                        None => format!("{given} {family}"),
+                    }
+                }
+                None => name.to_string(),
+            };
+            clean_latex(&name)
+        })
+        .filter(|name| !name.is_empty() && name != "others")
+        .collect()
+}
+
+fn top_level_comma(value: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (index, ch) in value.char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+pub(super) fn parse_year(raw: &str) -> Option<i32> {
+    let digits: String = raw
+        .trim()
+        .chars()
+        .take_while(|ch| ch.is_ascii_digit())
+        .collect();
+    if digits.len() == 4 {
+        digits.parse().ok()
+    } else {
+        None
+    }
+}
+
+pub(super) fn leading_number(raw: &str) -> Option<u32> {
+    let digits: String = raw
+        .trim()
+        .chars()
+        .take_while(|ch| ch.is_ascii_digit())
+        .collect();
+    digits.parse().ok()
+}
+
+pub(super) fn split_keywords(raw: &str) -> Vec<String> {
+    raw.split([',', ';'])
+        .map(str::trim)
+        .filter(|keyword| !keyword.is_empty())
+        .map(ToOwned::to_owned)
+        .collect()
+}
+
+pub(super) fn parse_isbn_list(raw: &str) -> Vec<Isbn> {
+    raw.split([',', ';'])
+        .flat_map(|part| match Isbn::parse(part.trim()) {
+            Ok(isbn) => vec![isbn],
+            Err(_) => part
+                .split_whitespace()
+                .filter_map(|word| Isbn::parse(word).ok())
+                .collect(),
+        })
+        .collect()
+}
+
+fn split_unescaped(value: &str, separator: char) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\\' {
+            current.push(ch);
+            if let Some(next) = chars.next() {
+                current.push(next);
+            }
+        } else if ch == separator {
+            parts.push(std::mem::take(&mut current));
+        } else {
+            current.push(ch);
+        }
+    }
+    parts.push(current);
+    parts
+}
+
+fn unescape_file_path(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    let mut chars = path.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match (ch, chars.peek()) {
+            ('\\', Some(&next)) if matches!(next, ':' | ';' | '\\' | '_' | '{' | '}') => {
+                out.push(next);
+                chars.next();
+            }
+            ('{' | '}', _) => {}
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// Strip braces and turn common LaTeX accents and escapes into Unicode.
+fn clean_latex(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '{' | '}' => {}
+            '~' => out.push(' '),
+            '\\' => {
+                let Some(&next) = chars.peek() else {
+                    break;
+                };
+                if !next.is_ascii_alphabetic() {
+                    chars.next();
+                    if ACCENT_MARKS.contains(next) {
+                        out.push(accented(next, &mut chars));
+                    } else if matches!(next, '\\' | ',' | ' ') {
+                        out.push(' ');
+                    } else {
+                        out.push(next);
+                    }
+                    continue;
+                }
+
+                let mut command = String::new();
+                while let Some(&letter) = chars.peek() {
+                    if !letter.is_ascii_alphabetic() {
+                        break;
+                    }
+                    command.push(letter);
+                    chars.next();
+                }
+                match command.as_str() {
+                    "c" | "v" | "u" | "H" | "k" | "r" => {
+                        let mark = command.chars().next().unwrap_or_default();
+                        out.push(accented(mark, &mut chars));
+                        continue;
+                    }
+                    "ss" => out.push('ß'),
+                    "o" => out.push('ø'),
+                    "O" => out.push('Ø'),
+                    "aa" => out.push('å'),
+                    "AA" => out.push('Å'),
+                    "ae" => out.push('æ'),
+                    "AE" => out.push('Æ'),
+                    "oe" => out.push('œ'),
+                    "OE" => out.push('Œ'),
+                    "l" => out.push('ł'),
+                    "L" => out.push('Ł'),
+                    "i" => out.push('i'),
+                    "textendash" => out.push('–'),
+                    "textemdash" => out.push('—'),
+                    // Formatting commands (\emph, \textit, …) keep their argument.
+                    _ => {}
+                }
+                // Control words swallow the space after them.
+                if chars.peek() == Some(&' ') {
+                    chars.next();
+                }
+            }
+            _ => out.push(ch),
+        }
+    }
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+const ACCENT_MARKS: &str = "\"'`^~=.";
+
+/// Base letter and its accented form for each LaTeX accent command.
+fn accent_table(mark: char) -> &'static str {
+    match mark {
+        '"' => "aäeëiïoöuüyÿAÄEËIÏOÖUÜ",
+        '\'' => "aáeéiíoóuúyýcćnńsśzźAÁEÉIÍOÓUÚYÝCĆNŃSŚZŹ",
+        '`' => "aàeèiìoòuùAÀEÈIÌOÒUÙ",
+        '^' => "aâeêiîoôuûAÂEÊIÎOÔUÛ",
+        '~' => "aãnñoõAÃNÑOÕ",
+        '=' => "aāeēiīoōuūAĀEĒIĪOŌUŪ",
+        '.' => "zżeėZŻEĖ",
+        'c' => "cçsşCÇSŞ",
+        'v' => "cčsšzžrřeěnňCČSŠZŽRŘEĚNŇ",
+        'u' => "aăgğAĂGĞ",
+        'H' => "oőuűOŐUŰ",
+        'k' => "aąeęAĄEĘ",
+        'r' => "aåuůAÅUŮ",
+        _ => "",
+    }
+}
+
+/// Read the accent's argument (`o`, `{o}`, `{\i}`, ` c`) and combine it.
+fn accented(mark: char, chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> char {
+    while chars.peek().is_some_and(|ch| *ch == ' ' || *ch == '{') {
+        chars.next();
+    }
+    if chars.peek() == Some(&'\\') {
+        chars.next();
+    }
+    let Some(base) = chars.next() else {
+        return mark;
+    };
+    if chars.peek() == Some(&'}') {
+        chars.next();
+    }
+
+    let table: Vec<char> = accent_table(mark).chars().collect();
+    table
+        .chunks(2)
+        .find(|pair| pair[0] == base)
+        .map(|pair| pair[1])
+        .unwrap_or(base)
+}
+
 struct BibParser<'a> {
     input: &'a str,
     bytes: &'a [u8],
@@ -493,6 +983,11 @@ impl<'a> BibParser<'a> {
             };
             self.pos += 1;
             let _ = open_delim;
+            if matches!(entry_type.as_str(), "comment" | "preamble" | "string") {
+                // JabRef/Zotero metadata blocks and macros carry no entry.
+                self.skip_block(close_delim)?;
+                continue;
+            }
             self.skip_ws();
 
             let cite_key = self.parse_cite_key(close_delim)?;
@@ -540,6 +1035,20 @@ impl<'a> BibParser<'a> {
         Ok(entries)
     }
 
+    fn skip_block(&mut self, close_delim: u8) -> Result<()> {
+        let mut depth = 0usize;
+        while let Some(byte) = self.peek_byte() {
+            self.pos += 1;
+            match byte {
+                b'{' => depth += 1,
+                b'}' if depth > 0 => depth -= 1,
+                _ if byte == close_delim && depth == 0 => return Ok(()),
+                _ => {}
+            }
+        }
+        Err(self.error("unterminated BibTeX block"))
+    }
+
     fn parse_cite_key(&mut self, close_delim: u8) -> Result<String> {
         let start = self.pos;
         while let Some(byte) = self.peek_byte() {
@@ -567,23 +1076,23 @@ impl<'a> BibParser<'a> {
     fn parse_braced_value(&mut self) -> Result<String> {
         self.expect_byte(b'{', "expected '{' to start value")?;
         let mut depth = 1usize;
-        let mut out = String::new();
+        let mut out = Vec::new();
 
         while let Some(byte) = self.peek_byte() {
             self.pos += 1;
             match byte {
                 b'{' => {
                     depth += 1;
-                    out.push('{');
+                    out.push(b'{');
                 }
                 b'}' => {
                     depth -= 1;
                     if depth == 0 {
-                        return Ok(out.trim().to_string());
+                        return Ok(String::from_utf8_lossy(&out).trim().to_string());
                     }
-                    out.push('}');
+                    out.push(b'}');
                 }
-                _ => out.push(byte as char),
+                _ => out.push(byte),
             }
         }
 
@@ -592,13 +1101,13 @@ impl<'a> BibParser<'a> {
 
     fn parse_quoted_value(&mut self) -> Result<String> {
         self.expect_byte(b'"', "expected '\"' to start value")?;
-        let mut out = String::new();
+        let mut out = Vec::new();
         let mut escaped = false;
 
         while let Some(byte) = self.peek_byte() {
             self.pos += 1;
             if escaped {
-                out.push(byte as char);
+                out.push(byte);
                 escaped = false;
                 continue;
             }
@@ -607,9 +1116,9 @@ impl<'a> BibParser<'a> {
                 continue;
             }
             if byte == b'"' {
-                return Ok(out.trim().to_string());
+                return Ok(String::from_utf8_lossy(&out).trim().to_string());
             }
-            out.push(byte as char);
+            out.push(byte);
         }
 
         Err(self.error("unterminated quoted value"))
@@ -754,4 +1263,109 @@ mod tests {
         let key = generate_cite_key(&card, &CiteKeyScheme::DoiBased);
         assert_eq!(key, "arXiv1706.03762");
     }
+
+    #[test]
+    fn generate_cite_key_dblp_uses_dblp_key() {
+        let mut card = attention_card();
+        assert_eq!(
+            generate_cite_key(&card, &CiteKeyScheme::Dblp),
+            generate_cite_key(&card, &CiteKeyScheme::AuthorYearTitle)
+        );
+
+        card.identifiers
+            .get_or_insert_with(Default::default)
+            .dblp_key = Some("conf/nips/VaswaniSPUJGKP17".to_string());
+        assert_eq!(
+            generate_cite_key(&card, &CiteKeyScheme::Dblp),
+            "DBLP:conf/nips/VaswaniSPUJGKP17"
+        );
+    }
+
+    #[test]
+    fn generate_cite_key_keeps_dblp_separators_only_for_dblp_scheme() {
+        let mut card = attention_card();
+        card.identifiers
+            .get_or_insert_with(Default::default)
+            .dblp_key = Some("conf/nips/VaswaniSPUJGKP17".to_string());
+        let scheme = CiteKeyScheme::Custom("{first_author}:{year}/{dblp_key}".to_string());
+        assert_eq!(
+            generate_cite_key(&card, &scheme),
+            format!(
+                "{}confnipsVaswaniSPUJGKP17",
+                generate_cite_key(&card, &CiteKeyScheme::AuthorYear)
+            )
+        );
+    }
+
+    #[test]
+    fn to_book_card_maps_zotero_entry() {
+        let text = r#"@Comment{jabref-meta: grouping:
+0 AllEntriesGroup:;}
+
+@inproceedings{muller2020,
+  title = {{Graph} Networks for {M\"u}ller's Problem},
+  author = {M{\"u}ller, J{\"o}rg and {Research Lab, Inc.} and Ng, Andrew Y. and others},
+  booktitle = {Proceedings of NeurIPS},
+  year = {2020},
+  pages = {12--19},
+  doi = {10.1000/xyz123},
+  keywords = {graphs, deep learning; optimisation},
+  url = {https://example.org/paper\_1},
+  file = {Full Text PDF:C\:\\Zotero\\storage\\muller.pdf:application/pdf;Snapshot:snap.html:text/html}
+}"#;
+        let entries = parse_bibtex(text).expect("valid BibTeX");
+        assert_eq!(entries.len(), 1);
+
+        let card = to_book_card(&entries[0]);
+        assert_eq!(card.metadata.title, "Graph Networks for Müller's Problem");
+        assert_eq!(
+            card.metadata.authors,
+            vec!["Jörg Müller", "Research Lab, Inc.", "Andrew Y. Ng"]
+        );
+        assert_eq!(card.metadata.year, Some(2020));
+        assert_eq!(
+            card.organization.tags,
+            vec!["graphs", "deep learning", "optimisation"]
+        );
+        let publication = card.publication.as_ref().expect("publication");
+        assert_eq!(publication.doc_type, DocumentType::ConferencePaper);
+        assert_eq!(
+            publication.conference.as_deref(),
+            Some("Proceedings of NeurIPS")
+        );
+        assert_eq!(publication.pages.as_deref(), Some("12-19"));
+        assert_eq!(
+            card.identifiers.as_ref().and_then(|ids| ids.doi.as_deref()),
+            Some("10.1000/xyz123")
+        );
+        assert_eq!(card.web.sources[0].url, "https://example.org/paper_1");
+        assert_eq!(
+            file_paths(&entries[0]),
+            vec![r"C:\Zotero\storage\muller.pdf", "snap.html"]
+        );
+    }
+
+    #[test]
+    fn bibliography_cite_keys_are_unique_across_the_set() {
+        let first = attention_card();
+        let second = attention_card();
+        let third = attention_card();
+        let cards = [&first, &second, &third];
+
+        let keys = unique_cite_keys(&cards, &CiteKeyScheme::AuthorYear);
+        assert_eq!(keys, vec!["Vaswani2017", "Vaswani2017a", "Vaswani2017b"]);
+
+        let bib = generate_bibliography(&cards, &BibTeXOptions::default());
+        let entries = parse_bibtex(&bib).expect("parse");
+        let parsed: Vec<_> = entries.iter().map(|e| e.cite_key.as_str()).collect();
+        assert_eq!(
+            parsed,
+            vec![
+                "Vaswani2017Attention",
+                "Vaswani2017Attentiona",
+                "Vaswani2017Attentionb"
+            ]
+        );
+        assert_eq!(suffix_letters(26), "aa");
+    }
 }
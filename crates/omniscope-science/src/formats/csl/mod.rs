@@ -0,0 +1,994 @@
+//! Citation formatting. Styles are CSL 1.0.2 `.csl` files loaded from a
+//! styles directory; the bundled style names fall back to built-in
+//! formatters when no file of that name is installed.
+
+mod locale;
+mod render;
+mod style;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+use omniscope_core::models::{BookCard, DocumentType};
+use once_cell::sync::Lazy;
+use tracing::warn;
+
+use self::locale::{EN_US, Locale, Locales, parse_locale_file};
+use self::render::Engine;
+use self::style::{Style, parse_style};
+use crate::config::ExportConfig;
+use crate::error::{Result, ScienceError};
+use crate::identifiers::{arxiv::ArxivId, doi::Doi};
+
+pub const BUNDLED_STYLES: &[&str] = &[
+    "apa",
+    "apa-6th-edition",
+    "ieee",
+    "gost-r-7-0-5-2008",
+    "russian-gost-r-7-0-5-2008",
+];
+
+static EN_US_LOCALE: Lazy<Locale> =
+    Lazy::new(|| parse_locale_file(EN_US).expect("built-in en-US locale is valid"));
+
+#[derive(Debug, Clone)]
+pub struct CslProcessor {
+    /// Locale for terms and dates, e.g. `de-DE`. Empty means the style's
+    /// `default-locale`.
+    pub locale: String,
+    /// Where `<style>.csl` files and `locales-xx-XX.xml` files (directly or
+    /// under `locales/`) are looked up.
+    pub styles_dir: Option<PathBuf>,
+}
+
+impl Default for CslProcessor {
+    fn default() -> Self {
+        Self::new("en-US")
+    }
+}
+
+impl CslProcessor {
+    pub fn new(locale: impl Into<String>) -> Self {
+        Self {
+            locale: locale.into(),
+            styles_dir: None,
+        }
+    }
+
+    pub fn with_styles_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.styles_dir = Some(dir.into());
+        self
+    }
+
+    /// Locale and styles directory from `[export]` in `science.toml`.
+    pub fn from_config(config: &ExportConfig) -> Self {
+        Self::new(config.csl_locale.clone()).with_styles_dir(config.styles_dir())
+    }
+
+    /// Bundled style names plus every `.csl` file in the styles directory.
+    pub fn available_styles(&self) -> Vec<String> {
+        let mut styles: Vec<String> = BUNDLED_STYLES.iter().map(|s| s.to_string()).collect();
+        if let Some(entries) = self
+            .styles_dir
+            .as_ref()
+            .and_then(|dir| fs::read_dir(dir).ok())
+        {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.extension().is_some_and(|ext| ext == "csl") {
+                    if let Some(stem) = path.file_stem() {
+                        styles.push(stem.to_string_lossy().into_owned());
+                    }
+                }
+            }
+        }
+        styles.sort();
+        styles.dedup();
+        styles
+    }
+
+    pub fn has_style(&self, style: &str) -> bool {
+        self.style_path(style).is_some()
+            || BUNDLED_STYLES.contains(&normalize_style(style).as_str())
+    }
+
+    /// Whether `style` is rendered by a built-in formatter rather than a `.csl` file.
+    pub fn is_builtin(&self, style: &str) -> bool {
+        self.style_path(style).is_none()
+            && BUNDLED_STYLES.contains(&normalize_style(style).as_str())
+    }
+
+    /// The bibliography entry for a single card.
+    pub fn format_citation(&self, card: &BookCard, style: &str) -> Result<String> {
+        let mut entries = self.format_items(&[card_to_csl_item(card)], style)?;
+        Ok(entries.pop().unwrap_or_default())
+    }
+
+    pub fn format_bibliography(&self, cards: &[&BookCard], style: &str) -> Result<Vec<String>> {
+        let items: Vec<CslItem> = cards.iter().map(|card| card_to_csl_item(card)).collect();
+        self.format_items(&items, style)
+    }
+
+    /// Bibliography entries for `items`. CSL styles sort, number and
+    /// disambiguate them as a set; built-in styles keep the input order.
+    pub fn format_items(&self, items: &[CslItem], style: &str) -> Result<Vec<String>> {
+        match self.load_style(style)? {
+            Some(csl) => {
+                let locales = self.locales_for(&csl);
+                Ok(Engine::new(&csl, &locales).bibliography(items))
+            }
+            None => items.iter().map(|item| format_item(item, style)).collect(),
+        }
+    }
+
+    /// An in-text citation citing all `cards` at once, disambiguated
+    /// against each other, e.g. `(Smith, 2020a; Smith, 2020b)`.
+    pub fn format_in_text(&self, cards: &[&BookCard], style: &str) -> Result<String> {
+        let Some(csl) = self.load_style(style)? else {
+            return Err(ScienceError::Parse(format!(
+                "in-text citations need a .csl style; `{style}` is built in"
+            )));
+        };
+        if csl.citation.is_none() {
+            return Err(ScienceError::Parse(format!(
+                "style `{style}` has no <citation> layout"
+            )));
+        }
+        let items: Vec<CslItem> = cards.iter().map(|card| card_to_csl_item(card)).collect();
+        let locales = self.locales_for(&csl);
+        Ok(Engine::new(&csl, &locales).citation(&items))
+    }
+
+    /// `style` may be a path to a `.csl` file or a file name in the styles directory.
+    fn style_path(&self, style: &str) -> Option<PathBuf> {
+        let style = style.trim();
+        if style.ends_with(".csl") {
+            let path = Path::new(style);
+            if path.is_file() {
+                return Some(path.to_path_buf());
+            }
+            let path = self.styles_dir.as_ref()?.join(style);
+            return path.is_file().then_some(path);
+        }
+        let path = self
+            .styles_dir
+            .as_ref()?
+            .join(format!("{}.csl", normalize_style(style)));
+        path.is_file().then_some(path)
+    }
+
+    /// The parsed style, or `None` when a built-in formatter should be used.
+    fn load_style(&self, style: &str) -> Result<Option<Style>> {
+        let Some(path) = self.style_path(style) else {
+            return Ok(None);
+        };
+        let source = fs::read_to_string(&path)
+            .map_err(|err| ScienceError::Parse(format!("{}: {err}", path.display())))?;
+        let parsed = parse_style(&source)
+            .map_err(|err| ScienceError::Parse(format!("{}: {err}", path.display())))?;
+        if parsed.citation.is_none() && parsed.bibliography.is_none() {
+            return Err(ScienceError::Parse(format!(
+                "{}: style has neither <citation> nor <bibliography>",
+                path.display()
+            )));
+        }
+        Ok(Some(parsed))
+    }
+
+    /// Style-embedded locales first, then the locale file, then en-US.
+    fn locales_for(&self, style: &Style) -> Locales {
+        let lang = Some(self.locale.trim())
+            .filter(|lang| !lang.is_empty())
+            .or(style.default_locale.as_deref())
+            .unwrap_or("en-US")
+            .to_string();
+        let primary = lang.split('-').next().unwrap_or_default();
+
+        let mut chain: Vec<Locale> = Vec::new();
+        for wanted in [Some(lang.as_str()), Some(primary), None] {
+            chain.extend(
+                style
+                    .locales
+                    .iter()
+                    .filter(|locale| locale.lang.as_deref() == wanted)
+                    .cloned(),
+            );
+        }
+        if let Some(file) = self.locale_file(&lang) {
+            chain.push(file);
+        }
+        chain.push(EN_US_LOCALE.clone());
+        Locales(chain)
+    }
+
+    /// `locales-de-DE.xml`, or any `locales-de-*.xml` for a bare or unknown region.
+    fn locale_file(&self, lang: &str) -> Option<Locale> {
+        let dir = self.styles_dir.as_ref()?;
+        let primary = lang.split('-').next().unwrap_or_default();
+        for dir in [dir.join("locales"), dir.clone()] {
+            let exact = dir.join(format!("locales-{lang}.xml"));
+            let path = if exact.is_file() {
+                Some(exact)
+            } else {
+                let prefix = format!("locales-{primary}-");
+                let mut matches: Vec<PathBuf> = fs::read_dir(&dir)
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|path| {
+                        path.file_name()
+                            .and_then(|name| name.to_str())
+                            .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".xml"))
+                    })
+                    .collect();
+                matches.sort();
+                matches.into_iter().next()
+            };
+            let Some(path) = path else {
+                continue;
+            };
+            match fs::read_to_string(&path)
+                .map_err(|err| ScienceError::Parse(err.to_string()))
+                .and_then(|source| parse_locale_file(&source))
+            {
+                Ok(locale) => return Some(locale),
+                Err(err) => warn!("skipping CSL locale {}: {err}", path.display()),
+            }
+        }
+        None
+    }
+}
+
+/// The CSL variables omniscope fills from a card.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CslItem {
+    pub csl_type: String,
+    pub title: String,
+    pub authors: Vec<String>,
+    pub editors: Vec<String>,
+    pub year: Option<i32>,
+    pub container_title: Option<String>,
+    pub collection_title: Option<String>,
+    pub volume: Option<String>,
+    pub issue: Option<String>,
+    pub pages: Option<String>,
+    pub number_of_pages: Option<String>,
+    pub edition: Option<String>,
+    pub publisher: Option<String>,
+    pub publisher_place: Option<String>,
+    pub language: Option<String>,
+    pub doi: Option<String>,
+    pub isbn: Option<String>,
+    pub url: Option<String>,
+    pub accessed: Option<NaiveDate>,
+}
+
+pub fn format_bibliography(cards: &[&BookCard], style: &str) -> Result<Vec<String>> {
+    CslProcessor::default().format_bibliography(cards, style)
+}
+
+pub fn card_to_csl_item(card: &BookCard) -> CslItem {
+    let publication = card.publication.as_ref();
+    let identifiers = card.identifiers.as_ref();
+    let csl_type = publication
+        .map(|publication| publication_type_to_csl(publication.doc_type))
+        .unwrap_or("book")
+        .to_string();
+
+    let doi = identifiers
+        .and_then(|identifiers| identifiers.doi.as_deref())
+        .map(normalize_doi_for_export)
+        .filter(|value| !value.is_empty());
+
+    // Web pages are cited with the date they were saved to the library.
+    let accessed = (csl_type == "webpage").then(|| card.created_at.date_naive());
+
+    CslItem {
+        title: card.metadata.title.clone(),
+        authors: card.metadata.authors.clone(),
+        editors: Vec::new(),
+        year: card.metadata.year,
+        container_title: publication.and_then(|publication| {
+            publication
+                .journal
+                .clone()
+                .or_else(|| publication.venue.clone())
+                .or_else(|| publication.conference.clone())
+        }),
+        collection_title: card.metadata.series.clone(),
+        volume: publication.and_then(|publication| publication.volume.clone()),
+        issue: publication.and_then(|publication| publication.issue.clone()),
+        pages: publication.and_then(|publication| publication.pages.clone()),
+        number_of_pages: card.metadata.pages.map(|pages| pages.to_string()),
+        edition: card.metadata.edition.map(|edition| edition.to_string()),
+        publisher: card.metadata.publisher.clone(),
+        publisher_place: None,
+        language: card.metadata.language.clone(),
+        doi,
+        isbn: identifiers
+            .and_then(|identifiers| identifiers.isbn13.clone())
+            .or_else(|| card.metadata.isbn.first().cloned()),
+        url: derive_url(card),
+        accessed,
+        csl_type,
+    }
+}
+
+fn format_item(item: &CslItem, style: &str) -> Result<String> {
+    let style_key = normalize_style(style);
+    match style_key.as_str() {
+        "apa" | "apa-6th-edition" => Ok(format_apa(item)),
+        "ieee" => Ok(format_ieee(item)),
+        "gost-r-7-0-5-2008" | "russian-gost-r-7-0-5-2008" => Ok(format_gost(item)),
+        _ => Err(ScienceError::Parse(format!(
+            "unsupported CSL style: {style}"
+        ))),
+    }
+}
+
+fn format_apa(item: &CslItem) -> String {
+    let authors = format_authors_apa(&item.authors);
+    let year = item
+        .year
+        .map(|y| y.to_string())
+        .unwrap_or_else(|| "n.d.".to_string());
+
+    let mut parts = Vec::new();
+    parts.push(format!("{authors} ({year})."));
+    parts.push(format!("{}.", item.title.trim()));
+
+    if let Some(journal) = non_empty(&item.container_title) {
+        let mut source = journal.to_string();
+        if let Some(volume) = non_empty(&item.volume) {
+            source.push_str(&format!(", {volume}"));
+        }
+        if let Some(issue) = non_empty(&item.issue) {
+            source.push_str(&format!("({issue})"));
+        }
+        if let Some(pages) = non_empty(&item.pages) {
+            source.push_str(&format!(", {}", page_range(pages)));
+        }
+        parts.push(format!("{source}."));
+    }
+
+    if let Some(doi) = item.doi.as_deref() {
+        parts.push(format!("doi:{doi}"));
+    } else if let Some(url) = item.url.as_deref() {
+        parts.push(url.to_string());
+    }
+
+    parts.join(" ")
+}
+
+fn format_ieee(item: &CslItem) -> String {
+    let authors = format_authors_ieee(&item.authors);
+    let mut out = format!("{authors}, \"{}\"", item.title.trim());
+
+    if let Some(journal) = non_empty(&item.container_title) {
+        out.push_str(&format!(", in {journal}"));
+    }
+    if let Some(volume) = non_empty(&item.volume) {
+        out.push_str(&format!(", vol. {volume}"));
+    }
+    if let Some(issue) = non_empty(&item.issue) {
+        out.push_str(&format!(", no. {issue}"));
+    }
+    if let Some(pages) = non_empty(&item.pages) {
+        let label = if pages.contains(['-', '–']) {
+            "pp."
+        } else {
+            "p."
+        };
+        out.push_str(&format!(", {label} {}", page_range(pages)));
+    }
+    if let Some(year) = item.year {
+        out.push_str(&format!(", {year}"));
+    }
+    out.push('.');
+
+    if let Some(doi) = item.doi.as_deref() {
+        out.push_str(&format!(" doi: {doi}"));
+    }
+
+    out
+}
+
+fn format_gost(item: &CslItem) -> String {
+    let mut authors = format_authors_gost(&item.authors);
+    if !authors.ends_with('.') {
+        authors.push('.');
+    }
+    let mut out = format!("{authors} {} ", item.title.trim());
+
+    if let Some(journal) = non_empty(&item.container_title) {
+        out.push_str(&format!("// {journal}. "));
+    }
+
+    if let Some(year) = item.year {
+        out.push_str(&format!("{year}. "));
+    }
+    let numbering = [
+        non_empty(&item.volume).map(|volume| format!("Vol. {volume}")),
+        non_empty(&item.issue).map(|issue| format!("№ {issue}")),
+    ];
+    let numbering: Vec<String> = numbering.into_iter().flatten().collect();
+    if !numbering.is_empty() {
+        out.push_str(&format!("{}. ", numbering.join(", ")));
+    }
+    if let Some(pages) = non_empty(&item.pages) {
+        out.push_str(&format!("P. {}. ", page_range(pages)));
+    }
+    if let Some(doi) = item.doi.as_deref() {
+        out.push_str(&format!("DOI: {doi}"));
+    } else if let Some(url) = item.url.as_deref() {
+        out.push_str(url);
+    }
+
+    out.trim().to_string()
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// `5998-6008` and `5998--6008` → `5998–6008`.
+fn page_range(pages: &str) -> String {
+    pages.replace("--", "-").replace('-', "–")
+}
+
+pub(super) fn publication_type_to_csl(doc_type: DocumentType) -> &'static str {
+    match doc_type {
+        DocumentType::Article => "article-journal",
+        DocumentType::MagazineArticle => "article-magazine",
+        DocumentType::Book => "book",
+        DocumentType::Chapter => "chapter",
+        DocumentType::ConferencePaper => "paper-conference",
+        DocumentType::Preprint => "article",
+        DocumentType::Thesis => "thesis",
+        DocumentType::Report => "report",
+        DocumentType::Dataset => "dataset",
+        DocumentType::Software => "software",
+        DocumentType::Patent => "patent",
+        DocumentType::Standard => "standard",
+        DocumentType::WebPage => "webpage",
+        DocumentType::Other => "document",
+    }
+}
+
+pub(super) fn normalize_doi_for_export(raw: &str) -> String {
+    Doi::parse(raw)
+        .map(|doi| doi.normalized)
+        .unwrap_or_else(|_| raw.trim().to_string())
+}
+
+pub(super) fn derive_url(card: &BookCard) -> Option<String> {
+    let from_doi = card
+        .identifiers
+        .as_ref()
+        .and_then(|identifiers| identifiers.doi.as_deref())
+        .and_then(|raw| Doi::parse(raw).ok())
+        .map(|doi| doi.url);
+    if from_doi.is_some() {
+        return from_doi;
+    }
+
+    let from_arxiv = card
+        .identifiers
+        .as_ref()
+        .and_then(|identifiers| identifiers.arxiv_id.as_deref())
+        .and_then(|raw| ArxivId::parse(raw).ok())
+        .map(|id| id.abs_url);
+    if from_arxiv.is_some() {
+        return from_arxiv;
+    }
+
+    card.open_access
+        .as_ref()
+        .and_then(|oa| oa.oa_url.as_deref())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(ToOwned::to_owned)
+}
+
+fn normalize_style(style: &str) -> String {
+    style.trim().to_ascii_lowercase()
+}
+
+fn format_authors_apa(authors: &[String]) -> String {
+    let formatted = authors
+        .iter()
+        .filter_map(|author| {
+            let parts = split_name(author)?;
+            Some(format!(
+                "{}, {}",
+                parts.family,
+                initials(&parts.given_names).join(" ")
+            ))
+        })
+        .collect::<Vec<_>>();
+
+    match formatted.len() {
+        0 => "Unknown Author".to_string(),
+        1 => formatted[0].clone(),
+        2 => format!("{} & {}", formatted[0], formatted[1]),
+        _ => {
+            let mut joined = formatted[..formatted.len() - 1].join(", ");
+            joined.push_str(", & ");
+            joined.push_str(&formatted[formatted.len() - 1]);
+            joined
+        }
+    }
+}
+
+fn format_authors_ieee(authors: &[String]) -> String {
+    let formatted = authors
+        .iter()
+        .filter_map(|author| {
+            let parts = split_name(author)?;
+            let initials = initials(&parts.given_names).join(" ");
+            let label = if initials.is_empty() {
+                parts.family
+            } else {
+                format!("{initials} {}", parts.family)
+            };
+            Some(label)
+        })
+        .collect::<Vec<_>>();
+
+    match formatted.len() {
+        0 => "Unknown Author".to_string(),
+        1 => formatted[0].clone(),
+        2 => format!("{} and {}", formatted[0], formatted[1]),
+        _ => format!("{} et al.", formatted[0]),
+    }
+}
+
+fn format_authors_gost(authors: &[String]) -> String {
+    let formatted = authors
+        .iter()
+        .filter_map(|author| {
+            let parts = split_name(author)?;
+            let initials = initials(&parts.given_names).join(" ");
+            let label = if initials.is_empty() {
+                parts.family
+            } else {
+                format!("{} {}", parts.family, initials)
+            };
+            Some(label)
+        })
+        .collect::<Vec<_>>();
+
+    if formatted.is_empty() {
+        "Unknown Author".to_string()
+    } else {
+        formatted.join(", ")
+    }
+}
+
+fn initials(parts: &[String]) -> Vec<String> {
+    parts
+        .iter()
+        .filter_map(|part| {
+            part.chars()
+                .find(|ch| ch.is_ascii_alphabetic())
+                .map(|ch| format!("{}.", ch.to_ascii_uppercase()))
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct NameParts {
+    pub(crate) family: String,
+    pub(crate) given_names: Vec<String>,
+}
+
+pub(crate) fn split_name(value: &str) -> Option<NameParts> {
+    let cleaned = value.trim();
+    if cleaned.is_empty() {
+        return None;
+    }
+
+    if let Some((family, given)) = cleaned.split_once(',') {
+        let family = family.trim();
+        if family.is_empty() {
+            return None;
+        }
+        let given_names = given
+            .split_whitespace()
+            .filter(|part| !part.is_empty())
+            .map(ToOwned::to_owned)
+            .collect::<Vec<_>>();
+        return Some(NameParts {
+            family: family.to_string(),
+            given_names,
+        });
+    }
+
+    let tokens = cleaned.split_whitespace().collect::<Vec<_>>();
+    if tokens.is_empty() {
+        return None;
+    }
+    if tokens.len() == 1 {
+        return Some(NameParts {
+            family: tokens[0].to_string(),
+            given_names: Vec::new(),
+        });
+    }
+
+    let family = tokens[tokens.len() - 1].to_string();
+    let given_names = tokens[..tokens.len() - 1]
+        .iter()
+        .map(|token| (*token).to_string())
+        .collect::<Vec<_>>();
+
+    Some(NameParts {
+        family,
+        given_names,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use omniscope_core::models::{BookPublication, ScientificIdentifiers};
+
+    use super::*;
+
+    static TMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    const AUTHOR_DATE: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<style xmlns="http://purl.org/net/xbiblio/csl" class="in-text" version="1.0" page-range-format="expanded">
+  <info><title>Test author-date</title></info>
+  <macro name="author">
+    <names variable="author">
+      <name name-as-sort-order="all" and="symbol" initialize-with=". " delimiter-precedes-last="always"/>
+      <substitute><names variable="editor"/><text variable="title"/></substitute>
+    </names>
+  </macro>
+  <macro name="author-short">
+    <names variable="author">
+      <name form="short" and="symbol"/>
+      <substitute><names variable="editor"/><text variable="title"/></substitute>
+    </names>
+  </macro>
+  <macro name="issued">
+    <choose>
+      <if variable="issued"><date variable="issued"><date-part name="year"/></date></if>
+      <else><text term="no date" form="short"/></else>
+    </choose>
+  </macro>
+  <citation et-al-min="3" et-al-use-first="1" disambiguate-add-names="true" disambiguate-add-year-suffix="true">
+    <sort><key macro="author-short"/><key macro="issued"/></sort>
+    <layout prefix="(" suffix=")" delimiter="; ">
+      <group delimiter=", "><text macro="author-short"/><text macro="issued"/></group>
+    </layout>
+  </citation>
+  <bibliography>
+    <sort><key macro="author"/><key variable="issued"/><key variable="title"/></sort>
+    <layout>
+      <group delimiter=". " suffix=".">
+        <group delimiter=" "><text macro="author"/><text macro="issued" prefix="(" suffix=")"/></group>
+        <text variable="title"/>
+        <group delimiter=", ">
+          <text variable="container-title"/>
+          <group><text variable="volume"/><text variable="issue" prefix="(" suffix=")"/></group>
+          <text variable="page"/>
+        </group>
+      </group>
+      <text variable="DOI" prefix=" https://doi.org/"/>
+    </layout>
+  </bibliography>
+</style>"#;
+
+    const NUMERIC: &str = r#"<style xmlns="http://purl.org/net/xbiblio/csl" class="in-text" version="1.0" page-range-format="expanded">
+  <citation>
+    <layout prefix="[" suffix="]" delimiter=", "><text variable="citation-number"/></layout>
+  </citation>
+  <bibliography second-field-align="flush">
+    <layout>
+      <text variable="citation-number" prefix="[" suffix="]"/>
+      <group delimiter=", " suffix=".">
+        <names variable="author"><name initialize-with=". " and="text"/></names>
+        <text variable="title" quotes="true"/>
+        <group delimiter=" ">
+          <text term="in"/>
+          <names variable="editor">
+            <name initialize-with=". "/>
+            <label form="short" prefix=", " text-case="capitalize-first"/>
+          </names>
+        </group>
+        <text variable="container-title"/>
+        <group delimiter=" "><label variable="volume" form="short"/><number variable="volume"/></group>
+        <group delimiter=" "><label variable="issue" form="short"/><text variable="issue"/></group>
+        <group delimiter=" "><label variable="page" form="short"/><text variable="page"/></group>
+        <date variable="issued"><date-part name="year"/></date>
+      </group>
+      <group prefix=" " delimiter=": ">
+        <text term="accessed" text-case="capitalize-first"/>
+        <date variable="accessed" form="text"/>
+      </group>
+    </layout>
+  </bibliography>
+</style>"#;
+
+    const DE_DE: &str = r#"<locale xmlns="http://purl.org/net/xbiblio/csl" version="1.0" xml:lang="de-DE">
+  <style-options punctuation-in-quote="false"/>
+  <date form="text">
+    <date-part name="day" suffix=". "/>
+    <date-part name="month" suffix=" "/>
+    <date-part name="year"/>
+  </date>
+  <terms>
+    <term name="and">und</term>
+    <term name="accessed">zugegriffen</term>
+    <term name="open-quote">„</term>
+    <term name="close-quote">“</term>
+    <term name="month-03">März</term>
+  </terms>
+</locale>"#;
+
+    fn styles_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "omniscope_csl_test_{}_{}",
+            std::process::id(),
+            TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(dir.join("locales")).unwrap();
+        fs::write(dir.join("author-date.csl"), AUTHOR_DATE).unwrap();
+        fs::write(dir.join("numeric.csl"), NUMERIC).unwrap();
+        fs::write(dir.join("locales/locales-de-DE.xml"), DE_DE).unwrap();
+        dir
+    }
+
+    fn paper(title: &str, authors: &[&str], year: i32) -> BookCard {
+        let mut card = BookCard::new(title);
+        card.metadata.authors = authors.iter().map(|author| author.to_string()).collect();
+        card.metadata.year = Some(year);
+        card
+    }
+
+    fn chapter() -> CslItem {
+        CslItem {
+            csl_type: "chapter".to_string(),
+            title: "Notes on the Engine".to_string(),
+            authors: vec!["Ada Lovelace".to_string(), "Charles Babbage".to_string()],
+            editors: vec!["Luigi Menabrea".to_string()],
+            year: Some(1843),
+            container_title: Some("Scientific Memoirs".to_string()),
+            volume: Some("3".to_string()),
+            issue: Some("2".to_string()),
+            pages: Some("666-731".to_string()),
+            accessed: NaiveDate::from_ymd_opt(2024, 3, 5),
+            ..Default::default()
+        }
+    }
+
+    fn attention_card() -> BookCard {
+        let mut card = BookCard::new("Attention Is All You Need");
+        card.metadata.authors = vec![
+            "Ashish Vaswani".to_string(),
+            "Noam Shazeer".to_string(),
+            "Niki Parmar".to_string(),
+        ];
+        card.metadata.year = Some(2017);
+        card.identifiers = Some(ScientificIdentifiers {
+            doi: Some("10.48550/arXiv.1706.03762".to_string()),
+            ..Default::default()
+        });
+        card.publication = Some(BookPublication {
+            doc_type: DocumentType::Article,
+            journal: Some("Advances in Neural Information Processing Systems".to_string()),
+            volume: Some("30".to_string()),
+            ..Default::default()
+        });
+        card
+    }
+
+    #[test]
+    fn formats_apa_citation() {
+        let card = attention_card();
+        let processor = CslProcessor::new("en-US");
+        let formatted = processor
+            .format_citation(&card, "apa")
+            .expect("apa formatting should succeed");
+
+        assert_eq!(
+            formatted,
+            "Vaswani, A., Shazeer, N., & Parmar, N. (2017). Attention Is All You Need. Advances in Neural Information Processing Systems, 30. doi:10.48550/arxiv.1706.03762"
+        );
+    }
+
+    #[test]
+    fn formats_ieee_citation() {
+        let card = attention_card();
+        let processor = CslProcessor::default();
+        let formatted = processor
+            .format_citation(&card, "ieee")
+            .expect("ieee formatting should succeed");
+
+        assert_eq!(
+            formatted,
+            "A. Vaswani et al., \"Attention Is All You Need\", in Advances in Neural Information Processing Systems, vol. 30, 2017. doi: 10.48550/arxiv.1706.03762"
+        );
+    }
+
+    #[test]
+    fn formats_gost_citation() {
+        let card = attention_card();
+        let processor = CslProcessor::new("ru-RU");
+        let formatted = processor
+            .format_citation(&card, "gost-r-7-0-5-2008")
+            .expect("gost formatting should succeed");
+
+        assert_eq!(
+            formatted,
+            "Vaswani A., Shazeer N., Parmar N. Attention Is All You Need // Advances in Neural Information Processing Systems. 2017. Vol. 30. DOI: 10.48550/arxiv.1706.03762"
+        );
+    }
+
+    #[test]
+    fn format_bibliography_for_list() {
+        let first = attention_card();
+        let mut second = attention_card();
+        second.metadata.title = "A Different Paper".to_string();
+
+        let entries = format_bibliography(&[&first, &second], "apa")
+            .expect("bibliography formatting should succeed");
+
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].contains("Attention Is All You Need"));
+        assert!(entries[1].contains("A Different Paper"));
+    }
+
+    #[test]
+    fn csl_style_sorts_and_disambiguates() {
+        let dir = styles_dir();
+        let processor = CslProcessor::default().with_styles_dir(&dir);
+
+        let mut beta = paper("Beta Paper", &["Alice Smith"], 2020);
+        beta.publication = Some(BookPublication {
+            doc_type: DocumentType::Article,
+            journal: Some("Journal of Tests".to_string()),
+            volume: Some("12".to_string()),
+            issue: Some("3".to_string()),
+            pages: Some("45-67".to_string()),
+            ..Default::default()
+        });
+        beta.identifiers = Some(ScientificIdentifiers {
+            doi: Some("10.1000/xyz".to_string()),
+            ..Default::default()
+        });
+        let alpha = paper("Alpha Paper", &["Alice Smith"], 2020);
+        let gamma = paper(
+            "Gamma",
+            &["John Ronald Adams", "Bob Brown", "Carol White"],
+            2019,
+        );
+        let delta = paper(
+            "Delta",
+            &["John Ronald Adams", "Dan Green", "Eve Black"],
+            2019,
+        );
+        let cards = [&beta, &alpha, &gamma, &delta];
+
+        assert_eq!(
+            processor.format_in_text(&cards, "author-date").unwrap(),
+            "(Adams, Brown, et al., 2019; Adams, Green, et al., 2019; Smith, 2020b; Smith, 2020a)"
+        );
+        assert_eq!(
+            processor
+                .format_bibliography(&cards, "author-date")
+                .unwrap(),
+            vec![
+                "Adams, J. R., Brown, B., & White, C. (2019). Gamma.",
+                "Adams, J. R., Green, D., & Black, E. (2019). Delta.",
+                "Smith, A. (2020a). Alpha Paper.",
+                "Smith, A. (2020b). Beta Paper. Journal of Tests, 12(3), 45–67. https://doi.org/10.1000/xyz",
+            ]
+        );
+        assert!(
+            processor
+                .available_styles()
+                .contains(&"author-date".to_string())
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn csl_style_renders_editors_pages_and_accessed_date() {
+        let dir = styles_dir();
+        let processor = CslProcessor::default().with_styles_dir(&dir);
+
+        let entries = processor.format_items(&[chapter()], "numeric").unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                "[1] A. Lovelace and C. Babbage, “Notes on the Engine,” in L. Menabrea, Ed., Scientific Memoirs, vol. 3, no. 2, pp. 666–731, 1843. Accessed: March 5, 2024"
+            ]
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn locale_files_supply_terms_and_dates() {
+        let dir = styles_dir();
+        let processor = CslProcessor::new("de-DE").with_styles_dir(&dir);
+
+        let entry = processor
+            .format_items(&[chapter()], "numeric")
+            .unwrap()
+            .remove(0);
+        assert!(entry.contains("A. Lovelace und C. Babbage"), "{entry}");
+        assert!(entry.contains("„Notes on the Engine“,"), "{entry}");
+        assert!(entry.ends_with("Zugegriffen: 5. März 2024"), "{entry}");
+        // Terms the locale file lacks come from en-US.
+        assert!(entry.contains("vol. 3"), "{entry}");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn empty_close_quote_leaves_punctuation_alone() {
+        let dir = styles_dir();
+        fs::write(
+            dir.join("locales/locales-xx-XX.xml"),
+            r#"<locale xmlns="http://purl.org/net/xbiblio/csl" version="1.0" xml:lang="xx-XX">
+  <style-options punctuation-in-quote="true"/>
+  <terms>
+    <term name="open-quote"></term>
+    <term name="close-quote"></term>
+  </terms>
+</locale>"#,
+        )
+        .unwrap();
+        let processor = CslProcessor::new("xx-XX").with_styles_dir(&dir);
+
+        let entry = processor
+            .format_items(&[chapter()], "numeric")
+            .unwrap()
+            .remove(0);
+        assert!(entry.contains("Notes on the Engine,"), "{entry}");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn builtin_styles_include_issue_and_pages() {
+        let mut card = attention_card();
+        if let Some(publication) = card.publication.as_mut() {
+            publication.issue = Some("2".to_string());
+            publication.pages = Some("5998-6008".to_string());
+        }
+        let processor = CslProcessor::default();
+
+        assert!(
+            processor
+                .format_citation(&card, "apa")
+                .unwrap()
+                .contains("Advances in Neural Information Processing Systems, 30(2), 5998–6008.")
+        );
+        assert!(
+            processor
+                .format_citation(&card, "ieee")
+                .unwrap()
+                .contains("vol. 30, no. 2, pp. 5998–6008, 2017.")
+        );
+    }
+
+    #[test]
+    fn web_pages_carry_an_accessed_date() {
+        let mut card = BookCard::new("Rust Blog");
+        card.publication = Some(BookPublication {
+            doc_type: DocumentType::WebPage,
+            ..Default::default()
+        });
+        let item = card_to_csl_item(&card);
+        assert_eq!(item.csl_type, "webpage");
+        assert_eq!(item.accessed, Some(card.created_at.date_naive()));
+    }
+}
@@ -0,0 +1,1288 @@
+//! Renders [`CslItem`]s through a parsed style: sorting, numbering,
+//! disambiguation and the element tree itself. Output is plain text.
+
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use chrono::Datelike;
+
+use super::locale::Locales;
+use super::style::{
+    Branch, Condition, DateElement, DatePart, Element, Formatting, LabelElement, Match,
+    NameElement, NameOptions, NamesElement, Section, SortKey, SortSource, Style, TextSource,
+};
+use super::{CslItem, split_name};
+use crate::formats::bibtex::suffix_letters;
+
+/// Per-item state settled before the final render.
+#[derive(Debug, Clone, Default)]
+struct ItemState {
+    citation_number: usize,
+    year_suffix: Option<String>,
+    /// Names shown before "et al." once `disambiguate-add-names` kicked in.
+    names_shown: Option<usize>,
+    add_givenname: bool,
+    disambiguate: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Citation,
+    Bibliography,
+    Sort,
+}
+
+/// Whether a rendered element called variables, and if any were filled.
+/// Groups whose variables are all empty are suppressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Vars {
+    None,
+    Empty,
+    Filled,
+}
+
+#[derive(Debug)]
+struct Out {
+    text: String,
+    vars: Vars,
+}
+
+impl Out {
+    fn plain(text: String) -> Self {
+        Self {
+            text,
+            vars: Vars::None,
+        }
+    }
+
+    fn filled(text: String) -> Self {
+        Self {
+            text,
+            vars: Vars::Filled,
+        }
+    }
+
+    fn empty() -> Self {
+        Self {
+            text: String::new(),
+            vars: Vars::Empty,
+        }
+    }
+}
+
+struct Ctx<'a> {
+    item: &'a CslItem,
+    state: &'a ItemState,
+    options: NameOptions,
+    mode: Mode,
+    sort_key: Option<&'a SortKey>,
+    /// Variables already used as a `<substitute>` for names.
+    suppressed: RefCell<HashSet<String>>,
+    year_suffix_used: Cell<bool>,
+    first_names: RefCell<Option<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum SortValue {
+    Number(i64),
+    Text(String),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct DateValue {
+    year: i32,
+    month: Option<u32>,
+    day: Option<u32>,
+}
+
+pub(crate) struct Engine<'a> {
+    style: &'a Style,
+    locales: &'a Locales,
+    /// The style prints `year-suffix` itself instead of after the year.
+    explicit_year_suffix: bool,
+}
+
+impl<'a> Engine<'a> {
+    pub fn new(style: &'a Style, locales: &'a Locales) -> Self {
+        let mut variables = Vec::new();
+        let layouts = [&style.citation, &style.bibliography];
+        for element in style.macros.values().flatten().chain(
+            layouts
+                .into_iter()
+                .flatten()
+                .flat_map(|s| &s.layout.children),
+        ) {
+            collect_variables(element, None, &mut variables);
+        }
+        Self {
+            style,
+            locales,
+            explicit_year_suffix: variables.iter().any(|name| name == "year-suffix"),
+        }
+    }
+
+    /// Bibliography entries, sorted and numbered as the style asks. Styles
+    /// without a bibliography get their citation layout, one per item.
+    pub fn bibliography(&self, items: &[CslItem]) -> Vec<String> {
+        let (order, states) = self.prepare(items);
+        let Some(section) = self.style.bibliography.as_ref() else {
+            let Some(citation) = self.style.citation.as_ref() else {
+                return Vec::new();
+            };
+            return order
+                .iter()
+                .map(|&index| {
+                    let cite = self.render_cite(&items[index], &states[index], citation);
+                    self.finish(&self.format(&citation.layout.fmt, cite))
+                })
+                .collect();
+        };
+
+        let mut previous: Option<String> = None;
+        order
+            .iter()
+            .map(|&index| {
+                let (text, names) = self.render_entry(&items[index], &states[index], section);
+                let text = match (&section.subsequent_author_substitute, &names) {
+                    (Some(substitute), Some(names)) if previous.as_ref() == Some(names) => {
+                        text.replacen(names.as_str(), substitute, 1)
+                    }
+                    _ => text,
+                };
+                previous = names;
+                self.finish(&text)
+            })
+            .collect()
+    }
+
+    /// One citation cluster citing every item, e.g. `(Smith, 2020a; Smith, 2020b)`.
+    pub fn citation(&self, items: &[CslItem]) -> String {
+        let Some(section) = self.style.citation.as_ref() else {
+            return String::new();
+        };
+        let (_, states) = self.prepare(items);
+        let cites: Vec<String> = self
+            .sort(items, &states, section)
+            .into_iter()
+            .map(|index| self.render_cite(&items[index], &states[index], section))
+            .collect();
+        let text = join(cites.iter().map(String::as_str), &section.layout.delimiter);
+        self.finish(&self.format(&section.layout.fmt, text))
+    }
+
+    /// Bibliography order, citation numbers and disambiguation.
+    fn prepare(&self, items: &[CslItem]) -> (Vec<usize>, Vec<ItemState>) {
+        let mut states: Vec<ItemState> = (0..items.len())
+            .map(|index| ItemState {
+                citation_number: index + 1,
+                ..Default::default()
+            })
+            .collect();
+        let order = match &self.style.bibliography {
+            Some(section) => self.sort(items, &states, section),
+            None => (0..items.len()).collect(),
+        };
+        for (position, &index) in order.iter().enumerate() {
+            states[index].citation_number = position + 1;
+        }
+        if let Some(citation) = &self.style.citation {
+            self.disambiguate(items, &mut states, &order, citation);
+        }
+        (order, states)
+    }
+
+    fn sort(&self, items: &[CslItem], states: &[ItemState], section: &Section) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..items.len()).collect();
+        if section.sort.is_empty() {
+            return order;
+        }
+        let keys: Vec<Vec<Option<SortValue>>> = items
+            .iter()
+            .zip(states)
+            .map(|(item, state)| {
+                section
+                    .sort
+                    .iter()
+                    .map(|key| self.sort_value(item, state, section, key))
+                    .collect()
+            })
+            .collect();
+        order.sort_by(|&a, &b| {
+            section
+                .sort
+                .iter()
+                .enumerate()
+                .map(|(n, key)| compare_sort_values(&keys[a][n], &keys[b][n], key.descending))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+        order
+    }
+
+    fn sort_value(
+        &self,
+        item: &CslItem,
+        state: &ItemState,
+        section: &Section,
+        key: &SortKey,
+    ) -> Option<SortValue> {
+        let text = match &key.source {
+            SortSource::Variable(name) if name == "citation-number" => {
+                return Some(SortValue::Number(state.citation_number as i64));
+            }
+            SortSource::Variable(name) => {
+                if let Some(date) = item.date_variable(name) {
+                    let value = i64::from(date.year) * 10_000
+                        + i64::from(date.month.unwrap_or(0)) * 100
+                        + i64::from(date.day.unwrap_or(0));
+                    return Some(SortValue::Number(value));
+                }
+                let names = item.name_variable(name);
+                if names.is_empty() {
+                    item.text_variable(name)?
+                } else {
+                    names
+                        .iter()
+                        .map(|name| sort_name(name))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                }
+            }
+            SortSource::Macro(name) => {
+                let elements = self.style.macros.get(name)?;
+                let ctx = self.ctx(item, state, section, Mode::Sort, Some(key));
+                self.render_seq(elements, "", &ctx).text
+            }
+        };
+
+        let text = text.trim();
+        if text.is_empty() {
+            return None;
+        }
+        Some(match text.parse::<i64>() {
+            Ok(number) => SortValue::Number(number),
+            Err(_) => SortValue::Text(
+                text.trim_start_matches(|ch: char| !ch.is_alphanumeric())
+                    .to_lowercase(),
+            ),
+        })
+    }
+
+    /// Add names, then given names, then year suffixes, then
+    /// `disambiguate="true"` branches until identical cites differ.
+    fn disambiguate(
+        &self,
+        items: &[CslItem],
+        states: &mut [ItemState],
+        order: &[usize],
+        citation: &Section,
+    ) {
+        let render = |states: &[ItemState], index: usize| {
+            self.render_cite(&items[index], &states[index], citation)
+        };
+
+        if citation.disambiguate_add_names {
+            for group in ambiguous_groups(items.len(), |index| render(states, index)) {
+                let most = group
+                    .iter()
+                    .map(|&index| items[index].authors.len().max(items[index].editors.len()))
+                    .max()
+                    .unwrap_or(0);
+                let mut best = (1, None);
+                for shown in 1..=most {
+                    for &index in &group {
+                        states[index].names_shown = Some(shown);
+                    }
+                    let distinct = distinct_count(group.iter().map(|&index| render(states, index)));
+                    if distinct > best.0 {
+                        best = (distinct, Some(shown));
+                    }
+                    if distinct == group.len() {
+                        break;
+                    }
+                }
+                for &index in &group {
+                    states[index].names_shown = best.1;
+                }
+            }
+        }
+
+        if citation.disambiguate_add_givenname {
+            for group in ambiguous_groups(items.len(), |index| render(states, index)) {
+                for &index in &group {
+                    states[index].add_givenname = true;
+                }
+                if distinct_count(group.iter().map(|&index| render(states, index))) == 1 {
+                    for &index in &group {
+                        states[index].add_givenname = false;
+                    }
+                }
+            }
+        }
+
+        if citation.disambiguate_add_year_suffix {
+            for mut group in ambiguous_groups(items.len(), |index| render(states, index)) {
+                group.sort_by_key(|index| order.iter().position(|other| other == index));
+                for (n, index) in group.into_iter().enumerate() {
+                    states[index].year_suffix = Some(suffix_letters(n));
+                }
+            }
+        }
+
+        for group in ambiguous_groups(items.len(), |index| render(states, index)) {
+            for &index in &group {
+                states[index].disambiguate = true;
+            }
+            if distinct_count(group.iter().map(|&index| render(states, index))) == 1 {
+                for &index in &group {
+                    states[index].disambiguate = false;
+                }
+            }
+        }
+    }
+
+    fn ctx<'b>(
+        &self,
+        item: &'b CslItem,
+        state: &'b ItemState,
+        section: &Section,
+        mode: Mode,
+        sort_key: Option<&'b SortKey>,
+    ) -> Ctx<'b> {
+        Ctx {
+            item,
+            state,
+            options: section.name_options.or(&self.style.name_options),
+            mode,
+            sort_key,
+            suppressed: RefCell::new(HashSet::new()),
+            year_suffix_used: Cell::new(false),
+            first_names: RefCell::new(None),
+        }
+    }
+
+    fn render_cite(&self, item: &CslItem, state: &ItemState, section: &Section) -> String {
+        let ctx = self.ctx(item, state, section, Mode::Citation, None);
+        self.render_seq(&section.layout.children, "", &ctx).text
+    }
+
+    /// An entry and the names it opened with, for `subsequent-author-substitute`.
+    fn render_entry(
+        &self,
+        item: &CslItem,
+        state: &ItemState,
+        section: &Section,
+    ) -> (String, Option<String>) {
+        let ctx = self.ctx(item, state, section, Mode::Bibliography, None);
+        let children = &section.layout.children;
+        let text = if section.second_field_align && children.len() > 1 {
+            let first = self.render_seq(&children[..1], "", &ctx).text;
+            let rest = self.render_seq(&children[1..], "", &ctx).text;
+            if first.is_empty() || rest.starts_with(char::is_whitespace) {
+                format!("{first}{rest}")
+            } else {
+                format!("{first} {rest}")
+            }
+        } else {
+            self.render_seq(children, "", &ctx).text
+        };
+        (
+            self.format(&section.layout.fmt, text),
+            ctx.first_names.into_inner(),
+        )
+    }
+
+    fn render_seq(&self, elements: &[Element], delimiter: &str, ctx: &Ctx<'_>) -> Out {
+        let outs: Vec<Out> = elements
+            .iter()
+            .map(|element| self.render(element, ctx))
+            .collect();
+        let vars = if outs.iter().any(|out| out.vars == Vars::Filled) {
+            Vars::Filled
+        } else if outs.iter().any(|out| out.vars == Vars::Empty) {
+            Vars::Empty
+        } else {
+            Vars::None
+        };
+        Out {
+            text: join(outs.iter().map(|out| out.text.as_str()), delimiter),
+            vars,
+        }
+    }
+
+    fn render_group(
+        &self,
+        elements: &[Element],
+        delimiter: &str,
+        fmt: &Formatting,
+        ctx: &Ctx<'_>,
+    ) -> Out {
+        let out = self.render_seq(elements, delimiter, ctx);
+        if out.vars == Vars::Empty {
+            return Out::empty();
+        }
+        Out {
+            text: self.format(fmt, out.text),
+            vars: out.vars,
+        }
+    }
+
+    fn render(&self, element: &Element, ctx: &Ctx<'_>) -> Out {
+        match element {
+            Element::Text(source, fmt) => self.render_text(source, fmt, ctx),
+            Element::Date(date) => self.render_date(date, ctx),
+            Element::Number {
+                variable,
+                form,
+                fmt,
+            } => self.render_number(variable, form, fmt, ctx),
+            Element::Names(names) => self.render_names(names, None, ctx),
+            Element::Label(label) => Out::plain(self.render_label(label, ctx)),
+            Element::Group {
+                delimiter,
+                children,
+                fmt,
+            } => self.render_group(children, delimiter, fmt, ctx),
+            Element::Choose(branches) => branches
+                .iter()
+                .find(|branch| self.branch_matches(branch, ctx))
+                .map(|branch| self.render_seq(&branch.children, "", ctx))
+                .unwrap_or_else(|| Out::plain(String::new())),
+        }
+    }
+
+    fn render_text(&self, source: &TextSource, fmt: &Formatting, ctx: &Ctx<'_>) -> Out {
+        match source {
+            TextSource::Variable { name, short } => {
+                if ctx.suppressed.borrow().contains(name) {
+                    return Out::empty();
+                }
+                let value = match name.as_str() {
+                    "citation-number" => Some(ctx.state.citation_number.to_string()),
+                    "year-suffix" => {
+                        ctx.year_suffix_used.set(true);
+                        ctx.state.year_suffix.clone()
+                    }
+                    "page" => ctx
+                        .item
+                        .text_variable("page")
+                        .map(|pages| self.page_range(&pages)),
+                    "title" if *short => ctx.item.text_variable("title-short"),
+                    _ => ctx.item.text_variable(name),
+                };
+                match value {
+                    Some(value) => Out::filled(self.format(fmt, value)),
+                    None => Out::empty(),
+                }
+            }
+            TextSource::Macro(name) => match self.style.macros.get(name) {
+                Some(elements) => self.render_group(elements, "", fmt, ctx),
+                None => Out::plain(String::new()),
+            },
+            TextSource::Term { name, form, plural } => Out::plain(
+                self.locales
+                    .term(name, form, *plural)
+                    .map(|term| self.format(fmt, term))
+                    .unwrap_or_default(),
+            ),
+            TextSource::Value(value) => Out::plain(self.format(fmt, value.clone())),
+        }
+    }
+
+    fn render_number(&self, variable: &str, form: &str, fmt: &Formatting, ctx: &Ctx<'_>) -> Out {
+        if ctx.suppressed.borrow().contains(variable) {
+            return Out::empty();
+        }
+        let value = match variable {
+            "citation-number" => Some(ctx.state.citation_number.to_string()),
+            _ => ctx.item.text_variable(variable),
+        };
+        let Some(value) = value else {
+            return Out::empty();
+        };
+        let text = match value.parse::<i64>() {
+            Ok(number) => match form {
+                "ordinal" => self.locales.ordinal(number),
+                "long-ordinal" => self.locales.long_ordinal(number),
+                "roman" => roman(number),
+                _ => number.to_string(),
+            },
+            Err(_) if variable == "page" => self.page_range(&value),
+            Err(_) => value,
+        };
+        Out::filled(self.format(fmt, text))
+    }
+
+    fn render_label(&self, label: &LabelElement, ctx: &Ctx<'_>) -> String {
+        let Some(value) = ctx.item.text_variable(&label.variable) else {
+            return String::new();
+        };
+        let plural = match label.plural.as_str() {
+            "always" => true,
+            "never" => false,
+            _ => is_plural(&label.variable, &value),
+        };
+        let term = if label.variable == "locator" {
+            "page"
+        } else {
+            label.variable.as_str()
+        };
+        self.locales
+            .term(term, &label.form, plural)
+            .map(|term| self.format(&label.fmt, term))
+            .unwrap_or_default()
+    }
+
+    fn render_date(&self, element: &DateElement, ctx: &Ctx<'_>) -> Out {
+        if ctx.suppressed.borrow().contains(&element.variable) {
+            return Out::empty();
+        }
+        let Some(date) = ctx.item.date_variable(&element.variable) else {
+            return Out::empty();
+        };
+        if ctx.mode == Mode::Sort {
+            return Out::filled(format!(
+                "{:04}{:02}{:02}",
+                date.year,
+                date.month.unwrap_or(0),
+                date.day.unwrap_or(0)
+            ));
+        }
+
+        let (parts, delimiter) = match element
+            .form
+            .as_deref()
+            .and_then(|form| self.locales.date_format(form))
+        {
+            Some(localized) => {
+                let wanted: &[&str] = match element.date_parts.as_str() {
+                    "year" => &["year"],
+                    "year-month" => &["year", "month"],
+                    _ => &["year", "month", "day"],
+                };
+                let parts: Vec<DatePart> = localized
+                    .parts
+                    .iter()
+                    .filter(|part| wanted.contains(&part.name.as_str()))
+                    .map(|part| localized_part(part, &element.parts))
+                    .collect();
+                (parts, localized.delimiter.clone())
+            }
+            None => (element.parts.clone(), element.delimiter.clone()),
+        };
+
+        let mut rendered = Vec::new();
+        for part in &parts {
+            let text = match part.name.as_str() {
+                "year" => {
+                    let mut year = match part.form.as_deref() {
+                        Some("short") => format!("{:02}", date.year.rem_euclid(100)),
+                        _ => date.year.to_string(),
+                    };
+                    if element.variable == "issued"
+                        && !self.explicit_year_suffix
+                        && !ctx.year_suffix_used.get()
+                        && let Some(suffix) = &ctx.state.year_suffix
+                    {
+                        year.push_str(suffix);
+                        ctx.year_suffix_used.set(true);
+                    }
+                    year
+                }
+                "month" => {
+                    let Some(month) = date.month else {
+                        continue;
+                    };
+                    match part.form.as_deref() {
+                        Some("numeric") => month.to_string(),
+                        Some("numeric-leading-zeros") => format!("{month:02}"),
+                        form => self
+                            .locales
+                            .term(
+                                &format!("month-{month:02}"),
+                                if form == Some("short") {
+                                    "short"
+                                } else {
+                                    "long"
+                                },
+                                false,
+                            )
+                            .unwrap_or_else(|| month.to_string()),
+                    }
+                }
+                "day" => {
+                    let Some(day) = date.day else {
+                        continue;
+                    };
+                    match part.form.as_deref() {
+                        Some("numeric-leading-zeros") => format!("{day:02}"),
+                        Some("ordinal") => self.locales.ordinal(i64::from(day)),
+                        _ => day.to_string(),
+                    }
+                }
+                _ => continue,
+            };
+            rendered.push(self.format(&part.fmt, text));
+        }
+
+        let text = join(rendered.iter().map(String::as_str), &delimiter);
+        Out::filled(self.format(&element.fmt, text))
+    }
+
+    fn render_names(
+        &self,
+        element: &NamesElement,
+        parent: Option<&NamesElement>,
+        ctx: &Ctx<'_>,
+    ) -> Out {
+        // `<names>` inside `<substitute>` inherits the outer name, et-al and label.
+        let resolved = NamesElement {
+            name: element
+                .name
+                .clone()
+                .or_else(|| parent.and_then(|parent| parent.name.clone())),
+            et_al: element
+                .et_al
+                .clone()
+                .or_else(|| parent.and_then(|parent| parent.et_al.clone())),
+            label: element
+                .label
+                .clone()
+                .or_else(|| parent.and_then(|parent| parent.label.clone())),
+            ..Default::default()
+        };
+        let name_element = resolved.name.clone().unwrap_or_default();
+        let options = name_element.options.or(&ctx.options);
+
+        let lists: Vec<(&str, &[String])> = element
+            .variables
+            .iter()
+            .filter(|variable| !ctx.suppressed.borrow().contains(variable.as_str()))
+            .map(|variable| (variable.as_str(), ctx.item.name_variable(variable)))
+            .filter(|(_, names)| !names.is_empty())
+            .collect();
+
+        if lists.is_empty() {
+            for substitute in &element.substitute {
+                let out = match substitute {
+                    Element::Names(inner) => self.render_names(inner, Some(&resolved), ctx),
+                    other => self.render(other, ctx),
+                };
+                if !out.text.is_empty() {
+                    let mut used = Vec::new();
+                    collect_variables(substitute, Some(&self.style.macros), &mut used);
+                    ctx.suppressed.borrow_mut().extend(used);
+                    return out;
+                }
+            }
+            return Out::empty();
+        }
+
+        if options.form.as_deref() == Some("count") {
+            let count: usize = lists
+                .iter()
+                .map(|(_, names)| self.shown_names(names.len(), &options, ctx).0)
+                .sum();
+            return Out::filled(self.format(&element.fmt, count.to_string()));
+        }
+
+        let rendered: Vec<String> = lists
+            .iter()
+            .map(|(variable, names)| {
+                let text = self.format(
+                    &name_element.fmt,
+                    self.render_name_list(names, &options, &name_element, &resolved, ctx),
+                );
+                let Some((label, before)) = &resolved.label else {
+                    return text;
+                };
+                if ctx.mode == Mode::Sort {
+                    return text;
+                }
+                let term = self
+                    .locales
+                    .term(variable, &label.form, names.len() > 1)
+                    .map(|term| self.format(&label.fmt, term))
+                    .unwrap_or_default();
+                if *before {
+                    format!("{term}{text}")
+                } else {
+                    format!("{text}{term}")
+                }
+            })
+            .collect();
+
+        let delimiter = element
+            .delimiter
+            .as_deref()
+            .or(options.names_delimiter.as_deref())
+            .unwrap_or(", ");
+        let text = join(rendered.iter().map(String::as_str), delimiter);
+        ctx.first_names
+            .borrow_mut()
+            .get_or_insert_with(|| text.clone());
+        Out::filled(self.format(&element.fmt, text))
+    }
+
+    /// How many names to show out of `count`, and whether "et al." follows.
+    fn shown_names(&self, count: usize, options: &NameOptions, ctx: &Ctx<'_>) -> (usize, bool) {
+        let (min, mut use_first) = match ctx.sort_key {
+            Some(key) => (
+                key.names_min.or(options.et_al_min),
+                key.names_use_first.or(options.et_al_use_first),
+            ),
+            None => (options.et_al_min, options.et_al_use_first),
+        };
+        if let Some(shown) = ctx.state.names_shown {
+            use_first = use_first.map(|first| first.max(shown));
+        }
+        match (min, use_first) {
+            (Some(min), Some(first)) if count >= min && first < count => (first, true),
+            _ => (count, false),
+        }
+    }
+
+    fn render_name_list(
+        &self,
+        names: &[String],
+        options: &NameOptions,
+        element: &NameElement,
+        resolved: &NamesElement,
+        ctx: &Ctx<'_>,
+    ) -> String {
+        let (shown, truncated) = self.shown_names(names.len(), options, ctx);
+        if ctx.mode == Mode::Sort {
+            return names[..shown]
+                .iter()
+                .map(|name| sort_name(name))
+                .collect::<Vec<_>>()
+                .join(", ");
+        }
+
+        let formatted: Vec<(String, bool)> = names[..shown]
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let inverted = match options.name_as_sort_order.as_deref() {
+                    Some("all") => true,
+                    Some("first") => index == 0,
+                    _ => false,
+                };
+                (
+                    self.format_name(name, inverted, options, element, ctx),
+                    inverted,
+                )
+            })
+            .filter(|(name, _)| !name.is_empty())
+            .collect();
+        let Some((last, last_inverted)) = formatted.last() else {
+            return String::new();
+        };
+        let delimiter = options.delimiter.as_deref().unwrap_or(", ");
+        let all = || {
+            formatted
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>()
+                .join(delimiter)
+        };
+
+        if truncated {
+            if options.et_al_use_last == Some(true) && names.len() > shown + 1 {
+                let final_name =
+                    self.format_name(&names[names.len() - 1], false, options, element, ctx);
+                return format!("{}{delimiter}… {final_name}", all());
+            }
+            let term_name = resolved.et_al.as_deref().unwrap_or("et-al");
+            let Some(term) = self.locales.term(term_name, "long", false) else {
+                return all();
+            };
+            let precedes = match options.delimiter_precedes_et_al.as_deref() {
+                Some("always") => true,
+                Some("never") => false,
+                Some("after-inverted-name") => *last_inverted,
+                _ => formatted.len() > 1,
+            };
+            let separator = if precedes { delimiter } else { " " };
+            return format!("{}{separator}{term}", all());
+        }
+
+        let and = match options.and.as_deref() {
+            Some("symbol") => Some("&".to_string()),
+            Some("text") => self.locales.term("and", "long", false),
+            _ => None,
+        };
+        let Some(and) = and.filter(|_| formatted.len() > 1) else {
+            return all();
+        };
+        let head = &formatted[..formatted.len() - 1];
+        let precedes = match options.delimiter_precedes_last.as_deref() {
+            Some("always") => true,
+            Some("never") => false,
+            Some("after-inverted-name") => head.last().is_some_and(|(_, inverted)| *inverted),
+            _ => formatted.len() > 2,
+        };
+        let head = head
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>()
+            .join(delimiter);
+        let separator = if precedes { delimiter } else { " " };
+        format!("{head}{separator}{and} {last}")
+    }
+
+    fn format_name(
+        &self,
+        raw: &str,
+        inverted: bool,
+        options: &NameOptions,
+        element: &NameElement,
+        ctx: &Ctx<'_>,
+    ) -> String {
+        let Some(parts) = split_name(raw) else {
+            return String::new();
+        };
+        let family = self.format(&element.family, parts.family);
+        if parts.given_names.is_empty() {
+            return family;
+        }
+
+        let form = options.form.as_deref().unwrap_or("long");
+        if form == "short" && !ctx.state.add_givenname {
+            return family;
+        }
+        let given = match options
+            .initialize_with
+            .as_deref()
+            .filter(|_| options.initialize != Some(false))
+        {
+            Some(with) => initialize(&parts.given_names, with),
+            None => parts.given_names.join(" "),
+        };
+        let given = self.format(&element.given, given);
+        if inverted {
+            let separator = options.sort_separator.as_deref().unwrap_or(", ");
+            format!("{family}{separator}{given}")
+        } else {
+            format!("{given} {family}")
+        }
+    }
+
+    fn branch_matches(&self, branch: &Branch, ctx: &Ctx<'_>) -> bool {
+        if branch.conditions.is_empty() {
+            return true;
+        }
+        let results: Vec<bool> = branch
+            .conditions
+            .iter()
+            .map(|condition| self.test(condition, ctx))
+            .collect();
+        match branch.matching {
+            Match::All => results.iter().all(|result| *result),
+            Match::Any => results.iter().any(|result| *result),
+            Match::None => !results.iter().any(|result| *result),
+        }
+    }
+
+    fn test(&self, condition: &Condition, ctx: &Ctx<'_>) -> bool {
+        match condition {
+            Condition::Type(csl_type) => ctx.item.csl_type == *csl_type,
+            Condition::Variable(variable) => {
+                if ctx.suppressed.borrow().contains(variable) {
+                    return false;
+                }
+                match variable.as_str() {
+                    "citation-number" => true,
+                    "year-suffix" => ctx.state.year_suffix.is_some(),
+                    _ => {
+                        ctx.item.text_variable(variable).is_some()
+                            || !ctx.item.name_variable(variable).is_empty()
+                            || ctx.item.date_variable(variable).is_some()
+                    }
+                }
+            }
+            Condition::IsNumeric(variable) => ctx
+                .item
+                .text_variable(variable)
+                .is_some_and(|value| is_numeric(&value)),
+            Condition::Position(position) => ctx.mode == Mode::Citation && position == "first",
+            Condition::Disambiguate(flag) => ctx.state.disambiguate == *flag,
+            Condition::IsUncertainDate | Condition::Locator => false,
+        }
+    }
+
+    fn format(&self, fmt: &Formatting, text: String) -> String {
+        if text.is_empty() {
+            return text;
+        }
+        let mut text = text;
+        if fmt.strip_periods {
+            text = text.replace('.', "");
+        }
+        if let Some(case) = &fmt.text_case {
+            text = text_case(&text, case);
+        }
+        if fmt.quotes {
+            let open = self.locales.term("open-quote", "long", false);
+            let close = self.locales.term("close-quote", "long", false);
+            text = format!(
+                "{}{text}{}",
+                open.as_deref().unwrap_or("\""),
+                close.as_deref().unwrap_or("\"")
+            );
+        }
+        let mut out = fmt.prefix.clone();
+        push_joined(&mut out, &text);
+        push_joined(&mut out, &fmt.suffix);
+        out
+    }
+
+    /// Page ranges use the locale's delimiter once a `page-range-format` is set.
+    fn page_range(&self, pages: &str) -> String {
+        if self.style.page_range_format.is_none() {
+            return pages.to_string();
+        }
+        let delimiter = self
+            .locales
+            .term("page-range-delimiter", "long", false)
+            .unwrap_or_else(|| "–".to_string());
+        pages.replace("--", "-").replace('-', &delimiter)
+    }
+
+    fn finish(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        if self.locales.punctuation_in_quote()
+            && let Some(close) = self.locales.term("close-quote", "long", false)
+        {
+            text = punctuation_inside_quotes(&text, &close);
+        }
+        while text.contains("  ") {
+            text = text.replace("  ", " ");
+        }
+        text.trim().to_string()
+    }
+}
+
+impl CslItem {
+    pub(crate) fn text_variable(&self, name: &str) -> Option<String> {
+        let value = match name {
+            "title" => Some(self.title.clone()),
+            "title-short" => self
+                .title
+                .split(':')
+                .next()
+                .map(|title| title.trim().to_string()),
+            "container-title" => self.container_title.clone(),
+            "collection-title" => self.collection_title.clone(),
+            "volume" => self.volume.clone(),
+            "issue" => self.issue.clone(),
+            "page" => self.pages.clone(),
+            "page-first" => self.pages.as_deref().map(|pages| {
+                pages
+                    .split(['-', '–', ','])
+                    .next()
+                    .unwrap_or_default()
+                    .to_string()
+            }),
+            "number-of-pages" => self.number_of_pages.clone(),
+            "edition" => self.edition.clone(),
+            "publisher" => self.publisher.clone(),
+            "publisher-place" => self.publisher_place.clone(),
+            "language" => self.language.clone(),
+            "DOI" => self.doi.clone(),
+            "ISBN" => self.isbn.clone(),
+            "URL" => self.url.clone(),
+            _ => None,
+        };
+        value
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    }
+
+    fn name_variable(&self, name: &str) -> &[String] {
+        match name {
+            "author" => &self.authors,
+            "editor" => &self.editors,
+            _ => &[],
+        }
+    }
+
+    fn date_variable(&self, name: &str) -> Option<DateValue> {
+        match name {
+            "issued" => self.year.map(|year| DateValue {
+                year,
+                month: None,
+                day: None,
+            }),
+            "accessed" => self.accessed.map(|date| DateValue {
+                year: date.year(),
+                month: Some(date.month()),
+                day: Some(date.day()),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Locale date parts with the style's `form`/`text-case` overrides; affixes
+/// always come from the locale.
+fn localized_part(part: &DatePart, overrides: &[DatePart]) -> DatePart {
+    let mut part = part.clone();
+    if let Some(local) = overrides.iter().find(|local| local.name == part.name) {
+        if local.form.is_some() {
+            part.form = local.form.clone();
+        }
+        if local.fmt.text_case.is_some() {
+            part.fmt.text_case = local.fmt.text_case.clone();
+        }
+        part.fmt.strip_periods |= local.fmt.strip_periods;
+    }
+    part
+}
+
+/// Variables an element prints, following macros when `macros` is given.
+fn collect_variables(
+    element: &Element,
+    macros: Option<&HashMap<String, Vec<Element>>>,
+    out: &mut Vec<String>,
+) {
+    match element {
+        Element::Text(TextSource::Variable { name, .. }, _) => out.push(name.clone()),
+        Element::Text(TextSource::Macro(name), _) => {
+            if let Some(elements) = macros.and_then(|macros| macros.get(name)) {
+                for element in elements {
+                    collect_variables(element, macros, out);
+                }
+            }
+        }
+        Element::Text(..) | Element::Label(_) => {}
+        Element::Date(date) => out.push(date.variable.clone()),
+        Element::Number { variable, .. } => out.push(variable.clone()),
+        Element::Names(names) => {
+            out.extend(names.variables.iter().cloned());
+            for element in &names.substitute {
+                collect_variables(element, macros, out);
+            }
+        }
+        Element::Group { children, .. } => {
+            for element in children {
+                collect_variables(element, macros, out);
+            }
+        }
+        Element::Choose(branches) => {
+            for element in branches.iter().flat_map(|branch| &branch.children) {
+                collect_variables(element, macros, out);
+            }
+        }
+    }
+}
+
+/// Indices whose renders collide, grouped; singletons are left out.
+fn ambiguous_groups(len: usize, render: impl Fn(usize) -> String) -> Vec<Vec<usize>> {
+    let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+    for index in 0..len {
+        let text = render(index);
+        match groups.iter_mut().find(|(other, _)| *other == text) {
+            Some((_, members)) => members.push(index),
+            None => groups.push((text, vec![index])),
+        }
+    }
+    groups
+        .into_iter()
+        .map(|(_, members)| members)
+        .filter(|members| members.len() > 1)
+        .collect()
+}
+
+fn distinct_count(renders: impl Iterator<Item = String>) -> usize {
+    renders.collect::<HashSet<_>>().len()
+}
+
+/// Empty keys sort last in either direction.
+fn compare_sort_values(a: &Option<SortValue>, b: &Option<SortValue>, descending: bool) -> Ordering {
+    let (a, b) = match (a, b) {
+        (None, None) => return Ordering::Equal,
+        (None, Some(_)) => return Ordering::Greater,
+        (Some(_), None) => return Ordering::Less,
+        (Some(a), Some(b)) => (a, b),
+    };
+    let ordering = match (a, b) {
+        (SortValue::Number(a), SortValue::Number(b)) => a.cmp(b),
+        (SortValue::Number(_), SortValue::Text(_)) => Ordering::Less,
+        (SortValue::Text(_), SortValue::Number(_)) => Ordering::Greater,
+        (SortValue::Text(a), SortValue::Text(b)) => a.cmp(b),
+    };
+    if descending {
+        ordering.reverse()
+    } else {
+        ordering
+    }
+}
+
+fn sort_name(raw: &str) -> String {
+    match split_name(raw) {
+        Some(parts) if parts.given_names.is_empty() => parts.family,
+        Some(parts) => format!("{} {}", parts.family, parts.given_names.join(" ")),
+        None => String::new(),
+    }
+}
+
+/// `["Jean-Paul", "Charles"]` with `". "` → `J.-P. C.`
+fn initialize(given_names: &[String], with: &str) -> String {
+    let trailing = &with[with.trim_end().len()..];
+    let mut out = String::new();
+    for name in given_names {
+        let initials: Vec<String> = name
+            .split('-')
+            .filter_map(|part| part.chars().find(|ch| ch.is_alphabetic()))
+            .map(|ch| format!("{}{}", ch.to_uppercase(), with.trim_end()))
+            .collect();
+        if initials.is_empty() {
+            continue;
+        }
+        out.push_str(&initials.join("-"));
+        out.push_str(trailing);
+    }
+    out.trim_end().to_string()
+}
+
+/// Append `piece`, dropping a period or space that would double up.
+fn push_joined(out: &mut String, piece: &str) {
+    let mut piece = piece;
+    match out.chars().last() {
+        Some('.' | '?' | '!') if piece.starts_with('.') => piece = &piece[1..],
+        Some(' ') if piece.starts_with(' ') => piece = &piece[1..],
+        _ => {}
+    }
+    out.push_str(piece);
+}
+
+fn join<'s>(parts: impl Iterator<Item = &'s str>, delimiter: &str) -> String {
+    let mut out = String::new();
+    for part in parts.filter(|part| !part.is_empty()) {
+        if !out.is_empty() {
+            push_joined(&mut out, delimiter);
+        }
+        push_joined(&mut out, part);
+    }
+    out
+}
+
+/// `“Title”.` → `“Title.”` for locales that put punctuation inside quotes.
+fn punctuation_inside_quotes(text: &str, close: &str) -> String {
+    if close.is_empty() {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(position) = rest.find(close) {
+        out.push_str(&rest[..position]);
+        rest = &rest[position + close.len()..];
+        if let Some(mark @ ('.' | ',')) = rest.chars().next() {
+            if !out.ends_with(['.', ',', '?', '!']) {
+                out.push(mark);
+            }
+            rest = &rest[1..];
+        }
+        out.push_str(close);
+    }
+    out.push_str(rest);
+    out
+}
+
+fn is_plural(variable: &str, value: &str) -> bool {
+    if matches!(variable, "number-of-pages" | "number-of-volumes") {
+        return value.parse::<u32>().is_ok_and(|count| count > 1);
+    }
+    value.contains(['-', '–', ',', '&']) || value.contains(" and ")
+}
+
+/// CSL's notion of numeric: numbers with optional letter affixes, possibly
+/// joined by `-`, `,` or `&` (`2`, `L2d`, `2-4`, `2, 3`).
+fn is_numeric(value: &str) -> bool {
+    value.split(['-', '–', ',', '&']).all(|part| {
+        let part = part.trim();
+        part.chars().any(|ch| ch.is_ascii_digit()) && part.chars().all(char::is_alphanumeric)
+    })
+}
+
+fn roman(number: i64) -> String {
+    if !(1..4000).contains(&number) {
+        return number.to_string();
+    }
+    const NUMERALS: &[(i64, &str)] = &[
+        (1000, "m"),
+        (900, "cm"),
+        (500, "d"),
+        (400, "cd"),
+        (100, "c"),
+        (90, "xc"),
+        (50, "l"),
+        (40, "xl"),
+        (10, "x"),
+        (9, "ix"),
+        (5, "v"),
+        (4, "iv"),
+        (1, "i"),
+    ];
+    let mut number = number;
+    let mut out = String::new();
+    for (value, numeral) in NUMERALS {
+        while number >= *value {
+            out.push_str(numeral);
+            number -= value;
+        }
+    }
+    out
+}
+
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "as", "at", "but", "by", "down", "for", "from", "in", "into", "nor", "of",
+    "on", "onto", "or", "over", "so", "the", "till", "to", "up", "via", "with", "yet",
+];
+
+fn text_case(text: &str, case: &str) -> String {
+    match case {
+        "lowercase" => text.to_lowercase(),
+        "uppercase" => text.to_uppercase(),
+        "capitalize-first" => capitalize(text),
+        "capitalize-all" => text
+            .split(' ')
+            .map(capitalize)
+            .collect::<Vec<_>>()
+            .join(" "),
+        "sentence" => {
+            if text.chars().any(char::is_lowercase) {
+                capitalize(text)
+            } else {
+                capitalize(&text.to_lowercase())
+            }
+        }
+        "title" => {
+            let words: Vec<&str> = text.split(' ').collect();
+            words
+                .iter()
+                .enumerate()
+                .map(|(index, word)| {
+                    let stop = STOP_WORDS.contains(&word.to_lowercase().as_str());
+                    if stop && index != 0 && index + 1 != words.len() {
+                        word.to_string()
+                    } else {
+                        capitalize(word)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+        _ => text.to_string(),
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
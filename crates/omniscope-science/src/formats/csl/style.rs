@@ -0,0 +1,622 @@
+//! CSL 1.0.2 style files parsed into an element tree.
+
+use std::collections::HashMap;
+
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+
+use super::locale::Locale;
+use crate::error::{Result, ScienceError};
+
+/// A bare XML element: enough structure for styles and locale files.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct XmlNode {
+    pub name: String,
+    pub attrs: Vec<(String, String)>,
+    pub children: Vec<XmlNode>,
+    pub text: String,
+}
+
+impl XmlNode {
+    pub fn attr(&self, key: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn child(&self, name: &str) -> Option<&XmlNode> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    pub fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a XmlNode> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+}
+
+/// Parse a document and return its root element.
+pub(crate) fn parse_xml(source: &str) -> Result<XmlNode> {
+    let mut reader = Reader::from_str(source);
+    reader.config_mut().trim_text(true);
+
+    let mut stack: Vec<XmlNode> = Vec::new();
+    let mut root = None;
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|err| ScienceError::Parse(format!("invalid CSL XML: {err}")))?;
+        match event {
+            Event::Start(start) => stack.push(element(&start)?),
+            Event::Empty(start) => {
+                let node = element(&start)?;
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(node),
+                    None => root = Some(node),
+                }
+            }
+            Event::End(_) => {
+                let node = stack
+                    .pop()
+                    .ok_or_else(|| ScienceError::Parse("unbalanced CSL XML".to_string()))?;
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(node),
+                    None => root = Some(node),
+                }
+            }
+            Event::Text(text) => {
+                if let Some(node) = stack.last_mut() {
+                    let text = text
+                        .unescape()
+                        .map_err(|err| ScienceError::Parse(format!("invalid CSL XML: {err}")))?;
+                    node.text.push_str(&text);
+                }
+            }
+            Event::CData(data) => {
+                if let Some(node) = stack.last_mut() {
+                    node.text.push_str(&String::from_utf8_lossy(&data));
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    root.ok_or_else(|| ScienceError::Parse("empty CSL document".to_string()))
+}
+
+fn element(start: &BytesStart<'_>) -> Result<XmlNode> {
+    let name = String::from_utf8_lossy(start.local_name().as_ref()).into_owned();
+    let mut attrs = Vec::new();
+    for attr in start.attributes() {
+        let attr = attr.map_err(|err| ScienceError::Parse(format!("invalid CSL XML: {err}")))?;
+        let key = String::from_utf8_lossy(attr.key.local_name().as_ref()).into_owned();
+        let value = attr
+            .unescape_value()
+            .map_err(|err| ScienceError::Parse(format!("invalid CSL XML: {err}")))?;
+        attrs.push((key, value.into_owned()));
+    }
+    Ok(XmlNode {
+        name,
+        attrs,
+        ..Default::default()
+    })
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Style {
+    pub default_locale: Option<String>,
+    /// Name options set on `<style>`, inherited by both sections.
+    pub name_options: NameOptions,
+    pub page_range_format: Option<String>,
+    pub locales: Vec<Locale>,
+    pub macros: HashMap<String, Vec<Element>>,
+    pub citation: Option<Section>,
+    pub bibliography: Option<Section>,
+}
+
+/// `<citation>` or `<bibliography>`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Section {
+    pub name_options: NameOptions,
+    pub sort: Vec<SortKey>,
+    pub layout: Layout,
+    pub disambiguate_add_names: bool,
+    pub disambiguate_add_givenname: bool,
+    pub disambiguate_add_year_suffix: bool,
+    pub subsequent_author_substitute: Option<String>,
+    pub second_field_align: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Layout {
+    pub delimiter: String,
+    pub fmt: Formatting,
+    pub children: Vec<Element>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct SortKey {
+    pub source: SortSource,
+    pub descending: bool,
+    pub names_min: Option<usize>,
+    pub names_use_first: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum SortSource {
+    Variable(String),
+    Macro(String),
+}
+
+/// Affixes and the text transformations that make sense in plain-text
+/// output. Font styling is accepted and ignored.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Formatting {
+    pub prefix: String,
+    pub suffix: String,
+    pub quotes: bool,
+    pub strip_periods: bool,
+    pub text_case: Option<String>,
+}
+
+impl Formatting {
+    fn from_node(node: &XmlNode) -> Self {
+        Self {
+            prefix: node.attr("prefix").unwrap_or_default().to_string(),
+            suffix: node.attr("suffix").unwrap_or_default().to_string(),
+            quotes: node.attr("quotes") == Some("true"),
+            strip_periods: node.attr("strip-periods") == Some("true"),
+            text_case: node.attr("text-case").map(ToOwned::to_owned),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum Element {
+    Text(TextSource, Formatting),
+    Date(DateElement),
+    Number {
+        variable: String,
+        form: String,
+        fmt: Formatting,
+    },
+    Names(Box<NamesElement>),
+    Label(LabelElement),
+    Group {
+        delimiter: String,
+        children: Vec<Element>,
+        fmt: Formatting,
+    },
+    Choose(Vec<Branch>),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum TextSource {
+    Variable {
+        name: String,
+        short: bool,
+    },
+    Macro(String),
+    Term {
+        name: String,
+        form: String,
+        plural: bool,
+    },
+    Value(String),
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DateElement {
+    pub variable: String,
+    /// `text` or `numeric` for localized dates.
+    pub form: Option<String>,
+    pub date_parts: String,
+    pub parts: Vec<DatePart>,
+    pub delimiter: String,
+    pub fmt: Formatting,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DatePart {
+    pub name: String,
+    pub form: Option<String>,
+    pub fmt: Formatting,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct NamesElement {
+    pub variables: Vec<String>,
+    pub name: Option<NameElement>,
+    pub et_al: Option<String>,
+    pub label: Option<(LabelElement, bool)>,
+    pub substitute: Vec<Element>,
+    pub delimiter: Option<String>,
+    pub fmt: Formatting,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct NameElement {
+    pub options: NameOptions,
+    pub fmt: Formatting,
+    pub family: Formatting,
+    pub given: Formatting,
+}
+
+/// The inheritable name options; `None` means "take the parent's".
+#[derive(Debug, Clone, Default)]
+pub(crate) struct NameOptions {
+    pub and: Option<String>,
+    pub delimiter: Option<String>,
+    pub delimiter_precedes_et_al: Option<String>,
+    pub delimiter_precedes_last: Option<String>,
+    pub et_al_min: Option<usize>,
+    pub et_al_use_first: Option<usize>,
+    pub et_al_use_last: Option<bool>,
+    pub initialize: Option<bool>,
+    pub initialize_with: Option<String>,
+    pub name_as_sort_order: Option<String>,
+    pub sort_separator: Option<String>,
+    pub form: Option<String>,
+    pub names_delimiter: Option<String>,
+}
+
+impl NameOptions {
+    /// Read name options from `node`. On `<style>`, `<citation>` and
+    /// `<bibliography>` the form and delimiters carry a `name-`/`names-`
+    /// prefix.
+    fn from_node(node: &XmlNode, inherited: bool) -> Self {
+        let (form, delimiter) = if inherited {
+            ("name-form", "name-delimiter")
+        } else {
+            ("form", "delimiter")
+        };
+        let number = |key: &str| node.attr(key).and_then(|value| value.trim().parse().ok());
+        let text = |key: &str| node.attr(key).map(ToOwned::to_owned);
+        Self {
+            and: text("and"),
+            delimiter: text(delimiter),
+            delimiter_precedes_et_al: text("delimiter-precedes-et-al"),
+            delimiter_precedes_last: text("delimiter-precedes-last"),
+            et_al_min: number("et-al-min"),
+            et_al_use_first: number("et-al-use-first"),
+            et_al_use_last: node.attr("et-al-use-last").map(|value| value == "true"),
+            initialize: node.attr("initialize").map(|value| value != "false"),
+            initialize_with: text("initialize-with"),
+            name_as_sort_order: text("name-as-sort-order"),
+            sort_separator: text("sort-separator"),
+            form: text(form),
+            names_delimiter: if inherited {
+                text("names-delimiter")
+            } else {
+                None
+            },
+        }
+    }
+
+    /// `self`, with unset options taken from `parent`.
+    pub fn or(&self, parent: &NameOptions) -> NameOptions {
+        NameOptions {
+            and: self.and.clone().or_else(|| parent.and.clone()),
+            delimiter: self.delimiter.clone().or_else(|| parent.delimiter.clone()),
+            delimiter_precedes_et_al: self
+                .delimiter_precedes_et_al
+                .clone()
+                .or_else(|| parent.delimiter_precedes_et_al.clone()),
+            delimiter_precedes_last: self
+                .delimiter_precedes_last
+                .clone()
+                .or_else(|| parent.delimiter_precedes_last.clone()),
+            et_al_min: self.et_al_min.or(parent.et_al_min),
+            et_al_use_first: self.et_al_use_first.or(parent.et_al_use_first),
+            et_al_use_last: self.et_al_use_last.or(parent.et_al_use_last),
+            initialize: self.initialize.or(parent.initialize),
+            initialize_with: self
+                .initialize_with
+                .clone()
+                .or_else(|| parent.initialize_with.clone()),
+            name_as_sort_order: self
+                .name_as_sort_order
+                .clone()
+                .or_else(|| parent.name_as_sort_order.clone()),
+            sort_separator: self
+                .sort_separator
+                .clone()
+                .or_else(|| parent.sort_separator.clone()),
+            form: self.form.clone().or_else(|| parent.form.clone()),
+            names_delimiter: self
+                .names_delimiter
+                .clone()
+                .or_else(|| parent.names_delimiter.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LabelElement {
+    pub variable: String,
+    pub form: String,
+    pub plural: String,
+    pub fmt: Formatting,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Branch {
+    pub matching: Match,
+    /// Empty for `<else>`.
+    pub conditions: Vec<Condition>,
+    pub children: Vec<Element>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Match {
+    All,
+    Any,
+    None,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum Condition {
+    Type(String),
+    Variable(String),
+    IsNumeric(String),
+    /// Dates are never uncertain and cites never carry locators here, so
+    /// these tests always fail.
+    IsUncertainDate,
+    Locator,
+    Position(String),
+    Disambiguate(bool),
+}
+
+/// Parse a `.csl` style document.
+pub(crate) fn parse_style(source: &str) -> Result<Style> {
+    let root = parse_xml(source)?;
+    if root.name != "style" {
+        return Err(ScienceError::Parse(format!(
+            "expected a <style> document, found <{}>",
+            root.name
+        )));
+    }
+
+    let macros = root
+        .children_named("macro")
+        .filter_map(|node| {
+            let name = node.attr("name")?;
+            Some((name.to_string(), parse_children(&node.children)))
+        })
+        .collect();
+
+    Ok(Style {
+        default_locale: root.attr("default-locale").map(ToOwned::to_owned),
+        name_options: NameOptions::from_node(&root, true),
+        page_range_format: root.attr("page-range-format").map(ToOwned::to_owned),
+        locales: root
+            .children_named("locale")
+            .map(super::locale::parse_locale)
+            .collect(),
+        macros,
+        citation: root.child("citation").map(parse_section),
+        bibliography: root.child("bibliography").map(parse_section),
+    })
+}
+
+fn parse_section(node: &XmlNode) -> Section {
+    let flag = |key: &str| node.attr(key) == Some("true");
+    let layout = node
+        .child("layout")
+        .map(|layout| Layout {
+            delimiter: layout.attr("delimiter").unwrap_or_default().to_string(),
+            fmt: Formatting::from_node(layout),
+            children: parse_children(&layout.children),
+        })
+        .unwrap_or_default();
+    let sort = node
+        .child("sort")
+        .map(|sort| {
+            sort.children_named("key")
+                .filter_map(|key| {
+                    let source = match (key.attr("variable"), key.attr("macro")) {
+                        (Some(variable), _) => SortSource::Variable(variable.to_string()),
+                        (None, Some(name)) => SortSource::Macro(name.to_string()),
+                        (None, None) => return None,
+                    };
+                    let number = |attr: &str| key.attr(attr).and_then(|value| value.parse().ok());
+                    Some(SortKey {
+                        source,
+                        descending: key.attr("sort") == Some("descending"),
+                        names_min: number("names-min"),
+                        names_use_first: number("names-use-first"),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Section {
+        name_options: NameOptions::from_node(node, true),
+        sort,
+        layout,
+        disambiguate_add_names: flag("disambiguate-add-names"),
+        disambiguate_add_givenname: flag("disambiguate-add-givenname"),
+        disambiguate_add_year_suffix: flag("disambiguate-add-year-suffix"),
+        subsequent_author_substitute: node
+            .attr("subsequent-author-substitute")
+            .map(ToOwned::to_owned),
+        second_field_align: node.attr("second-field-align").is_some(),
+    }
+}
+
+pub(crate) fn parse_children(nodes: &[XmlNode]) -> Vec<Element> {
+    nodes.iter().filter_map(parse_element).collect()
+}
+
+fn parse_element(node: &XmlNode) -> Option<Element> {
+    let fmt = Formatting::from_node(node);
+    let element = match node.name.as_str() {
+        "text" => {
+            let source = if let Some(name) = node.attr("variable") {
+                TextSource::Variable {
+                    name: name.to_string(),
+                    short: node.attr("form") == Some("short"),
+                }
+            } else if let Some(name) = node.attr("macro") {
+                TextSource::Macro(name.to_string())
+            } else if let Some(name) = node.attr("term") {
+                TextSource::Term {
+                    name: name.to_string(),
+                    form: node.attr("form").unwrap_or("long").to_string(),
+                    plural: node.attr("plural") == Some("true"),
+                }
+            } else {
+                TextSource::Value(node.attr("value").unwrap_or_default().to_string())
+            };
+            Element::Text(source, fmt)
+        }
+        "date" => Element::Date(parse_date(node)),
+        "number" => Element::Number {
+            variable: node.attr("variable")?.to_string(),
+            form: node.attr("form").unwrap_or("numeric").to_string(),
+            fmt,
+        },
+        "names" => Element::Names(Box::new(parse_names(node))),
+        "label" => Element::Label(parse_label(node)),
+        "group" => Element::Group {
+            delimiter: node.attr("delimiter").unwrap_or_default().to_string(),
+            children: parse_children(&node.children),
+            fmt,
+        },
+        "choose" => Element::Choose(node.children.iter().filter_map(parse_branch).collect()),
+        _ => return None,
+    };
+    Some(element)
+}
+
+pub(crate) fn parse_date(node: &XmlNode) -> DateElement {
+    DateElement {
+        variable: node.attr("variable").unwrap_or_default().to_string(),
+        form: node.attr("form").map(ToOwned::to_owned),
+        date_parts: node
+            .attr("date-parts")
+            .unwrap_or("year-month-day")
+            .to_string(),
+        parts: node
+            .children_named("date-part")
+            .map(|part| DatePart {
+                name: part.attr("name").unwrap_or_default().to_string(),
+                form: part.attr("form").map(ToOwned::to_owned),
+                fmt: Formatting::from_node(part),
+            })
+            .collect(),
+        delimiter: node.attr("delimiter").unwrap_or_default().to_string(),
+        fmt: Formatting::from_node(node),
+    }
+}
+
+fn parse_names(node: &XmlNode) -> NamesElement {
+    let mut names = NamesElement {
+        variables: node
+            .attr("variable")
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(ToOwned::to_owned)
+            .collect(),
+        delimiter: node.attr("delimiter").map(ToOwned::to_owned),
+        fmt: Formatting::from_node(node),
+        ..Default::default()
+    };
+
+    let mut seen_name = false;
+    for child in &node.children {
+        match child.name.as_str() {
+            "name" => {
+                seen_name = true;
+                let part = |which: &str| {
+                    child
+                        .children_named("name-part")
+                        .find(|part| part.attr("name") == Some(which))
+                        .map(Formatting::from_node)
+                        .unwrap_or_default()
+                };
+                names.name = Some(NameElement {
+                    options: NameOptions::from_node(child, false),
+                    fmt: Formatting::from_node(child),
+                    family: part("family"),
+                    given: part("given"),
+                });
+            }
+            "et-al" => {
+                names.et_al = Some(child.attr("term").unwrap_or("et-al").to_string());
+            }
+            "label" => names.label = Some((parse_label(child), !seen_name)),
+            "substitute" => names.substitute = parse_children(&child.children),
+            _ => {}
+        }
+    }
+    names
+}
+
+fn parse_label(node: &XmlNode) -> LabelElement {
+    LabelElement {
+        variable: node.attr("variable").unwrap_or_default().to_string(),
+        form: node.attr("form").unwrap_or("long").to_string(),
+        plural: node.attr("plural").unwrap_or("contextual").to_string(),
+        fmt: Formatting::from_node(node),
+    }
+}
+
+fn parse_branch(node: &XmlNode) -> Option<Branch> {
+    let children = parse_children(&node.children);
+    if node.name == "else" {
+        return Some(Branch {
+            matching: Match::All,
+            conditions: Vec::new(),
+            children,
+        });
+    }
+    if node.name != "if" && node.name != "else-if" {
+        return None;
+    }
+
+    let matching = match node.attr("match") {
+        Some("any") => Match::Any,
+        Some("none") => Match::None,
+        _ => Match::All,
+    };
+    let mut conditions = Vec::new();
+    for (key, value) in &node.attrs {
+        let make: fn(String) -> Condition = match key.as_str() {
+            "type" => Condition::Type,
+            "variable" => Condition::Variable,
+            "is-numeric" => Condition::IsNumeric,
+            "is-uncertain-date" | "locator" => {
+                let condition = if key == "locator" {
+                    Condition::Locator
+                } else {
+                    Condition::IsUncertainDate
+                };
+                conditions.extend(value.split_whitespace().map(|_| condition.clone()));
+                continue;
+            }
+            "position" => Condition::Position,
+            "disambiguate" => {
+                conditions.push(Condition::Disambiguate(value == "true"));
+                continue;
+            }
+            _ => continue,
+        };
+        conditions.extend(
+            value
+                .split_whitespace()
+                .map(|value| make(value.to_string())),
+        );
+    }
+    // An `<if>` without any test never matches.
+    if conditions.is_empty() {
+        conditions.push(Condition::Variable(String::new()));
+    }
+
+    Some(Branch {
+        matching,
+        conditions,
+        children,
+    })
+}
@@ -0,0 +1,600 @@
+//! CSL-JSON, the interchange format of Zotero, Pandoc and citeproc-js.
+//!
+//! Items follow the CSL 1.0.2 schema: names are split into `family` and
+//! `given`, dates use `date-parts`, and numeric variables are accepted as
+//! either JSON numbers or strings when reading.
+
+use chrono::{Datelike, NaiveDate};
+use omniscope_core::models::{BookCard, DocumentType, WebSource};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+use super::bibtex::{leading_number, parse_isbn_list, parse_year, split_keywords};
+use super::csl::{derive_url, normalize_doi_for_export, publication_type_to_csl, split_name};
+use crate::enrichment::{BookCardMergeExt, MetadataSource, PartialMetadata};
+use crate::error::{Result, ScienceError};
+use crate::identifiers::{arxiv::ArxivId, doi::Doi};
+
+/// One CSL-JSON item, as read by Zotero, Pandoc and citeproc-js.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CslJsonItem {
+    #[serde(default, deserialize_with = "id_string")]
+    pub id: String,
+    #[serde(rename = "type", default)]
+    pub item_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub author: Vec<CslName>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub editor: Vec<CslName>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issued: Option<CslDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accessed: Option<CslDate>,
+    #[serde(
+        rename = "container-title",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub container_title: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "string_or_number",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub volume: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "string_or_number",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub issue: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "string_or_number",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub page: Option<String>,
+    #[serde(
+        rename = "number-of-pages",
+        default,
+        deserialize_with = "string_or_number",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub number_of_pages: Option<String>,
+    /// Report, patent or preprint number; Zotero stores `arXiv:<id>` here.
+    #[serde(
+        default,
+        deserialize_with = "string_or_number",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub number: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "string_or_number",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub edition: Option<String>,
+    #[serde(
+        rename = "collection-title",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub collection_title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publisher: Option<String>,
+    #[serde(
+        rename = "publisher-place",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub publisher_place: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(rename = "abstract", default, skip_serializing_if = "Option::is_none")]
+    pub abstract_text: Option<String>,
+    #[serde(rename = "DOI", default, skip_serializing_if = "Option::is_none")]
+    pub doi: Option<String>,
+    #[serde(rename = "ISBN", default, skip_serializing_if = "Option::is_none")]
+    pub isbn: Option<String>,
+    #[serde(
+        rename = "PMID",
+        default,
+        deserialize_with = "string_or_number",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub pmid: Option<String>,
+    #[serde(rename = "PMCID", default, skip_serializing_if = "Option::is_none")]
+    pub pmcid: Option<String>,
+    #[serde(rename = "URL", default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keyword: Option<String>,
+}
+
+/// A CSL name: either structured (`family`/`given` plus particles) or a
+/// `literal` for institutions.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CslName {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub family: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub given: Option<String>,
+    #[serde(
+        rename = "dropping-particle",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub dropping_particle: Option<String>,
+    #[serde(
+        rename = "non-dropping-particle",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub non_dropping_particle: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suffix: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub literal: Option<String>,
+}
+
+impl CslName {
+    /// The name as written on a card: `Ludwig van Beethoven Jr.`.
+    pub fn display(&self) -> Option<String> {
+        if let Some(literal) = non_blank(&self.literal) {
+            return Some(literal.to_string());
+        }
+        let parts: Vec<&str> = [
+            &self.given,
+            &self.dropping_particle,
+            &self.non_dropping_particle,
+            &self.family,
+            &self.suffix,
+        ]
+        .into_iter()
+        .filter_map(non_blank)
+        .collect();
+        (!parts.is_empty()).then(|| parts.join(" "))
+    }
+}
+
+/// A CSL date. Writers emit `date-parts`; `raw` and `literal` are only
+/// read, for exporters that don't split dates.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CslDate {
+    #[serde(
+        rename = "date-parts",
+        default,
+        deserialize_with = "date_parts",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub date_parts: Vec<Vec<i32>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub literal: Option<String>,
+}
+
+impl CslDate {
+    pub fn from_year(year: i32) -> Self {
+        Self {
+            date_parts: vec![vec![year]],
+            ..Default::default()
+        }
+    }
+
+    pub fn from_date(date: NaiveDate) -> Self {
+        Self {
+            date_parts: vec![vec![date.year(), date.month() as i32, date.day() as i32]],
+            ..Default::default()
+        }
+    }
+
+    /// Year of the (first) date, from `date-parts` or a four-digit `raw`.
+    pub fn year(&self) -> Option<i32> {
+        self.date_parts
+            .first()
+            .and_then(|parts| parts.first())
+            .copied()
+            .or_else(|| {
+                [&self.raw, &self.literal]
+                    .into_iter()
+                    .filter_map(non_blank)
+                    .find_map(parse_year)
+            })
+    }
+}
+
+/// Convert a card to a CSL-JSON item with the given `id` (usually its cite key).
+pub fn from_book_card(card: &BookCard, id: &str) -> CslJsonItem {
+    let publication = card.publication.as_ref();
+    let identifiers = card.identifiers.as_ref();
+    let item_type = publication
+        .map(|publication| publication_type_to_csl(publication.doc_type))
+        .unwrap_or("book");
+
+    CslJsonItem {
+        id: id.to_string(),
+        item_type: item_type.to_string(),
+        title: Some(card.metadata.title.clone()).filter(|title| !title.trim().is_empty()),
+        author: card
+            .metadata
+            .authors
+            .iter()
+            .filter_map(|author| name_from_author(author))
+            .collect(),
+        issued: card.metadata.year.map(CslDate::from_year),
+        // Same convention as the formatted bibliographies.
+        accessed: (item_type == "webpage")
+            .then(|| CslDate::from_date(card.created_at.date_naive())),
+        container_title: publication.and_then(|publication| {
+            publication
+                .journal
+                .clone()
+                .or_else(|| publication.conference.clone())
+                .or_else(|| publication.venue.clone())
+        }),
+        volume: publication.and_then(|publication| publication.volume.clone()),
+        issue: publication.and_then(|publication| publication.issue.clone()),
+        page: publication.and_then(|publication| publication.pages.clone()),
+        number_of_pages: card.metadata.pages.map(|pages| pages.to_string()),
+        number: identifiers
+            .and_then(|identifiers| identifiers.arxiv_id.as_deref())
+            .and_then(|raw| ArxivId::parse(raw).ok())
+            .map(|arxiv| format!("arXiv:{}", arxiv.id)),
+        edition: card.metadata.edition.map(|edition| edition.to_string()),
+        collection_title: card.metadata.series.clone(),
+        publisher: card.metadata.publisher.clone(),
+        language: card.metadata.language.clone(),
+        doi: identifiers
+            .and_then(|identifiers| identifiers.doi.as_deref())
+            .map(normalize_doi_for_export),
+        isbn: identifiers
+            .and_then(|identifiers| identifiers.isbn13.clone())
+            .or_else(|| card.metadata.isbn.first().cloned()),
+        pmid: identifiers.and_then(|identifiers| identifiers.pmid.clone()),
+        pmcid: identifiers.and_then(|identifiers| identifiers.pmcid.clone()),
+        url: derive_url(card),
+        keyword: Some(card.organization.tags.join(", ")).filter(|tags| !tags.is_empty()),
+        ..Default::default()
+    }
+}
+
+/// Serialize items as a pretty-printed CSL-JSON array.
+pub fn to_string(items: &[CslJsonItem]) -> Result<String> {
+    serde_json::to_string_pretty(items)
+        .map_err(|err| ScienceError::Parse(format!("CSL-JSON serialization failed: {err}")))
+}
+
+/// Parse a CSL-JSON document: an array of items, or a single item.
+pub fn parse(content: &str) -> Result<Vec<CslJsonItem>> {
+    let document: Value = serde_json::from_str(content)
+        .map_err(|err| ScienceError::Parse(format!("invalid CSL-JSON: {err}")))?;
+    match document {
+        Value::Array(values) => values
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| {
+                serde_json::from_value(value).map_err(|err| {
+                    ScienceError::Parse(format!("CSL-JSON item {}: {err}", index + 1))
+                })
+            })
+            .collect(),
+        value @ Value::Object(_) => serde_json::from_value(value)
+            .map(|item| vec![item])
+            .map_err(|err| ScienceError::Parse(format!("invalid CSL-JSON item: {err}"))),
+        _ => Err(ScienceError::Parse(
+            "CSL-JSON must be an array of items or a single item".to_string(),
+        )),
+    }
+}
+
+/// Map an item to a new card. The item `id` is kept as the cite key when it
+/// looks like one (Better BibTeX exports), not a Zotero URI.
+pub fn to_book_card(item: &CslJsonItem) -> BookCard {
+    let doc_type = csl_to_document_type(&item.item_type);
+    let container = non_blank(&item.container_title).map(ToOwned::to_owned);
+    let (journal, conference, venue) = match doc_type {
+        DocumentType::ConferencePaper => (None, container, None),
+        DocumentType::Chapter => (None, None, container),
+        _ => (container, None, None),
+    };
+    let names = if item.author.is_empty() {
+        &item.editor
+    } else {
+        &item.author
+    };
+    let text = |value: &Option<String>| non_blank(value).map(ToOwned::to_owned);
+
+    let title = text(&item.title)
+        .or_else(|| Some(item.id.clone()).filter(|id| !id.trim().is_empty()))
+        .unwrap_or_else(|| "Untitled".to_string());
+    let mut card = BookCard::new(title.clone());
+    card.merge_metadata(
+        PartialMetadata {
+            title: Some(title),
+            authors: names.iter().filter_map(CslName::display).collect(),
+            year: item.issued.as_ref().and_then(CslDate::year),
+            publisher: text(&item.publisher),
+            language: text(&item.language),
+            pages: non_blank(&item.number_of_pages).and_then(leading_number),
+            edition: non_blank(&item.edition).and_then(leading_number),
+            series: text(&item.collection_title),
+            tags: non_blank(&item.keyword)
+                .map(split_keywords)
+                .unwrap_or_default(),
+            abstract_text: text(&item.abstract_text),
+            doi: non_blank(&item.doi).and_then(|raw| Doi::parse(raw).ok()),
+            arxiv_id: non_blank(&item.number).and_then(|raw| ArxivId::parse(raw).ok()),
+            isbn: non_blank(&item.isbn)
+                .map(parse_isbn_list)
+                .unwrap_or_default(),
+            pmid: text(&item.pmid),
+            pmcid: text(&item.pmcid),
+            doc_type: Some(doc_type),
+            journal,
+            conference,
+            venue,
+            volume: text(&item.volume),
+            issue: text(&item.issue),
+            publication_pages: text(&item.page),
+            ..Default::default()
+        },
+        MetadataSource::BibImport,
+    );
+
+    if let Some(url) = text(&item.url) {
+        card.web.sources.push(WebSource {
+            name: "csl-json".to_string(),
+            url,
+        });
+    }
+    if looks_like_cite_key(&item.id) {
+        card.identifiers
+            .get_or_insert_with(Default::default)
+            .cite_key = Some(item.id.trim().to_string());
+    }
+    card
+}
+
+fn csl_to_document_type(item_type: &str) -> DocumentType {
+    match item_type.trim() {
+        "article-journal" | "review" | "review-book" => DocumentType::Article,
+        "article-magazine" | "article-newspaper" => DocumentType::MagazineArticle,
+        "book" | "classic" => DocumentType::Book,
+        "chapter" | "entry" | "entry-dictionary" | "entry-encyclopedia" => DocumentType::Chapter,
+        "paper-conference" => DocumentType::ConferencePaper,
+        "article" | "manuscript" => DocumentType::Preprint,
+        "thesis" => DocumentType::Thesis,
+        "report" => DocumentType::Report,
+        "dataset" => DocumentType::Dataset,
+        "software" => DocumentType::Software,
+        "patent" => DocumentType::Patent,
+        "standard" | "legislation" | "regulation" => DocumentType::Standard,
+        "webpage" | "post" | "post-weblog" => DocumentType::WebPage,
+        _ => DocumentType::Other,
+    }
+}
+
+fn looks_like_cite_key(id: &str) -> bool {
+    let id = id.trim();
+    !id.is_empty()
+        && !id.chars().all(|ch| ch.is_ascii_digit())
+        && !id.contains(|ch: char| ch == '/' || ch.is_whitespace())
+}
+
+fn name_from_author(author: &str) -> Option<CslName> {
+    let parts = split_name(author)?;
+    if parts.given_names.is_empty() {
+        return Some(CslName {
+            literal: Some(parts.family),
+            ..Default::default()
+        });
+    }
+    Some(CslName {
+        family: Some(parts.family),
+        given: Some(parts.given_names.join(" ")),
+        ..Default::default()
+    })
+}
+
+fn non_blank(value: &Option<String>) -> Option<&str> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+fn scalar_to_string(value: Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text),
+        Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+/// CSL allows numeric variables to be written as numbers or strings.
+fn string_or_number<'de, D>(deserializer: D) -> std::result::Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<Value>::deserialize(deserializer)?.and_then(scalar_to_string))
+}
+
+fn id_string<'de, D>(deserializer: D) -> std::result::Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(string_or_number(deserializer)?.unwrap_or_default())
+}
+
+/// `[[2017, 6, 12]]`, also with the parts written as strings.
+fn date_parts<'de, D>(deserializer: D) -> std::result::Result<Vec<Vec<i32>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw = Option::<Vec<Vec<Value>>>::deserialize(deserializer)?.unwrap_or_default();
+    Ok(raw
+        .into_iter()
+        .map(|parts| {
+            parts
+                .into_iter()
+                .map_while(|part| scalar_to_string(part)?.trim().parse().ok())
+                .collect::<Vec<i32>>()
+        })
+        .filter(|parts| !parts.is_empty())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use omniscope_core::models::{BookPublication, DocumentType, ScientificIdentifiers};
+
+    use super::*;
+
+    #[test]
+    fn card_exports_as_csl_json_item() {
+        let mut card = BookCard::new("Attention Is All You Need");
+        card.metadata.authors = vec!["Ashish Vaswani".to_string(), "OpenAI".to_string()];
+        card.metadata.year = Some(2017);
+        card.identifiers = Some(ScientificIdentifiers {
+            doi: Some("10.48550/arXiv.1706.03762".to_string()),
+            ..Default::default()
+        });
+        card.publication = Some(BookPublication {
+            doc_type: DocumentType::ConferencePaper,
+            conference: Some("NeurIPS".to_string()),
+            pages: Some("5998-6008".to_string()),
+            ..Default::default()
+        });
+
+        let item = from_book_card(&card, "Vaswani2017Attention");
+        let json: serde_json::Value = serde_json::from_str(&to_string(&[item]).unwrap()).unwrap();
+        let item = &json[0];
+
+        assert_eq!(item["id"], "Vaswani2017Attention");
+        assert_eq!(item["type"], "paper-conference");
+        assert_eq!(item["author"][0]["family"], "Vaswani");
+        assert_eq!(item["author"][0]["given"], "Ashish");
+        assert_eq!(item["author"][1]["literal"], "OpenAI");
+        assert_eq!(item["issued"]["date-parts"][0][0], 2017);
+        assert_eq!(item["container-title"], "NeurIPS");
+        assert_eq!(item["page"], "5998-6008");
+        assert_eq!(item["DOI"], "10.48550/arxiv.1706.03762");
+        assert!(item.get("volume").is_none());
+    }
+
+    #[test]
+    fn zotero_items_parse_into_cards() {
+        let items = parse(
+            r#"[
+              {
+                "id": "http://zotero.org/users/1/items/ABCD1234",
+                "type": "article-journal",
+                "title": "Symphony analysis",
+                "author": [
+                  {"family": "Beethoven", "given": "Ludwig", "non-dropping-particle": "van"},
+                  {"literal": "Vienna Philharmonic"}
+                ],
+                "issued": {"date-parts": [["1808", "12", "22"]]},
+                "container-title": "Journal of Music",
+                "volume": 12,
+                "issue": "3",
+                "page": "101-120",
+                "DOI": "https://doi.org/10.1000/XYZ123",
+                "PMID": 123456,
+                "ISBN": "978-0-306-40615-7",
+                "keyword": "music, classical",
+                "abstract": "A close reading.",
+                "URL": "https://example.org/symphony"
+              },
+              {
+                "id": "Vaswani2017",
+                "type": "article",
+                "title": "Attention Is All You Need",
+                "number": "arXiv:1706.03762",
+                "issued": {"raw": "2017-06-12"}
+              }
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(
+            items[0].issued.as_ref().unwrap().date_parts,
+            vec![vec![1808, 12, 22]]
+        );
+
+        let card = to_book_card(&items[0]);
+        assert_eq!(
+            card.metadata.authors,
+            vec!["Ludwig van Beethoven", "Vienna Philharmonic"]
+        );
+        assert_eq!(card.metadata.year, Some(1808));
+        assert_eq!(card.organization.tags, vec!["music", "classical"]);
+        let publication = card.publication.as_ref().unwrap();
+        assert_eq!(publication.doc_type, DocumentType::Article);
+        assert_eq!(publication.journal.as_deref(), Some("Journal of Music"));
+        assert_eq!(publication.volume.as_deref(), Some("12"));
+        assert_eq!(publication.pages.as_deref(), Some("101-120"));
+        let identifiers = card.identifiers.as_ref().unwrap();
+        assert_eq!(identifiers.doi.as_deref(), Some("10.1000/xyz123"));
+        assert_eq!(identifiers.pmid.as_deref(), Some("123456"));
+        assert_eq!(identifiers.cite_key, None);
+        assert_eq!(card.web.sources[0].url, "https://example.org/symphony");
+
+        let preprint = to_book_card(&items[1]);
+        assert_eq!(preprint.metadata.year, Some(2017));
+        let identifiers = preprint.identifiers.as_ref().unwrap();
+        assert_eq!(identifiers.arxiv_id.as_deref(), Some("1706.03762"));
+        assert_eq!(identifiers.cite_key.as_deref(), Some("Vaswani2017"));
+    }
+
+    #[test]
+    fn cards_round_trip_through_csl_json() {
+        let mut card = BookCard::new("Distributed Systems");
+        card.metadata.authors = vec!["Maarten Van Steen".to_string()];
+        card.metadata.year = Some(2017);
+        card.metadata.edition = Some(3);
+        card.metadata.publisher = Some("CreateSpace".to_string());
+        card.organization.tags = vec!["distributed".to_string()];
+        card.publication = Some(BookPublication {
+            doc_type: DocumentType::Chapter,
+            venue: Some("Handbook of Systems".to_string()),
+            pages: Some("1-42".to_string()),
+            ..Default::default()
+        });
+
+        let json = to_string(&[from_book_card(&card, "VanSteen2017")]).unwrap();
+        let items = parse(&json).unwrap();
+        assert_eq!(items[0].item_type, "chapter");
+        let restored = to_book_card(&items[0]);
+
+        assert_eq!(restored.metadata.title, card.metadata.title);
+        assert_eq!(restored.metadata.authors, card.metadata.authors);
+        assert_eq!(restored.metadata.year, Some(2017));
+        assert_eq!(restored.metadata.edition, Some(3));
+        assert_eq!(restored.organization.tags, card.organization.tags);
+        let publication = restored.publication.as_ref().unwrap();
+        assert_eq!(publication.doc_type, DocumentType::Chapter);
+        assert_eq!(publication.venue.as_deref(), Some("Handbook of Systems"));
+        assert_eq!(
+            restored.identifiers.as_ref().unwrap().cite_key.as_deref(),
+            Some("VanSteen2017")
+        );
+    }
+
+    #[test]
+    fn single_objects_parse_and_scalars_are_rejected() {
+        let items = parse(r#"{"id": 7, "type": "book", "title": "Dune"}"#).unwrap();
+        assert_eq!(items[0].id, "7");
+        let card = to_book_card(&items[0]);
+        assert!(
+            card.identifiers
+                .and_then(|identifiers| identifiers.cite_key)
+                .is_none()
+        );
+        assert!(parse("42").is_err());
+        assert!(parse(r#"[{"id": "x", "author": "nobody"}]"#).is_err());
+    }
+}
@@ -1,3 +1,4 @@
 pub mod bibtex;
 pub mod csl;
+pub mod csl_json;
 pub mod ris;
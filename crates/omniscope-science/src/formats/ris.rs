@@ -1,7 +1,9 @@
 use std::collections::BTreeMap;
 
-use omniscope_core::models::{BookCard, DocumentType};
+use omniscope_core::models::{BookCard, DocumentType, WebSource};
 
+use super::bibtex::{parse_isbn_list, parse_year, split_keywords};
+use crate::enrichment::{BookCardMergeExt, MetadataSource, PartialMetadata};
 use crate::error::{Result, ScienceError};
 use crate::identifiers::{arxiv::ArxivId, doi::Doi};
 
@@ -21,6 +23,20 @@ impl RisEntry {
 }
 
 pub fn generate_ris(card: &BookCard) -> String {
+    render_ris(card, None)
+}
+
+/// Render several cards as one RIS file; `ids` become the `ID` tags.
+pub fn generate_ris_set(cards: &[&BookCard], ids: &[String]) -> String {
+    cards
+        .iter()
+        .zip(ids)
+        .map(|(card, id)| render_ris(card, Some(id)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_ris(card: &BookCard, id: Option<&str>) -> String {
     let mut lines = Vec::new();
     lines.push(format!(
         "TY  - {}",
@@ -31,6 +47,7 @@ pub fn generate_ris(card: &BookCard) -> String {
                 .unwrap_or(DocumentType::Book),
         )
     ));
+    push_line(&mut lines, "ID", id.map(str::to_string));
 
     push_line(&mut lines, "TI", Some(card.metadata.title.clone()));
     for author in &card.metadata.authors {
@@ -134,6 +151,105 @@ pub fn parse_ris(content: &str) -> Result<Vec<RisEntry>> {
     Ok(entries)
 }
 
+/// Card for a parsed RIS record. Attachments are left to the caller, see
+/// [`file_paths`].
+pub fn to_book_card(entry: &RisEntry) -> BookCard {
+    let first = |tags: &[&str]| {
+        tags.iter()
+            .find_map(|tag| entry.first(tag))
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(ToOwned::to_owned)
+    };
+    let all = |tags: &[&str]| -> Vec<String> {
+        tags.iter()
+            .flat_map(|tag| entry.fields.get(*tag).into_iter().flatten())
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .collect()
+    };
+
+    let doc_type = ris_to_document_type(&entry.entry_type);
+    let container = first(&["T2", "JO", "JF", "JA", "J2"]);
+    let (journal, conference, venue) = match doc_type {
+        DocumentType::ConferencePaper => (None, container, None),
+        DocumentType::Chapter => (None, None, container),
+        _ => (first(&["JO", "JF", "T2", "JA", "J2"]), None, None),
+    };
+    let pages = match (first(&["SP"]), first(&["EP"])) {
+        (Some(start), Some(end)) if !start.contains('-') => Some(format!("{start}-{end}")),
+        (start, _) => start,
+    };
+
+    let title = first(&["TI", "T1", "CT", "BT"]).unwrap_or_else(|| "Untitled".to_string());
+    let mut card = BookCard::new(title.clone());
+    card.merge_metadata(
+        PartialMetadata {
+            title: Some(title),
+            authors: all(&["AU", "A1"])
+                .iter()
+                .map(|author| ris_author_to_display(author))
+                .collect(),
+            year: first(&["PY", "Y1", "DA"]).and_then(|raw| parse_year(&raw)),
+            publisher: first(&["PB"]),
+            language: first(&["LA"]),
+            edition: first(&["ET"]).and_then(|raw| raw.parse().ok()),
+            series: first(&["T3"]),
+            tags: all(&["KW"])
+                .iter()
+                .flat_map(|keyword| split_keywords(keyword))
+                .collect(),
+            abstract_text: first(&["AB", "N2"]),
+            doi: first(&["DO"]).and_then(|raw| Doi::parse(&raw).ok()),
+            isbn: all(&["SN"])
+                .iter()
+                .flat_map(|raw| parse_isbn_list(raw))
+                .collect(),
+            doc_type: Some(doc_type),
+            journal,
+            conference,
+            venue,
+            volume: first(&["VL"]),
+            issue: first(&["IS"]),
+            publication_pages: pages,
+            ..Default::default()
+        },
+        MetadataSource::BibImport,
+    );
+
+    for url in all(&["UR"]) {
+        card.web.sources.push(WebSource {
+            name: "ris".to_string(),
+            url,
+        });
+    }
+    if let Some(key) = first(&["ID"]) {
+        card.identifiers
+            .get_or_insert_with(Default::default)
+            .cite_key = Some(key);
+    }
+    card
+}
+
+/// Local attachments (`L1`, `L4`), with `file://` prefixes removed.
+pub fn file_paths(entry: &RisEntry) -> Vec<String> {
+    ["L1", "L4"]
+        .iter()
+        .flat_map(|tag| entry.fields.get(*tag).into_iter().flatten())
+        .flat_map(|value| value.split(';'))
+        .map(str::trim)
+        .filter(|value| !value.starts_with("http://") && !value.starts_with("https://"))
+        .map(|value| {
+            value
+                .strip_prefix("file://")
+                .or_else(|| value.strip_prefix("file:"))
+                .unwrap_or(value)
+                .replace("%20", " ")
+        })
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
 fn document_type_to_ris(doc_type: DocumentType) -> &'static str {
     match doc_type {
         DocumentType::Article | DocumentType::MagazineArticle => "JOUR",
@@ -176,6 +292,38 @@ fn author_to_ris(value: &str) -> String {
     format!("{family}, {given}")
 }
 
+fn ris_to_document_type(entry_type: &str) -> DocumentType {
+    match entry_type.trim() {
+        "JOUR" | "JFULL" | "EJOUR" => DocumentType::Article,
+        "BOOK" | "EBOOK" | "EDBOOK" => DocumentType::Book,
+        "CHAP" | "ECHAP" => DocumentType::Chapter,
+        "CONF" | "CPAPER" => DocumentType::ConferencePaper,
+        "THES" => DocumentType::Thesis,
+        "RPRT" => DocumentType::Report,
+        "MGZN" | "NEWS" => DocumentType::MagazineArticle,
+        "ELEC" | "WEB" | "BLOG" => DocumentType::WebPage,
+        "DATA" | "DBASE" => DocumentType::Dataset,
+        "COMP" => DocumentType::Software,
+        "PAT" => DocumentType::Patent,
+        "STAND" => DocumentType::Standard,
+        "UNPB" | "INPR" => DocumentType::Preprint,
+        _ => DocumentType::Other,
+    }
+}
+
+/// `Vaswani, Ashish` → `Ashish Vaswani`; other spellings are kept.
+fn ris_author_to_display(value: &str) -> String {
+    let cleaned = value.trim();
+    let Some((family, given)) = cleaned.split_once(',') else {
+        return cleaned.to_string();
+    };
+    match given.split_once(',') {
+        Some((given, suffix)) => format!("{} {} {}", given.trim(), family.trim(), suffix.trim()),
+        None if given.trim().is_empty() => family.trim().to_string(),
+        None => format!("{} {}", given.trim(), family.trim()),
+    }
+}
+
 fn normalize_doi_for_export(raw: &str) -> String {
     Doi::parse(raw)
         .map(|doi| doi.normalized)
@@ -246,4 +394,29 @@ mod tests {
         assert_eq!(entry.first("TI"), Some("Attention Is All You Need"));
         assert_eq!(entry.first("DO"), Some("10.48550/arxiv.1706.03762"));
     }
+
+    #[test]
+    fn to_book_card_maps_ris_record() {
+        let text = "TY  - JOUR\nTI  - Deep Residual Learning\nAU  - He, Kaiming\nAU  - Zhang, Xiangyu\nPY  - 2016///\nJO  - CVPR Journal\nSP  - 770\nEP  - 778\nSN  - 978-0-306-40615-7\nKW  - vision\nL1  - file:///home/me/papers/resnet%20v2.pdf\nUR  - https://example.org/resnet\nER  -\n";
+        let entries = parse_ris(text).expect("valid RIS");
+        let card = to_book_card(&entries[0]);
+
+        assert_eq!(card.metadata.title, "Deep Residual Learning");
+        assert_eq!(card.metadata.authors, vec!["Kaiming He", "Xiangyu Zhang"]);
+        assert_eq!(card.metadata.year, Some(2016));
+        assert_eq!(card.organization.tags, vec!["vision"]);
+        let publication = card.publication.as_ref().expect("publication");
+        assert_eq!(publication.journal.as_deref(), Some("CVPR Journal"));
+        assert_eq!(publication.pages.as_deref(), Some("770-778"));
+        assert_eq!(
+            card.identifiers
+                .as_ref()
+                .and_then(|ids| ids.isbn13.as_deref()),
+            Some("9780306406157")
+        );
+        assert_eq!(
+            file_paths(&entries[0]),
+            vec!["/home/me/papers/resnet v2.pdf"]
+        );
+    }
 }
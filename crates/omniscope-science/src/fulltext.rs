@@ -0,0 +1,178 @@
+//! Plain text of a card's attached file, for AI indexing.
+
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use omniscope_core::models::{BookCard, FileFormat};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use zip::ZipArchive;
+
+use crate::enrichment::pipeline::{parse_container_full_path, read_zip_entry_to_string};
+use crate::error::{Result, ScienceError};
+use crate::references::{PdfTextExtractor, PdftotextExtractor};
+
+static SPINE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?is)<itemref\b[^>]*\bidref\s*=\s*["']([^"']+)["']"#).expect("valid regex")
+});
+static ITEM_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?is)<item\b[^>]*>").expect("valid regex"));
+static ATTR_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?is)\b(id|href|media-type)\s*=\s*["']([^"']+)["']"#).expect("valid regex")
+});
+static INVISIBLE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?is)<(head|script|style)\b.*?</(head|script|style)>").expect("valid regex")
+});
+static BLOCK_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)</?(p|div|br|h[1-6]|li|tr|section|blockquote)\b[^>]*>").expect("valid regex")
+});
+static TAG_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<[^>]+>").expect("valid regex"));
+static BLANK_LINES_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\n[ \t]*(\n[ \t]*)+").expect("valid regex"));
+
+/// Text of the card's file, or `None` when it has none or the format has no
+/// text extractor (DjVu, comics, …).
+pub fn extract_card_text(card: &BookCard) -> Result<Option<String>> {
+    let Some(file) = card.file.as_ref() else {
+        return Ok(None);
+    };
+    let path = Path::new(file.path.as_str());
+    let text = match file.format {
+        FileFormat::Pdf => PdftotextExtractor.extract_text(path)?,
+        FileFormat::Epub => extract_epub_text(path)?,
+        FileFormat::Html => html_to_text(&read_file(path)?),
+        FileFormat::Txt => read_file(path)?,
+        _ => return Ok(None),
+    };
+    Ok(Some(text))
+}
+
+/// Chapters of an EPUB in reading (spine) order, as plain text.
+pub fn extract_epub_text(epub_path: &Path) -> Result<String> {
+    let file = File::open(epub_path).map_err(|e| {
+        ScienceError::Parse(format!("failed to open EPUB {}: {e}", epub_path.display()))
+    })?;
+    let mut archive =
+        ZipArchive::new(file).map_err(|e| ScienceError::Parse(format!("invalid EPUB ZIP: {e}")))?;
+
+    let documents = read_zip_entry_to_string(&mut archive, "META-INF/container.xml")
+        .ok()
+        .and_then(|container| parse_container_full_path(&container))
+        .and_then(|opf_path| {
+            let opf = read_zip_entry_to_string(&mut archive, &opf_path).ok()?;
+            Some(spine_documents(&opf, &opf_path))
+        })
+        .filter(|documents| !documents.is_empty())
+        .unwrap_or_else(|| {
+            // No usable package document: fall back to every XHTML file.
+            let mut names: Vec<String> = archive
+                .file_names()
+                .filter(|name| is_html_name(name))
+                .map(ToOwned::to_owned)
+                .collect();
+            names.sort();
+            names
+        });
+
+    let chapters: Vec<String> = documents
+        .iter()
+        .filter_map(|name| read_zip_entry_to_string(&mut archive, name).ok())
+        .map(|html| html_to_text(&html))
+        .filter(|text| !text.is_empty())
+        .collect();
+    Ok(chapters.join("\n\n"))
+}
+
+/// Archive paths of the spine's XHTML documents, resolved against the OPF.
+fn spine_documents(opf_xml: &str, opf_path: &str) -> Vec<String> {
+    let base = Path::new(opf_path)
+        .parent()
+        .map(PathBuf::from)
+        .unwrap_or_default();
+    let manifest: Vec<(String, String, String)> = ITEM_RE
+        .find_iter(opf_xml)
+        .map(|item| {
+            let mut attrs = (String::new(), String::new(), String::new());
+            for caps in ATTR_RE.captures_iter(item.as_str()) {
+                let value = caps[2].to_string();
+                match caps[1].to_ascii_lowercase().as_str() {
+                    "id" => attrs.0 = value,
+                    "href" => attrs.1 = value,
+                    _ => attrs.2 = value,
+                }
+            }
+            attrs
+        })
+        .collect();
+
+    SPINE_RE
+        .captures_iter(opf_xml)
+        .filter_map(|caps| {
+            let (_, href, media_type) = manifest.iter().find(|(id, _, _)| id == &caps[1])?;
+            (media_type.contains("html") || is_html_name(href))
+                .then(|| base.join(href).to_string_lossy().replace('\\', "/"))
+        })
+        .collect()
+}
+
+fn is_html_name(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    name.ends_with(".xhtml") || name.ends_with(".html") || name.ends_with(".htm")
+}
+
+/// Visible text of an (X)HTML document, one paragraph per line.
+pub fn html_to_text(html: &str) -> String {
+    let text = INVISIBLE_RE.replace_all(html, "");
+    let text = BLOCK_RE.replace_all(&text, "\n");
+    let text = TAG_RE.replace_all(&text, "");
+    let text = decode_entities(&text);
+    BLANK_LINES_RE.replace_all(text.trim(), "\n\n").to_string()
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn read_file(path: &Path) -> Result<String> {
+    let bytes = fs::read(path)
+        .map_err(|e| ScienceError::Parse(format!("failed to read {}: {e}", path.display())))?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn html_to_text_keeps_paragraphs_and_drops_markup() {
+        let html = r#"<html><head><title>x</title><style>p {}</style></head>
+            <body><h1>Chapter 1</h1><p>It was a <em>dark</em> &amp; stormy night.</p>
+            <p>Second&nbsp;paragraph.</p></body></html>"#;
+
+        let text = html_to_text(html);
+        assert!(!text.contains("p {}"));
+        assert!(text.starts_with("Chapter 1"));
+        assert!(text.contains("It was a dark & stormy night."));
+        assert!(text.contains("Second paragraph."));
+    }
+
+    #[test]
+    fn spine_documents_follow_reading_order() {
+        let opf = r#"<package><manifest>
+            <item id="c2" href="text/ch2.xhtml" media-type="application/xhtml+xml"/>
+            <item href="text/ch1.xhtml" id="c1" media-type="application/xhtml+xml"/>
+            <item id="css" href="style.css" media-type="text/css"/>
+            </manifest><spine><itemref idref="c1"/><itemref idref="css"/><itemref idref="c2"/></spine>
+            </package>"#;
+
+        assert_eq!(
+            spine_documents(opf, "OEBPS/content.opf"),
+            vec!["OEBPS/text/ch1.xhtml", "OEBPS/text/ch2.xhtml"]
+        );
+    }
+}
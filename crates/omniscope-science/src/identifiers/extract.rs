@@ -35,6 +35,12 @@ static ARXIV_BRACKET_REGEX: Lazy<std::result::Result<Regex, regex::Error>> =
 static ISBN13_REGEX: Lazy<std::result::Result<Regex, regex::Error>> =
     Lazy::new(|| Regex::new(r"(?i)\b(?:isbn(?:-13)?:?\s*)?((?:97[89][-\s]?)(?:\d[-\s]?){9}\d)\b"));
 
+static PMID_REGEX: Lazy<std::result::Result<Regex, regex::Error>> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)^(?:pmid:?\s*|https?://(?:www\.)?(?:pubmed\.ncbi\.nlm\.nih\.gov|ncbi\.nlm\.nih\.gov/pubmed)/)?(\d{1,8})/?$",
+    )
+});
+
 static ISBN10_REGEX: Lazy<std::result::Result<Regex, regex::Error>> =
     Lazy::new(|| Regex::new(r"(?i)\b(?:isbn(?:-10)?:?\s*)?((?:\d[-\s]?){9}[\dXx])\b"));
 
@@ -120,6 +126,42 @@ pub fn extract_isbn_from_text(text: &str) -> Option<Isbn> {
     None
 }
 
+/// An identifier recognised in user input by [`detect_identifier`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Identifier {
+    Doi(Doi),
+    Arxiv(ArxivId),
+    Isbn(Isbn),
+    /// PubMed id, digits only.
+    Pmid(String),
+}
+
+/// Work out what kind of identifier `input` is: a DOI (bare, `doi:` or a
+/// doi.org URL), an arXiv id or URL, an ISBN, or a PubMed id (`PMID:`, a
+/// PubMed URL, or up to eight bare digits).
+pub fn detect_identifier(input: &str) -> Option<Identifier> {
+    let input = input.trim();
+    if let Some(doi) = extract_dois_from_text(input).into_iter().next() {
+        return Some(Identifier::Doi(doi));
+    }
+    if let Some(arxiv_id) = extract_arxiv_ids_from_text(input)
+        .into_iter()
+        .next()
+        .or_else(|| ArxivId::parse(input).ok())
+    {
+        return Some(Identifier::Arxiv(arxiv_id));
+    }
+    if let Ok(re) = PMID_REGEX.as_ref()
+        && let Some(m) = re.captures(input).and_then(|caps| caps.get(1))
+    {
+        return Some(Identifier::Pmid(m.as_str().to_string()));
+    }
+    Isbn::parse(input)
+        .ok()
+        .or_else(|| extract_isbn_from_text(input))
+        .map(Identifier::Isbn)
+}
+
 pub fn find_doi_in_first_page(pdf_path: &Path) -> Result<Doi> {
     let text = extract_pdf_first_pages_text(pdf_path)?;
     extract_dois_from_text(&text)
@@ -332,6 +374,29 @@ mod tests {
         assert_eq!(isbn.isbn10.as_deref(), Some("007462542X"));
     }
 
+    #[test]
+    fn detects_identifier_kind() {
+        let kind = |input: &str| match detect_identifier(input) {
+            Some(Identifier::Doi(doi)) => format!("doi {}", doi.normalized),
+            Some(Identifier::Arxiv(id)) => format!("arxiv {}", id.id),
+            Some(Identifier::Isbn(isbn)) => format!("isbn {}", isbn.isbn13),
+            Some(Identifier::Pmid(pmid)) => format!("pmid {pmid}"),
+            None => "none".to_string(),
+        };
+
+        assert_eq!(kind("https://doi.org/10.1000/XYZ123"), "doi 10.1000/xyz123");
+        assert_eq!(kind("2301.04567v2"), "arxiv 2301.04567");
+        assert_eq!(kind("https://arxiv.org/abs/1706.03762"), "arxiv 1706.03762");
+        assert_eq!(kind("978-0-306-40615-7"), "isbn 9780306406157");
+        assert_eq!(kind("PMID: 31452104"), "pmid 31452104");
+        assert_eq!(
+            kind("https://pubmed.ncbi.nlm.nih.gov/31452104/"),
+            "pmid 31452104"
+        );
+        assert_eq!(kind("31452104"), "pmid 31452104");
+        assert_eq!(kind("not an identifier"), "none");
+    }
+
     #[test]
     fn returns_none_when_isbn_missing() {
         let text = "There is no valid isbn in this line: 123-456";
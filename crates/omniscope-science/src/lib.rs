@@ -1,11 +1,16 @@
-//! Omniscope Science — arXiv, DOI, CrossRef, metadata enrichment.
+//! Omniscope Science — arXiv, DOI, CrossRef, metadata enrichment, BibTeX/RIS import,
+//! full-text extraction.
 
 pub mod arxiv;
+pub mod bib_import;
+pub mod bib_sync;
 pub mod config;
 pub mod dedup;
 pub mod enrichment;
+pub mod export;
 pub mod error;
 pub mod formats;
+pub mod fulltext;
 pub mod http;
 pub mod identifiers;
 pub mod references;
@@ -30,7 +30,8 @@ pub trait PdfTextExtractor: Send + Sync {
     fn extract_text(&self, pdf_path: &Path) -> Result<String>;
 }
 
-struct PdftotextExtractor;
+/// Text of a whole PDF: lopdf first, the `pdftotext` tool as a fallback.
+pub struct PdftotextExtractor;
 
 impl PdfTextExtractor for PdftotextExtractor {
     fn extract_text(&self, pdf_path: &Path) -> Result<String> {
@@ -331,7 +332,7 @@ fn extract_pdf_text_with_pdftotext(pdf_path: &Path) -> Result<String> {
                     let _ = child.wait();
                     let _ = fs::remove_file(&output_path);
                     return Err(ScienceError::PdfExtraction(
-                        "pdftotext timed out while extracting references".to_string(),
+                        "pdftotext timed out while extracting text".to_string(),
                     ));
                 }
                 thread::sleep(Duration::from_millis(75));
@@ -0,0 +1,710 @@
+//! DBLP, the computer science bibliography.
+//!
+//! Records are fetched as XML from `/rec/{key}.xml`; a conference paper's
+//! record points (`<crossref>`) at its proceedings, whose title is the full
+//! conference name DBLP itself uses as BibTeX `booktitle`. Search and DOI
+//! lookup go through the JSON publication search API.
+
+#[cfg(test)]
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use quick_xml::de::from_str;
+use regex::Regex;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{Result, ScienceError};
+use crate::http::{DiskCache, RateLimitedClient};
+use crate::identifiers::doi::Doi;
+use crate::sources::{
+    DownloadUrl, ExternalSource, Metadata, RateLimit, SearchResult, SourceStatus, SourceType,
+};
+
+const BASE_URL: &str = "https://dblp.org";
+const CACHE_TTL_SECS: u64 = 7 * 24 * 60 * 60;
+#[cfg(test)]
+static TEST_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Inline markup DBLP keeps in titles (`<i>`, `<sub>`, …).
+static INLINE_MARKUP_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"</?(?:i|b|u|sup|sub|tt)>").expect("valid regex"));
+/// The homonym number DBLP appends to author names, as in `Wei Wang 0001`.
+static HOMONYM_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+\d{4}$").expect("valid regex"));
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub enum DblpEntryType {
+    Conference,
+    Journal,
+    /// CoRR and other informal publications, mostly arXiv preprints.
+    Informal,
+    Book,
+    Chapter,
+    Thesis,
+    Editorship,
+    #[default]
+    Other,
+}
+
+impl DblpEntryType {
+    /// From the record element name and its `publtype` attribute.
+    fn from_record(element: &str, key: &str, publtype: Option<&str>) -> Self {
+        if publtype == Some("informal") || key.starts_with("journals/corr/") {
+            return Self::Informal;
+        }
+        match element {
+            "inproceedings" => Self::Conference,
+            "article" => Self::Journal,
+            "incollection" => Self::Chapter,
+            "book" => Self::Book,
+            "phdthesis" | "mastersthesis" => Self::Thesis,
+            "proceedings" => Self::Editorship,
+            _ => Self::Other,
+        }
+    }
+
+    /// From the `type` the search API reports.
+    fn from_search_type(kind: &str, key: &str) -> Self {
+        if key.starts_with("journals/corr/") {
+            return Self::Informal;
+        }
+        match kind {
+            "Conference and Workshop Papers" => Self::Conference,
+            "Journal Articles" => Self::Journal,
+            "Informal and Other Publications" | "Informal Publications" => Self::Informal,
+            "Parts in Books or Collections" => Self::Chapter,
+            "Books and Theses" if key.starts_with("phd/") => Self::Thesis,
+            "Books and Theses" => Self::Book,
+            "Editorship" => Self::Editorship,
+            _ => Self::Other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct DblpPublication {
+    pub key: String,
+    pub entry_type: DblpEntryType,
+    pub title: String,
+    pub authors: Vec<String>,
+    /// DBLP's short venue name: the journal, or the `booktitle` of a
+    /// conference paper (`NeurIPS`, `ACL (1)`).
+    pub venue: Option<String>,
+    /// Full title of the proceedings a conference paper appeared in.
+    pub proceedings_title: Option<String>,
+    /// Key of the proceedings or collection the record is part of.
+    pub crossref: Option<String>,
+    pub year: Option<i32>,
+    pub volume: Option<String>,
+    pub number: Option<String>,
+    pub pages: Option<String>,
+    pub doi: Option<String>,
+    pub ee: Vec<String>,
+}
+
+impl DblpPublication {
+    pub fn from_search_hit(info: &Value) -> Option<Self> {
+        let key = info.get("key").and_then(Value::as_str)?.to_string();
+        let kind = info.get("type").and_then(Value::as_str).unwrap_or_default();
+
+        let authors = one_or_many(info.get("authors").and_then(|a| a.get("author")))
+            .into_iter()
+            .filter_map(|author| {
+                author
+                    .get("text")
+                    .and_then(Value::as_str)
+                    .or_else(|| author.as_str())
+            })
+            .map(clean_author_name)
+            .collect();
+        let ee = one_or_many(info.get("ee"))
+            .into_iter()
+            .filter_map(Value::as_str)
+            .map(ToOwned::to_owned)
+            .collect::<Vec<_>>();
+        let venue = one_or_many(info.get("venue"))
+            .into_iter()
+            .filter_map(Value::as_str)
+            .next()
+            .map(ToOwned::to_owned);
+        let doi = text_field(info, "doi").or_else(|| doi_from_links(&ee));
+
+        Some(Self {
+            entry_type: DblpEntryType::from_search_type(kind, &key),
+            title: clean_title(info.get("title").and_then(Value::as_str).unwrap_or("")),
+            authors,
+            venue,
+            proceedings_title: None,
+            crossref: None,
+            year: text_field(info, "year").and_then(|year| year.parse().ok()),
+            volume: text_field(info, "volume"),
+            number: text_field(info, "number"),
+            pages: text_field(info, "pages"),
+            doi,
+            ee,
+            key,
+        })
+    }
+}
+
+pub struct DblpSource {
+    pub client: RateLimitedClient,
+    pub cache: DiskCache,
+    base_url: String,
+}
+
+impl DblpSource {
+    pub fn new() -> Self {
+        Self::with_config(
+            BASE_URL.to_string(),
+            Duration::from_secs(1),
+            Duration::from_secs(CACHE_TTL_SECS),
+            "dblp".to_string(),
+        )
+    }
+
+    /// The record behind a DBLP key such as `conf/nips/VaswaniSPUJGKP17`,
+    /// with the proceedings title filled in for conference papers.
+    pub async fn fetch_by_key(&self, key: &str) -> Result<DblpPublication> {
+        let mut publication = self.fetch_record(key).await?;
+        if publication.entry_type == DblpEntryType::Conference
+            && let Some(crossref) = publication.crossref.as_deref()
+        {
+            publication.proceedings_title = self
+                .fetch_record(crossref)
+                .await
+                .ok()
+                .map(|proceedings| proceedings.title);
+        }
+        Ok(publication)
+    }
+
+    /// The DBLP record carrying `doi`, if DBLP lists the work.
+    pub async fn find_by_doi(&self, doi: &Doi) -> Result<Option<DblpPublication>> {
+        let hits = self.search(&doi.normalized, 10).await?;
+        Ok(hits.into_iter().find(|hit| {
+            hit.doi
+                .as_deref()
+                .is_some_and(|found| found.eq_ignore_ascii_case(&doi.normalized))
+        }))
+    }
+
+    pub async fn search(&self, query: &str, limit: u32) -> Result<Vec<DblpPublication>> {
+        let cap_limit = limit.clamp(1, 1000);
+        let cache_key = format!("search:{}:{}", query.trim().to_lowercase(), cap_limit);
+        if let Some(cached) = self.cache.get::<Vec<DblpPublication>>(&cache_key).await {
+            return Ok(cached);
+        }
+
+        let mut url = parse_base_url(&self.base_url)?;
+        {
+            let mut segs = url
+                .path_segments_mut()
+                .map_err(|_| ScienceError::Parse("invalid DBLP base URL".to_string()))?;
+            segs.pop_if_empty().extend(["search", "publ", "api"]);
+        }
+        url.query_pairs_mut()
+            .append_pair("q", query)
+            .append_pair("format", "json")
+            .append_pair("h", &cap_limit.to_string());
+
+        let body = self.client.get(url.as_str()).await?;
+        let json: Value =
+            serde_json::from_str(&body).map_err(|e| ScienceError::Parse(e.to_string()))?;
+        let publications = one_or_many(
+            json.get("result")
+                .and_then(|result| result.get("hits"))
+                .and_then(|hits| hits.get("hit")),
+        )
+        .into_iter()
+        .filter_map(|hit| hit.get("info"))
+        .filter_map(DblpPublication::from_search_hit)
+        .collect::<Vec<_>>();
+
+        self.cache.set(&cache_key, &publications).await;
+        Ok(publications)
+    }
+
+    async fn fetch_record(&self, key: &str) -> Result<DblpPublication> {
+        let key = normalize_key(key)
+            .ok_or_else(|| ScienceError::Parse(format!("invalid DBLP key: {key}")))?;
+        let cache_key = format!("rec:{key}");
+        if let Some(cached) = self.cache.get::<DblpPublication>(&cache_key).await {
+            return Ok(cached);
+        }
+
+        let mut url = parse_base_url(&self.base_url)?;
+        {
+            let mut segs = url
+                .path_segments_mut()
+                .map_err(|_| ScienceError::Parse("invalid DBLP base URL".to_string()))?;
+            segs.pop_if_empty().push("rec");
+            let mut parts = key.split('/').collect::<Vec<_>>();
+            let last = parts.pop().unwrap_or_default();
+            segs.extend(parts).push(&format!("{last}.xml"));
+        }
+
+        let body = self.client.get(url.as_str()).await?;
+        let publication = parse_record_xml(&body)?;
+
+        self.cache.set(&cache_key, &publication).await;
+        Ok(publication)
+    }
+
+    fn with_config(
+        base_url: String,
+        min_interval: Duration,
+        cache_ttl: Duration,
+        cache_namespace: String,
+    ) -> Self {
+        Self {
+            client: RateLimitedClient::new(min_interval, 3, "omniscope-science/0.1"),
+            cache: DiskCache::new(&cache_namespace, cache_ttl),
+            base_url,
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn new_for_tests(base_url: String) -> Self {
+        Self::with_config(
+            base_url,
+            Duration::from_millis(1),
+            Duration::from_secs(60),
+            format!(
+                "dblp_test_{}_{}",
+                std::process::id(),
+                TEST_COUNTER.fetch_add(1, Ordering::Relaxed)
+            ),
+        )
+    }
+}
+
+impl Default for DblpSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ExternalSource for DblpSource {
+    fn name() -> &'static str {
+        "dblp"
+    }
+
+    fn source_type() -> SourceType {
+        SourceType::AcademicMetadata
+    }
+
+    fn requires_auth() -> bool {
+        false
+    }
+
+    fn rate_limit() -> RateLimit {
+        RateLimit {
+            requests_per_second: 1.0,
+        }
+    }
+
+    async fn search(&self, query: &str) -> Result<Vec<SearchResult>> {
+        let publications = self.search(query, 10).await?;
+        let total = publications.len();
+        Ok(publications
+            .into_iter()
+            .enumerate()
+            .map(|(rank, publication)| SearchResult {
+                title: publication.title,
+                authors: publication.authors,
+                year: publication.year,
+                identifier: Some(publication.key),
+                source: Self::name().to_string(),
+                relevance_score: (total - rank) as f32,
+            })
+            .collect())
+    }
+
+    /// `id` is a DBLP key or a DOI.
+    async fn fetch_metadata(&self, id: &str) -> Result<Option<Metadata>> {
+        let publication = match Doi::parse(id) {
+            Ok(doi) => match self.find_by_doi(&doi).await? {
+                Some(hit) => self.fetch_by_key(&hit.key).await?,
+                None => return Ok(None),
+            },
+            Err(_) => self.fetch_by_key(id).await?,
+        };
+        let journal = match publication.entry_type {
+            DblpEntryType::Conference => publication.proceedings_title.or(publication.venue),
+            _ => publication.venue,
+        };
+        Ok(Some(Metadata {
+            title: publication.title,
+            authors: publication.authors,
+            year: publication.year,
+            abstract_text: None,
+            doi: publication.doi,
+            isbn: None,
+            publisher: None,
+            journal,
+            volume: publication.volume,
+            issue: publication.number,
+            pages: publication.pages,
+        }))
+    }
+
+    /// DBLP only links to publishers; it has no downloads of its own.
+    async fn find_download_url(&self, _id: &str) -> Result<Option<DownloadUrl>> {
+        Ok(None)
+    }
+
+    async fn health_check(&self) -> SourceStatus {
+        let start = Instant::now();
+        let available = self.search("attention", 1).await.is_ok();
+        SourceStatus {
+            available,
+            latency_ms: Some(start.elapsed().as_millis() as u64),
+            last_checked: Some(Utc::now()),
+            mirror: None,
+        }
+    }
+}
+
+/// `conf/nips/VaswaniSPUJGKP17` from a bare key, `DBLP:` BibTeX key or
+/// `https://dblp.org/rec/…` URL.
+pub fn normalize_key(value: &str) -> Option<String> {
+    let trimmed = value.trim();
+    let key = trimmed
+        .strip_prefix("DBLP:")
+        .or_else(|| trimmed.strip_prefix("https://dblp.org/rec/"))
+        .or_else(|| trimmed.strip_prefix("https://dblp.uni-trier.de/rec/"))
+        .unwrap_or(trimmed);
+    let key = key
+        .trim_end_matches(".html")
+        .trim_end_matches(".xml")
+        .trim_end_matches(".bib")
+        .trim_matches('/');
+    let valid = key.split('/').count() >= 2
+        && key.split('/').all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || "-_.:".contains(ch))
+        });
+    valid.then(|| key.to_string())
+}
+
+// ─── Record XML ──────────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct RawDblp {
+    article: Option<RawRecord>,
+    inproceedings: Option<RawRecord>,
+    proceedings: Option<RawRecord>,
+    incollection: Option<RawRecord>,
+    book: Option<RawRecord>,
+    phdthesis: Option<RawRecord>,
+    mastersthesis: Option<RawRecord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawRecord {
+    #[serde(rename = "@key")]
+    key: String,
+    #[serde(rename = "@publtype")]
+    publtype: Option<String>,
+    #[serde(rename = "author", default)]
+    authors: Vec<RawText>,
+    #[serde(default)]
+    title: String,
+    booktitle: Option<String>,
+    journal: Option<String>,
+    year: Option<String>,
+    volume: Option<String>,
+    number: Option<String>,
+    pages: Option<String>,
+    crossref: Option<String>,
+    #[serde(rename = "ee", default)]
+    ee: Vec<RawText>,
+}
+
+/// An element with text and attributes we do not need (`pid`, `type`).
+#[derive(Debug, Deserialize)]
+struct RawText {
+    #[serde(rename = "$text", default)]
+    text: String,
+}
+
+fn parse_record_xml(xml: &str) -> Result<DblpPublication> {
+    let xml = INLINE_MARKUP_RE.replace_all(xml, "");
+    let raw: RawDblp =
+        from_str(&xml).map_err(|e| ScienceError::Parse(format!("invalid DBLP XML: {e}")))?;
+
+    let (element, record) = [
+        ("inproceedings", raw.inproceedings),
+        ("article", raw.article),
+        ("incollection", raw.incollection),
+        ("proceedings", raw.proceedings),
+        ("book", raw.book),
+        ("phdthesis", raw.phdthesis),
+        ("mastersthesis", raw.mastersthesis),
+    ]
+    .into_iter()
+    .find_map(|(element, record)| record.map(|record| (element, record)))
+    .ok_or_else(|| ScienceError::Parse("DBLP XML has no publication record".to_string()))?;
+
+    let entry_type = DblpEntryType::from_record(element, &record.key, record.publtype.as_deref());
+    let ee = record
+        .ee
+        .into_iter()
+        .map(|link| link.text.trim().to_string())
+        .filter(|link| !link.is_empty())
+        .collect::<Vec<_>>();
+
+    Ok(DblpPublication {
+        entry_type,
+        title: clean_title(&record.title),
+        authors: record
+            .authors
+            .iter()
+            .map(|author| clean_author_name(&author.text))
+            .filter(|name| !name.is_empty())
+            .collect(),
+        venue: clean_optional(record.booktitle.or(record.journal)),
+        proceedings_title: None,
+        crossref: clean_optional(record.crossref),
+        year: record.year.and_then(|year| year.trim().parse().ok()),
+        volume: clean_optional(record.volume),
+        number: clean_optional(record.number),
+        pages: clean_optional(record.pages),
+        doi: doi_from_links(&ee),
+        ee,
+        key: record.key,
+    })
+}
+
+fn doi_from_links(links: &[String]) -> Option<String> {
+    links.iter().find_map(|link| {
+        link.strip_prefix("https://doi.org/")
+            .or_else(|| link.strip_prefix("http://doi.org/"))
+            .or_else(|| link.strip_prefix("https://dx.doi.org/"))
+            .map(ToOwned::to_owned)
+    })
+}
+
+/// A JSON value that is a single object when there is one of it.
+fn one_or_many(value: Option<&Value>) -> Vec<&Value> {
+    match value {
+        Some(Value::Array(items)) => items.iter().collect(),
+        Some(Value::Null) | None => Vec::new(),
+        Some(item) => vec![item],
+    }
+}
+
+fn text_field(value: &Value, key: &str) -> Option<String> {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(ToOwned::to_owned)
+}
+
+/// DBLP ends every title with a period.
+fn clean_title(value: &str) -> String {
+    let title = clean_text(value);
+    match title.strip_suffix('.') {
+        Some(stripped) if !stripped.ends_with('.') => stripped.to_string(),
+        _ => title,
+    }
+}
+
+fn clean_author_name(value: &str) -> String {
+    HOMONYM_RE.replace(&clean_text(value), "").into_owned()
+}
+
+fn clean_text(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn clean_optional(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| clean_text(&value))
+        .filter(|value| !value.is_empty())
+}
+
+fn parse_base_url(base_url: &str) -> Result<Url> {
+    Url::parse(base_url).map_err(|e| ScienceError::Parse(format!("invalid URL {base_url}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use mockito::{Matcher, Server};
+    use serde_json::json;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn fetch_by_key_resolves_proceedings_title() {
+        let mut server = Server::new_async().await;
+        let record_mock = server
+            .mock("GET", "/rec/conf/nips/VaswaniSPUJGKP17.xml")
+            .with_status(200)
+            .with_header("content-type", "application/xml")
+            .with_body(
+                r#"<?xml version="1.0" encoding="US-ASCII"?>
+<dblp>
+<inproceedings key="conf/nips/VaswaniSPUJGKP17" mdate="2021-02-08">
+<author pid="21/9942">Ashish Vaswani</author>
+<author pid="11/7937">Noam Shazeer</author>
+<author pid="64/1001">Wei Wang 0001</author>
+<title>Attention is All you Need.</title>
+<pages>5998-6008</pages>
+<year>2017</year>
+<booktitle>NIPS</booktitle>
+<ee type="oa">https://proceedings.neurips.cc/paper/2017/hash/3f5ee243-Abstract.html</ee>
+<crossref>conf/nips/2017</crossref>
+<url>db/conf/nips/nips2017.html#VaswaniSPUJGKP17</url>
+</inproceedings>
+</dblp>"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let proceedings_mock = server
+            .mock("GET", "/rec/conf/nips/2017.xml")
+            .with_status(200)
+            .with_header("content-type", "application/xml")
+            .with_body(
+                r#"<?xml version="1.0" encoding="US-ASCII"?>
+<dblp>
+<proceedings key="conf/nips/2017" mdate="2020-11-04">
+<editor>Isabelle Guyon</editor>
+<title>Advances in Neural Information Processing Systems 30: Annual Conference on Neural Information Processing Systems 2017</title>
+<booktitle>NIPS</booktitle>
+<year>2017</year>
+</proceedings>
+</dblp>"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let source = DblpSource::new_for_tests(server.url());
+        let publication = source
+            .fetch_by_key("DBLP:conf/nips/VaswaniSPUJGKP17")
+            .await
+            .unwrap();
+
+        assert_eq!(publication.entry_type, DblpEntryType::Conference);
+        assert_eq!(publication.title, "Attention is All you Need");
+        assert_eq!(
+            publication.authors,
+            vec!["Ashish Vaswani", "Noam Shazeer", "Wei Wang"]
+        );
+        assert_eq!(publication.venue.as_deref(), Some("NIPS"));
+        assert_eq!(
+            publication.proceedings_title.as_deref(),
+            Some(
+                "Advances in Neural Information Processing Systems 30: Annual Conference on Neural Information Processing Systems 2017"
+            )
+        );
+        assert_eq!(publication.year, Some(2017));
+        assert_eq!(publication.pages.as_deref(), Some("5998-6008"));
+        assert_eq!(publication.doi, None);
+
+        record_mock.assert_async().await;
+        proceedings_mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn find_by_doi_matches_search_hit() {
+        let mut server = Server::new_async().await;
+        let search_mock = server
+            .mock("GET", "/search/publ/api")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("q".to_string(), "10.1145/3292500.3330701".to_string()),
+                Matcher::UrlEncoded("format".to_string(), "json".to_string()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "result": {
+                        "hits": {
+                            "@total": "2",
+                            "hit": [
+                                {
+                                    "@score": "5",
+                                    "info": {
+                                        "authors": {"author": {"@pid": "1/1", "text": "Someone Else"}},
+                                        "title": "Unrelated.",
+                                        "venue": "KDD",
+                                        "year": "2019",
+                                        "type": "Conference and Workshop Papers",
+                                        "key": "conf/kdd/Else19",
+                                        "doi": "10.1145/0000000.0000000"
+                                    }
+                                },
+                                {
+                                    "@score": "9",
+                                    "info": {
+                                        "authors": {"author": [
+                                            {"@pid": "2/2", "text": "Ada Lovelace"},
+                                            {"@pid": "3/3", "text": "Alan Turing 0002"}
+                                        ]},
+                                        "title": "Optuna: A Next-generation Hyperparameter Optimization Framework.",
+                                        "venue": "KDD",
+                                        "pages": "2623-2631",
+                                        "year": "2019",
+                                        "type": "Conference and Workshop Papers",
+                                        "key": "conf/kdd/AkibaSYOK19",
+                                        "doi": "10.1145/3292500.3330701",
+                                        "ee": "https://doi.org/10.1145/3292500.3330701"
+                                    }
+                                }
+                            ]
+                        }
+                    }
+                })
+                .to_string(),
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let source = DblpSource::new_for_tests(server.url());
+        let doi = Doi::parse("10.1145/3292500.3330701").unwrap();
+        let hit = source.find_by_doi(&doi).await.unwrap().unwrap();
+
+        assert_eq!(hit.key, "conf/kdd/AkibaSYOK19");
+        assert_eq!(hit.entry_type, DblpEntryType::Conference);
+        assert_eq!(
+            hit.title,
+            "Optuna: A Next-generation Hyperparameter Optimization Framework"
+        );
+        assert_eq!(hit.authors, vec!["Ada Lovelace", "Alan Turing"]);
+        assert_eq!(hit.venue.as_deref(), Some("KDD"));
+        search_mock.assert_async().await;
+    }
+
+    #[test]
+    fn normalizes_keys_and_detects_informal_records() {
+        assert_eq!(
+            normalize_key("https://dblp.org/rec/journals/corr/abs-1706-03762.html").as_deref(),
+            Some("journals/corr/abs-1706-03762")
+        );
+        assert_eq!(normalize_key("not a key"), None);
+
+        let record = parse_record_xml(
+            r#"<dblp><article key="journals/corr/abs-1706-03762" publtype="informal">
+<author>Ashish Vaswani</author><title>Attention Is All You Need.</title>
+<journal>CoRR</journal><volume>abs/1706.03762</volume><year>2017</year>
+<ee type="oa">https://doi.org/10.48550/arXiv.1706.03762</ee></article></dblp>"#,
+        )
+        .unwrap();
+        assert_eq!(record.entry_type, DblpEntryType::Informal);
+        assert_eq!(record.doi.as_deref(), Some("10.48550/arXiv.1706.03762"));
+    }
+}
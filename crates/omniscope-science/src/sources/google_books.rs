@@ -0,0 +1,461 @@
+#[cfg(test)]
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{Result, ScienceError};
+use crate::http::{DiskCache, RateLimitedClient};
+use crate::identifiers::isbn::Isbn;
+use crate::sources::{
+    DownloadUrl, ExternalSource, Metadata, RateLimit, SearchResult, SourceStatus, SourceType,
+};
+
+const BASE_URL: &str = "https://www.googleapis.com/books/v1";
+const CACHE_TTL_SECS: u64 = 7 * 24 * 60 * 60;
+#[cfg(test)]
+static TEST_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+static HTML_TAG_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^>]+>").expect("valid regex"));
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct GoogleBooksVolume {
+    pub id: String,
+    pub title: String,
+    pub subtitle: Option<String>,
+    pub authors: Vec<String>,
+    pub publisher: Option<String>,
+    pub published_date: Option<String>,
+    pub description: Option<String>,
+    pub isbn13: Option<String>,
+    pub isbn10: Option<String>,
+    pub page_count: Option<u32>,
+    pub categories: Vec<String>,
+    pub language: Option<String>,
+    pub cover_url: Option<String>,
+}
+
+impl GoogleBooksVolume {
+    pub fn from_json(v: &Value) -> Self {
+        let info = v.get("volumeInfo").unwrap_or(&Value::Null);
+        let text = |key: &str| {
+            info.get(key)
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(ToOwned::to_owned)
+        };
+        let strings = |key: &str| {
+            info.get(key)
+                .and_then(Value::as_array)
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(Value::as_str)
+                        .map(ToOwned::to_owned)
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        };
+        let industry_id = |kind: &str| {
+            info.get("industryIdentifiers")
+                .and_then(Value::as_array)
+                .and_then(|arr| {
+                    arr.iter()
+                        .find(|item| item.get("type").and_then(Value::as_str) == Some(kind))
+                })
+                .and_then(|item| item.get("identifier"))
+                .and_then(Value::as_str)
+                .map(ToOwned::to_owned)
+        };
+
+        Self {
+            id: v
+                .get("id")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            title: text("title").unwrap_or_default(),
+            subtitle: text("subtitle"),
+            authors: strings("authors"),
+            publisher: text("publisher"),
+            published_date: text("publishedDate"),
+            description: text("description")
+                .map(|description| strip_html(&description))
+                .filter(|description| !description.is_empty()),
+            isbn13: industry_id("ISBN_13"),
+            isbn10: industry_id("ISBN_10"),
+            page_count: info
+                .get("pageCount")
+                .and_then(Value::as_u64)
+                .and_then(|count| u32::try_from(count).ok())
+                .filter(|count| *count > 0),
+            categories: strings("categories"),
+            language: text("language"),
+            cover_url: info.get("imageLinks").and_then(cover_from_image_links),
+        }
+    }
+
+    pub fn year(&self) -> Option<i32> {
+        self.published_date
+            .as_deref()
+            .and_then(|date| date.get(..4))
+            .and_then(|year| year.parse().ok())
+    }
+}
+
+pub struct GoogleBooksSource {
+    pub client: RateLimitedClient,
+    pub cache: DiskCache,
+    api_key: Option<String>,
+    base_url: String,
+}
+
+impl GoogleBooksSource {
+    pub fn new(api_key: Option<String>) -> Self {
+        Self::with_config(
+            BASE_URL.to_string(),
+            api_key,
+            Duration::from_millis(500),
+            Duration::from_secs(CACHE_TTL_SECS),
+            "google_books".to_string(),
+        )
+    }
+
+    pub async fn fetch_by_isbn(&self, isbn: &Isbn) -> Result<GoogleBooksVolume> {
+        let cache_key = format!("isbn:{}", isbn.isbn13);
+        if let Some(cached) = self.cache.get::<GoogleBooksVolume>(&cache_key).await {
+            return Ok(cached);
+        }
+
+        let volumes = self
+            .query_volumes(&format!("isbn:{}", isbn.isbn13), 1)
+            .await?;
+        let Some(volume) = volumes.into_iter().next() else {
+            return Err(ScienceError::ApiError(
+                "google_books".to_string(),
+                format!("book not found for ISBN {}", isbn.isbn13),
+            ));
+        };
+
+        self.cache.set(&cache_key, &volume).await;
+        Ok(volume)
+    }
+
+    pub async fn search_by_title(
+        &self,
+        title: &str,
+        author: Option<&str>,
+    ) -> Result<Vec<GoogleBooksVolume>> {
+        let mut query = format!("intitle:{}", title.trim());
+        if let Some(author) = author.map(str::trim).filter(|a| !a.is_empty()) {
+            query.push_str(&format!(" inauthor:{author}"));
+        }
+        self.query_volumes(&query, 10).await
+    }
+
+    async fn query_volumes(&self, query: &str, limit: u32) -> Result<Vec<GoogleBooksVolume>> {
+        let cache_key = format!("q:{}:{}", query.to_lowercase(), limit);
+        if let Some(cached) = self.cache.get::<Vec<GoogleBooksVolume>>(&cache_key).await {
+            return Ok(cached);
+        }
+
+        let mut url = parse_base_url(&self.base_url)?;
+        {
+            let mut segs = url
+                .path_segments_mut()
+                .map_err(|_| ScienceError::Parse("invalid Google Books base URL".to_string()))?;
+            segs.pop_if_empty().push("volumes");
+        }
+        url.query_pairs_mut()
+            .append_pair("q", query)
+            .append_pair("maxResults", &limit.clamp(1, 40).to_string())
+            .append_pair("printType", "books");
+        if let Some(key) = self.api_key.as_deref() {
+            url.query_pairs_mut().append_pair("key", key);
+        }
+
+        let body = self.client.get(url.as_str()).await?;
+        let json: Value =
+            serde_json::from_str(&body).map_err(|e| ScienceError::Parse(e.to_string()))?;
+
+        let volumes = json
+            .get("items")
+            .and_then(Value::as_array)
+            .map(|arr| {
+                arr.iter()
+                    .map(GoogleBooksVolume::from_json)
+                    .filter(|volume| !volume.title.is_empty())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        self.cache.set(&cache_key, &volumes).await;
+        Ok(volumes)
+    }
+
+    fn with_config(
+        base_url: String,
+        api_key: Option<String>,
+        min_interval: Duration,
+        cache_ttl: Duration,
+        cache_namespace: String,
+    ) -> Self {
+        Self {
+            client: RateLimitedClient::new(min_interval, 3, "omniscope-science/0.1"),
+            cache: DiskCache::new(&cache_namespace, cache_ttl),
+            api_key,
+            base_url,
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn new_for_tests(base_url: String) -> Self {
+        Self::with_config(
+            base_url,
+            None,
+            Duration::from_millis(1),
+            Duration::from_secs(60),
+            format!(
+                "google_books_test_{}_{}",
+                std::process::id(),
+                TEST_COUNTER.fetch_add(1, Ordering::Relaxed)
+            ),
+        )
+    }
+}
+
+impl Default for GoogleBooksSource {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+#[async_trait]
+impl ExternalSource for GoogleBooksSource {
+    fn name() -> &'static str {
+        "google_books"
+    }
+
+    fn source_type() -> SourceType {
+        SourceType::BookMetadata
+    }
+
+    fn requires_auth() -> bool {
+        false
+    }
+
+    fn rate_limit() -> RateLimit {
+        RateLimit {
+            requests_per_second: 2.0,
+        }
+    }
+
+    async fn search(&self, query: &str) -> Result<Vec<SearchResult>> {
+        let volumes = self.query_volumes(query, 10).await?;
+        let total = volumes.len();
+        Ok(volumes
+            .into_iter()
+            .enumerate()
+            .map(|(rank, volume)| SearchResult {
+                year: volume.year(),
+                title: volume.title,
+                authors: volume.authors,
+                identifier: volume.isbn13.or(Some(volume.id)),
+                source: Self::name().to_string(),
+                relevance_score: (total - rank) as f32,
+            })
+            .collect())
+    }
+
+    async fn fetch_metadata(&self, id: &str) -> Result<Option<Metadata>> {
+        let isbn = match Isbn::parse(id) {
+            Ok(isbn) => isbn,
+            Err(_) => return Ok(None),
+        };
+
+        let volume = self.fetch_by_isbn(&isbn).await?;
+        Ok(Some(Metadata {
+            year: volume.year(),
+            title: volume.title,
+            authors: volume.authors,
+            abstract_text: volume.description,
+            doi: None,
+            isbn: Some(isbn.isbn13),
+            publisher: volume.publisher,
+            journal: None,
+            volume: None,
+            issue: None,
+            pages: volume.page_count.map(|count| count.to_string()),
+        }))
+    }
+
+    async fn find_download_url(&self, _id: &str) -> Result<Option<DownloadUrl>> {
+        Ok(None)
+    }
+
+    async fn health_check(&self) -> SourceStatus {
+        let start = Instant::now();
+        let available = self.query_volumes("attention", 1).await.is_ok();
+        SourceStatus {
+            available,
+            latency_ms: Some(start.elapsed().as_millis() as u64),
+            last_checked: Some(Utc::now()),
+            mirror: None,
+        }
+    }
+}
+
+/// The largest cover in `imageLinks`, over https and without the page-curl
+/// effect Google adds to thumbnails.
+fn cover_from_image_links(links: &Value) -> Option<String> {
+    let url = [
+        "extraLarge",
+        "large",
+        "medium",
+        "small",
+        "thumbnail",
+        "smallThumbnail",
+    ]
+    .iter()
+    .find_map(|size| links.get(*size).and_then(Value::as_str))?;
+
+    let url = match url.strip_prefix("http://") {
+        Some(rest) => format!("https://{rest}"),
+        None => url.to_string(),
+    };
+    Some(url.replace("&edge=curl", ""))
+}
+
+fn strip_html(input: &str) -> String {
+    HTML_TAG_RE
+        .replace_all(input, " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn parse_base_url(base_url: &str) -> Result<Url> {
+    Url::parse(base_url).map_err(|e| ScienceError::Parse(format!("invalid URL {base_url}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use mockito::{Matcher, Server};
+    use serde_json::json;
+
+    use super::*;
+
+    fn volume_json() -> Value {
+        json!({
+            "id": "zyTCAlFPjgYC",
+            "volumeInfo": {
+                "title": "The Google Story",
+                "subtitle": "Inside the Hottest Business",
+                "authors": ["David A. Vise", "Mark Malseed"],
+                "publisher": "Random House Digital, Inc.",
+                "publishedDate": "2005-11-15",
+                "description": "<p>Here is the story behind one of the most <b>remarkable</b> Internet successes.</p>",
+                "industryIdentifiers": [
+                    {"type": "ISBN_10", "identifier": "055380457X"},
+                    {"type": "ISBN_13", "identifier": "9780553804577"}
+                ],
+                "pageCount": 207,
+                "categories": ["Browsers (Computer programs)"],
+                "language": "en",
+                "imageLinks": {
+                    "smallThumbnail": "http://books.google.com/books/content?id=zyTCAlFPjgYC&printsec=frontcover&img=1&zoom=5&edge=curl&source=gbs_api",
+                    "thumbnail": "http://books.google.com/books/content?id=zyTCAlFPjgYC&printsec=frontcover&img=1&zoom=1&edge=curl&source=gbs_api"
+                }
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn fetch_by_isbn_maps_volume() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/volumes")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("q".to_string(), "isbn:9780553804577".to_string()),
+                Matcher::UrlEncoded("maxResults".to_string(), "1".to_string()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({"totalItems": 1, "items": [volume_json()]}).to_string())
+            .expect(1)
+            .create_async()
+            .await;
+
+        let source = GoogleBooksSource::new_for_tests(server.url());
+        let isbn = Isbn::parse("9780553804577").unwrap();
+        let volume = source.fetch_by_isbn(&isbn).await.unwrap();
+
+        assert_eq!(volume.title, "The Google Story");
+        assert_eq!(volume.authors.len(), 2);
+        assert_eq!(volume.year(), Some(2005));
+        assert_eq!(volume.page_count, Some(207));
+        assert_eq!(volume.isbn10.as_deref(), Some("055380457X"));
+        assert_eq!(
+            volume.description.as_deref(),
+            Some("Here is the story behind one of the most remarkable Internet successes.")
+        );
+        assert_eq!(
+            volume.cover_url.as_deref(),
+            Some(
+                "https://books.google.com/books/content?id=zyTCAlFPjgYC&printsec=frontcover&img=1&zoom=1&source=gbs_api"
+            )
+        );
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn missing_isbn_is_an_api_error() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/volumes")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({"kind": "books#volumes", "totalItems": 0}).to_string())
+            .create_async()
+            .await;
+
+        let source = GoogleBooksSource::new_for_tests(server.url());
+        let isbn = Isbn::parse("9780306406157").unwrap();
+        let err = source.fetch_by_isbn(&isbn).await.unwrap_err();
+        assert!(matches!(err, ScienceError::ApiError(_, _)));
+    }
+
+    #[tokio::test]
+    async fn search_by_title_queries_title_and_author() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/volumes")
+            .match_query(Matcher::UrlEncoded(
+                "q".to_string(),
+                "intitle:The Google Story inauthor:Vise".to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({"totalItems": 1, "items": [volume_json()]}).to_string())
+            .expect(1)
+            .create_async()
+            .await;
+
+        let source = GoogleBooksSource::new_for_tests(server.url());
+        let volumes = source
+            .search_by_title("The Google Story", Some("Vise"))
+            .await
+            .unwrap();
+
+        assert_eq!(volumes.len(), 1);
+        assert_eq!(volumes[0].isbn13.as_deref(), Some("9780553804577"));
+        mock.assert_async().await;
+    }
+}
@@ -77,8 +77,11 @@ pub struct RateLimit {
 pub mod annas_archive;
 pub mod core_ac;
 pub mod crossref;
+pub mod dblp;
+pub mod google_books;
 pub mod openalex;
 pub mod openlibrary;
+pub mod pubmed;
 pub mod scihub;
 pub mod semantic_scholar;
 pub mod unpaywall;
@@ -0,0 +1,920 @@
+//! PubMed and PubMed Central through the NCBI E-utilities.
+//!
+//! Metadata comes from `efetch` (MEDLINE XML, which unlike `esummary` carries
+//! the abstract), id conversion between PMID, PMCID and DOI from the PMC ID
+//! converter, and PDF links from the PMC open-access web service.
+
+#[cfg(test)]
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use quick_xml::de::from_str;
+use regex::Regex;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{Result, ScienceError};
+use crate::http::{DiskCache, RateLimitedClient};
+use crate::sources::{
+    DownloadUrl, ExternalSource, Metadata, RateLimit, SearchResult, SourceStatus, SourceType,
+};
+
+const EUTILS_URL: &str = "https://eutils.ncbi.nlm.nih.gov/entrez/eutils";
+const IDCONV_URL: &str = "https://www.ncbi.nlm.nih.gov/pmc/utils/idconv/v1.0/";
+const OA_URL: &str = "https://www.ncbi.nlm.nih.gov/pmc/utils/oa/oa.fcgi";
+const TOOL_NAME: &str = "omniscope";
+const CACHE_TTL_SECS: u64 = 7 * 24 * 60 * 60;
+#[cfg(test)]
+static TEST_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Inline formatting NCBI leaves in titles and abstracts (`<i>`, `<sup>`, …).
+static INLINE_MARKUP_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"</?(?:i|b|u|sup|sub|em|strong|mml:[a-z]+)(?:\s[^>]*)?>").expect("valid regex")
+});
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PubMedArticle {
+    pub pmid: String,
+    pub pmcid: Option<String>,
+    pub doi: Option<String>,
+    pub title: String,
+    pub authors: Vec<String>,
+    pub abstract_text: Option<String>,
+    pub journal: Option<String>,
+    pub volume: Option<String>,
+    pub issue: Option<String>,
+    pub pages: Option<String>,
+    pub year: Option<i32>,
+    pub publication_types: Vec<String>,
+}
+
+impl PubMedArticle {
+    pub fn is_preprint(&self) -> bool {
+        self.publication_types
+            .iter()
+            .any(|kind| kind.eq_ignore_ascii_case("preprint"))
+    }
+}
+
+/// The ids the PMC converter knows for one article.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct PubMedIds {
+    pub pmid: Option<String>,
+    pub pmcid: Option<String>,
+    pub doi: Option<String>,
+}
+
+/// An article in the PMC open-access subset.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct PmcOpenAccess {
+    pub pmcid: String,
+    pub license: Option<String>,
+    pub pdf_url: Option<String>,
+}
+
+pub struct PubMedSource {
+    pub client: RateLimitedClient,
+    pub cache: DiskCache,
+    pub api_key: Option<String>,
+    pub email: Option<String>,
+    eutils_url: String,
+    idconv_url: String,
+    oa_url: String,
+}
+
+impl PubMedSource {
+    /// NCBI allows 3 requests per second, or 10 with an API key.
+    pub fn new(api_key: Option<String>, email: Option<String>) -> Self {
+        let min_interval = if api_key.as_deref().is_some_and(|k| !k.trim().is_empty()) {
+            Duration::from_millis(110)
+        } else {
+            Duration::from_millis(350)
+        };
+
+        Self {
+            api_key,
+            email,
+            ..Self::with_config(
+                EUTILS_URL.to_string(),
+                IDCONV_URL.to_string(),
+                OA_URL.to_string(),
+                min_interval,
+                Duration::from_secs(CACHE_TTL_SECS),
+                "pubmed".to_string(),
+            )
+        }
+    }
+
+    pub async fn fetch_by_pmid(&self, pmid: &str) -> Result<PubMedArticle> {
+        let pmid = normalize_pmid(pmid)
+            .ok_or_else(|| ScienceError::Parse(format!("invalid PMID: {pmid}")))?;
+        let cache_key = format!("article:{pmid}");
+        if let Some(cached) = self.cache.get::<PubMedArticle>(&cache_key).await {
+            return Ok(cached);
+        }
+
+        let article = self
+            .fetch_articles(std::slice::from_ref(&pmid))
+            .await?
+            .into_iter()
+            .find(|article| article.pmid == pmid)
+            .ok_or_else(|| ScienceError::Parse(format!("PubMed has no record for PMID {pmid}")))?;
+
+        self.cache.set(&cache_key, &article).await;
+        Ok(article)
+    }
+
+    /// Fetch several records in one `efetch` call; unknown PMIDs are skipped.
+    pub async fn fetch_articles(&self, pmids: &[String]) -> Result<Vec<PubMedArticle>> {
+        if pmids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut url = self.eutils_endpoint("efetch.fcgi")?;
+        url.query_pairs_mut()
+            .append_pair("db", "pubmed")
+            .append_pair("id", &pmids.join(","))
+            .append_pair("retmode", "xml");
+        self.append_credentials(&mut url);
+
+        let body = self.client.get(url.as_str()).await?;
+        parse_efetch_response(&body)
+    }
+
+    /// PMIDs matching a PubMed query, most relevant first.
+    pub async fn search_pmids(&self, query: &str, limit: u32) -> Result<Vec<String>> {
+        let cap_limit = limit.clamp(1, 200);
+        let cache_key = format!("search:{}:{}", query.trim().to_lowercase(), cap_limit);
+        if let Some(cached) = self.cache.get::<Vec<String>>(&cache_key).await {
+            return Ok(cached);
+        }
+
+        let mut url = self.eutils_endpoint("esearch.fcgi")?;
+        url.query_pairs_mut()
+            .append_pair("db", "pubmed")
+            .append_pair("term", query)
+            .append_pair("retmax", &cap_limit.to_string())
+            .append_pair("sort", "relevance")
+            .append_pair("retmode", "json");
+        self.append_credentials(&mut url);
+
+        let body = self.client.get(url.as_str()).await?;
+        let json: Value =
+            serde_json::from_str(&body).map_err(|e| ScienceError::Parse(e.to_string()))?;
+        let pmids = json
+            .get("esearchresult")
+            .and_then(|result| result.get("idlist"))
+            .and_then(Value::as_array)
+            .map(|ids| {
+                ids.iter()
+                    .filter_map(Value::as_str)
+                    .map(ToOwned::to_owned)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        self.cache.set(&cache_key, &pmids).await;
+        Ok(pmids)
+    }
+
+    /// Look up the PMID, PMCID and DOI of an article from any one of them.
+    /// Only articles known to PMC can be converted; `None` for the rest.
+    pub async fn convert_ids(&self, id: &str) -> Result<Option<PubMedIds>> {
+        let id = id.trim();
+        let cache_key = format!("ids:{}", id.to_lowercase());
+        if let Some(cached) = self.cache.get::<Option<PubMedIds>>(&cache_key).await {
+            return Ok(cached);
+        }
+
+        let mut url = parse_base_url(&self.idconv_url)?;
+        url.query_pairs_mut()
+            .append_pair("ids", id)
+            .append_pair("format", "json");
+        self.append_credentials(&mut url);
+
+        let body = self.client.get(url.as_str()).await?;
+        let json: Value =
+            serde_json::from_str(&body).map_err(|e| ScienceError::Parse(e.to_string()))?;
+        let ids = json
+            .get("records")
+            .and_then(Value::as_array)
+            .and_then(|records| records.first())
+            .filter(|record| record.get("status").and_then(Value::as_str) != Some("error"))
+            .map(|record| PubMedIds {
+                pmid: id_field(record, "pmid"),
+                pmcid: id_field(record, "pmcid"),
+                doi: id_field(record, "doi"),
+            });
+        self.cache.set(&cache_key, &ids).await;
+        Ok(ids)
+    }
+
+    /// The PMID of the article with `doi`, searched across all of PubMed
+    /// rather than only PMC.
+    pub async fn pmid_for_doi(&self, doi: &str) -> Result<Option<String>> {
+        let term = format!("\"{}\"[doi]", doi.trim());
+        Ok(self.search_pmids(&term, 1).await?.into_iter().next())
+    }
+
+    /// The PMC open-access record of `pmcid`, or `None` when the article is
+    /// not in the open-access subset.
+    pub async fn find_open_access(&self, pmcid: &str) -> Result<Option<PmcOpenAccess>> {
+        let pmcid = normalize_pmcid(pmcid)
+            .ok_or_else(|| ScienceError::Parse(format!("invalid PMCID: {pmcid}")))?;
+        let cache_key = format!("oa:{pmcid}");
+        if let Some(cached) = self.cache.get::<Option<PmcOpenAccess>>(&cache_key).await {
+            return Ok(cached);
+        }
+
+        let mut url = parse_base_url(&self.oa_url)?;
+        url.query_pairs_mut().append_pair("id", &pmcid);
+        self.append_credentials(&mut url);
+
+        let body = self.client.get(url.as_str()).await?;
+        let record = parse_oa_response(&body)?;
+
+        self.cache.set(&cache_key, &record).await;
+        Ok(record)
+    }
+
+    /// The PMID behind a PMID, PMCID or DOI.
+    async fn resolve_pmid(&self, id: &str) -> Result<Option<String>> {
+        if let Some(pmid) = normalize_pmid(id) {
+            return Ok(Some(pmid));
+        }
+        Ok(self.convert_ids(id).await?.and_then(|ids| ids.pmid))
+    }
+
+    /// The PMCID behind a PMCID, PMID or DOI.
+    async fn resolve_pmcid(&self, id: &str) -> Result<Option<String>> {
+        if let Some(pmcid) = normalize_pmcid(id) {
+            return Ok(Some(pmcid));
+        }
+        Ok(self.convert_ids(id).await?.and_then(|ids| ids.pmcid))
+    }
+
+    fn eutils_endpoint(&self, endpoint: &str) -> Result<Url> {
+        let mut url = parse_base_url(&self.eutils_url)?;
+        url.path_segments_mut()
+            .map_err(|_| ScienceError::Parse("invalid E-utilities base URL".to_string()))?
+            .pop_if_empty()
+            .push(endpoint);
+        Ok(url)
+    }
+
+    /// NCBI asks every client to identify itself with `tool` and `email`.
+    fn append_credentials(&self, url: &mut Url) {
+        let mut pairs = url.query_pairs_mut();
+        pairs.append_pair("tool", TOOL_NAME);
+        if let Some(email) = self.email.as_deref().filter(|e| !e.trim().is_empty()) {
+            pairs.append_pair("email", email);
+        }
+        if let Some(key) = self.api_key.as_deref().filter(|k| !k.trim().is_empty()) {
+            pairs.append_pair("api_key", key);
+        }
+    }
+
+    fn with_config(
+        eutils_url: String,
+        idconv_url: String,
+        oa_url: String,
+        min_interval: Duration,
+        cache_ttl: Duration,
+        cache_namespace: String,
+    ) -> Self {
+        Self {
+            client: RateLimitedClient::new(min_interval, 3, "omniscope-science/0.1"),
+            cache: DiskCache::new(&cache_namespace, cache_ttl),
+            api_key: None,
+            email: None,
+            eutils_url,
+            idconv_url,
+            oa_url,
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn new_for_tests(base_url: String) -> Self {
+        Self::with_config(
+            format!("{base_url}/eutils"),
+            format!("{base_url}/idconv"),
+            format!("{base_url}/oa"),
+            Duration::from_millis(1),
+            Duration::from_secs(60),
+            format!(
+                "pubmed_test_{}_{}",
+                std::process::id(),
+                TEST_COUNTER.fetch_add(1, Ordering::Relaxed)
+            ),
+        )
+    }
+}
+
+impl Default for PubMedSource {
+    fn default() -> Self {
+        Self::new(None, None)
+    }
+}
+
+#[async_trait]
+impl ExternalSource for PubMedSource {
+    fn name() -> &'static str {
+        "pubmed"
+    }
+
+    fn source_type() -> SourceType {
+        SourceType::AcademicMetadata
+    }
+
+    fn requires_auth() -> bool {
+        false
+    }
+
+    fn rate_limit() -> RateLimit {
+        RateLimit {
+            requests_per_second: 3.0,
+        }
+    }
+
+    async fn search(&self, query: &str) -> Result<Vec<SearchResult>> {
+        let pmids = self.search_pmids(query, 10).await?;
+        let total = pmids.len();
+        let mut articles = self.fetch_articles(&pmids).await?;
+        articles.sort_by_key(|article| pmids.iter().position(|pmid| *pmid == article.pmid));
+
+        Ok(articles
+            .into_iter()
+            .enumerate()
+            .map(|(rank, article)| SearchResult {
+                title: article.title,
+                authors: article.authors,
+                year: article.year,
+                identifier: Some(article.pmid),
+                source: Self::name().to_string(),
+                relevance_score: (total - rank) as f32,
+            })
+            .collect())
+    }
+
+    async fn fetch_metadata(&self, id: &str) -> Result<Option<Metadata>> {
+        let Some(pmid) = self.resolve_pmid(id).await? else {
+            return Ok(None);
+        };
+        let article = self.fetch_by_pmid(&pmid).await?;
+        Ok(Some(Metadata {
+            title: article.title,
+            authors: article.authors,
+            year: article.year,
+            abstract_text: article.abstract_text,
+            doi: article.doi,
+            isbn: None,
+            publisher: None,
+            journal: article.journal,
+            volume: article.volume,
+            issue: article.issue,
+            pages: article.pages,
+        }))
+    }
+
+    async fn find_download_url(&self, id: &str) -> Result<Option<DownloadUrl>> {
+        let Some(pmcid) = self.resolve_pmcid(id).await? else {
+            return Ok(None);
+        };
+        let record = self.find_open_access(&pmcid).await?;
+        Ok(record.and_then(|oa| oa.pdf_url).map(|url| DownloadUrl {
+            url,
+            source_name: Self::name().to_string(),
+            requires_redirect: false,
+        }))
+    }
+
+    async fn health_check(&self) -> SourceStatus {
+        let start = Instant::now();
+        let available = self.search_pmids("crispr", 1).await.is_ok();
+        SourceStatus {
+            available,
+            latency_ms: Some(start.elapsed().as_millis() as u64),
+            last_checked: Some(Utc::now()),
+            mirror: None,
+        }
+    }
+}
+
+/// `12345678` or `PMID: 12345678` as bare digits.
+pub fn normalize_pmid(value: &str) -> Option<String> {
+    let trimmed = value.trim();
+    let digits = trimmed
+        .get(..4)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("pmid"))
+        .map(|_| trimmed[4..].trim_start_matches(':').trim())
+        .unwrap_or(trimmed);
+    (!digits.is_empty() && digits.len() <= 8 && digits.chars().all(|ch| ch.is_ascii_digit()))
+        .then(|| digits.to_string())
+}
+
+/// `PMC1234567`, `pmc1234567` or `1234567` as `PMC1234567`.
+pub fn normalize_pmcid(value: &str) -> Option<String> {
+    let trimmed = value.trim();
+    let digits = trimmed
+        .get(..3)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("pmc"))
+        .map(|_| &trimmed[3..])
+        .unwrap_or(trimmed);
+    (!digits.is_empty() && digits.chars().all(|ch| ch.is_ascii_digit()))
+        .then(|| format!("PMC{digits}"))
+}
+
+pub fn pmc_article_url(pmcid: &str) -> String {
+    format!("https://pmc.ncbi.nlm.nih.gov/articles/{pmcid}/")
+}
+
+fn id_field(record: &Value, key: &str) -> Option<String> {
+    let value = record.get(key)?;
+    value
+        .as_str()
+        .map(ToOwned::to_owned)
+        .or_else(|| value.as_u64().map(|n| n.to_string()))
+}
+
+// ─── efetch XML ───────────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct ArticleSet {
+    #[serde(rename = "PubmedArticle", default)]
+    articles: Vec<RawArticle>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawArticle {
+    #[serde(rename = "MedlineCitation")]
+    citation: RawCitation,
+    #[serde(rename = "PubmedData")]
+    data: Option<RawPubmedData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawCitation {
+    #[serde(rename = "PMID")]
+    pmid: String,
+    #[serde(rename = "Article")]
+    article: RawArticleBody,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawArticleBody {
+    #[serde(rename = "Journal")]
+    journal: Option<RawJournal>,
+    #[serde(rename = "ArticleTitle", default)]
+    title: String,
+    #[serde(rename = "Pagination")]
+    pagination: Option<RawPagination>,
+    #[serde(rename = "ELocationID", default)]
+    locations: Vec<RawTypedId>,
+    #[serde(rename = "Abstract")]
+    abstract_text: Option<RawAbstract>,
+    #[serde(rename = "AuthorList")]
+    authors: Option<RawAuthorList>,
+    #[serde(rename = "PublicationTypeList")]
+    publication_types: Option<RawPublicationTypeList>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawJournal {
+    #[serde(rename = "Title")]
+    title: Option<String>,
+    #[serde(rename = "JournalIssue")]
+    issue: Option<RawJournalIssue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawJournalIssue {
+    #[serde(rename = "Volume")]
+    volume: Option<String>,
+    #[serde(rename = "Issue")]
+    issue: Option<String>,
+    #[serde(rename = "PubDate")]
+    pub_date: Option<RawPubDate>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawPubDate {
+    #[serde(rename = "Year")]
+    year: Option<String>,
+    #[serde(rename = "MedlineDate")]
+    medline_date: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawPagination {
+    #[serde(rename = "MedlinePgn")]
+    medline_pgn: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawAbstract {
+    #[serde(rename = "AbstractText", default)]
+    sections: Vec<RawAbstractText>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawAbstractText {
+    #[serde(rename = "@Label")]
+    label: Option<String>,
+    #[serde(rename = "$text", default)]
+    text: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawAuthorList {
+    #[serde(rename = "Author", default)]
+    authors: Vec<RawAuthor>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawAuthor {
+    #[serde(rename = "LastName")]
+    last_name: Option<String>,
+    #[serde(rename = "ForeName")]
+    fore_name: Option<String>,
+    #[serde(rename = "CollectiveName")]
+    collective_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawPublicationTypeList {
+    #[serde(rename = "PublicationType", default)]
+    types: Vec<RawTypedId>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawPubmedData {
+    #[serde(rename = "ArticleIdList")]
+    ids: Option<RawArticleIdList>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawArticleIdList {
+    #[serde(rename = "ArticleId", default)]
+    ids: Vec<RawTypedId>,
+}
+
+/// `<ArticleId IdType="doi">`, `<ELocationID EIdType="doi">` and other
+/// elements whose text is qualified by one attribute.
+#[derive(Debug, Deserialize)]
+struct RawTypedId {
+    #[serde(rename = "@IdType", alias = "@EIdType")]
+    id_type: Option<String>,
+    #[serde(rename = "$text", default)]
+    value: String,
+}
+
+fn parse_efetch_response(xml: &str) -> Result<Vec<PubMedArticle>> {
+    let xml = INLINE_MARKUP_RE.replace_all(xml, "");
+    let set: ArticleSet =
+        from_str(&xml).map_err(|e| ScienceError::Parse(format!("invalid PubMed XML: {e}")))?;
+    Ok(set.articles.into_iter().map(map_article).collect())
+}
+
+fn map_article(raw: RawArticle) -> PubMedArticle {
+    let RawArticleBody {
+        journal,
+        title,
+        pagination,
+        locations,
+        abstract_text,
+        authors,
+        publication_types,
+    } = raw.citation.article;
+
+    let article_ids = raw
+        .data
+        .and_then(|data| data.ids)
+        .map(|list| list.ids)
+        .unwrap_or_default();
+    let typed = |ids: &[RawTypedId], kind: &str| {
+        ids.iter()
+            .find(|id| id.id_type.as_deref() == Some(kind))
+            .and_then(|id| clean_optional(Some(id.value.clone())))
+    };
+    let doi = typed(&article_ids, "doi").or_else(|| typed(&locations, "doi"));
+    let pmcid = typed(&article_ids, "pmc").and_then(|id| normalize_pmcid(&id));
+
+    let (journal_title, journal_issue) = match journal {
+        Some(journal) => (journal.title, journal.issue),
+        None => (None, None),
+    };
+    let (volume, issue, pub_date) = match journal_issue {
+        Some(issue) => (issue.volume, issue.issue, issue.pub_date),
+        None => (None, None, None),
+    };
+    let year = pub_date.and_then(|date| {
+        date.year
+            .or(date.medline_date)
+            .and_then(|value| value.trim().get(..4).and_then(|y| y.parse().ok()))
+    });
+
+    let sections = abstract_text
+        .map(|abstract_text| abstract_text.sections)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|section| {
+            let text = clean_text(&section.text);
+            if text.is_empty() {
+                return None;
+            }
+            Some(match clean_optional(section.label) {
+                Some(label) => format!("{label}: {text}"),
+                None => text,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let authors = authors
+        .map(|list| list.authors)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|author| match (author.fore_name, author.last_name) {
+            (Some(fore), Some(last)) => Some(clean_text(&format!("{fore} {last}"))),
+            (None, Some(last)) => Some(clean_text(&last)),
+            _ => clean_optional(author.collective_name),
+        })
+        .collect();
+
+    PubMedArticle {
+        pmid: raw.citation.pmid.trim().to_string(),
+        pmcid,
+        doi,
+        title: clean_text(&title),
+        authors,
+        abstract_text: (!sections.is_empty()).then(|| sections.join("\n\n")),
+        journal: clean_optional(journal_title),
+        volume: clean_optional(volume),
+        issue: clean_optional(issue),
+        pages: clean_optional(pagination.and_then(|p| p.medline_pgn)),
+        year,
+        publication_types: publication_types
+            .map(|list| list.types)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|kind| clean_optional(Some(kind.value)))
+            .collect(),
+    }
+}
+
+// ─── PMC open-access service XML ─────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct OaResponse {
+    records: Option<OaRecords>,
+    error: Option<OaError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OaRecords {
+    #[serde(rename = "record", default)]
+    records: Vec<OaRecord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OaRecord {
+    #[serde(rename = "@id")]
+    id: String,
+    #[serde(rename = "@license")]
+    license: Option<String>,
+    #[serde(rename = "link", default)]
+    links: Vec<OaLink>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OaLink {
+    #[serde(rename = "@format")]
+    format: Option<String>,
+    #[serde(rename = "@href")]
+    href: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OaError {
+    #[serde(rename = "@code")]
+    code: Option<String>,
+    #[serde(rename = "$text", default)]
+    message: String,
+}
+
+fn parse_oa_response(xml: &str) -> Result<Option<PmcOpenAccess>> {
+    let response: OaResponse =
+        from_str(xml).map_err(|e| ScienceError::Parse(format!("invalid PMC OA XML: {e}")))?;
+
+    if let Some(error) = response.error {
+        return match error.code.as_deref() {
+            Some("idIsNotOpenAccess" | "idDoesNotExist") => Ok(None),
+            _ => Err(ScienceError::Parse(format!(
+                "PMC OA service error: {}",
+                error.message.trim()
+            ))),
+        };
+    }
+
+    Ok(response
+        .records
+        .and_then(|records| records.records.into_iter().next())
+        .map(|record| PmcOpenAccess {
+            pmcid: record.id,
+            license: clean_optional(record.license),
+            // Links point at the NCBI FTP server, which also serves HTTPS.
+            pdf_url: record
+                .links
+                .into_iter()
+                .find(|link| link.format.as_deref() == Some("pdf"))
+                .and_then(|link| link.href)
+                .map(|href| match href.strip_prefix("ftp://") {
+                    Some(rest) => format!("https://{rest}"),
+                    None => href,
+                }),
+        }))
+}
+
+fn clean_text(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn clean_optional(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| clean_text(&value))
+        .filter(|value| !value.is_empty())
+}
+
+fn parse_base_url(base_url: &str) -> Result<Url> {
+    Url::parse(base_url).map_err(|e| ScienceError::Parse(format!("invalid URL {base_url}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use mockito::{Matcher, Server};
+    use serde_json::json;
+
+    use super::*;
+
+    const EFETCH_FIXTURE: &str = r#"<?xml version="1.0" ?>
+<!DOCTYPE PubmedArticleSet PUBLIC "-//NLM//DTD PubMedArticle, 1st January 2024//EN" "https://dtd.nlm.nih.gov/ncbi/pubmed/out/pubmed_240101.dtd">
+<PubmedArticleSet>
+  <PubmedArticle>
+    <MedlineCitation Status="MEDLINE" Owner="NLM">
+      <PMID Version="1">31452104</PMID>
+      <Article PubModel="Print">
+        <Journal>
+          <JournalIssue CitedMedium="Internet">
+            <Volume>572</Volume>
+            <Issue>7770</Issue>
+            <PubDate><Year>2019</Year><Month>Aug</Month></PubDate>
+          </JournalIssue>
+          <Title>Nature</Title>
+        </Journal>
+        <ArticleTitle>Base editing of <i>PCSK9</i> in H<sub>2</sub>O.</ArticleTitle>
+        <Pagination><MedlinePgn>102-107</MedlinePgn></Pagination>
+        <ELocationID EIdType="doi" ValidYN="Y">10.1000/pm</ELocationID>
+        <Abstract>
+          <AbstractText Label="BACKGROUND" NlmCategory="BACKGROUND">Cells &amp; <i>genes</i>.</AbstractText>
+          <AbstractText Label="RESULTS">It
+            works.</AbstractText>
+        </Abstract>
+        <AuthorList CompleteYN="Y">
+          <Author ValidYN="Y"><LastName>Doudna</LastName><ForeName>Jennifer A</ForeName><Initials>JA</Initials></Author>
+          <Author ValidYN="Y"><CollectiveName>Gene Editing Consortium</CollectiveName></Author>
+        </AuthorList>
+        <PublicationTypeList>
+          <PublicationType UI="D016428">Journal Article</PublicationType>
+        </PublicationTypeList>
+      </Article>
+    </MedlineCitation>
+    <PubmedData>
+      <ArticleIdList>
+        <ArticleId IdType="pubmed">31452104</ArticleId>
+        <ArticleId IdType="pmc">PMC6789012</ArticleId>
+      </ArticleIdList>
+      <ReferenceList>
+        <Reference>
+          <Citation>Someone else. 2001.</Citation>
+          <ArticleIdList><ArticleId IdType="doi">10.9999/other</ArticleId></ArticleIdList>
+        </Reference>
+      </ReferenceList>
+    </PubmedData>
+  </PubmedArticle>
+</PubmedArticleSet>"#;
+
+    #[test]
+    fn parse_efetch_response_maps_medline_citation() {
+        let articles = parse_efetch_response(EFETCH_FIXTURE).unwrap();
+        assert_eq!(articles.len(), 1);
+
+        let article = &articles[0];
+        assert_eq!(article.pmid, "31452104");
+        assert_eq!(article.pmcid.as_deref(), Some("PMC6789012"));
+        assert_eq!(article.doi.as_deref(), Some("10.1000/pm"));
+        assert_eq!(article.title, "Base editing of PCSK9 in H2O.");
+        assert_eq!(
+            article.authors,
+            vec!["Jennifer A Doudna", "Gene Editing Consortium"]
+        );
+        assert_eq!(
+            article.abstract_text.as_deref(),
+            Some("BACKGROUND: Cells & genes.\n\nRESULTS: It works.")
+        );
+        assert_eq!(article.journal.as_deref(), Some("Nature"));
+        assert_eq!(article.volume.as_deref(), Some("572"));
+        assert_eq!(article.issue.as_deref(), Some("7770"));
+        assert_eq!(article.pages.as_deref(), Some("102-107"));
+        assert_eq!(article.year, Some(2019));
+        assert!(!article.is_preprint());
+    }
+
+    #[test]
+    fn normalizes_pubmed_ids() {
+        assert_eq!(
+            normalize_pmid(" PMID: 31452104").as_deref(),
+            Some("31452104")
+        );
+        assert_eq!(normalize_pmid("PMC6789012"), None);
+        assert_eq!(normalize_pmcid("pmc6789012").as_deref(), Some("PMC6789012"));
+        assert_eq!(normalize_pmcid("6789012").as_deref(), Some("PMC6789012"));
+        assert_eq!(normalize_pmcid("10.1000/pm"), None);
+    }
+
+    #[tokio::test]
+    async fn converts_ids_and_finds_pmc_pdf() {
+        let mut server = Server::new_async().await;
+        let idconv_mock = server
+            .mock("GET", "/idconv")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("ids".to_string(), "10.1000/pm".to_string()),
+                Matcher::UrlEncoded("format".to_string(), "json".to_string()),
+                Matcher::UrlEncoded("tool".to_string(), TOOL_NAME.to_string()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "status": "ok",
+                    "records": [
+                        {"pmcid": "PMC6789012", "pmid": 31452104, "doi": "10.1000/pm"}
+                    ]
+                })
+                .to_string(),
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let oa_mock = server
+            .mock("GET", "/oa")
+            .match_query(Matcher::UrlEncoded(
+                "id".to_string(),
+                "PMC6789012".to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "text/xml")
+            .with_body(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<OA><responseDate>2024-01-01 00:00:00</responseDate>
+<request id="PMC6789012">oa.fcgi?id=PMC6789012</request>
+<records returned-count="1" total-count="1">
+<record id="PMC6789012" citation="Nature. 2019" license="CC BY" retracted="no">
+<link format="tgz" href="ftp://ftp.ncbi.nlm.nih.gov/pub/pmc/oa_package/aa/bb/PMC6789012.tar.gz" />
+<link format="pdf" href="ftp://ftp.ncbi.nlm.nih.gov/pub/pmc/oa_pdf/aa/bb/paper.PMC6789012.pdf" />
+</record></records></OA>"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let source = PubMedSource::new_for_tests(server.url());
+        let ids = source.convert_ids("10.1000/pm").await.unwrap();
+        assert_eq!(
+            ids,
+            Some(PubMedIds {
+                pmid: Some("31452104".to_string()),
+                pmcid: Some("PMC6789012".to_string()),
+                doi: Some("10.1000/pm".to_string()),
+            })
+        );
+
+        let download = source.find_download_url("10.1000/pm").await.unwrap();
+        assert_eq!(
+            download.map(|d| d.url).as_deref(),
+            Some("https://ftp.ncbi.nlm.nih.gov/pub/pmc/oa_pdf/aa/bb/paper.PMC6789012.pdf")
+        );
+
+        idconv_mock.assert_async().await;
+        oa_mock.assert_async().await;
+    }
+
+    #[test]
+    fn articles_outside_the_open_access_subset_have_no_pdf() {
+        let xml = r#"<OA><responseDate>x</responseDate><request id="PMC1">y</request>
+<error code="idIsNotOpenAccess">identifier 'PMC1' is not Open Access</error></OA>"#;
+        assert_eq!(parse_oa_response(xml).unwrap(), None);
+    }
+}
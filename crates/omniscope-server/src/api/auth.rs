@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::http::header;
+use axum::middleware::Next;
+use axum::response::Response;
+
+use omniscope_core::config::ServerConfig;
+
+use super::ApiError;
+
+/// The token clients must send as `Authorization: Bearer <token>`, shared by
+/// the REST API and MCP over HTTP.
+#[derive(Clone)]
+pub struct BearerToken(Arc<str>);
+
+impl BearerToken {
+    pub fn new(token: impl Into<Arc<str>>) -> Self {
+        Self(token.into())
+    }
+
+    /// Read the token from the env var named by
+    /// [`ServerConfig::auth_token_env`]; `None` when it is unset or blank.
+    pub fn from_env(config: &ServerConfig) -> Option<Self> {
+        std::env::var(&config.auth_token_env)
+            .ok()
+            .filter(|token| !token.trim().is_empty())
+            .map(Self::new)
+    }
+}
+
+/// Reject requests whose `Authorization: Bearer <token>` does not match.
+///
+/// WebSocket upgrades need the header too: a token in the URL would end up
+/// in access logs and proxy caches.
+pub async fn require_bearer(
+    State(expected): State<BearerToken>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+        .is_some_and(|token| constant_time_eq(token.as_bytes(), expected.0.as_bytes()));
+
+    if authorized {
+        Ok(next.run(request).await)
+    } else {
+        Err(ApiError::Unauthorized)
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
@@ -0,0 +1,200 @@
+use axum::Json;
+use axum::body::Body;
+use axum::extract::{Path, Query, State};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tokio_util::io::ReaderStream;
+use uuid::Uuid;
+
+use omniscope_ai::actions::merge_json;
+use omniscope_core::{BookCard, FileFormat, OmniscopeAction};
+
+use super::{ApiError, ApiResult, ApiState, ok};
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+const MAX_PAGE_SIZE: usize = 500;
+
+#[derive(Debug, Deserialize)]
+pub struct ListParams {
+    /// Search DSL query; omitted or empty lists everything.
+    #[serde(default)]
+    pub q: Option<String>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub offset: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TagBody {
+    pub tag: String,
+}
+
+pub async fn list_books(
+    State(state): State<ApiState>,
+    Query(params): Query<ListParams>,
+) -> ApiResult<Json<Value>> {
+    let limit = params.limit.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
+    let offset = params.offset.unwrap_or(0);
+    let query = params.q.unwrap_or_default();
+
+    let (items, total) = if query.trim().is_empty() {
+        let items = state.library.db().list_books(limit, offset)?;
+        (items, state.library.db().count_books()?)
+    } else {
+        let all = state.library.search_all(&query)?;
+        let total = all.len();
+        (all.into_iter().skip(offset).take(limit).collect(), total)
+    };
+
+    Ok(ok(json!({
+        "items": items,
+        "total": total,
+        "limit": limit,
+        "offset": offset,
+        "query": query,
+    })))
+}
+
+pub async fn get_book(
+    State(state): State<ApiState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<Value>> {
+    let card = state.library.load_card(&id)?;
+    Ok(ok(serde_json::to_value(card)?))
+}
+
+/// Create a card from a partial JSON object; `metadata.title` is required.
+pub async fn create_book(
+    State(state): State<ApiState>,
+    Json(fields): Json<Value>,
+) -> ApiResult<Json<Value>> {
+    let title = fields
+        .pointer("/metadata/title")
+        .and_then(Value::as_str)
+        .filter(|title| !title.trim().is_empty())
+        .ok_or_else(|| ApiError::BadRequest("metadata.title is required".to_string()))?;
+    check_file_path(&state, &fields)?;
+
+    let mut card = serde_json::to_value(BookCard::new(title))?;
+    let id = card["id"].clone();
+    merge_json(&mut card, &fields);
+    card["id"] = id;
+
+    let created = state
+        .library
+        .apply_action(&OmniscopeAction::CreateCard { card })?;
+    Ok(ok(created))
+}
+
+pub async fn update_book(
+    State(state): State<ApiState>,
+    Path(id): Path<Uuid>,
+    Json(fields): Json<Value>,
+) -> ApiResult<Json<Value>> {
+    if !fields.is_object() {
+        return Err(ApiError::BadRequest("body must be a JSON object".to_string()));
+    }
+    check_file_path(&state, &fields)?;
+    let updated = state
+        .library
+        .apply_action(&OmniscopeAction::UpdateCard { id, fields })?;
+    Ok(ok(updated))
+}
+
+pub async fn delete_book(
+    State(state): State<ApiState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<Value>> {
+    let deleted = state
+        .library
+        .apply_action(&OmniscopeAction::DeleteCard { id })?;
+    Ok(ok(deleted))
+}
+
+pub async fn add_tag(
+    State(state): State<ApiState>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<TagBody>,
+) -> ApiResult<Json<Value>> {
+    let tags = state.library.apply_action(&OmniscopeAction::AddTag {
+        book_id: id,
+        tag: body.tag,
+    })?;
+    Ok(ok(tags))
+}
+
+pub async fn remove_tag(
+    State(state): State<ApiState>,
+    Path((id, tag)): Path<(Uuid, String)>,
+) -> ApiResult<Json<Value>> {
+    let tags = state
+        .library
+        .apply_action(&OmniscopeAction::RemoveTag { book_id: id, tag })?;
+    Ok(ok(tags))
+}
+
+/// Stream the file attached to a card.
+pub async fn book_file(
+    State(state): State<ApiState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Response> {
+    let card = state.library.load_card(&id)?;
+    let file = card
+        .file
+        .ok_or_else(|| ApiError::NotFound(format!("book {id} has no attached file")))?;
+
+    let path = state.library.resolve_file(&file.path).ok_or_else(|| {
+        ApiError::BadRequest(format!("file is outside the library: {}", file.path))
+    })?;
+    let handle = tokio::fs::File::open(&path)
+        .await
+        .map_err(|_| ApiError::NotFound(format!("file missing on disk: {}", path.display())))?;
+    let length = handle.metadata().await.map(|meta| meta.len()).ok();
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().replace('"', ""))
+        .unwrap_or_else(|| format!("{id}.{}", file.format));
+
+    let mut response = Body::from_stream(ReaderStream::new(handle)).into_response();
+    let headers = response.headers_mut();
+    headers.insert(header::CONTENT_TYPE, content_type(file.format).parse().unwrap());
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        format!("inline; filename=\"{file_name}\"")
+            .parse()
+            .map_err(|_| ApiError::BadRequest("unrepresentable file name".to_string()))?,
+    );
+    if let Some(length) = length {
+        headers.insert(header::CONTENT_LENGTH, length.into());
+    }
+    Ok(response)
+}
+
+/// Refuse a `file.path` that points outside the library, so the file route
+/// cannot be used to read arbitrary files.
+fn check_file_path(state: &ApiState, fields: &Value) -> ApiResult<()> {
+    match fields.pointer("/file/path").and_then(Value::as_str) {
+        Some(path) if state.library.resolve_file(path).is_none() => Err(ApiError::BadRequest(
+            format!("file.path must be inside the library: {path}"),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn content_type(format: FileFormat) -> &'static str {
+    match format {
+        FileFormat::Pdf => "application/pdf",
+        FileFormat::Epub => "application/epub+zip",
+        FileFormat::Djvu => "image/vnd.djvu",
+        FileFormat::Mobi | FileFormat::Azw3 => "application/x-mobipocket-ebook",
+        FileFormat::Fb2 => "application/x-fictionbook+xml",
+        FileFormat::Txt => "text/plain; charset=utf-8",
+        FileFormat::Html => "text/html; charset=utf-8",
+        FileFormat::Cbz => "application/vnd.comicbook+zip",
+        FileFormat::Cbr => "application/vnd.comicbook-rar",
+        FileFormat::Other => "application/octet-stream",
+    }
+}
@@ -0,0 +1,140 @@
+//! CSL-JSON export and import, for citeproc-js, Pandoc and Zotero clients.
+//!
+//! Exports are returned as bare CSL-JSON rather than inside the usual
+//! envelope, so the response can be handed straight to a citation processor.
+
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use omniscope_core::search_dsl::SearchQuery;
+use omniscope_core::storage::json_cards;
+use omniscope_core::{BookCard, OmniscopeAction};
+use omniscope_science::ScienceConfig;
+use omniscope_science::bib_import::{self, BibFormat, ImportDecision};
+use omniscope_science::export::{self, ExportFormat};
+use omniscope_science::formats::bibtex::unique_cite_keys;
+use omniscope_science::formats::csl_json;
+
+use super::{ApiError, ApiResult, ApiState, ok};
+
+const CSL_JSON_CONTENT_TYPE: &str = "application/vnd.citationstyles.csl+json";
+
+#[derive(Debug, Deserialize)]
+pub struct ExportParams {
+    /// Search DSL query; omitted or empty exports the whole library.
+    #[serde(default)]
+    pub q: Option<String>,
+}
+
+/// The cards matching `q` as a CSL-JSON array.
+pub async fn export_csl_json(
+    State(state): State<ApiState>,
+    Query(params): Query<ExportParams>,
+) -> ApiResult<Response> {
+    let query = SearchQuery::parse(params.q.as_deref().unwrap_or_default());
+    if query.semantic_text().is_some() {
+        return Err(ApiError::BadRequest(
+            "`~` terms can't be used in an export query".to_string(),
+        ));
+    }
+
+    let cards = json_cards::list_cards(state.library.cards_dir())?;
+    let selected = export::select_cards(cards, &query);
+    let refs: Vec<&BookCard> = selected.iter().collect();
+    let config = ScienceConfig::load().unwrap_or_default().export;
+    let body = export::render(&refs, &ExportFormat::CslJson, &config)
+        .map_err(|err| ApiError::Internal(err.into()))?;
+    Ok(csl_json_response(body))
+}
+
+/// One card as a CSL-JSON item, keyed by its cite key.
+pub async fn book_csl_json(
+    State(state): State<ApiState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Response> {
+    let card = state.library.load_card(&id)?;
+    let scheme = ScienceConfig::load()
+        .unwrap_or_default()
+        .export
+        .cite_key_scheme();
+    let key = unique_cite_keys(&[&card], &scheme).remove(0);
+    let item = csl_json::from_book_card(&card, &key);
+    Ok(csl_json_response(serde_json::to_string_pretty(&item)?))
+}
+
+/// Create cards from a CSL-JSON array (or single item). Entries already in
+/// the library are skipped, using the same matching as `omniscope import-bib`.
+pub async fn import_csl_json(
+    State(state): State<ApiState>,
+    body: String,
+) -> ApiResult<Json<Value>> {
+    // A handle opened without a library root trusts no attachment path.
+    let library_root = match state.library.root() {
+        Some(root) => root.root().to_path_buf(),
+        None => state.library.cards_dir().to_path_buf(),
+    };
+    let candidates =
+        bib_import::parse_candidates(&body, BibFormat::CslJson, &library_root, &library_root)
+            .map_err(|err| ApiError::BadRequest(err.to_string()))?;
+    let existing = json_cards::list_cards(state.library.cards_dir())?;
+    let plan = bib_import::plan_import(candidates, &existing);
+
+    // One journaled transaction, so the whole import is undone in one step.
+    let mut created = Vec::new();
+    let mut actions = Vec::new();
+    for card in plan.cards_to_create() {
+        created.push(card.id);
+        actions.push(OmniscopeAction::CreateCard {
+            card: serde_json::to_value(card)?,
+        });
+    }
+    if !actions.is_empty() {
+        state
+            .library
+            .apply_action(&OmniscopeAction::Transaction { actions })?;
+    }
+
+    let items: Vec<Value> = plan
+        .entries
+        .iter()
+        .map(|(candidate, decision)| {
+            let mut item = json!({
+                "key": candidate.key,
+                "title": candidate.card.metadata.title,
+            });
+            match decision {
+                ImportDecision::Create => {
+                    item["action"] = "create".into();
+                    item["id"] = candidate.card.id.to_string().into();
+                }
+                ImportDecision::Existing { id, matched_by } => {
+                    item["action"] = "skip".into();
+                    item["duplicate_of"] = id.to_string().into();
+                    item["matched_by"] = matched_by.as_str().into();
+                }
+                ImportDecision::Repeated { key, matched_by } => {
+                    item["action"] = "skip".into();
+                    item["repeats"] = key.as_str().into();
+                    item["matched_by"] = matched_by.as_str().into();
+                }
+            }
+            item
+        })
+        .collect();
+
+    Ok(ok(json!({
+        "entries": items.len(),
+        "created": created,
+        "duplicates": plan.duplicate_count(),
+        "items": items,
+    })))
+}
+
+fn csl_json_response(body: String) -> Response {
+    ([(header::CONTENT_TYPE, CSL_JSON_CONTENT_TYPE)], body).into_response()
+}
@@ -0,0 +1,75 @@
+use axum::Json;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+
+use omniscope_ai::actions::ActionError;
+use omniscope_core::OmniscopeError;
+
+/// Error returned by REST handlers, rendered as the CLI's JSON error envelope.
+#[derive(Debug)]
+pub enum ApiError {
+    Unauthorized,
+    NotFound(String),
+    BadRequest(String),
+    Internal(anyhow::Error),
+}
+
+impl ApiError {
+    fn status_and_code(&self) -> (StatusCode, &'static str) {
+        match self {
+            Self::Unauthorized => (StatusCode::UNAUTHORIZED, "unauthorized"),
+            Self::NotFound(_) => (StatusCode::NOT_FOUND, "not_found"),
+            Self::BadRequest(_) => (StatusCode::BAD_REQUEST, "invalid_args"),
+            Self::Internal(_) => (StatusCode::INTERNAL_SERVER_ERROR, "internal"),
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            Self::Unauthorized => "missing or invalid bearer token".to_string(),
+            Self::NotFound(msg) | Self::BadRequest(msg) => msg.clone(),
+            Self::Internal(err) => format!("{err:#}"),
+        }
+    }
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(err: anyhow::Error) -> Self {
+        let core = match err.downcast_ref::<ActionError>() {
+            Some(ActionError::Core(core)) => Some(core),
+            Some(action_err) => return Self::BadRequest(action_err.to_string()),
+            None => err.downcast_ref::<OmniscopeError>(),
+        };
+        match core {
+            Some(OmniscopeError::BookNotFound(id)) => Self::NotFound(format!("book not found: {id}")),
+            Some(OmniscopeError::ValidationError(msg)) => Self::BadRequest(msg.clone()),
+            _ => Self::Internal(err),
+        }
+    }
+}
+
+impl From<OmniscopeError> for ApiError {
+    fn from(err: OmniscopeError) -> Self {
+        anyhow::Error::from(err).into()
+    }
+}
+
+impl From<serde_json::Error> for ApiError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::BadRequest(err.to_string())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, code) = self.status_and_code();
+        let body = serde_json::json!({
+            "status": "error",
+            "error": code,
+            "message": self.message(),
+        });
+        (status, Json(body)).into_response()
+    }
+}
+
+pub type ApiResult<T> = std::result::Result<T, ApiError>;
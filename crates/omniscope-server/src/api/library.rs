@@ -0,0 +1,54 @@
+use axum::Json;
+use axum::extract::State;
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use omniscope_core::{Folder, FolderTree};
+
+use super::{ApiResult, ApiState, ok};
+
+/// Nested, serializable view of [`FolderTree`].
+#[derive(Debug, Serialize)]
+pub struct FolderTreeNode {
+    #[serde(flatten)]
+    pub folder: Folder,
+    pub children: Vec<FolderTreeNode>,
+}
+
+pub async fn folder_tree(State(state): State<ApiState>) -> ApiResult<Json<Value>> {
+    let tree = FolderTree::build(state.library.db().list_all_folders()?);
+    let roots = tree
+        .root_ids
+        .iter()
+        .filter_map(|id| nest(&tree, id))
+        .collect::<Vec<_>>();
+    Ok(ok(json!({ "folders": roots })))
+}
+
+pub async fn stats(State(state): State<ApiState>) -> ApiResult<Json<Value>> {
+    let stats = state.library.db().library_stats()?;
+    Ok(ok(serde_json::to_value(stats)?))
+}
+
+pub async fn list_tags(State(state): State<ApiState>) -> ApiResult<Json<Value>> {
+    let tags = state
+        .library
+        .db()
+        .list_tags()?
+        .into_iter()
+        .map(|(name, count)| json!({ "name": name, "count": count }))
+        .collect::<Vec<_>>();
+    Ok(ok(json!({ "tags": tags })))
+}
+
+fn nest(tree: &FolderTree, id: &str) -> Option<FolderTreeNode> {
+    let node = tree.nodes.get(id)?;
+    Some(FolderTreeNode {
+        folder: node.folder.clone(),
+        children: node
+            .children
+            .iter()
+            .filter_map(|child| nest(tree, child))
+            .collect(),
+    })
+}
@@ -0,0 +1,370 @@
+//! REST API routes.
+//!
+//! JSON endpoints for scripts and web front-ends, served by `omniscope serve`.
+//! Every route requires `Authorization: Bearer <token>`, where the token is
+//! read from the env var named by [`ServerConfig::auth_token_env`]. Responses
+//! use the same `{"status": "ok", "data": ...}` envelope as the CLI's `--json`,
+//! except CSL-JSON exports, which are bare so citation processors can read them.
+//! `/api/events` upgrades to a WebSocket carrying the live change feed.
+
+pub(crate) mod auth;
+mod books;
+mod csl_json;
+mod error;
+mod events;
+mod library;
+
+pub use auth::BearerToken;
+pub use error::{ApiError, ApiResult};
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use axum::routing::{delete, get, post};
+use axum::{Json, Router, middleware};
+use serde_json::{Value, json};
+
+use omniscope_core::config::ServerConfig;
+
+use crate::library::LibraryHandle;
+
+#[derive(Clone)]
+pub struct ApiState {
+    pub library: Arc<LibraryHandle>,
+    token: BearerToken,
+}
+
+impl ApiState {
+    pub fn new(library: Arc<LibraryHandle>, token: impl Into<Arc<str>>) -> Self {
+        Self {
+            library,
+            token: BearerToken::new(token),
+        }
+    }
+
+    /// Build state, reading the bearer token from the configured env var.
+    pub fn from_config(library: Arc<LibraryHandle>, config: &ServerConfig) -> Result<Self> {
+        let token = BearerToken::from_env(config).with_context(|| {
+            format!(
+                "set {} to the bearer token clients must send",
+                config.auth_token_env
+            )
+        })?;
+        Ok(Self { library, token })
+    }
+}
+
+pub fn router(state: ApiState) -> Router {
+    Router::new()
+        .route("/api/books", get(books::list_books).post(books::create_book))
+        .route(
+            "/api/books/{id}",
+            get(books::get_book)
+                .patch(books::update_book)
+                .delete(books::delete_book),
+        )
+        .route("/api/books/{id}/tags", post(books::add_tag))
+        .route("/api/books/{id}/tags/{tag}", delete(books::remove_tag))
+        .route("/api/books/{id}/file", get(books::book_file))
+        .route("/api/books/{id}/csl-json", get(csl_json::book_csl_json))
+        .route(
+            "/api/csl-json",
+            get(csl_json::export_csl_json).post(csl_json::import_csl_json),
+        )
+        .route("/api/tags", get(library::list_tags))
+        .route("/api/folders", get(library::folder_tree))
+        .route("/api/stats", get(library::stats))
+        .route("/api/events", get(events::events))
+        .route_layer(middleware::from_fn_with_state(
+            state.token.clone(),
+            auth::require_bearer,
+        ))
+        .with_state(state)
+}
+
+pub async fn serve(state: ApiState, addr: SocketAddr) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, router(state)).await?;
+    Ok(())
+}
+
+fn ok(data: Value) -> Json<Value> {
+    Json(json!({ "status": "ok", "data": data }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{Body, to_bytes};
+    use axum::http::{Request, StatusCode};
+    use omniscope_core::{BookCard, Database};
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    const TOKEN: &str = "secret";
+
+    fn app() -> (tempfile::TempDir, Router, Arc<LibraryHandle>) {
+        let dir = tempfile::tempdir().unwrap();
+        let library = Arc::new(LibraryHandle::new(
+            Database::open_in_memory().unwrap(),
+            dir.path().join("cards"),
+        ));
+        let router = router(ApiState::new(library.clone(), TOKEN));
+        (dir, router, library)
+    }
+
+    fn request(method: &str, uri: &str, body: Option<Value>) -> Request<Body> {
+        let builder = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("authorization", format!("Bearer {TOKEN}"))
+            .header("content-type", "application/json");
+        match body {
+            Some(body) => builder.body(Body::from(body.to_string())).unwrap(),
+            None => builder.body(Body::empty()).unwrap(),
+        }
+    }
+
+    async fn json_body(response: axum::response::Response) -> Value {
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_missing_token_is_rejected() {
+        let (_dir, app, _) = app();
+        let response = app
+            .oneshot(Request::get("/api/books").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_create_then_get_book() {
+        let (_dir, app, _) = app();
+        let response = app
+            .clone()
+            .oneshot(request(
+                "POST",
+                "/api/books",
+                Some(json!({ "metadata": { "title": "Dune", "year": 1965 } })),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let created = json_body(response).await;
+        let id = created["data"]["id"].as_str().unwrap().to_string();
+
+        let response = app
+            .oneshot(request("GET", &format!("/api/books/{id}"), None))
+            .await
+            .unwrap();
+        let fetched = json_body(response).await;
+        assert_eq!(fetched["data"]["metadata"]["title"], "Dune");
+        assert_eq!(fetched["data"]["metadata"]["year"], 1965);
+    }
+
+    #[tokio::test]
+    async fn test_list_books_pages_results() {
+        let (_dir, app, library) = app();
+        for title in ["A", "B", "C"] {
+            library.save_card(&BookCard::new(title)).unwrap();
+        }
+
+        let response = app
+            .oneshot(request("GET", "/api/books?limit=2&offset=0", None))
+            .await
+            .unwrap();
+        let body = json_body(response).await;
+        assert_eq!(body["data"]["total"], 3);
+        assert_eq!(body["data"]["items"].as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_book_is_not_found() {
+        let (_dir, app, _) = app();
+        let id = uuid::Uuid::new_v4();
+        let response = app
+            .oneshot(request("GET", &format!("/api/books/{id}"), None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(json_body(response).await["error"], "not_found");
+    }
+
+    #[tokio::test]
+    async fn test_tag_routes() {
+        let (_dir, app, library) = app();
+        let card = BookCard::new("Tagged");
+        library.save_card(&card).unwrap();
+
+        app.clone()
+            .oneshot(request(
+                "POST",
+                &format!("/api/books/{}/tags", card.id),
+                Some(json!({ "tag": "scifi" })),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(library.load_card(&card.id).unwrap().organization.tags, vec!["scifi"]);
+
+        app.oneshot(request(
+            "DELETE",
+            &format!("/api/books/{}/tags/scifi", card.id),
+            None,
+        ))
+        .await
+        .unwrap();
+        assert!(library.load_card(&card.id).unwrap().organization.tags.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_file_route_stays_inside_the_library() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("books");
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("book.pdf"), b"%PDF-1.7").unwrap();
+        let secret = dir.path().join("secret.txt");
+        std::fs::write(&secret, b"private").unwrap();
+
+        let library =
+            Arc::new(LibraryHandle::open(omniscope_core::LibraryRoot::new(root.clone())).unwrap());
+        let app = router(ApiState::new(library.clone(), TOKEN));
+        let mut card = BookCard::new("Attached");
+        card.file = Some(omniscope_core::models::BookFile {
+            path: secret.display().to_string(),
+            format: omniscope_core::FileFormat::Txt,
+            size_bytes: 7,
+            hash_sha256: None,
+            added_at: chrono::Utc::now(),
+        });
+        library.save_card(&card).unwrap();
+        let file_uri = format!("/api/books/{}/file", card.id);
+        let book_uri = format!("/api/books/{}", card.id);
+
+        let response = app
+            .clone()
+            .oneshot(request("GET", &file_uri, None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        for path in [secret.display().to_string(), "../secret.txt".to_string()] {
+            let response = app
+                .clone()
+                .oneshot(request(
+                    "PATCH",
+                    &book_uri,
+                    Some(json!({ "file": { "path": path } })),
+                ))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+
+        let response = app
+            .clone()
+            .oneshot(request(
+                "PATCH",
+                &book_uri,
+                Some(json!({ "file": { "path": "book.pdf", "format": "pdf" } })),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = app.oneshot(request("GET", &file_uri, None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&bytes[..], b"%PDF-1.7");
+    }
+
+    #[tokio::test]
+    async fn test_csl_json_import_then_export() {
+        let (_dir, app, library) = app();
+        let items = json!([
+            {
+                "id": "herbert1965",
+                "type": "book",
+                "title": "Dune",
+                "author": [{ "family": "Herbert", "given": "Frank" }],
+                "issued": { "date-parts": [[1965]] },
+                "publisher": "Chilton"
+            },
+            { "id": "dune-again", "type": "book", "title": "Dune", "issued": { "date-parts": [[1965]] } }
+        ]);
+
+        let response = app
+            .clone()
+            .oneshot(request("POST", "/api/csl-json", Some(items)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = json_body(response).await;
+        assert_eq!(body["data"]["entries"], 2);
+        assert_eq!(body["data"]["duplicates"], 1);
+        assert_eq!(body["data"]["items"][1]["repeats"], "herbert1965");
+        let id: Uuid = body["data"]["created"][0]
+            .as_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(
+            library.load_card(&id).unwrap().metadata.authors,
+            vec!["Frank Herbert"]
+        );
+
+        let response = app
+            .clone()
+            .oneshot(request("GET", &format!("/api/books/{id}/csl-json"), None))
+            .await
+            .unwrap();
+        assert_eq!(
+            response.headers()[axum::http::header::CONTENT_TYPE],
+            "application/vnd.citationstyles.csl+json"
+        );
+        let item = json_body(response).await;
+        assert_eq!(item["id"], "herbert1965");
+        assert_eq!(item["author"][0]["family"], "Herbert");
+
+        let response = app
+            .oneshot(request("GET", "/api/csl-json?q=dune", None))
+            .await
+            .unwrap();
+        let exported = json_body(response).await;
+        assert_eq!(exported.as_array().unwrap().len(), 1);
+        assert_eq!(exported[0]["issued"]["date-parts"][0][0], 1965);
+    }
+
+    #[tokio::test]
+    async fn test_csl_json_import_is_one_journaled_action() {
+        let (_dir, app, library) = app();
+        let items = json!([
+            { "id": "a", "type": "book", "title": "Dune" },
+            { "id": "b", "type": "book", "title": "Children of Dune" }
+        ]);
+        let response = app
+            .oneshot(request("POST", "/api/csl-json", Some(items)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let executor =
+            omniscope_ai::actions::ActionExecutor::new(library.db(), library.cards_dir());
+        let history = executor.history(10).unwrap();
+        assert_eq!(history.len(), 1);
+        executor.reverse(&history[0].id).unwrap();
+        assert_eq!(library.db().count_books().unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_csl_json_is_a_bad_request() {
+        let (_dir, app, _) = app();
+        let response = app
+            .oneshot(request("POST", "/api/csl-json", Some(json!(42))))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
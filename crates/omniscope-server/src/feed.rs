@@ -0,0 +1,69 @@
+//! Filesystem watchers feeding the live change feed.
+//!
+//! Changes made through the server are published directly by
+//! [`LibraryHandle`]; these watchers pick up everything else — card writes
+//! from the CLI or TUI, and book files or folders touched on disk. Card
+//! changes also regenerate the `.bib` files registered with `bib-sync`.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+
+use omniscope_core::sync::{CardsWatcher, LibraryEvent, LibraryWatcher, WatcherEvent};
+use omniscope_science::ScienceConfig;
+use omniscope_science::bib_sync;
+
+use crate::library::LibraryHandle;
+
+const CARDS_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Keeps the underlying watchers alive; dropping it stops the feed.
+pub struct FeedWatchers {
+    _library: LibraryWatcher,
+    _cards: CardsWatcher,
+}
+
+/// Start watching the library behind `library`, if it has a root on disk.
+pub fn start_watchers(library: Arc<LibraryHandle>) -> Result<Option<FeedWatchers>> {
+    let Some(root) = library.root().cloned() else {
+        return Ok(None);
+    };
+    let config = root.load_manifest()?.settings.watcher;
+
+    let (library_watcher, fs_rx) = LibraryWatcher::start(root.root().to_path_buf(), config)?;
+    let (cards_watcher, cards_rx) = CardsWatcher::start(library.cards_dir(), CARDS_DEBOUNCE)?;
+
+    let fs_library = library.clone();
+    std::thread::spawn(move || {
+        while let Ok(event) = fs_rx.recv() {
+            let folders_changed = matches!(
+                event,
+                WatcherEvent::DirectoryCreated { .. }
+                    | WatcherEvent::DirectoryRemoved { .. }
+                    | WatcherEvent::DirectoryRenamed { .. }
+            );
+            fs_library.publish(event.into());
+            if folders_changed {
+                fs_library.publish(LibraryEvent::FoldersChanged);
+            }
+        }
+    });
+
+    let export = ScienceConfig::load().unwrap_or_default().export;
+    std::thread::spawn(move || {
+        while let Ok(event) = cards_rx.recv() {
+            library.publish_external(event);
+            while let Ok(event) = cards_rx.try_recv() {
+                library.publish_external(event);
+            }
+            // Failures are left for `omniscope bib-sync` to report.
+            let _ = bib_sync::sync_library(&root, &export);
+        }
+    });
+
+    Ok(Some(FeedWatchers {
+        _library: library_watcher,
+        _cards: cards_watcher,
+    }))
+}
@@ -1,10 +1,8 @@
 //! Omniscope Server — axum HTTP/WebSocket server, MCP protocol, sync.
-//! Stub for Phase 6 implementation.
 
-pub mod api {
-    //! REST API routes. Full implementation in Phase 6.
-}
+pub mod api;
+pub mod feed;
+pub mod library;
+pub mod mcp;
 
-pub mod mcp {
-    //! MCP protocol handler. Full implementation in Phase 4.
-}
+pub use library::LibraryHandle;
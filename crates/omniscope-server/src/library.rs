@@ -0,0 +1,349 @@
+//! Shared access to an opened library for the server front-ends.
+//!
+//! Both the MCP handler and the REST API talk to the library through
+//! [`LibraryHandle`], which keeps the JSON cards (source of truth) and the
+//! SQLite index in step.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use anyhow::Result;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use omniscope_ai::actions::{ActionExecutor, CardChange};
+use omniscope_ai::memory::LibraryMapCache;
+use omniscope_core::config::AiConfig;
+use omniscope_core::search_dsl::SearchQuery;
+use omniscope_core::storage::json_cards;
+use omniscope_core::sync::LibraryEvent;
+use omniscope_core::{
+    BookCard, BookSummaryView, Database, FuzzySearcher, LibraryMap, LibraryRoot, OmniscopeAction,
+};
+
+/// Buffered events per subscriber before slow clients start lagging.
+const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+pub struct LibraryHandle {
+    root: Option<LibraryRoot>,
+    cards_dir: PathBuf,
+    db: Database,
+    events: broadcast::Sender<LibraryEvent>,
+    map_cache: Mutex<LibraryMapCache>,
+    map_tokens: usize,
+}
+
+impl LibraryHandle {
+    /// Open the library rooted at `root` (cards + database under `.libr/`).
+    pub fn open(root: LibraryRoot) -> Result<Self> {
+        let db_path = root.database_path();
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let db = Database::open(&db_path)?;
+        Ok(Self {
+            cards_dir: root.cards_dir(),
+            map_cache: Mutex::new(LibraryMapCache::for_library(&root, &AiConfig::default())),
+            map_tokens: AiConfig::default().library_map_tokens,
+            root: Some(root),
+            db,
+            events: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
+        })
+    }
+
+    /// Build a handle from an already opened database and cards directory.
+    pub fn new(db: Database, cards_dir: PathBuf) -> Self {
+        let map_path = cards_dir
+            .parent()
+            .unwrap_or(&cards_dir)
+            .join("cache")
+            .join("library_map.json");
+        let ttl = LibraryMapCache::ttl_from_config(&AiConfig::default());
+        Self {
+            root: None,
+            cards_dir,
+            db,
+            events: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
+            map_cache: Mutex::new(LibraryMapCache::new(map_path, ttl)),
+            map_tokens: AiConfig::default().library_map_tokens,
+        }
+    }
+
+    /// Use the library map lifetime and token budget from `config` instead
+    /// of the defaults.
+    pub fn with_ai_config(mut self, config: &AiConfig) -> Self {
+        let path = self.map_cache().path().to_path_buf();
+        let ttl = LibraryMapCache::ttl_from_config(config);
+        *self.map_cache() = LibraryMapCache::new(path, ttl);
+        self.map_tokens = config.library_map_tokens;
+        self
+    }
+
+    pub fn root(&self) -> Option<&LibraryRoot> {
+        self.root.as_ref()
+    }
+
+    /// Resolve a card's `file.path` (relative paths start at the library
+    /// root) through any symlinks. `None` if it leads outside the root, or
+    /// if the handle has no root. A path that does not exist yet is checked
+    /// through its nearest existing ancestor.
+    pub fn resolve_file(&self, path: &str) -> Option<PathBuf> {
+        let root = self.root.as_ref()?.root().canonicalize().ok()?;
+        let path = root.join(path);
+
+        let mut existing = path.as_path();
+        let mut missing = Vec::new();
+        let mut resolved = loop {
+            match existing.canonicalize() {
+                Ok(resolved) => break resolved,
+                Err(_) => {
+                    missing.push(existing.file_name()?);
+                    existing = existing.parent()?;
+                }
+            }
+        };
+        resolved.extend(missing.into_iter().rev());
+        resolved.starts_with(&root).then_some(resolved)
+    }
+
+    pub fn cards_dir(&self) -> &Path {
+        &self.cards_dir
+    }
+
+    pub fn db(&self) -> &Database {
+        &self.db
+    }
+
+    /// Subscribe to the live change feed.
+    pub fn subscribe(&self) -> broadcast::Receiver<LibraryEvent> {
+        self.events.subscribe()
+    }
+
+    /// Broadcast an event; dropped silently when nobody is listening.
+    pub fn publish(&self, event: LibraryEvent) {
+        let _ = self.events.send(event);
+    }
+
+    /// Broadcast a card change noticed on disk, keeping the library map in
+    /// step with it.
+    pub fn publish_external(&self, event: LibraryEvent) {
+        self.map_cache().apply_event(&event);
+        self.publish(event);
+    }
+
+    fn map_cache(&self) -> MutexGuard<'_, LibraryMapCache> {
+        self.map_cache
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Run a search DSL query and return one page of results.
+    pub fn search(&self, query: &str, limit: usize, offset: usize) -> Result<Vec<BookSummaryView>> {
+        Ok(self
+            .search_all(query)?
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .collect())
+    }
+
+    /// Run a search DSL query: filters first, then fuzzy ranking on free text.
+    pub fn search_all(&self, query: &str) -> Result<Vec<BookSummaryView>> {
+        let parsed = SearchQuery::parse(query);
+        let total = self.db.count_books()?;
+        let mut books: Vec<_> = self
+            .db
+            .list_books(total.max(1), 0)?
+            .into_iter()
+            .filter(|book| parsed.matches(book))
+            .collect();
+        if parsed.has_source_filter() {
+            books.retain(|book| {
+                self.load_card(&book.id)
+                    .is_ok_and(|card| omniscope_science::export::matches_card(&parsed, &card))
+            });
+        }
+
+        let fuzzy_text = parsed.fuzzy_text();
+        if !fuzzy_text.is_empty() {
+            books = FuzzySearcher::new()
+                .search(&fuzzy_text, &books)
+                .into_iter()
+                .map(|result| result.book)
+                .collect();
+        }
+
+        Ok(books)
+    }
+
+    /// Load a full card, preferring the JSON file over the index.
+    pub fn load_card(&self, id: &Uuid) -> Result<BookCard> {
+        match json_cards::load_card_by_id(&self.cards_dir, id) {
+            Ok(card) => Ok(card),
+            Err(_) => Ok(self.db.get_book_card(&id.to_string())?),
+        }
+    }
+
+    /// Persist a card to disk and refresh its index row.
+    pub fn save_card(&self, card: &BookCard) -> Result<()> {
+        json_cards::save_card(&self.cards_dir, card)?;
+        self.db.upsert_book(card)?;
+        self.map_cache().card_upserted(card);
+        self.publish(LibraryEvent::CardUpserted {
+            book: BookSummaryView::from(card),
+        });
+        Ok(())
+    }
+
+    pub fn delete_card(&self, id: &Uuid) -> Result<()> {
+        self.db.delete_book(&id.to_string())?;
+        json_cards::delete_card(&self.cards_dir, id)?;
+        self.map_cache().card_deleted(id);
+        self.publish(LibraryEvent::CardDeleted { id: *id });
+        Ok(())
+    }
+
+    /// Compact overview of the library for agents, trimmed to
+    /// `ai.library_map_tokens`.
+    pub fn library_map(&self) -> Result<LibraryMap> {
+        self.map_cache().map_within(&self.db, self.map_tokens)
+    }
+
+    /// Apply a mutating action through the journaled executor and broadcast
+    /// the cards it changed. Returns a JSON summary of the effect.
+    pub fn apply_action(&self, action: &OmniscopeAction) -> Result<serde_json::Value> {
+        let outcome = ActionExecutor::new(&self.db, &self.cards_dir).execute(action, false)?;
+        self.publish_changes(&outcome.changes);
+        Ok(outcome.result)
+    }
+
+    fn publish_changes(&self, changes: &[CardChange]) {
+        self.map_cache().apply_changes(changes);
+        for change in changes {
+            self.publish(match change {
+                CardChange::Upserted(card) => LibraryEvent::CardUpserted {
+                    book: BookSummaryView::from(card),
+                },
+                CardChange::Deleted(id) => LibraryEvent::CardDeleted { id: *id },
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn handle() -> (tempfile::TempDir, LibraryHandle) {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open_in_memory().unwrap();
+        let handle = LibraryHandle::new(db, dir.path().join("cards"));
+        (dir, handle)
+    }
+
+    #[test]
+    fn test_tag_actions_persist_card() {
+        let (_dir, lib) = handle();
+        let card = BookCard::new("Rust in Action");
+        lib.save_card(&card).unwrap();
+
+        lib.apply_action(&OmniscopeAction::AddTag {
+            book_id: card.id,
+            tag: "rust".to_string(),
+        })
+        .unwrap();
+        assert_eq!(lib.load_card(&card.id).unwrap().organization.tags, vec!["rust"]);
+
+        lib.apply_action(&OmniscopeAction::RemoveTag {
+            book_id: card.id,
+            tag: "rust".to_string(),
+        })
+        .unwrap();
+        assert!(lib.load_card(&card.id).unwrap().organization.tags.is_empty());
+    }
+
+    #[test]
+    fn test_update_card_merges_fields() {
+        let (_dir, lib) = handle();
+        let card = BookCard::new("Old Title");
+        lib.save_card(&card).unwrap();
+
+        lib.apply_action(&OmniscopeAction::UpdateCard {
+            id: card.id,
+            fields: serde_json::json!({ "metadata": { "title": "New Title" } }),
+        })
+        .unwrap();
+
+        let updated = lib.load_card(&card.id).unwrap();
+        assert_eq!(updated.metadata.title, "New Title");
+        assert_eq!(updated.created_at, card.created_at);
+    }
+
+    #[test]
+    fn test_mutations_are_published() {
+        let (_dir, lib) = handle();
+        let mut events = lib.subscribe();
+        let card = BookCard::new("Observed");
+        lib.save_card(&card).unwrap();
+        lib.delete_card(&card.id).unwrap();
+
+        assert!(matches!(
+            events.try_recv().unwrap(),
+            LibraryEvent::CardUpserted { book } if book.id == card.id
+        ));
+        assert!(matches!(
+            events.try_recv().unwrap(),
+            LibraryEvent::CardDeleted { id } if id == card.id
+        ));
+    }
+
+    #[test]
+    fn test_search_applies_dsl_filters() {
+        let (_dir, lib) = handle();
+        let mut tagged = BookCard::new("Tagged");
+        tagged.organization.tags.push("ml".to_string());
+        lib.save_card(&tagged).unwrap();
+        lib.save_card(&BookCard::new("Plain")).unwrap();
+
+        let results = lib.search("#ml", 10, 0).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, tagged.id);
+    }
+
+    #[test]
+    fn test_search_applies_source_filters() {
+        let (_dir, lib) = handle();
+        let mut crawled = BookCard::new("Crawled");
+        crawled
+            .metadata_sources
+            .insert("metadata.title".to_string(), "crossref".to_string());
+        lib.save_card(&crawled).unwrap();
+        let typed = BookCard::new("Typed");
+        lib.save_card(&typed).unwrap();
+
+        let results = lib.search("src:crossref", 10, 0).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, crawled.id);
+
+        let results = lib.search("NOT src:crossref", 10, 0).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, typed.id);
+    }
+
+    #[test]
+    fn test_library_map_follows_actions() {
+        let (_dir, lib) = handle();
+        let card = BookCard::new("Rust in Action");
+        lib.save_card(&card).unwrap();
+        assert_eq!(lib.library_map().unwrap().stats.total, 1);
+
+        lib.apply_action(&OmniscopeAction::AddTag {
+            book_id: card.id,
+            tag: "rust".to_string(),
+        })
+        .unwrap();
+        let map = lib.library_map().unwrap();
+        assert_eq!(map.tag_cloud.get("rust"), Some(&1));
+        assert_eq!(map.books[0].tags, vec!["rust"]);
+    }
+}
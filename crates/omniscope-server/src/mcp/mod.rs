@@ -0,0 +1,281 @@
+//! MCP (Model Context Protocol) server.
+//!
+//! Exposes library search, card lookup, arXiv/DOI import, tagging and
+//! BibTeX export as MCP tools, plus the library map as a resource. Runs
+//! over stdio (one JSON-RPC message per line) or over streamable HTTP.
+
+pub mod protocol;
+pub mod tools;
+mod transport;
+
+pub use transport::{serve_http, serve_stdio};
+
+use std::sync::Arc;
+
+use serde_json::{Value, json};
+
+use crate::library::LibraryHandle;
+use protocol::{
+    INTERNAL_ERROR, INVALID_PARAMS, INVALID_REQUEST, JsonRpcError, JsonRpcRequest,
+    JsonRpcResponse, METHOD_NOT_FOUND, PARSE_ERROR, PROTOCOL_VERSION,
+};
+
+pub const SERVER_NAME: &str = "omniscope";
+pub const LIBRARY_MAP_URI: &str = "omniscope://library-map";
+
+#[derive(Clone)]
+pub struct McpServer {
+    library: Arc<LibraryHandle>,
+}
+
+impl McpServer {
+    pub fn new(library: Arc<LibraryHandle>) -> Self {
+        Self { library }
+    }
+
+    /// Handle one raw JSON-RPC message (single or batch).
+    ///
+    /// Returns `None` when nothing should be written back (notifications only).
+    pub async fn handle_message(&self, raw: &str) -> Option<Value> {
+        let message: Value = match serde_json::from_str(raw) {
+            Ok(value) => value,
+            Err(err) => {
+                let response = JsonRpcResponse::failure(
+                    Value::Null,
+                    JsonRpcError::new(PARSE_ERROR, err.to_string()),
+                );
+                return serde_json::to_value(response).ok();
+            }
+        };
+
+        match message {
+            Value::Array(batch) => {
+                let mut responses = Vec::new();
+                for item in batch {
+                    if let Some(response) = self.handle_value(item).await {
+                        responses.push(response);
+                    }
+                }
+                (!responses.is_empty()).then(|| Value::Array(responses))
+            }
+            single => self.handle_value(single).await,
+        }
+    }
+
+    async fn handle_value(&self, value: Value) -> Option<Value> {
+        let request: JsonRpcRequest = match serde_json::from_value(value) {
+            Ok(request) => request,
+            Err(err) => {
+                let response = JsonRpcResponse::failure(
+                    Value::Null,
+                    JsonRpcError::new(INVALID_REQUEST, err.to_string()),
+                );
+                return serde_json::to_value(response).ok();
+            }
+        };
+
+        let response = self.handle_request(&request).await;
+        if request.is_notification() {
+            return None;
+        }
+        serde_json::to_value(response).ok()
+    }
+
+    pub async fn handle_request(&self, request: &JsonRpcRequest) -> JsonRpcResponse {
+        let id = request.id.clone().unwrap_or(Value::Null);
+        match self.dispatch(&request.method, &request.params).await {
+            Ok(result) => JsonRpcResponse::success(id, result),
+            Err(error) => JsonRpcResponse::failure(id, error),
+        }
+    }
+
+    async fn dispatch(&self, method: &str, params: &Value) -> Result<Value, JsonRpcError> {
+        match method {
+            "initialize" => Ok(json!({
+                "protocolVersion": PROTOCOL_VERSION,
+                "capabilities": {
+                    "tools": { "listChanged": false },
+                    "resources": { "listChanged": false, "subscribe": false }
+                },
+                "serverInfo": {
+                    "name": SERVER_NAME,
+                    "version": env!("CARGO_PKG_VERSION")
+                }
+            })),
+            "notifications/initialized" | "notifications/cancelled" | "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tools::tool_definitions() })),
+            "tools/call" => {
+                let name = params
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| JsonRpcError::new(INVALID_PARAMS, "missing tool name"))?;
+                let args = params.get("arguments").cloned().unwrap_or(json!({}));
+                let result = tools::call_tool(&self.library, name, &args).await;
+                serde_json::to_value(result)
+                    .map_err(|err| JsonRpcError::new(INTERNAL_ERROR, err.to_string()))
+            }
+            "resources/list" => Ok(json!({
+                "resources": [{
+                    "uri": LIBRARY_MAP_URI,
+                    "name": "Library map",
+                    "description": "Compact overview of every book, tag and library.",
+                    "mimeType": "application/json"
+                }]
+            })),
+            "resources/read" => {
+                let uri = params
+                    .get("uri")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| JsonRpcError::new(INVALID_PARAMS, "missing resource uri"))?;
+                if uri != LIBRARY_MAP_URI {
+                    return Err(JsonRpcError::new(
+                        INVALID_PARAMS,
+                        format!("unknown resource '{uri}'"),
+                    ));
+                }
+                let map = self
+                    .library
+                    .library_map()
+                    .map_err(|err| JsonRpcError::new(INTERNAL_ERROR, err.to_string()))?;
+                let text = serde_json::to_string(&map)
+                    .map_err(|err| JsonRpcError::new(INTERNAL_ERROR, err.to_string()))?;
+                Ok(json!({
+                    "contents": [{
+                        "uri": LIBRARY_MAP_URI,
+                        "mimeType": "application/json",
+                        "text": text
+                    }]
+                }))
+            }
+            other => Err(JsonRpcError::new(
+                METHOD_NOT_FOUND,
+                format!("method '{other}' not found"),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use omniscope_core::{BookCard, Database};
+
+    fn server() -> (tempfile::TempDir, McpServer, BookCard) {
+        let dir = tempfile::tempdir().unwrap();
+        let library = LibraryHandle::new(
+            Database::open_in_memory().unwrap(),
+            dir.path().join("cards"),
+        );
+        let card = BookCard::new("Structure and Interpretation of Computer Programs");
+        library.save_card(&card).unwrap();
+        (dir, McpServer::new(Arc::new(library)), card)
+    }
+
+    #[tokio::test]
+    async fn test_initialize_reports_capabilities() {
+        let (_dir, server, _) = server();
+        let response = server
+            .handle_message(r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{}}"#)
+            .await
+            .unwrap();
+        assert_eq!(response["result"]["protocolVersion"], PROTOCOL_VERSION);
+        assert!(response["result"]["capabilities"]["tools"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_notification_has_no_response() {
+        let (_dir, server, _) = server();
+        let response = server
+            .handle_message(r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#)
+            .await;
+        assert!(response.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_add_tag_tool_goes_through_action() {
+        let (_dir, server, card) = server();
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 7,
+            "method": "tools/call",
+            "params": { "name": "add_tag", "arguments": { "id": card.id, "tag": "lisp" } }
+        });
+        let response = server
+            .handle_message(&request.to_string())
+            .await
+            .unwrap();
+        assert_eq!(response["result"]["isError"], false);
+        assert_eq!(
+            server.library.load_card(&card.id).unwrap().organization.tags,
+            vec!["lisp"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_total_and_export_keys_cover_all_matches() {
+        let (_dir, server, card) = server();
+        let twin = BookCard {
+            id: uuid::Uuid::now_v7(),
+            ..card.clone()
+        };
+        server.library.save_card(&twin).unwrap();
+
+        let call = |name: &str, arguments: Value| {
+            json!({
+                "jsonrpc": "2.0",
+                "id": 9,
+                "method": "tools/call",
+                "params": { "name": name, "arguments": arguments }
+            })
+            .to_string()
+        };
+        let response = server
+            .handle_message(&call("search", json!({ "query": "", "limit": 1 })))
+            .await
+            .unwrap();
+        let text = response["result"]["content"][0]["text"].as_str().unwrap();
+        let page: Value = serde_json::from_str(text).unwrap();
+        assert_eq!(page["items"].as_array().unwrap().len(), 1);
+        assert_eq!(page["total"], 2);
+
+        let response = server
+            .handle_message(&call("export_bibtex", json!({ "query": "" })))
+            .await
+            .unwrap();
+        let bibtex = response["result"]["content"][0]["text"].as_str().unwrap();
+        let keys = bibtex
+            .lines()
+            .filter(|line| line.starts_with('@'))
+            .map(|line| line.split_once('{').unwrap().1)
+            .collect::<Vec<_>>();
+        assert_eq!(keys.len(), 2);
+        assert_ne!(keys[0], keys[1]);
+    }
+
+    #[tokio::test]
+    async fn test_read_library_map_resource() {
+        let (_dir, server, card) = server();
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "resources/read",
+            "params": { "uri": LIBRARY_MAP_URI }
+        });
+        let response = server
+            .handle_message(&request.to_string())
+            .await
+            .unwrap();
+        let text = response["result"]["contents"][0]["text"].as_str().unwrap();
+        assert!(text.contains(&card.id.to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_method_is_rpc_error() {
+        let (_dir, server, _) = server();
+        let response = server
+            .handle_message(r#"{"jsonrpc":"2.0","id":3,"method":"nope"}"#)
+            .await
+            .unwrap();
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+    }
+}
@@ -0,0 +1,219 @@
+//! MCP tool catalogue and dispatch.
+//!
+//! Read-only tools query the library directly; every mutation is expressed
+//! as an [`OmniscopeAction`] and applied through [`LibraryHandle::apply_action`].
+
+use anyhow::{Context, Result, anyhow};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use omniscope_core::OmniscopeAction;
+use omniscope_science::arxiv::add::{ArxivAddOptions, ArxivAddService, FetchedCard};
+use omniscope_science::formats::bibtex::{BibTeXOptions, generate_bibliography};
+
+use super::protocol::ToolResult;
+use crate::library::LibraryHandle;
+
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+
+/// JSON descriptors returned from `tools/list`.
+pub fn tool_definitions() -> Vec<Value> {
+    vec![
+        json!({
+            "name": "search",
+            "description": "Search the library with the omniscope DSL (e.g. '@knuth #algorithms y:>2000').",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string" },
+                    "limit": { "type": "integer", "minimum": 1 },
+                    "offset": { "type": "integer", "minimum": 0 }
+                },
+                "required": ["query"]
+            }
+        }),
+        json!({
+            "name": "get_book",
+            "description": "Return the full book card for an ID.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "id": { "type": "string" } },
+                "required": ["id"]
+            }
+        }),
+        json!({
+            "name": "add_from_doi",
+            "description": "Fetch metadata for a DOI and add it to the library.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "doi": { "type": "string" } },
+                "required": ["doi"]
+            }
+        }),
+        json!({
+            "name": "add_from_arxiv",
+            "description": "Fetch an arXiv paper and add it to the library.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "arxiv_id": { "type": "string" },
+                    "download_pdf": { "type": "boolean" }
+                },
+                "required": ["arxiv_id"]
+            }
+        }),
+        json!({
+            "name": "add_tag",
+            "description": "Add a tag to a book.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "id": { "type": "string" },
+                    "tag": { "type": "string" }
+                },
+                "required": ["id", "tag"]
+            }
+        }),
+        json!({
+            "name": "remove_tag",
+            "description": "Remove a tag from a book.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "id": { "type": "string" },
+                    "tag": { "type": "string" }
+                },
+                "required": ["id", "tag"]
+            }
+        }),
+        json!({
+            "name": "export_bibtex",
+            "description": "Export books as BibTeX, either by IDs or by a DSL query.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "ids": { "type": "array", "items": { "type": "string" } },
+                    "query": { "type": "string" }
+                }
+            }
+        }),
+    ]
+}
+
+/// Execute a tool call. Failures are reported as `isError` results, not RPC errors.
+pub async fn call_tool(library: &LibraryHandle, name: &str, args: &Value) -> ToolResult {
+    match dispatch(library, name, args).await {
+        Ok(result) => result,
+        Err(err) => ToolResult::error(format!("{err:#}")),
+    }
+}
+
+async fn dispatch(library: &LibraryHandle, name: &str, args: &Value) -> Result<ToolResult> {
+    match name {
+        "search" => {
+            let query = str_arg(args, "query")?;
+            let limit = usize_arg(args, "limit").unwrap_or(DEFAULT_SEARCH_LIMIT);
+            let offset = usize_arg(args, "offset").unwrap_or(0);
+            let matches = library.search_all(query)?;
+            let total = matches.len();
+            let items = matches
+                .into_iter()
+                .skip(offset)
+                .take(limit)
+                .collect::<Vec<_>>();
+            Ok(ToolResult::json(
+                &json!({ "items": items, "total": total, "query": query }),
+            ))
+        }
+        "get_book" => {
+            let id = uuid_arg(args, "id")?;
+            let card = library.load_card(&id)?;
+            Ok(ToolResult::json(&serde_json::to_value(card)?))
+        }
+        "add_from_doi" => {
+            let doi = str_arg(args, "doi")?;
+            let fetched = ArxivAddService::from_env()
+                .fetch_doi(doi, &ArxivAddOptions::default(), library.db())
+                .await?;
+            add_fetched(library, fetched)
+        }
+        "add_from_arxiv" => {
+            let arxiv_id = str_arg(args, "arxiv_id")?;
+            let opts = ArxivAddOptions {
+                download_pdf: args
+                    .get("download_pdf")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false),
+                download_dir: library.root().map(|root| root.root().to_path_buf()),
+                auto_index: false,
+            };
+            let fetched = ArxivAddService::from_env()
+                .fetch_arxiv(arxiv_id, &opts, library.db())
+                .await?;
+            add_fetched(library, fetched)
+        }
+        "add_tag" => {
+            let action = OmniscopeAction::AddTag {
+                book_id: uuid_arg(args, "id")?,
+                tag: str_arg(args, "tag")?.to_string(),
+            };
+            Ok(ToolResult::json(&library.apply_action(&action)?))
+        }
+        "remove_tag" => {
+            let action = OmniscopeAction::RemoveTag {
+                book_id: uuid_arg(args, "id")?,
+                tag: str_arg(args, "tag")?.to_string(),
+            };
+            Ok(ToolResult::json(&library.apply_action(&action)?))
+        }
+        "export_bibtex" => {
+            let mut cards = Vec::new();
+            if let Some(ids) = args.get("ids").and_then(Value::as_array) {
+                for id in ids.iter().filter_map(Value::as_str) {
+                    let id = Uuid::parse_str(id).with_context(|| format!("invalid id '{id}'"))?;
+                    cards.push(library.load_card(&id)?);
+                }
+            }
+            if let Some(query) = args.get("query").and_then(Value::as_str) {
+                for summary in library.search_all(query)? {
+                    if !cards.iter().any(|card| card.id == summary.id) {
+                        cards.push(library.load_card(&summary.id)?);
+                    }
+                }
+            }
+            let refs = cards.iter().collect::<Vec<_>>();
+            let bibtex = generate_bibliography(&refs, &BibTeXOptions::default());
+            Ok(ToolResult::text(bibtex))
+        }
+        other => Err(anyhow!("unknown tool '{other}'")),
+    }
+}
+
+/// Store a newly fetched card through the executor, so adding it can be
+/// undone; a card the library already had is returned as it is.
+fn add_fetched(library: &LibraryHandle, fetched: FetchedCard) -> Result<ToolResult> {
+    match fetched {
+        FetchedCard::Existing(card) => Ok(ToolResult::json(&serde_json::to_value(card)?)),
+        FetchedCard::New(card) => {
+            let created = library.apply_action(&OmniscopeAction::CreateCard {
+                card: serde_json::to_value(card)?,
+            })?;
+            Ok(ToolResult::json(&created))
+        }
+    }
+}
+
+fn str_arg<'a>(args: &'a Value, key: &str) -> Result<&'a str> {
+    args.get(key)
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("missing string argument '{key}'"))
+}
+
+fn usize_arg(args: &Value, key: &str) -> Option<usize> {
+    args.get(key).and_then(Value::as_u64).map(|n| n as usize)
+}
+
+fn uuid_arg(args: &Value, key: &str) -> Result<Uuid> {
+    let raw = str_arg(args, key)?;
+    Uuid::parse_str(raw).with_context(|| format!("invalid id '{raw}'"))
+}
@@ -0,0 +1,113 @@
+//! stdio and streamable-HTTP transports for [`McpServer`].
+
+use std::net::SocketAddr;
+
+use anyhow::Result;
+use axum::Router;
+use axum::extract::State;
+use axum::http::{StatusCode, header};
+use axum::middleware;
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+use super::McpServer;
+use crate::api::BearerToken;
+use crate::api::auth::require_bearer;
+
+/// Serve newline-delimited JSON-RPC on stdin/stdout until stdin closes.
+///
+/// Nothing but protocol messages may be written to stdout while this runs.
+pub async fn serve_stdio(server: McpServer) -> Result<()> {
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdout = tokio::io::stdout();
+
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = server.handle_message(&line).await {
+            let mut payload = serde_json::to_vec(&response)?;
+            payload.push(b'\n');
+            stdout.write_all(&payload).await?;
+            stdout.flush().await?;
+        }
+    }
+    Ok(())
+}
+
+/// Serve MCP over streamable HTTP: clients POST JSON-RPC messages to `/mcp`
+/// with the same bearer token the REST API takes.
+pub async fn serve_http(server: McpServer, token: BearerToken, addr: SocketAddr) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, router(server, token)).await?;
+    Ok(())
+}
+
+fn router(server: McpServer, token: BearerToken) -> Router {
+    Router::new()
+        .route("/mcp", post(handle_post).get(handle_get))
+        .route_layer(middleware::from_fn_with_state(token, require_bearer))
+        .with_state(server)
+}
+
+async fn handle_post(State(server): State<McpServer>, body: String) -> Response {
+    match server.handle_message(&body).await {
+        Some(response) => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "application/json")],
+            response.to_string(),
+        )
+            .into_response(),
+        None => StatusCode::ACCEPTED.into_response(),
+    }
+}
+
+/// Server-initiated streams are not offered; clients fall back to POST only.
+async fn handle_get() -> StatusCode {
+    StatusCode::METHOD_NOT_ALLOWED
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LibraryHandle;
+    use axum::body::Body;
+    use axum::http::Request;
+    use omniscope_core::Database;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn app() -> (tempfile::TempDir, Router) {
+        let dir = tempfile::tempdir().unwrap();
+        let library = LibraryHandle::new(
+            Database::open_in_memory().unwrap(),
+            dir.path().join("cards"),
+        );
+        let server = McpServer::new(Arc::new(library));
+        (dir, router(server, BearerToken::new("secret")))
+    }
+
+    fn ping(token: Option<&str>) -> Request<Body> {
+        let mut builder = Request::post("/mcp").header(header::CONTENT_TYPE, "application/json");
+        if let Some(token) = token {
+            builder = builder.header(header::AUTHORIZATION, format!("Bearer {token}"));
+        }
+        builder
+            .body(Body::from(r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_http_requires_the_bearer_token() {
+        let (_dir, app) = app();
+        let response = app.clone().oneshot(ping(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app.clone().oneshot(ping(Some("wrong"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app.oneshot(ping(Some("secret"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
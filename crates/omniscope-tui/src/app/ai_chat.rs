@@ -0,0 +1,291 @@
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+use futures::StreamExt;
+use omniscope_ai::actions::ActionExecutor;
+use omniscope_ai::chat::{ChatHistory, ChatTurn, build_messages, describe_action, extract_actions};
+use omniscope_ai::memory::LibraryMapCache;
+use omniscope_ai::provider::{self, ChatChunk, ChatMessage, ChatRequest, Role};
+use omniscope_core::config::AiConfig;
+use omniscope_core::storage::json_cards;
+use omniscope_core::undo::UndoAction;
+use omniscope_core::{BookCard, LibraryMap, OmniscopeAction};
+
+use super::{App, Mode};
+
+/// Streamed from the worker thread while the model answers.
+pub enum AiChatEvent {
+    Delta(String),
+    Done,
+    Failed(String),
+}
+
+/// Conversation shown in the AI panel.
+#[derive(Default)]
+pub struct AiChatState {
+    /// Stored conversation for the book the panel was opened on.
+    pub history: Option<ChatHistory>,
+    pub book_title: String,
+    /// Reply text received so far while the model is answering.
+    pub pending_reply: Option<String>,
+    /// Actions proposed in the last reply, accepted with `1`-`9`.
+    pub suggestions: Vec<OmniscopeAction>,
+    /// Lines scrolled up from the bottom of the history.
+    pub scroll: usize,
+    pub error: Option<String>,
+    pub receiver: Option<Receiver<AiChatEvent>>,
+    /// Library overview sent with each question, created on first use.
+    pub map_cache: Option<LibraryMapCache>,
+}
+
+impl App {
+    /// Open the chat panel on the selected book and start typing.
+    pub fn open_ai_chat(&mut self) {
+        let Some(book) = self.selected_book() else {
+            self.status_message = "AI: no selected book".to_string();
+            return;
+        };
+        let (book_id, title) = (book.id, book.title.clone());
+
+        if self.ai_chat.history.as_ref().map(|h| h.book_id) != Some(book_id) {
+            let history = ChatHistory::load(&self.chats_dir(), book_id).unwrap_or_else(|err| {
+                self.status_message = format!("AI: failed to load chat history: {err}");
+                ChatHistory {
+                    book_id,
+                    turns: Vec::new(),
+                }
+            });
+            self.ai_chat.suggestions = history
+                .turns
+                .iter()
+                .rev()
+                .find(|turn| turn.role == Role::Assistant)
+                .map(|turn| extract_actions(&turn.content))
+                .unwrap_or_default();
+            self.ai_chat.history = Some(history);
+            self.ai_chat.book_title = title;
+            self.ai_chat.scroll = 0;
+            self.ai_chat.error = None;
+        }
+
+        self.ai_panel_active = true;
+        self.mode = Mode::Insert;
+    }
+
+    pub fn close_ai_chat(&mut self) {
+        self.ai_panel_active = false;
+        self.mode = Mode::Normal;
+    }
+
+    /// Where per-book chat histories are stored (`.libr/ai/chats/`).
+    pub fn chats_dir(&self) -> PathBuf {
+        match &self.library_root {
+            Some(root) => root.chats_dir(),
+            None => self
+                .cards_dir()
+                .parent()
+                .map(|libr| libr.join("ai").join("chats"))
+                .unwrap_or_else(|| PathBuf::from("ai").join("chats")),
+        }
+    }
+
+    /// Send `ai_input` to the configured provider and stream the answer.
+    pub fn submit_ai_message(&mut self) {
+        if self.ai_chat.receiver.is_some() {
+            self.status_message = "AI: still answering".to_string();
+            return;
+        }
+        let question = self.ai_input.trim().to_string();
+        if question.is_empty() {
+            return;
+        }
+        let Some(book_id) = self.ai_chat.history.as_ref().map(|h| h.book_id) else {
+            return;
+        };
+
+        let card = self.load_chat_card(&book_id);
+        let map = self.chat_library_map();
+        let chats_dir = self.chats_dir();
+        let Some(history) = self.ai_chat.history.as_mut() else {
+            return;
+        };
+        let messages = build_messages(card.as_ref(), map.as_ref(), &history.turns, &question);
+        history.turns.push(ChatTurn::new(Role::User, question));
+        if let Err(err) = history.save(&chats_dir) {
+            self.status_message = format!("AI: failed to save chat history: {err}");
+        }
+
+        self.ai_input.clear();
+        self.ai_chat.scroll = 0;
+        self.ai_chat.error = None;
+        self.ai_chat.suggestions.clear();
+        self.ai_chat.pending_reply = Some(String::new());
+        self.ai_chat.receiver = Some(spawn_chat_worker(self.config.ai.clone(), messages));
+    }
+
+    /// The cached library overview, trimmed to the prompt budget.
+    fn chat_library_map(&mut self) -> Option<LibraryMap> {
+        if self.ai_chat.map_cache.is_none() {
+            let cache = match &self.library_root {
+                Some(root) => LibraryMapCache::for_library(root, &self.config.ai),
+                None => {
+                    let cards_dir = self.cards_dir();
+                    let libr = cards_dir.parent().unwrap_or(&cards_dir);
+                    LibraryMapCache::new(
+                        libr.join("cache").join("library_map.json"),
+                        LibraryMapCache::ttl_from_config(&self.config.ai),
+                    )
+                }
+            };
+            self.ai_chat.map_cache = Some(cache);
+        }
+        let db = self.db.as_ref()?;
+        let cache = self.ai_chat.map_cache.as_mut()?;
+        match cache.map_within(db, self.config.ai.library_map_tokens) {
+            Ok(map) => Some(map),
+            Err(err) => {
+                self.status_message = format!("AI: failed to build library map: {err}");
+                None
+            }
+        }
+    }
+
+    fn load_chat_card(&self, id: &uuid::Uuid) -> Option<BookCard> {
+        json_cards::load_card_by_id(&self.cards_dir(), id)
+            .ok()
+            .or_else(|| self.db.as_ref()?.get_book_card(&id.to_string()).ok())
+    }
+
+    /// Drain streamed reply chunks; called on every tick.
+    pub fn pump_ai_chat(&mut self) {
+        let mut finished = None;
+        if let Some(rx) = &self.ai_chat.receiver {
+            loop {
+                match rx.try_recv() {
+                    Ok(AiChatEvent::Delta(text)) => {
+                        self.ai_chat
+                            .pending_reply
+                            .get_or_insert_with(String::new)
+                            .push_str(&text);
+                    }
+                    Ok(AiChatEvent::Done) => {
+                        finished = Some(None);
+                        break;
+                    }
+                    Ok(AiChatEvent::Failed(err)) => {
+                        finished = Some(Some(err));
+                        break;
+                    }
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        finished = Some(Some("worker thread disconnected".to_string()));
+                        break;
+                    }
+                }
+            }
+        }
+
+        let Some(error) = finished else {
+            return;
+        };
+        self.ai_chat.receiver = None;
+        let reply = self.ai_chat.pending_reply.take().unwrap_or_default();
+
+        if !reply.is_empty() {
+            self.ai_chat.suggestions = extract_actions(&reply);
+            let chats_dir = self.chats_dir();
+            if let Some(history) = self.ai_chat.history.as_mut() {
+                history.turns.push(ChatTurn::new(Role::Assistant, reply));
+                if let Err(err) = history.save(&chats_dir) {
+                    self.status_message = format!("AI: failed to save chat history: {err}");
+                }
+            }
+        }
+        if let Some(err) = error {
+            self.status_message = format!("AI: {err}");
+            self.ai_chat.error = Some(err);
+        } else if !self.ai_chat.suggestions.is_empty() {
+            self.status_message = format!(
+                "AI: {} suggested action(s) — press 1-{} to apply",
+                self.ai_chat.suggestions.len(),
+                self.ai_chat.suggestions.len().min(9)
+            );
+        }
+    }
+
+    /// Apply the `index`-th suggested action through the action executor.
+    pub fn accept_ai_suggestion(&mut self, index: usize) {
+        if index >= self.ai_chat.suggestions.len() {
+            return;
+        }
+        let Some(db) = self.db.as_ref() else {
+            self.status_message = "AI: no database".to_string();
+            return;
+        };
+        let action = self.ai_chat.suggestions[index].clone();
+        let label = describe_action(&action);
+
+        // Accepting a suggestion is the user's confirmation.
+        let result = ActionExecutor::new(db, self.cards_dir()).execute(&action, true);
+        match result {
+            Ok(outcome) => {
+                let before: Vec<_> = outcome
+                    .snapshot
+                    .cards
+                    .into_iter()
+                    .map(|snap| (snap.id, snap.before))
+                    .collect();
+                if !before.is_empty() {
+                    self.push_undo(format!("AI: {label}"), UndoAction::RestoreCards(before));
+                }
+
+                self.ai_chat.suggestions.remove(index);
+                self.refresh_books();
+                self.status_message = format!("AI: applied {label}");
+            }
+            Err(err) => {
+                self.status_message = format!("AI: {label} failed: {err}");
+            }
+        }
+    }
+
+    pub fn scroll_ai_chat(&mut self, delta: isize) {
+        self.ai_chat.scroll = self.ai_chat.scroll.saturating_add_signed(delta);
+    }
+}
+
+fn spawn_chat_worker(config: AiConfig, messages: Vec<ChatMessage>) -> Receiver<AiChatEvent> {
+    let (tx, rx) = mpsc::channel::<AiChatEvent>();
+    thread::spawn(move || {
+        let runtime = match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(runtime) => runtime,
+            Err(err) => {
+                let _ = tx.send(AiChatEvent::Failed(err.to_string()));
+                return;
+            }
+        };
+
+        let result = runtime.block_on(async {
+            let provider = provider::from_config(&config)?;
+            let mut stream = provider.chat_stream(ChatRequest::new(messages)).await?;
+            while let Some(chunk) = stream.next().await {
+                if let ChatChunk::Delta(text) = chunk? {
+                    if tx.send(AiChatEvent::Delta(text)).is_err() {
+                        break;
+                    }
+                }
+            }
+            anyhow::Ok(())
+        });
+
+        let _ = tx.send(match result {
+            Ok(()) => AiChatEvent::Done,
+            Err(err) => AiChatEvent::Failed(format!("{err:#}")),
+        });
+    });
+    rx
+}
@@ -0,0 +1,205 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use omniscope_ai::index::{cards_needing_index, index_cards};
+use omniscope_ai::provider;
+use omniscope_core::Database;
+use omniscope_core::config::AiConfig;
+use uuid::Uuid;
+
+use super::App;
+
+/// How often the library is checked for books that still need indexing.
+const AUTO_INDEX_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Sent by the indexing worker.
+pub enum AiIndexEvent {
+    Progress {
+        done: usize,
+        total: usize,
+        title: String,
+    },
+    Finished {
+        indexed: usize,
+        /// Cards that were skipped or failed; not retried this session.
+        given_up: Vec<Uuid>,
+        error: Option<String>,
+    },
+}
+
+/// Background summary/index job, run when `ai.auto_summary` or
+/// `ai.auto_index` is on.
+#[derive(Default)]
+pub struct AiIndexState {
+    pub receiver: Option<Receiver<AiIndexEvent>>,
+    pub last_run: Option<Instant>,
+    pub given_up: HashSet<Uuid>,
+    /// Set when a run stopped on a provider, config or budget error; the job
+    /// stays off until restart instead of failing again every interval.
+    pub halted: bool,
+}
+
+impl App {
+    /// Start the indexing worker if it is enabled and due.
+    pub fn maybe_start_ai_index(&mut self) {
+        if !(self.config.ai.auto_summary || self.config.ai.auto_index_enabled()) {
+            return;
+        }
+        if self.ai_index.halted || self.ai_index.receiver.is_some() || self.db.is_none() {
+            return;
+        }
+        if self
+            .ai_index
+            .last_run
+            .is_some_and(|at| at.elapsed() < AUTO_INDEX_INTERVAL)
+        {
+            return;
+        }
+        self.ai_index.last_run = Some(Instant::now());
+
+        let db_path = match &self.library_root {
+            Some(root) => root.database_path(),
+            None => self.config.database_path(),
+        };
+        self.ai_index.receiver = Some(spawn_index_worker(
+            self.config.ai.clone(),
+            db_path,
+            self.cards_dir(),
+            self.ai_index.given_up.clone(),
+        ));
+    }
+
+    /// Report worker progress and reload indexed cards; called on every tick.
+    pub fn pump_ai_index(&mut self) {
+        self.maybe_start_ai_index();
+
+        let mut finished = None;
+        if let Some(rx) = &self.ai_index.receiver {
+            loop {
+                match rx.try_recv() {
+                    Ok(AiIndexEvent::Progress { done, total, title }) => {
+                        self.status_message = format!("AI index: {}/{total} {title}", done + 1);
+                    }
+                    Ok(AiIndexEvent::Finished {
+                        indexed,
+                        given_up,
+                        error,
+                    }) => {
+                        finished = Some((indexed, given_up, error));
+                        break;
+                    }
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        finished = Some((0, Vec::new(), None));
+                        break;
+                    }
+                }
+            }
+        }
+
+        let Some((indexed, given_up, error)) = finished else {
+            return;
+        };
+        self.ai_index.receiver = None;
+        self.ai_index.given_up.extend(given_up);
+        if indexed > 0 {
+            self.refresh_books();
+        }
+        if let Some(err) = error {
+            self.ai_index.halted = true;
+            self.status_message = format!("AI index: {err}");
+        } else if indexed > 0 {
+            self.status_message = format!("AI index: {indexed} book(s) summarized");
+        }
+    }
+}
+
+fn spawn_index_worker(
+    config: AiConfig,
+    db_path: PathBuf,
+    cards_dir: PathBuf,
+    given_up: HashSet<Uuid>,
+) -> Receiver<AiIndexEvent> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let ids: Vec<Uuid> = match cards_needing_index(&cards_dir) {
+            Ok(ids) => ids
+                .into_iter()
+                .filter(|id| !given_up.contains(id))
+                .collect(),
+            Err(err) => {
+                let _ = tx.send(AiIndexEvent::Finished {
+                    indexed: 0,
+                    given_up: Vec::new(),
+                    error: Some(format!("{err:#}")),
+                });
+                return;
+            }
+        };
+        if ids.is_empty() {
+            let _ = tx.send(AiIndexEvent::Finished {
+                indexed: 0,
+                given_up: Vec::new(),
+                error: None,
+            });
+            return;
+        }
+        let provider = match provider::from_config(&config) {
+            Ok(provider) => provider,
+            Err(err) => {
+                let _ = tx.send(AiIndexEvent::Finished {
+                    indexed: 0,
+                    given_up: Vec::new(),
+                    error: Some(format!("{err:#}")),
+                });
+                return;
+            }
+        };
+
+        let total = ids.len();
+        let progress = tx.clone();
+        let result = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(anyhow::Error::from)
+            .and_then(|runtime| {
+                let db = Database::open(&db_path)?;
+                runtime.block_on(index_cards(
+                    &db,
+                    &cards_dir,
+                    provider.as_ref(),
+                    &ids,
+                    |done, title| {
+                        let _ = progress.send(AiIndexEvent::Progress {
+                            done,
+                            total,
+                            title: title.to_string(),
+                        });
+                    },
+                ))
+            });
+
+        let event = match result {
+            Ok(report) => AiIndexEvent::Finished {
+                indexed: report.indexed.len(),
+                given_up: report
+                    .skipped
+                    .iter()
+                    .chain(&report.failed)
+                    .map(|(id, _)| *id)
+                    .collect(),
+                error: report.stopped,
+            },
+            Err(err) => AiIndexEvent::Finished {
+                indexed: 0,
+                given_up: Vec::new(),
+                error: Some(format!("{err:#}")),
+            },
+        };
+        let _ = tx.send(event);
+    });
+    rx
+}
@@ -0,0 +1,432 @@
+use std::collections::HashSet;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread;
+
+use omniscope_core::models::ArxivFeed;
+use omniscope_core::storage::json_cards;
+use omniscope_core::undo::UndoAction;
+use omniscope_core::{BookCard, Database};
+use omniscope_science::ScienceConfig;
+use omniscope_science::arxiv::client::ArxivClient;
+use omniscope_science::arxiv::feeds::fetch_feed;
+use omniscope_science::arxiv::types::ArxivMetadata;
+use omniscope_science::arxiv::{
+    ArxivAddOptions, ArxivAddService, ArxivFeedState, FeedHistory, feed_from_terms,
+};
+use omniscope_science::identifiers::arxiv::ArxivId;
+
+use super::App;
+use super::arxiv_updates::run_async;
+use crate::panels::arxiv_feed::{ArxivFeedEntry, ArxivFeedPanel};
+use crate::popup::Popup;
+
+/// Sent by the feed workers. Workers only talk to arXiv; the feed state
+/// file is written on the UI thread.
+pub enum ArxivFeedEvent {
+    /// Submissions for a saved feed, or for the ad-hoc search when `feed` is
+    /// `None`.
+    Fetched {
+        feed: Option<String>,
+        result: Result<Vec<ArxivMetadata>, String>,
+    },
+    Added(Box<Result<AddedPaper, String>>),
+}
+
+pub struct AddedPaper {
+    pub arxiv_id: String,
+    pub card: BookCard,
+    /// The paper was already in the library; nothing was written.
+    pub existed: bool,
+}
+
+/// Saved-feed refreshes, `:arxiv` searches and papers being added.
+#[derive(Default)]
+pub struct ArxivFeedsState {
+    sender: Option<Sender<ArxivFeedEvent>>,
+    receiver: Option<Receiver<ArxivFeedEvent>>,
+    /// Stale feeds are fetched once per session, on the first tick.
+    pub startup_refreshed: bool,
+    /// Saved feeds and their new-submission counts, for the sidebar.
+    pub counts: Vec<(String, usize)>,
+    /// The last `:arxiv` search, re-run by `r` in its panel.
+    pub last_search: Option<ArxivFeed>,
+}
+
+impl ArxivFeedsState {
+    fn sender(&mut self) -> Sender<ArxivFeedEvent> {
+        if let Some(sender) = &self.sender {
+            return sender.clone();
+        }
+        let (tx, rx) = mpsc::channel();
+        self.sender = Some(tx.clone());
+        self.receiver = Some(rx);
+        tx
+    }
+}
+
+impl App {
+    /// Load the sidebar counts and fetch stale feeds, once per session.
+    pub fn maybe_refresh_arxiv_feeds(&mut self) {
+        if self.arxiv_feeds.startup_refreshed {
+            return;
+        }
+        self.arxiv_feeds.startup_refreshed = true;
+        self.reload_arxiv_feed_counts();
+        self.refresh_sidebar();
+
+        let Some(root) = &self.library_root else {
+            return;
+        };
+        let Ok(manifest) = root.load_manifest() else {
+            return;
+        };
+        let state = ArxivFeedState::load(&ArxivFeedState::path(root)).unwrap_or_default();
+        let interval = ScienceConfig::load()
+            .unwrap_or_default()
+            .arxiv
+            .feed_refresh_interval();
+        let now = chrono::Utc::now();
+        let stale: Vec<ArxivFeed> = manifest
+            .arxiv_feeds
+            .into_iter()
+            .filter(|feed| {
+                state
+                    .history(&feed.name)
+                    .is_none_or(|history| history.is_stale(now, interval))
+            })
+            .collect();
+        if !stale.is_empty() {
+            self.spawn_feed_fetch(stale, true);
+        }
+    }
+
+    /// Re-read the saved feeds and their new counts for the sidebar.
+    pub fn reload_arxiv_feed_counts(&mut self) {
+        let Some(root) = &self.library_root else {
+            self.arxiv_feeds.counts.clear();
+            return;
+        };
+        let feeds = root
+            .load_manifest()
+            .map(|manifest| manifest.arxiv_feeds)
+            .unwrap_or_default();
+        let state = ArxivFeedState::load(&ArxivFeedState::path(root)).unwrap_or_default();
+        self.arxiv_feeds.counts = feeds
+            .into_iter()
+            .map(|feed| {
+                let count = state
+                    .history(&feed.name)
+                    .map(FeedHistory::new_count)
+                    .unwrap_or(0);
+                (feed.name, count)
+            })
+            .collect();
+    }
+
+    /// Show a saved feed; cached submissions are shown right away unless
+    /// they are older than `[arxiv] feed_refresh_minutes`.
+    pub fn open_arxiv_feed(&mut self, name: &str) {
+        let Some(root) = &self.library_root else {
+            self.status_message = "arXiv: no library open".to_string();
+            return;
+        };
+        let feed = root.load_manifest().ok().and_then(|manifest| {
+            manifest
+                .arxiv_feeds
+                .into_iter()
+                .find(|feed| feed.name == name)
+        });
+        let Some(feed) = feed else {
+            self.status_message = format!("arXiv: no feed named '{name}'");
+            return;
+        };
+        let state = ArxivFeedState::load(&ArxivFeedState::path(root)).unwrap_or_default();
+        let interval = ScienceConfig::load()
+            .unwrap_or_default()
+            .arxiv
+            .feed_refresh_interval();
+
+        let mut panel = ArxivFeedPanel::loading(name, Some(name.to_string()));
+        match state.history(name) {
+            Some(history) if !history.is_stale(chrono::Utc::now(), interval) => {
+                let history = history.clone();
+                panel.set_entries(self.feed_entries(&history));
+                self.mark_arxiv_feed_visited(name);
+            }
+            _ => {
+                self.spawn_feed_fetch(vec![feed], true);
+                self.status_message = format!("arXiv: fetching '{name}'…");
+            }
+        }
+        self.popup = Some(Popup::ArxivFeed(panel));
+    }
+
+    /// Search arXiv for `terms` (`:arxiv cat:cs.LG au:Bengio diffusion`).
+    pub fn search_arxiv(&mut self, terms: &str) {
+        let search = feed_from_terms("search", terms);
+        if search.is_empty() {
+            self.status_message = "Usage: :arxiv <cat:… au:… keywords>".to_string();
+            return;
+        }
+        self.arxiv_feeds.last_search = Some(search.clone());
+        self.popup = Some(Popup::ArxivFeed(ArxivFeedPanel::loading(
+            format!("arXiv: {}", terms.trim()),
+            None,
+        )));
+        self.spawn_feed_fetch(vec![search], false);
+        self.status_message = "arXiv: searching…".to_string();
+    }
+
+    /// Save `terms` as a feed of the library (`:arxiv-save <name> <terms>`).
+    pub fn save_arxiv_feed(&mut self, name: &str, terms: &str) {
+        let feed = feed_from_terms(name, terms);
+        if feed.is_empty() {
+            self.status_message = "Usage: :arxiv-save <name> <cat:… au:… keywords>".to_string();
+            return;
+        }
+        let Some(root) = &self.library_root else {
+            self.status_message = "arXiv: no library open".to_string();
+            return;
+        };
+        let result = root.load_manifest().and_then(|mut manifest| {
+            manifest.arxiv_feeds.retain(|saved| saved.name != name);
+            manifest.arxiv_feeds.push(feed);
+            root.save_manifest(&manifest)
+        });
+        if let Err(err) = result {
+            self.status_message = format!("arXiv: {err}");
+            return;
+        }
+        self.reload_arxiv_feed_counts();
+        self.refresh_sidebar();
+        self.open_arxiv_feed(name);
+    }
+
+    /// Fetch the panel's feed or search again.
+    pub fn refresh_arxiv_feed(&mut self, panel: &ArxivFeedPanel) {
+        let feed = match &panel.feed {
+            Some(name) => self.library_root.as_ref().and_then(|root| {
+                root.load_manifest().ok().and_then(|manifest| {
+                    manifest
+                        .arxiv_feeds
+                        .into_iter()
+                        .find(|feed| feed.name == *name)
+                })
+            }),
+            None => self.arxiv_feeds.last_search.clone(),
+        };
+        match feed {
+            Some(feed) => {
+                self.spawn_feed_fetch(vec![feed], panel.feed.is_some());
+                self.status_message = "arXiv: fetching…".to_string();
+            }
+            None => self.status_message = "arXiv: nothing to refresh".to_string(),
+        }
+    }
+
+    /// Add a submission with `ArxivAddService::add_from_arxiv`; the new card
+    /// goes on the undo stack once it lands.
+    pub fn add_arxiv_paper(&mut self, metadata: &ArxivMetadata) {
+        let Some(root) = &self.library_root else {
+            self.status_message = "arXiv: no library open".to_string();
+            return;
+        };
+        let db_path = root.database_path();
+        let cards_dir = self.cards_dir();
+        let arxiv_id = metadata.arxiv_id.id.clone();
+        let tx = self.arxiv_feeds.sender();
+        thread::spawn(move || {
+            let result = run_async(|runtime| {
+                let db = Database::open(&db_path)?;
+                let card = runtime.block_on(ArxivAddService::default().add_from_arxiv(
+                    &arxiv_id,
+                    ArxivAddOptions::default(),
+                    &db,
+                ))?;
+                let existed = json_cards::load_card_by_id(&cards_dir, &card.id).is_ok();
+                if !existed {
+                    json_cards::save_card(&cards_dir, &card)?;
+                }
+                Ok(AddedPaper {
+                    arxiv_id,
+                    card,
+                    existed,
+                })
+            });
+            let _ = tx.send(ArxivFeedEvent::Added(Box::new(result)));
+        });
+        self.status_message = format!("arXiv: adding {}…", metadata.arxiv_id.id);
+    }
+
+    /// Pick up fetched feeds and added papers; called on every tick.
+    pub fn pump_arxiv_feeds(&mut self) {
+        self.maybe_refresh_arxiv_feeds();
+
+        loop {
+            let Some(rx) = &self.arxiv_feeds.receiver else {
+                return;
+            };
+            let event = match rx.try_recv() {
+                Ok(event) => event,
+                Err(TryRecvError::Empty | TryRecvError::Disconnected) => return,
+            };
+            match event {
+                ArxivFeedEvent::Fetched { feed, result } => self.feed_fetched(feed, result),
+                ArxivFeedEvent::Added(result) => self.paper_added(*result),
+            }
+        }
+    }
+
+    /// Fetch `feeds` in the background; `saved` feeds report under their
+    /// name, anything else as the ad-hoc search.
+    fn spawn_feed_fetch(&mut self, feeds: Vec<ArxivFeed>, saved: bool) {
+        let max_results = ScienceConfig::load()
+            .unwrap_or_default()
+            .arxiv
+            .feed_max_results;
+        let tx = self.arxiv_feeds.sender();
+        thread::spawn(move || {
+            let client = ArxivClient::new();
+            for feed in feeds {
+                let result = run_async(|runtime| {
+                    Ok(runtime.block_on(fetch_feed(&client, &feed, max_results))?)
+                });
+                let event = ArxivFeedEvent::Fetched {
+                    feed: saved.then_some(feed.name),
+                    result,
+                };
+                if tx.send(event).is_err() {
+                    return;
+                }
+            }
+        });
+    }
+
+    fn feed_fetched(&mut self, feed: Option<String>, result: Result<Vec<ArxivMetadata>, String>) {
+        let entries = match result {
+            Ok(entries) => entries,
+            Err(err) => {
+                if let Some(Popup::ArxivFeed(panel)) = &mut self.popup
+                    && panel.feed == feed
+                {
+                    panel.loading = false;
+                }
+                self.status_message = match feed {
+                    Some(name) => format!("arXiv feed '{name}': {err}"),
+                    None => format!("arXiv: {err}"),
+                };
+                return;
+            }
+        };
+
+        let Some(name) = feed else {
+            let history = FeedHistory {
+                entries,
+                ..FeedHistory::default()
+            };
+            let mut panel_entries = self.feed_entries(&history);
+            for entry in &mut panel_entries {
+                entry.is_new = false;
+            }
+            if let Some(Popup::ArxivFeed(panel)) = &mut self.popup
+                && panel.feed.is_none()
+            {
+                self.status_message = format!("arXiv: {} result(s)", panel_entries.len());
+                panel.set_entries(panel_entries);
+            }
+            return;
+        };
+
+        let Some(root) = &self.library_root else {
+            return;
+        };
+        let path = ArxivFeedState::path(root);
+        let mut state = ArxivFeedState::load(&path).unwrap_or_default();
+        state.store(&name, entries, chrono::Utc::now());
+        if let Err(err) = state.save(&path) {
+            self.status_message = format!("arXiv: {err}");
+        }
+
+        let showing = matches!(
+            &self.popup,
+            Some(Popup::ArxivFeed(panel)) if panel.feed.as_deref() == Some(name.as_str())
+        );
+        if showing && let Some(history) = state.history(&name).cloned() {
+            let entries = self.feed_entries(&history);
+            let new_count = history.new_count();
+            if let Some(Popup::ArxivFeed(panel)) = &mut self.popup {
+                panel.set_entries(entries);
+            }
+            self.mark_arxiv_feed_visited(&name);
+            self.status_message = format!("arXiv feed '{name}': {new_count} new");
+        } else {
+            self.reload_arxiv_feed_counts();
+            self.refresh_sidebar();
+        }
+    }
+
+    fn paper_added(&mut self, result: Result<AddedPaper, String>) {
+        let added = match result {
+            Ok(added) => added,
+            Err(err) => {
+                self.status_message = format!("arXiv: {err}");
+                return;
+            }
+        };
+        if let Some(Popup::ArxivFeed(panel)) = &mut self.popup {
+            panel.mark_in_library(&added.arxiv_id);
+        }
+        let title = added.card.metadata.title.clone();
+        if added.existed {
+            self.status_message = format!("arXiv: \"{title}\" is already in the library");
+            return;
+        }
+        self.push_undo(
+            format!("Add \"{title}\" from arXiv"),
+            UndoAction::DeleteCards(vec![added.card]),
+        );
+        self.refresh_books();
+        self.status_message = format!("arXiv: added \"{title}\"");
+    }
+
+    /// Panel rows for `history`, flagging what is new and what the library
+    /// already has.
+    fn feed_entries(&self, history: &FeedHistory) -> Vec<ArxivFeedEntry> {
+        let owned = self.library_arxiv_ids();
+        history
+            .entries
+            .iter()
+            .map(|metadata| ArxivFeedEntry {
+                is_new: history.is_new(metadata),
+                in_library: owned.contains(&metadata.arxiv_id.id),
+                metadata: metadata.clone(),
+            })
+            .collect()
+    }
+
+    fn library_arxiv_ids(&self) -> HashSet<String> {
+        json_cards::list_cards(&self.cards_dir())
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|card| card.identifiers?.arxiv_id)
+            .filter_map(|raw| ArxivId::parse(&raw).ok())
+            .map(|id| id.id)
+            .collect()
+    }
+
+    fn mark_arxiv_feed_visited(&mut self, name: &str) {
+        let Some(root) = &self.library_root else {
+            return;
+        };
+        let path = ArxivFeedState::path(root);
+        let result = ArxivFeedState::load(&path).and_then(|mut state| {
+            state.mark_visited(name, chrono::Utc::now());
+            state.save(&path)
+        });
+        if let Err(err) = result {
+            self.status_message = format!("arXiv: {err}");
+        }
+        self.reload_arxiv_feed_counts();
+        self.refresh_sidebar();
+    }
+}
@@ -0,0 +1,246 @@
+use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+use omniscope_core::Database;
+use omniscope_core::storage::json_cards;
+use omniscope_core::undo::UndoAction;
+use omniscope_science::ScienceConfig;
+use omniscope_science::arxiv::{
+    AppliedArxivUpdate, ArxivApplyOptions, ArxivUpdateResult, ArxivUpdateState, ArxivUpdater,
+};
+
+use super::App;
+use crate::panels::arxiv_updates::{ArxivUpdateItem, ArxivUpdatesPanel};
+use crate::popup::Popup;
+
+/// Sent by the arXiv workers.
+pub enum ArxivUpdateEvent {
+    Checked(Result<usize, String>),
+    Applied(Box<Result<AppliedArxivUpdate, String>>),
+}
+
+/// Background arXiv version checks and accepted updates.
+#[derive(Default)]
+pub struct ArxivUpdatesState {
+    pub receiver: Option<Receiver<ArxivUpdateEvent>>,
+    /// The startup check runs once per session; later ones are `:arxiv-check`.
+    pub startup_checked: bool,
+}
+
+impl App {
+    /// Check for new arXiv versions once per session, if it is due.
+    pub fn maybe_start_arxiv_check(&mut self) {
+        if self.arxiv_updates.startup_checked {
+            return;
+        }
+        self.arxiv_updates.startup_checked = true;
+
+        let config = ScienceConfig::load().unwrap_or_default().arxiv;
+        let Some(root) = &self.library_root else {
+            return;
+        };
+        if !config.check_updates_on_startup {
+            return;
+        }
+        let state = ArxivUpdateState::load(&ArxivUpdateState::path(root)).unwrap_or_default();
+        if !state.run_due(chrono::Utc::now(), config.update_check_interval()) {
+            if !state.pending.is_empty() {
+                self.status_message = format!(
+                    "{} arXiv update(s) waiting — :arxiv-updates",
+                    state.pending.len()
+                );
+            }
+            return;
+        }
+        self.start_arxiv_check();
+    }
+
+    /// Check the papers that are due in the background (`:arxiv-check`).
+    pub fn start_arxiv_check(&mut self) {
+        if self.arxiv_updates.receiver.is_some() {
+            self.status_message = "arXiv: already busy…".to_string();
+            return;
+        }
+        let Some(root) = &self.library_root else {
+            self.status_message = "arXiv: no library open".to_string();
+            return;
+        };
+        let state_path = ArxivUpdateState::path(root);
+        let db_path = root.database_path();
+        let interval = ScienceConfig::load()
+            .unwrap_or_default()
+            .arxiv
+            .update_check_interval();
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let result = run_async(|runtime| {
+                let mut state = ArxivUpdateState::load(&state_path)?;
+                let updater = ArxivUpdater::from_db(Arc::new(Database::open(&db_path)?));
+                let checked = runtime.block_on(updater.check_due_updates(&mut state, interval));
+                state.save(&state_path)?;
+                checked?;
+                Ok(state.pending.len())
+            });
+            let _ = tx.send(ArxivUpdateEvent::Checked(result));
+        });
+        self.arxiv_updates.receiver = Some(rx);
+        self.status_message = "arXiv: checking for new versions…".to_string();
+    }
+
+    /// Open the update inbox (`:arxiv-updates`).
+    pub fn open_arxiv_updates(&mut self) {
+        let Some(root) = &self.library_root else {
+            self.status_message = "arXiv: no library open".to_string();
+            return;
+        };
+        let state = match ArxivUpdateState::load(&ArxivUpdateState::path(root)) {
+            Ok(state) => state,
+            Err(err) => {
+                self.status_message = format!("arXiv: {err}");
+                return;
+            }
+        };
+        if state.pending.is_empty() {
+            self.status_message = match state.last_run {
+                Some(at) => format!(
+                    "arXiv: no pending updates (last check {})",
+                    at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+                ),
+                None => "arXiv: no pending updates — :arxiv-check to look".to_string(),
+            };
+            return;
+        }
+
+        let cards_dir = self.cards_dir();
+        let items = state
+            .pending
+            .into_iter()
+            .map(|update| ArxivUpdateItem {
+                diff: json_cards::load_card_by_id(&cards_dir, &update.book_id)
+                    .ok()
+                    .map(|card| update.diff(&card)),
+                update,
+            })
+            .collect();
+        self.popup = Some(Popup::ArxivUpdates(ArxivUpdatesPanel::new(items)));
+    }
+
+    /// Apply an accepted update in the background; the card as it was goes
+    /// on the undo stack once it lands.
+    pub fn accept_arxiv_update(&mut self, update: ArxivUpdateResult, download_pdf: bool) -> bool {
+        if self.arxiv_updates.receiver.is_some() {
+            self.status_message = "arXiv: already busy…".to_string();
+            return false;
+        }
+        let Some(root) = &self.library_root else {
+            return false;
+        };
+        let state_path = ArxivUpdateState::path(root);
+        let db_path = root.database_path();
+        let cards_dir = self.cards_dir();
+        let opts = ArxivApplyOptions {
+            download_pdf,
+            download_dir: ScienceConfig::load().unwrap_or_default().download_directory,
+        };
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let result = run_async(|runtime| {
+                let updater = ArxivUpdater::from_db(Arc::new(Database::open(&db_path)?));
+                let applied =
+                    runtime.block_on(updater.apply_to_library(&update, &cards_dir, &opts))?;
+                let mut state = ArxivUpdateState::load(&state_path)?;
+                state.take(&update.book_id);
+                state.save(&state_path)?;
+                Ok(applied)
+            });
+            let _ = tx.send(ArxivUpdateEvent::Applied(Box::new(result)));
+        });
+        self.arxiv_updates.receiver = Some(rx);
+        self.status_message = if download_pdf {
+            "arXiv: downloading new version…".to_string()
+        } else {
+            "arXiv: updating card…".to_string()
+        };
+        true
+    }
+
+    /// Drop an update from the inbox without applying it. It comes back if a
+    /// later check still finds the card behind.
+    pub fn dismiss_arxiv_update(&mut self, update: &ArxivUpdateResult) {
+        let Some(root) = &self.library_root else {
+            return;
+        };
+        let path = ArxivUpdateState::path(root);
+        let result = ArxivUpdateState::load(&path).and_then(|mut state| {
+            state.take(&update.book_id);
+            state.save(&path)
+        });
+        if let Err(err) = result {
+            self.status_message = format!("arXiv: {err}");
+        }
+    }
+
+    /// Pick up finished checks and updates; called on every tick.
+    pub fn pump_arxiv_updates(&mut self) {
+        self.maybe_start_arxiv_check();
+
+        let Some(rx) = &self.arxiv_updates.receiver else {
+            return;
+        };
+        let event = match rx.try_recv() {
+            Ok(event) => event,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => {
+                ArxivUpdateEvent::Checked(Err("worker disconnected".to_string()))
+            }
+        };
+        self.arxiv_updates.receiver = None;
+
+        match event {
+            ArxivUpdateEvent::Checked(Ok(0)) => {
+                self.status_message = "arXiv: all papers are up to date".to_string();
+            }
+            ArxivUpdateEvent::Checked(Ok(pending)) => {
+                self.status_message =
+                    format!("arXiv: {pending} paper(s) have a newer version — :arxiv-updates");
+            }
+            ArxivUpdateEvent::Applied(result) => match *result {
+                Ok(applied) => {
+                    let title = applied.card.metadata.title.clone();
+                    let path = |card: &omniscope_core::BookCard| {
+                        card.file.as_ref().map(|file| file.path.clone())
+                    };
+                    let new_file = path(&applied.card)
+                        .filter(|file| path(&applied.previous).as_ref() != Some(file));
+                    self.push_undo(
+                        format!("Update \"{title}\" from arXiv"),
+                        UndoAction::UpsertCards(vec![applied.previous]),
+                    );
+                    self.refresh_books();
+                    self.status_message = match new_file {
+                        Some(file) => format!("arXiv: updated \"{title}\", PDF saved to {file}"),
+                        None => format!("arXiv: updated \"{title}\""),
+                    };
+                }
+                Err(err) => self.status_message = format!("arXiv: {err}"),
+            },
+            ArxivUpdateEvent::Checked(Err(err)) => {
+                self.status_message = format!("arXiv: {err}");
+            }
+        }
+    }
+}
+
+pub(super) fn run_async<T>(
+    job: impl FnOnce(&tokio::runtime::Runtime) -> anyhow::Result<T>,
+) -> Result<T, String> {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(anyhow::Error::from)
+        .and_then(|runtime| job(&runtime))
+        .map_err(|err| format!("{err:#}"))
+}
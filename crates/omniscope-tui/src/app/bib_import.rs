@@ -0,0 +1,135 @@
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+use omniscope_core::storage::json_cards;
+use omniscope_core::undo::UndoAction;
+use omniscope_core::{BookCard, Database};
+use omniscope_science::bib_import;
+
+use super::App;
+
+/// What a finished `:import-bib` run reports back.
+pub struct BibImportSummary {
+    pub file: String,
+    pub created: Vec<BookCard>,
+    pub duplicates: usize,
+    pub unattached_files: usize,
+}
+
+type BibImportResult = Result<BibImportSummary, String>;
+
+/// Background `:import-bib` run.
+#[derive(Default)]
+pub struct BibImportState {
+    pub receiver: Option<Receiver<BibImportResult>>,
+}
+
+impl App {
+    /// Import a BibTeX, RIS or CSL-JSON export in the background
+    /// (`:import-bib <file>`).
+    pub fn start_bib_import(&mut self, file: &str) {
+        if self.bib_import.receiver.is_some() {
+            self.status_message = "Import already running…".to_string();
+            return;
+        }
+        let file = file.trim();
+        if file.is_empty() {
+            self.status_message = "Usage: :import-bib <file.bib|file.ris|file.json>".to_string();
+            return;
+        }
+
+        let (root, db_path) = match &self.library_root {
+            Some(root) => (root.root().to_path_buf(), root.database_path()),
+            None => (
+                PathBuf::from(&self.config.core.library_path),
+                self.config.database_path(),
+            ),
+        };
+        let path = expand_home(file);
+        self.status_message = format!("Importing {}…", path.display());
+        self.bib_import.receiver = Some(spawn_import_worker(path, root, db_path, self.cards_dir()));
+    }
+
+    /// Pick up a finished import; called on every tick.
+    pub fn pump_bib_import(&mut self) {
+        let Some(rx) = &self.bib_import.receiver else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => Err("import worker disconnected".to_string()),
+        };
+        self.bib_import.receiver = None;
+
+        match result {
+            Ok(summary) => {
+                let created = summary.created.len();
+                if created > 0 {
+                    self.push_undo(
+                        format!("Import {created} entries from {}", summary.file),
+                        UndoAction::DeleteCards(summary.created),
+                    );
+                    self.refresh_books();
+                }
+                self.status_message = format!(
+                    "Imported {created} from {}, skipped {} duplicate(s), {} file(s) not attached",
+                    summary.file, summary.duplicates, summary.unattached_files
+                );
+            }
+            Err(err) => {
+                self.status_message = format!("Import failed: {err}");
+            }
+        }
+    }
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn spawn_import_worker(
+    path: PathBuf,
+    library_root: PathBuf,
+    db_path: PathBuf,
+    cards_dir: PathBuf,
+) -> Receiver<BibImportResult> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let result = run_import(&path, &library_root, &db_path, &cards_dir);
+        let _ = tx.send(result.map_err(|err| format!("{err:#}")));
+    });
+    rx
+}
+
+fn run_import(
+    path: &Path,
+    library_root: &Path,
+    db_path: &Path,
+    cards_dir: &Path,
+) -> anyhow::Result<BibImportSummary> {
+    let candidates = bib_import::read_candidates(path, library_root)?;
+    let existing = json_cards::list_cards(cards_dir)?;
+    let plan = bib_import::plan_import(candidates, &existing);
+
+    let db = Database::open(db_path)?;
+    bib_import::apply_import(&plan, &db, cards_dir)?;
+
+    Ok(BibImportSummary {
+        file: path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        created: plan.cards_to_create().cloned().collect(),
+        duplicates: plan.duplicate_count(),
+        unattached_files: plan
+            .entries
+            .iter()
+            .map(|(candidate, _)| candidate.unresolved_files.len())
+            .sum(),
+    })
+}
@@ -566,6 +566,25 @@ impl App {
         }
     }
 
+    /// Source tags per card for `src:` filters, read once per book list.
+    fn source_tags(&mut self) -> &std::collections::HashMap<uuid::Uuid, Vec<String>> {
+        if self.source_tags.is_none() {
+            let cards_dir = self.cards_dir();
+            let tags = self
+                .all_books
+                .iter()
+                .filter_map(|book| {
+                    let card =
+                        omniscope_core::storage::json_cards::load_card_by_id(&cards_dir, &book.id)
+                            .ok()?;
+                    Some((book.id, card.metadata_sources.into_values().collect()))
+                })
+                .collect();
+            self.source_tags = Some(tags);
+        }
+        self.source_tags.get_or_insert_default()
+    }
+
     /// Run telescope search: parse DSL, apply filters, then fuzzy on remaining terms.
     pub fn telescope_search(&mut self, query: &str) {
         use omniscope_core::search_dsl::SearchQuery;
@@ -579,12 +598,36 @@ impl App {
             .cloned()
             .collect();
 
+        if parsed.has_source_filter() {
+            let source_tags = self.source_tags();
+            filtered.retain(|book| {
+                source_tags
+                    .get(&book.id)
+                    .is_some_and(|tags| parsed.matches_sources(tags))
+            });
+        }
+
         let fuzzy_text = parsed.fuzzy_text();
         if !fuzzy_text.is_empty() {
             let results = self.fuzzy_searcher.search(&fuzzy_text, &filtered);
             filtered = results.into_iter().map(|r| r.book).collect();
         }
 
+        if let Some(concept) = parsed.semantic_text() {
+            if !self.config.search.semantic_search {
+                self.status_message =
+                    "Semantic search is off (set search.semantic_search = true)".to_string();
+            } else if let Some(ranking) = self.semantic_ranking(&concept) {
+                let rank: std::collections::HashMap<uuid::Uuid, usize> = ranking
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (id, _))| (*id, i))
+                    .collect();
+                filtered.retain(|b| rank.contains_key(&b.id));
+                filtered.sort_by_key(|b| rank[&b.id]);
+            }
+        }
+
         let chips: Vec<String> = parsed
             .filters
             .iter()
@@ -609,6 +652,8 @@ impl App {
                 omniscope_core::search_dsl::SearchFilter::HasFile => "has:file".to_string(),
                 omniscope_core::search_dsl::SearchFilter::HasSummary => "has:summary".to_string(),
                 omniscope_core::search_dsl::SearchFilter::HasTags => "has:tags".to_string(),
+                omniscope_core::search_dsl::SearchFilter::Source(s) => format!("src:{s}"),
+                omniscope_core::search_dsl::SearchFilter::Semantic(q) => format!("~{q}"),
                 omniscope_core::search_dsl::SearchFilter::Not(_inner) => "NOT ...".to_string(),
             })
             .collect();
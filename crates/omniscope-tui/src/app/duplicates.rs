@@ -0,0 +1,102 @@
+use omniscope_core::storage::json_cards;
+use omniscope_core::undo::UndoAction;
+use omniscope_science::dedup::{self, DedupStrategy, DuplicateFinder};
+
+use super::App;
+use crate::panels::duplicates::{DuplicateGroupView, DuplicatesPanel};
+use crate::popup::Popup;
+
+impl App {
+    /// Find duplicate cards and open the review popup (`:dedup [doi|isbn|title]`).
+    pub fn open_duplicates(&mut self, strategy: Option<&str>) {
+        let strategy = match strategy.map(DedupStrategy::parse).transpose() {
+            Ok(strategy) => strategy,
+            Err(err) => {
+                self.status_message = format!("dedup: {err}");
+                return;
+            }
+        };
+        let cards = match json_cards::list_cards(&self.cards_dir()) {
+            Ok(cards) => cards,
+            Err(err) => {
+                self.status_message = format!("dedup: {err}");
+                return;
+            }
+        };
+
+        let finder = DuplicateFinder::default();
+        let groups = match strategy {
+            Some(strategy) => finder.find(&cards, strategy),
+            None => finder.find_all(&cards),
+        };
+        if groups.is_empty() {
+            self.status_message = "No duplicates found".to_string();
+            return;
+        }
+
+        let views: Vec<DuplicateGroupView> = groups
+            .iter()
+            .map(|group| {
+                let members = std::iter::once(&group.canonical)
+                    .chain(&group.duplicates)
+                    .filter_map(|id| cards.iter().find(|card| card.id == *id).cloned())
+                    .collect();
+                DuplicateGroupView::new(group.strategy, members)
+            })
+            .collect();
+        self.status_message = format!("{} duplicate group(s)", views.len());
+        self.popup = Some(Popup::Duplicates(DuplicatesPanel::new(views)));
+    }
+
+    /// Merge one reviewed group into its chosen canonical card. The cards as
+    /// they were go on the undo stack, so `u` brings the merged-away ones back
+    /// and redo removes them again.
+    pub fn merge_duplicate_group(&mut self, panel: &DuplicatesPanel, group: usize) -> bool {
+        let Some(view) = panel.groups.get(group) else {
+            return false;
+        };
+        let Some(canonical) = view.cards.get(view.canonical) else {
+            return false;
+        };
+        let Some(db) = self.db.as_ref() else {
+            self.status_message = "dedup: no database".to_string();
+            return false;
+        };
+        let others: Vec<_> = view
+            .cards
+            .iter()
+            .filter(|card| card.id != canonical.id)
+            .map(|card| card.id)
+            .collect();
+
+        match dedup::merge_group(&canonical.id, &others, db, &self.cards_dir()) {
+            Ok(outcome) => {
+                let count = outcome.originals.len();
+                self.push_undo(
+                    format!(
+                        "Merge {count} cards into \"{}\"",
+                        outcome.merged.metadata.title
+                    ),
+                    UndoAction::RestoreCards(
+                        outcome
+                            .originals
+                            .into_iter()
+                            .map(|card| (card.id, Some(card)))
+                            .collect(),
+                    ),
+                );
+                self.refresh_books();
+                self.status_message = format!(
+                    "Merged {} card(s) into \"{}\"",
+                    others.len(),
+                    outcome.merged.metadata.title
+                );
+                true
+            }
+            Err(err) => {
+                self.status_message = format!("dedup: {err}");
+                false
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use std::sync::mpsc::{self, TryRecvError};
+use std::thread;
+use std::time::Duration;
+
+use omniscope_core::sync::LibraryEvent;
+use tungstenite::client::IntoClientRequest;
+use tungstenite::http::HeaderValue;
+use tungstenite::{Message, connect};
+
+use super::App;
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+/// Failed connection attempts in a row before the feed is given up.
+const MAX_RECONNECT_ATTEMPTS: u32 = 10;
+
+impl App {
+    /// Subscribe to the server's change feed if `[server]` is enabled.
+    pub fn subscribe_configured_feed(&mut self) {
+        if !self.config.server.enabled {
+            return;
+        }
+        let server = &self.config.server;
+        let url = format!("ws://{}:{}/api/events", server.host, server.port);
+        let token = std::env::var(&server.auth_token_env).ok();
+        self.subscribe_feed(url, token);
+    }
+
+    /// Connect to a `/api/events` WebSocket on a background thread.
+    ///
+    /// The thread reconnects after failures, waiting twice as long after
+    /// each one, and exits once the app drops its receiver or the server
+    /// has been unreachable for [`MAX_RECONNECT_ATTEMPTS`] tries in a row.
+    pub fn subscribe_feed(&mut self, url: String, token: Option<String>) {
+        let (tx, rx) = mpsc::channel::<LibraryEvent>();
+        thread::spawn(move || {
+            let mut delay = RECONNECT_DELAY;
+            let mut failures = 0;
+            loop {
+                if let Ok(mut request) = url.as_str().into_client_request() {
+                    if let Some(value) = token
+                        .as_deref()
+                        .and_then(|token| HeaderValue::from_str(&format!("Bearer {token}")).ok())
+                    {
+                        request.headers_mut().insert("Authorization", value);
+                    }
+                    if let Ok((mut socket, _)) = connect(request) {
+                        delay = RECONNECT_DELAY;
+                        failures = 0;
+                        while let Ok(message) = socket.read() {
+                            let Message::Text(text) = message else {
+                                continue;
+                            };
+                            let Ok(event) = serde_json::from_str::<LibraryEvent>(&text) else {
+                                continue;
+                            };
+                            if tx.send(event).is_err() {
+                                return;
+                            }
+                        }
+                    }
+                }
+                failures += 1;
+                if failures >= MAX_RECONNECT_ATTEMPTS {
+                    return;
+                }
+                thread::sleep(delay);
+                delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+            }
+        });
+        self.feed_rx = Some(rx);
+    }
+
+    /// Drain pending feed events into the book list.
+    pub fn pump_feed_events(&mut self) {
+        let mut events = Vec::new();
+        if let Some(rx) = &self.feed_rx {
+            loop {
+                match rx.try_recv() {
+                    Ok(event) => events.push(event),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        self.feed_rx = None;
+                        self.status_message =
+                            "Live feed: server unreachable, stopped reconnecting".to_string();
+                        break;
+                    }
+                }
+            }
+        }
+
+        let mut books_changed = false;
+        let mut folders_changed = false;
+        for event in events {
+            match event {
+                LibraryEvent::CardUpserted { .. } | LibraryEvent::CardDeleted { .. } => {
+                    books_changed |= self.apply_library_event(event);
+                }
+                LibraryEvent::FoldersChanged => folders_changed = true,
+                LibraryEvent::Watcher { .. } => {}
+            }
+        }
+
+        if folders_changed {
+            self.rebuild_folder_tree();
+        }
+        if books_changed {
+            self.apply_filter();
+        }
+        if books_changed || folders_changed {
+            self.refresh_sidebar();
+        }
+    }
+
+    /// Patch `all_books` for a single card event. Returns whether it changed.
+    pub fn apply_library_event(&mut self, event: LibraryEvent) -> bool {
+        match event {
+            LibraryEvent::CardUpserted { book } => {
+                match self.all_books.iter_mut().find(|existing| existing.id == book.id) {
+                    Some(existing) => *existing = book,
+                    None => self.all_books.insert(0, book),
+                }
+                true
+            }
+            LibraryEvent::CardDeleted { id } => {
+                let before = self.all_books.len();
+                self.all_books.retain(|book| book.id != id);
+                self.all_books.len() != before
+            }
+            LibraryEvent::FoldersChanged | LibraryEvent::Watcher { .. } => false,
+        }
+    }
+}
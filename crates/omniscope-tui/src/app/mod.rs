@@ -1,9 +1,24 @@
+mod ai_chat;
+mod ai_index;
+mod arxiv_feeds;
+mod arxiv_updates;
+mod bib_import;
 mod books;
+mod duplicates;
+mod feed;
 mod navigation;
 mod science;
+mod semantic;
 mod sidebar;
 mod vim;
 
+pub use ai_chat::{AiChatEvent, AiChatState};
+pub use ai_index::{AiIndexEvent, AiIndexState};
+pub use arxiv_feeds::{AddedPaper, ArxivFeedEvent, ArxivFeedsState};
+pub use arxiv_updates::{ArxivUpdateEvent, ArxivUpdatesState};
+pub use bib_import::{BibImportState, BibImportSummary};
+pub use semantic::SemanticSearchState;
+
 use crate::keys::core::operator::Operator;
 use crate::keys::ext::jump_list::JumpList;
 use crate::keys::ui::macro_recorder::MacroRecorder;
@@ -129,6 +144,8 @@ pub enum SidebarItem {
     Library { name: String, count: u32 },
     TagHeader,
     Tag { name: String, count: u32 },
+    FeedHeader,
+    Feed { name: String, new_count: u32 },
     FolderHeader,
     Folder { path: String },
 }
@@ -149,6 +166,15 @@ pub struct MetadataTaskState {
     pub status_prefix: String,
 }
 
+/// An enrichment result held back until the user has reviewed the fields
+/// where sources disagreed with the card.
+pub struct PendingMetadataReview {
+    pub before: BookCard,
+    pub after: BookCard,
+    pub report: EnrichmentReport,
+    pub status_prefix: String,
+}
+
 /// Main application state.
 pub struct App {
     pub should_quit: bool,
@@ -190,6 +216,9 @@ pub struct App {
 
     /// Fuzzy searcher instance.
     pub fuzzy_searcher: FuzzySearcher,
+    /// Source tags of every card, read from the JSON cards on the first
+    /// `src:` search and dropped when the book list is reloaded.
+    pub source_tags: Option<std::collections::HashMap<uuid::Uuid, Vec<String>>>,
 
     /// Database handle.
     pub db: Option<Database>,
@@ -283,6 +312,17 @@ pub struct App {
     // ─── Phase 1: AI Panel ──────────────────────────────────
     pub ai_panel_active: bool,
     pub ai_input: String,
+    pub ai_chat: AiChatState,
+    /// Similarity ranking behind `~` telescope queries.
+    pub semantic: SemanticSearchState,
+    /// Background summary/index job (`ai.auto_summary` / `ai.auto_index`).
+    pub ai_index: AiIndexState,
+    /// Running `:import-bib`, if any.
+    pub bib_import: BibImportState,
+    /// arXiv version checks and accepted updates.
+    pub arxiv_updates: ArxivUpdatesState,
+    /// Saved arXiv feeds, `:arxiv` searches and papers added from them.
+    pub arxiv_feeds: ArxivFeedsState,
 
     /// UI Theme
     pub theme: NordTheme,
@@ -298,6 +338,13 @@ pub struct App {
 
     /// Background metadata enrichment task (if currently running).
     pub metadata_task: Option<MetadataTaskState>,
+
+    /// Enrichment result waiting on the merge review popup.
+    pub metadata_review: Option<PendingMetadataReview>,
+
+    // ─── Live change feed ───────────────────────────────────
+    /// Events from the server's `/api/events` WebSocket, if subscribed.
+    pub feed_rx: Option<Receiver<omniscope_core::sync::LibraryEvent>>,
 }
 
 impl App {
@@ -334,7 +381,7 @@ impl App {
         let books = all_books.clone();
 
         let status_message = if library_root.is_some() {
-            String::new()
+            omniscope_ai::provider::legacy_provider_warning(&config.ai).unwrap_or_default()
         } else {
             "No library found. Run 'omniscope init' to create one.".to_string()
         };
@@ -357,6 +404,7 @@ impl App {
             visual_selections: Vec::new(),
             visual_anchor: None,
             fuzzy_searcher: FuzzySearcher::new(),
+            source_tags: None,
             db,
             config,
             library_root,
@@ -389,14 +437,23 @@ impl App {
             macro_recorder: MacroRecorder::new(),
             ai_panel_active: false,
             ai_input: String::new(),
+            ai_chat: AiChatState::default(),
+            semantic: SemanticSearchState::default(),
+            ai_index: AiIndexState::default(),
+            bib_import: BibImportState::default(),
+            arxiv_updates: ArxivUpdatesState::default(),
+            arxiv_feeds: ArxivFeedsState::default(),
             theme: NordTheme::default(),
             clipboard: arboard::Clipboard::new().ok(),
             pending_editor_path: None,
             preview_scroll: 0,
             metadata_task: None,
+            metadata_review: None,
+            feed_rx: None,
         };
 
         app.refresh_sidebar();
+        app.subscribe_configured_feed();
         app
     }
 
@@ -239,6 +239,7 @@ impl App {
                     item,
                     super::SidebarItem::Library { .. }
                         | super::SidebarItem::TagHeader
+                        | super::SidebarItem::FeedHeader
                         | super::SidebarItem::FolderHeader
                 )
             })
@@ -250,7 +251,9 @@ impl App {
             // Let's loop manually for clarity
             for i in current + 1..self.sidebar_items.len() {
                 match self.sidebar_items[i] {
-                    super::SidebarItem::TagHeader | super::SidebarItem::FolderHeader => {
+                    super::SidebarItem::TagHeader
+                    | super::SidebarItem::FeedHeader
+                    | super::SidebarItem::FolderHeader => {
                         self.sidebar_selected = i;
                         return;
                     }
@@ -266,7 +269,9 @@ impl App {
         }
         for i in (0..self.sidebar_selected).rev() {
             match self.sidebar_items[i] {
-                super::SidebarItem::TagHeader | super::SidebarItem::FolderHeader => {
+                super::SidebarItem::TagHeader
+                | super::SidebarItem::FeedHeader
+                | super::SidebarItem::FolderHeader => {
                     self.sidebar_selected = i;
                     return;
                 }
@@ -1,14 +1,20 @@
-use super::{App, MetadataTaskResult, MetadataTaskState, Register, RegisterContent};
+use super::{
+    App, MetadataTaskResult, MetadataTaskState, PendingMetadataReview, Register, RegisterContent,
+};
 use crate::panels::citation_graph::{CitationEdge, CitationGraphPanel, GraphMode};
 use crate::panels::find_download::{
     FindDownloadPanel, FindResult, FindSource, SearchIdentifierKind,
 };
+use crate::panels::merge_review::MergeReviewPanel;
 use crate::panels::references::ReferencesPanel;
 use crate::popup::Popup;
 use chrono::Utc;
 use omniscope_core::models::{BookCard, BookPublication, DocumentType, ScientificIdentifiers};
 use omniscope_core::storage::json_cards;
-use omniscope_science::enrichment::EnrichmentPipeline;
+use omniscope_science::enrichment::provenance::{lock_current_value, unlock_field};
+use omniscope_science::enrichment::{
+    EnrichmentPipeline, EnrichmentReport, lock_field, resolve_field, review_fields,
+};
 use omniscope_science::formats::bibtex::{BibTeXOptions, generate_bibtex};
 use omniscope_science::formats::csl::CslProcessor;
 use omniscope_science::identifiers::arxiv::ArxivId;
@@ -288,7 +294,7 @@ impl App {
             .map(ToOwned::to_owned)
             .unwrap_or_else(|| self.default_citation_style());
 
-        let processor = CslProcessor::default();
+        let processor = citation_processor();
         match processor.format_citation(&card, &style) {
             Ok(citation) => self.copy_text_to_clipboard(&format!("citation ({style})"), &citation),
             Err(err) => {
@@ -501,6 +507,14 @@ impl App {
     }
 
     pub fn poll_background_tasks(&mut self) {
+        self.pump_feed_events();
+        self.pump_ai_chat();
+        self.pump_semantic_search();
+        self.pump_ai_index();
+        self.pump_bib_import();
+        self.pump_arxiv_updates();
+        self.pump_arxiv_feeds();
+
         let mut finished = None;
         let mut disconnected = None;
 
@@ -545,6 +559,26 @@ impl App {
                 after,
                 report,
             } => {
+                let reviews = review_fields(&before, &report.candidates);
+                if !reviews.is_empty() {
+                    self.status_message = format!(
+                        "{status_prefix}: review {} conflicting field(s)",
+                        reviews.len()
+                    );
+                    self.popup = Some(Popup::MergeReview(MergeReviewPanel::new(
+                        after.metadata.title.clone(),
+                        reviews,
+                        &after,
+                    )));
+                    self.metadata_review = Some(PendingMetadataReview {
+                        before,
+                        after,
+                        report,
+                        status_prefix: status_prefix.to_string(),
+                    });
+                    return;
+                }
+
                 if report.fields_updated.is_empty() {
                     if let Some(first_error) = report.errors.first() {
                         self.status_message =
@@ -555,36 +589,121 @@ impl App {
                     return;
                 }
 
-                self.push_undo(
-                    format!("{status_prefix}: {}", after.metadata.title),
-                    omniscope_core::undo::UndoAction::UpsertCards(vec![before]),
-                );
-
-                let cards_dir = self.cards_dir();
-                if let Err(err) = json_cards::save_card(&cards_dir, &after) {
-                    self.status_message = format!("{status_prefix}: save failed: {err}");
-                    return;
+                if self.save_enriched_card(status_prefix, before, &after) {
+                    self.status_message = enrichment_summary(status_prefix, &report);
                 }
-                if let Some(ref db) = self.db {
-                    let _ = db.upsert_book(&after);
-                }
-                self.refresh_books();
-                let _ = self.select_book_by_id(after.id);
+            }
+        }
+    }
 
-                if report.errors.is_empty() {
-                    self.status_message = format!(
-                        "{status_prefix}: {} field(s) updated",
-                        report.fields_updated.len()
-                    );
-                } else {
-                    self.status_message = format!(
-                        "{status_prefix}: {} field(s) updated, {} warning(s)",
-                        report.fields_updated.len(),
-                        report.errors.len()
-                    );
+    /// Save the merged card, locking the values the user picked in review
+    /// as their own. Fields left on the automatic choice stay unlocked.
+    pub fn apply_metadata_review(&mut self, selections: Vec<(&'static str, String)>) {
+        let Some(PendingMetadataReview {
+            before,
+            mut after,
+            mut report,
+            status_prefix,
+        }) = self.metadata_review.take()
+        else {
+            return;
+        };
+
+        let mut locked = 0;
+        for (field, value) in selections {
+            if lock_field(&mut after, field, &value) {
+                locked += 1;
+                if !report.fields_updated.iter().any(|name| name == field) {
+                    report.fields_updated.push(field.to_string());
                 }
             }
         }
+
+        if self.save_enriched_card(&status_prefix, before, &after) {
+            self.status_message = format!(
+                "{}, {locked} locked",
+                enrichment_summary(&status_prefix, &report)
+            );
+        }
+    }
+
+    pub fn cancel_metadata_review(&mut self) {
+        if let Some(review) = self.metadata_review.take() {
+            self.status_message =
+                format!("{}: review discarded, nothing saved", review.status_prefix);
+        }
+    }
+
+    /// `:lock <field>` / `:unlock <field>` on the selected card.
+    pub fn set_selected_field_lock(&mut self, name: &str, lock: bool) {
+        let command = if lock { "lock" } else { "unlock" };
+        let Some(field) = resolve_field(name) else {
+            self.status_message = format!("{command}: unknown field '{name}'");
+            return;
+        };
+        let Some(selected) = self.selected_book() else {
+            self.status_message = format!("{command}: no selected book");
+            return;
+        };
+        let cards_dir = self.cards_dir();
+        let Ok(mut card) = json_cards::load_card_by_id(&cards_dir, &selected.id) else {
+            self.status_message = format!("{command}: card not found");
+            return;
+        };
+
+        let before = card.clone();
+        let changed = if lock {
+            lock_current_value(&mut card, field)
+        } else {
+            unlock_field(&mut card, field)
+        };
+        if !changed {
+            self.status_message = if lock && !card.is_field_locked(field) {
+                format!("{command}: {field} is empty")
+            } else {
+                format!("{command}: {field} already {command}ed")
+            };
+            return;
+        }
+
+        if let Err(err) = json_cards::save_card(&cards_dir, &card) {
+            self.status_message = format!("{command}: save failed: {err}");
+            return;
+        }
+        if let Some(ref db) = self.db {
+            let _ = db.upsert_book(&card);
+        }
+        // Unlocking can drop `user_manual` provenance, which `src:` reads.
+        self.source_tags = None;
+        self.push_undo(
+            format!("{command} {field}: {}", card.metadata.title),
+            omniscope_core::undo::UndoAction::UpsertCards(vec![before]),
+        );
+        self.status_message = format!("{field} {command}ed");
+    }
+
+    fn save_enriched_card(
+        &mut self,
+        status_prefix: &str,
+        before: BookCard,
+        after: &BookCard,
+    ) -> bool {
+        self.push_undo(
+            format!("{status_prefix}: {}", after.metadata.title),
+            omniscope_core::undo::UndoAction::UpsertCards(vec![before]),
+        );
+
+        let cards_dir = self.cards_dir();
+        if let Err(err) = json_cards::save_card(&cards_dir, after) {
+            self.status_message = format!("{status_prefix}: save failed: {err}");
+            return false;
+        }
+        if let Some(ref db) = self.db {
+            let _ = db.upsert_book(after);
+        }
+        self.refresh_books();
+        let _ = self.select_book_by_id(after.id);
+        true
     }
 
     pub fn trigger_ai_extract_references(&mut self) {
@@ -674,7 +793,7 @@ impl App {
             .map(ToOwned::to_owned)
             .unwrap_or_else(|| self.default_citation_style());
 
-        let processor = CslProcessor::default();
+        let processor = citation_processor();
         match processor.format_citation(&card, &style) {
             Ok(citation) => {
                 self.open_science_text_viewer(format!(" Citation ({style}) "), citation)
@@ -1430,3 +1549,25 @@ impl LibraryLookup for CardLibraryLookup {
         self.arxiv_to_book.get(&key).copied()
     }
 }
+
+/// Styles and locale come from `[export]` in `science.toml`, so `:cite` can
+/// use any installed `.csl` style.
+fn citation_processor() -> CslProcessor {
+    let config = omniscope_science::ScienceConfig::load().unwrap_or_default();
+    CslProcessor::from_config(&config.export)
+}
+
+fn enrichment_summary(status_prefix: &str, report: &EnrichmentReport) -> String {
+    if report.errors.is_empty() {
+        format!(
+            "{status_prefix}: {} field(s) updated",
+            report.fields_updated.len()
+        )
+    } else {
+        format!(
+            "{status_prefix}: {} field(s) updated, {} warning(s)",
+            report.fields_updated.len(),
+            report.errors.len()
+        )
+    }
+}
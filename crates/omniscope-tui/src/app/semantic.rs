@@ -0,0 +1,130 @@
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+use omniscope_ai::{provider, semantic};
+use omniscope_core::Database;
+use omniscope_core::config::AiConfig;
+use uuid::Uuid;
+
+use super::App;
+use crate::popup::Popup;
+
+/// Books kept for a `~` query, closest first.
+const SEMANTIC_RESULTS: usize = 50;
+
+/// Embedded query vector and the model that produced it.
+type QueryEmbedding = Result<(String, Vec<f32>), String>;
+
+/// Ranking for the `~` part of the telescope query.
+#[derive(Default)]
+pub struct SemanticSearchState {
+    /// Concept typed in the telescope.
+    pub concept: Option<String>,
+    /// Books closest to `concept`, best first; `None` until computed.
+    pub ranking: Option<Vec<(Uuid, f32)>>,
+    /// Concept the worker is currently embedding.
+    pub requested: Option<String>,
+    pub receiver: Option<Receiver<QueryEmbedding>>,
+}
+
+impl App {
+    /// Similarity ranking for `concept`, starting the lookup on first use.
+    pub(crate) fn semantic_ranking(&mut self, concept: &str) -> Option<&[(Uuid, f32)]> {
+        if self.semantic.concept.as_deref() != Some(concept) {
+            self.semantic.concept = Some(concept.to_string());
+            self.semantic.ranking = None;
+            // A lookup in flight restarts with the new concept when it lands.
+            if self.semantic.receiver.is_none() {
+                self.start_semantic_query(concept.to_string());
+            }
+        }
+        self.semantic.ranking.as_deref()
+    }
+
+    fn start_semantic_query(&mut self, concept: String) {
+        let db_path = match &self.library_root {
+            Some(root) => root.database_path(),
+            None => self.config.database_path(),
+        };
+        self.status_message = format!("Semantic: searching for \"{concept}\"…");
+        self.semantic.requested = Some(concept.clone());
+        self.semantic.receiver = Some(spawn_query_worker(
+            self.config.ai.clone(),
+            db_path,
+            self.cards_dir(),
+            concept,
+        ));
+    }
+
+    /// Pick up a finished query embedding; called on every tick.
+    pub fn pump_semantic_search(&mut self) {
+        let Some(rx) = &self.semantic.receiver else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => Err("worker thread disconnected".to_string()),
+        };
+        self.semantic.receiver = None;
+
+        let requested = self.semantic.requested.take();
+        let Some(concept) = self.semantic.concept.clone() else {
+            return;
+        };
+        if requested.as_deref() != Some(concept.as_str()) {
+            self.start_semantic_query(concept);
+            return;
+        }
+
+        let ranking = result.and_then(|(model, vector)| {
+            let db = self.db.as_ref().ok_or("no database")?;
+            db.nearest_books(&vector, &model, SEMANTIC_RESULTS)
+                .map_err(|err| err.to_string())
+        });
+        match ranking {
+            Ok(ranking) => {
+                self.status_message = format!("Semantic: {} close match(es)", ranking.len());
+                self.semantic.ranking = Some(ranking);
+                let query = match &self.popup {
+                    Some(Popup::Telescope(state)) => Some(state.query.clone()),
+                    _ => None,
+                };
+                if let Some(query) = query {
+                    self.telescope_search(&query);
+                }
+            }
+            Err(err) => {
+                self.status_message = format!("Semantic: {err}");
+            }
+        }
+    }
+}
+
+/// Embed cards changed since the last search, then the query itself.
+fn spawn_query_worker(
+    config: AiConfig,
+    db_path: PathBuf,
+    cards_dir: PathBuf,
+    concept: String,
+) -> Receiver<QueryEmbedding> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let result = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(anyhow::Error::from)
+            .and_then(|runtime| {
+                runtime.block_on(async {
+                    let provider = provider::from_config(&config)?;
+                    let db = Database::open(&db_path)?;
+                    semantic::refresh_embeddings(&db, &cards_dir, provider.as_ref()).await?;
+                    let vector = semantic::embed_query(provider.as_ref(), &concept).await?;
+                    anyhow::Ok((provider.embedding_model().to_string(), vector))
+                })
+            });
+        let _ = tx.send(result.map_err(|err| format!("{err:#}")));
+    });
+    rx
+}
@@ -30,6 +30,17 @@ impl App {
                         }
                     }
                 }
+
+                // Saved arXiv feeds
+                if !self.arxiv_feeds.counts.is_empty() {
+                    items.push(SidebarItem::FeedHeader);
+                    for (name, new_count) in &self.arxiv_feeds.counts {
+                        items.push(SidebarItem::Feed {
+                            name: name.clone(),
+                            new_count: *new_count as u32,
+                        });
+                    }
+                }
             }
             crate::app::LeftPanelMode::FolderTree => {
                 // Virtual Folders
@@ -94,6 +105,7 @@ impl App {
             .as_ref()
             .and_then(|db| db.list_books(500, 0).ok())
             .unwrap_or_default();
+        self.source_tags = None;
 
         self.apply_filter_preserve_cursor(current_id);
         self.refresh_sidebar();
@@ -180,6 +192,12 @@ impl App {
                 SidebarItem::Tag { name, .. } => {
                     self.sidebar_filter = SidebarFilter::Tag(name.clone());
                 }
+                // Feeds open in a popup and leave the book filter alone.
+                SidebarItem::Feed { name, .. } => {
+                    let name = name.clone();
+                    self.open_arxiv_feed(&name);
+                    return;
+                }
                 SidebarItem::FolderNode { disk_path, .. } => {
                     self.sidebar_filter = SidebarFilter::Folder(disk_path.clone());
                 }
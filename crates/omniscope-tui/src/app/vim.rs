@@ -112,6 +112,32 @@ impl App {
                 }
                 Some(UndoAction::UpsertCards(cards.clone()))
             }
+            UndoAction::RestoreCards(states) => {
+                let mut prev_state = Vec::new();
+                for (id, state) in states {
+                    let current =
+                        omniscope_core::storage::json_cards::load_card_by_id(&cards_dir, id).ok();
+                    prev_state.push((*id, current));
+
+                    match state {
+                        Some(card) => {
+                            let _ =
+                                omniscope_core::storage::json_cards::save_card(&cards_dir, card);
+                            if let Some(ref db) = self.db {
+                                let _ = db.upsert_book(card);
+                            }
+                        }
+                        None => {
+                            let _ =
+                                omniscope_core::storage::json_cards::delete_card(&cards_dir, id);
+                            if let Some(ref db) = self.db {
+                                let _ = db.delete_book(&id.to_string());
+                            }
+                        }
+                    }
+                }
+                Some(UndoAction::RestoreCards(prev_state))
+            }
         }
     }
 
@@ -267,6 +267,33 @@ pub fn execute_command(app: &mut App, cmd: &str) {
         CommandAction::CitedBy => {
             app.open_science_citation_graph_panel(GraphMode::CitedBy);
         }
+        CommandAction::ImportBib(path) => {
+            app.start_bib_import(&path);
+        }
+        CommandAction::Dedup(strategy) => {
+            app.open_duplicates(strategy.as_deref());
+        }
+        CommandAction::ArxivUpdates => {
+            app.open_arxiv_updates();
+        }
+        CommandAction::ArxivCheck => {
+            app.start_arxiv_check();
+        }
+        CommandAction::ArxivSearch(terms) => {
+            app.search_arxiv(&terms);
+        }
+        CommandAction::ArxivFeed(name) => {
+            app.open_arxiv_feed(&name);
+        }
+        CommandAction::ArxivSaveFeed { name, terms } => {
+            app.save_arxiv_feed(&name, &terms);
+        }
+        CommandAction::LockField(field) => {
+            app.set_selected_field_lock(&field, true);
+        }
+        CommandAction::UnlockField(field) => {
+            app.set_selected_field_lock(&field, false);
+        }
         CommandAction::Unknown(unknown_cmd) => {
             app.status_message = format!("Unknown command: {unknown_cmd}");
         }
@@ -40,6 +40,15 @@ const COMMANDS: &[&str] = &[
     "bibtex",
     "refs",
     "cited-by",
+    "import-bib",
+    "dedup",
+    "arxiv-updates",
+    "arxiv-check",
+    "arxiv",
+    "arxiv-feed",
+    "arxiv-save",
+    "lock",
+    "unlock",
 ];
 
 pub fn get_command_suggestions(prefix: &str) -> Vec<&'static str> {
@@ -95,6 +104,18 @@ pub enum CommandAction {
     Bibtex,
     Refs,
     CitedBy,
+    ImportBib(String),
+    Dedup(Option<String>),
+    ArxivUpdates,
+    ArxivCheck,
+    ArxivSearch(String),
+    ArxivFeed(String),
+    ArxivSaveFeed {
+        name: String,
+        terms: String,
+    },
+    LockField(String),
+    UnlockField(String),
     Unknown(String),
 }
 
@@ -141,6 +162,21 @@ pub fn parse_command(cmd: &str) -> CommandAction {
         ["bibtex"] => CommandAction::Bibtex,
         ["refs"] => CommandAction::Refs,
         ["cited-by"] => CommandAction::CitedBy,
+        ["import-bib", rest @ ..] => CommandAction::ImportBib(rest.join(" ")),
+        ["dedup"] => CommandAction::Dedup(None),
+        ["dedup", strategy] => CommandAction::Dedup(Some(strategy.to_string())),
+        ["arxiv-updates"] => CommandAction::ArxivUpdates,
+        ["arxiv-check"] => CommandAction::ArxivCheck,
+        ["arxiv", rest @ ..] => CommandAction::ArxivSearch(rest.join(" ")),
+        ["arxiv-feed", name] => CommandAction::ArxivFeed(name.to_string()),
+        ["arxiv-save", name, rest @ ..] => CommandAction::ArxivSaveFeed {
+            name: name.to_string(),
+            terms: rest.join(" "),
+        },
+        ["lock", field @ ..] if !field.is_empty() => CommandAction::LockField(field.join(" ")),
+        ["unlock", field @ ..] if !field.is_empty() => {
+            CommandAction::UnlockField(field.join(" "))
+        }
         ["tabnew", ..] => {
             // Tabs not implemented yet, but parse gracefully
             CommandAction::Unknown("tabnew (tabs not implemented)".to_string())
@@ -245,6 +281,40 @@ mod tests {
         assert_eq!(parse_command("bibtex"), CommandAction::Bibtex);
         assert_eq!(parse_command("refs"), CommandAction::Refs);
         assert_eq!(parse_command("cited-by"), CommandAction::CitedBy);
+        assert_eq!(
+            parse_command("import-bib ~/zotero.bib"),
+            CommandAction::ImportBib("~/zotero.bib".to_string())
+        );
+        assert_eq!(parse_command("dedup"), CommandAction::Dedup(None));
+        assert_eq!(
+            parse_command("dedup doi"),
+            CommandAction::Dedup(Some("doi".to_string()))
+        );
+        assert_eq!(parse_command("arxiv-updates"), CommandAction::ArxivUpdates);
+        assert_eq!(parse_command("arxiv-check"), CommandAction::ArxivCheck);
+        assert_eq!(
+            parse_command("arxiv cat:cs.LG diffusion"),
+            CommandAction::ArxivSearch("cat:cs.LG diffusion".to_string())
+        );
+        assert_eq!(
+            parse_command("arxiv-feed ml"),
+            CommandAction::ArxivFeed("ml".to_string())
+        );
+        assert_eq!(
+            parse_command(r#"arxiv-save ml cat:cs.LG au:"Yoshua Bengio""#),
+            CommandAction::ArxivSaveFeed {
+                name: "ml".to_string(),
+                terms: r#"cat:cs.LG au:"Yoshua Bengio""#.to_string()
+            }
+        );
+        assert_eq!(
+            parse_command("lock year"),
+            CommandAction::LockField("year".to_string())
+        );
+        assert_eq!(
+            parse_command("unlock page range"),
+            CommandAction::UnlockField("page range".to_string())
+        );
     }
 
     #[test]
@@ -82,6 +82,10 @@ pub fn handle_change_science_command(app: &mut App, code: char) -> bool {
 
 pub fn handle_at_science_command(app: &mut App, code: char) -> bool {
     match code {
+        'c' => {
+            app.open_ai_chat();
+            true
+        }
         'e' => {
             app.trigger_ai_enrich_metadata();
             true
@@ -28,6 +28,10 @@ pub(crate) fn handle_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers)
         return;
     }
 
+    if app.ai_panel_active && modes::ai_chat::handle_ai_chat_key(app, code, modifiers) {
+        return;
+    }
+
     // Handle register selection
     if app.mode != Mode::Insert
         && app.mode != Mode::Command
@@ -0,0 +1,48 @@
+use crate::app::{App, Mode};
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// Keys for the AI chat panel. Returns `false` when the key should fall
+/// through to the regular mode handlers.
+pub(crate) fn handle_ai_chat_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) -> bool {
+    match app.mode {
+        Mode::Insert => {
+            match code {
+                KeyCode::Esc => app.mode = Mode::Normal,
+                KeyCode::Enter => app.submit_ai_message(),
+                KeyCode::Backspace => {
+                    app.ai_input.pop();
+                }
+                KeyCode::Char('u') if modifiers.contains(KeyModifiers::CONTROL) => {
+                    app.ai_input.clear();
+                }
+                KeyCode::Char(c) => app.ai_input.push(c),
+                _ => {}
+            }
+            true
+        }
+        Mode::Normal if app.pending_key.is_none() => match code {
+            KeyCode::Char('i') | KeyCode::Char('a') => {
+                app.mode = Mode::Insert;
+                true
+            }
+            KeyCode::Char('q') | KeyCode::Esc => {
+                app.close_ai_chat();
+                true
+            }
+            KeyCode::Char(c @ '1'..='9') => {
+                app.accept_ai_suggestion(c as usize - '1' as usize);
+                true
+            }
+            KeyCode::Char('K') | KeyCode::PageUp => {
+                app.scroll_ai_chat(5);
+                true
+            }
+            KeyCode::Char('J') | KeyCode::PageDown => {
+                app.scroll_ai_chat(-5);
+                true
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
@@ -1,3 +1,4 @@
+pub mod ai_chat;
 pub mod command_mode;
 pub mod normal;
 pub mod pending;
@@ -875,6 +875,32 @@ fn test_command_cite_opens_text_viewer() {
     );
 }
 
+#[test]
+fn test_command_unlock_updates_source_filters() {
+    let (mut app, _temp) = create_test_app();
+    let id = app.books[0].id;
+    let cards_dir = app.cards_dir();
+    let mut card = omniscope_core::storage::json_cards::load_card_by_id(&cards_dir, &id).unwrap();
+    card.set_field_locked("metadata.year", true);
+    card.metadata_sources
+        .insert("metadata.year".to_string(), "user_manual".to_string());
+    omniscope_core::storage::json_cards::save_card(&cards_dir, &card).unwrap();
+
+    app.open_telescope();
+    app.telescope_search("src:user_manual");
+    assert_eq!(telescope_results(&app).0, vec![id]);
+    app.popup = None;
+
+    app.mode = Mode::Command;
+    app.command_input = "unlock year".to_string();
+    crate::keys::handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+    assert_eq!(app.status_message, "metadata.year unlocked");
+
+    app.open_telescope();
+    app.telescope_search("src:user_manual");
+    assert!(telescope_results(&app).0.is_empty());
+}
+
 #[test]
 fn test_command_refs_opens_science_references_panel() {
     let (mut app, _temp) = create_test_app();
@@ -892,3 +918,199 @@ fn test_command_refs_opens_science_references_panel() {
         ":refs should open references panel"
     );
 }
+
+#[test]
+fn test_feed_upsert_updates_existing_book_in_place() {
+    let (mut app, _temp) = create_test_app();
+    let mut book = app.all_books[0].clone();
+    book.title = "Renamed elsewhere".to_string();
+
+    assert!(app.apply_library_event(omniscope_core::sync::LibraryEvent::CardUpserted {
+        book: book.clone()
+    }));
+    assert_eq!(app.all_books.len(), 10);
+    assert_eq!(app.all_books[0].title, "Renamed elsewhere");
+}
+
+#[test]
+fn test_feed_upsert_and_delete_new_book() {
+    let (mut app, _temp) = create_test_app();
+    let card = BookCard::new("Added in another terminal");
+    let book = omniscope_core::BookSummaryView::from(&card);
+
+    app.apply_library_event(omniscope_core::sync::LibraryEvent::CardUpserted { book });
+    assert_eq!(app.all_books.len(), 11);
+
+    assert!(app.apply_library_event(omniscope_core::sync::LibraryEvent::CardDeleted {
+        id: card.id
+    }));
+    assert_eq!(app.all_books.len(), 10);
+    assert!(!app.apply_library_event(omniscope_core::sync::LibraryEvent::CardDeleted {
+        id: card.id
+    }));
+}
+
+#[test]
+fn test_ai_chat_opens_in_insert_mode_and_closes() {
+    let (mut app, _temp) = create_test_app();
+
+    run_keys(&mut app, "@c");
+    assert!(app.ai_panel_active);
+    assert_eq!(app.mode, Mode::Insert);
+    assert_eq!(app.ai_chat.book_title, app.books[0].title);
+
+    run_keys(&mut app, "why q?");
+    assert_eq!(app.ai_input, "why q?");
+
+    run_keys(&mut app, "_q");
+    assert!(!app.ai_panel_active);
+    assert_eq!(app.mode, Mode::Normal);
+}
+
+#[test]
+fn test_ai_chat_accepting_suggestion_applies_action() {
+    let (mut app, _temp) = create_test_app();
+    let book_id = app.books[0].id;
+    run_keys(&mut app, "@c_");
+
+    app.ai_chat.suggestions = vec![omniscope_core::OmniscopeAction::AddTag {
+        book_id,
+        tag: "suggested".to_string(),
+    }];
+    run_keys(&mut app, "1");
+
+    let card =
+        omniscope_core::storage::json_cards::load_card_by_id(&app.cards_dir(), &book_id).unwrap();
+    assert!(card.organization.tags.contains(&"suggested".to_string()));
+    assert!(app.ai_chat.suggestions.is_empty());
+    assert_eq!(app.undo_stack.len(), 1);
+}
+
+fn telescope_results(app: &App) -> (Vec<uuid::Uuid>, Vec<String>) {
+    match &app.popup {
+        Some(crate::popup::Popup::Telescope(state)) => (
+            state.results.iter().map(|b| b.id).collect(),
+            state.active_filters.clone(),
+        ),
+        _ => panic!("telescope is not open"),
+    }
+}
+
+#[test]
+fn test_telescope_semantic_query_is_ignored_when_disabled() {
+    let (mut app, _temp) = create_test_app();
+    app.open_telescope();
+
+    app.telescope_search("~distributed systems");
+    let (results, chips) = telescope_results(&app);
+    assert_eq!(results.len(), app.all_books.len());
+    assert_eq!(chips, vec!["~distributed systems".to_string()]);
+    assert!(app.semantic.receiver.is_none());
+    assert!(app.status_message.contains("semantic_search"));
+}
+
+#[test]
+fn test_telescope_semantic_query_orders_by_ranking() {
+    let (mut app, _temp) = create_test_app();
+    app.config.search.semantic_search = true;
+    let (first, second) = (app.all_books[4].id, app.all_books[1].id);
+    app.semantic.concept = Some("graphs".to_string());
+    app.semantic.ranking = Some(vec![(first, 0.9), (second, 0.4)]);
+    app.open_telescope();
+
+    app.telescope_search("~graphs");
+    assert_eq!(telescope_results(&app).0, vec![first, second]);
+
+    // DSL filters still apply on top of the ranking.
+    let year = app.all_books[1].year.unwrap();
+    app.telescope_search(&format!("~graphs y:{year}"));
+    assert_eq!(telescope_results(&app).0, vec![second]);
+}
+
+#[test]
+fn test_ai_index_job_only_runs_when_enabled() {
+    let (mut app, _temp) = create_test_app();
+    app.config.ai.auto_index = false;
+    app.config.ai.auto_summary = false;
+    app.poll_background_tasks();
+    assert!(app.ai_index.receiver.is_none());
+    assert!(app.ai_index.last_run.is_none());
+
+    // A recent run holds the next one back until the interval passes.
+    app.config.ai.auto_index = true;
+    app.ai_index.last_run = Some(std::time::Instant::now());
+    app.poll_background_tasks();
+    assert!(app.ai_index.receiver.is_none());
+}
+
+#[test]
+fn test_ai_index_ignores_auto_index_saved_with_the_legacy_provider() {
+    let (mut app, _temp) = create_test_app();
+    app.config.ai.provider = "anthropic".to_string();
+    app.config.ai.model = "claude-sonnet-4-20250514".to_string();
+    app.config.ai.api_key_env = "ANTHROPIC_API_KEY".to_string();
+    app.config.ai.auto_index = true;
+    app.config.ai.auto_summary = false;
+    app.poll_background_tasks();
+    assert!(app.ai_index.receiver.is_none());
+    assert!(app.ai_index.last_run.is_none());
+}
+
+#[test]
+fn test_ai_index_stays_off_after_a_provider_error() {
+    use crate::app::AiIndexEvent;
+
+    let (mut app, _temp) = create_test_app();
+    app.config.ai.auto_index = true;
+    let (tx, rx) = std::sync::mpsc::channel();
+    tx.send(AiIndexEvent::Finished {
+        indexed: 0,
+        given_up: Vec::new(),
+        error: Some("connection refused".to_string()),
+    })
+    .unwrap();
+    app.ai_index.receiver = Some(rx);
+    app.ai_index.last_run = Some(std::time::Instant::now());
+
+    app.poll_background_tasks();
+    assert!(app.ai_index.halted);
+    assert!(app.status_message.contains("connection refused"));
+
+    // Even once the interval has passed, the job is not started again.
+    app.ai_index.last_run = None;
+    app.poll_background_tasks();
+    assert!(app.ai_index.receiver.is_none());
+    assert!(app.ai_index.last_run.is_none());
+}
+
+#[test]
+fn test_duplicate_merge_undo_and_redo_cover_every_card() {
+    use crate::panels::duplicates::{DuplicateGroupView, DuplicatesPanel};
+    use omniscope_core::storage::json_cards;
+
+    let (mut app, _temp) = create_test_app();
+    let cards_dir = app.config.cards_dir();
+    let (keep, merged) = (app.all_books[0].id, app.all_books[1].id);
+    let members: Vec<BookCard> = [keep, merged]
+        .iter()
+        .map(|id| json_cards::load_card_by_id(&cards_dir, id).unwrap())
+        .collect();
+    for card in &members {
+        app.db.as_ref().unwrap().upsert_book(card).unwrap();
+    }
+    let panel = DuplicatesPanel::new(vec![DuplicateGroupView::new(
+        omniscope_science::dedup::DedupStrategy::Title,
+        members,
+    )]);
+
+    assert!(app.merge_duplicate_group(&panel, 0));
+    assert!(json_cards::load_card_by_id(&cards_dir, &merged).is_err());
+
+    app.undo();
+    assert!(json_cards::load_card_by_id(&cards_dir, &keep).is_ok());
+    assert!(json_cards::load_card_by_id(&cards_dir, &merged).is_ok());
+
+    app.redo();
+    assert!(json_cards::load_card_by_id(&cards_dir, &keep).is_ok());
+    assert!(json_cards::load_card_by_id(&cards_dir, &merged).is_err());
+}
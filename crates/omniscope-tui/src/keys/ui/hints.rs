@@ -426,6 +426,10 @@ pub fn get_hints(app: &App) -> Vec<KeyHint> {
                         key: "@",
                         desc: "replay last",
                     },
+                    KeyHint {
+                        key: "c",
+                        desc: "AI chat",
+                    },
                     KeyHint {
                         key: "e",
                         desc: "AI enrich",
@@ -468,8 +472,8 @@ pub fn get_hints(app: &App) -> Vec<KeyHint> {
                 desc: "refs/cited/related",
             },
             KeyHint {
-                key: "@m/@e/@r",
-                desc: "meta/ai/refs",
+                key: "@m/@e/@r/@c",
+                desc: "meta/ai/refs/chat",
             },
             KeyHint {
                 key: ":help",
@@ -1,8 +1,12 @@
 use crate::app::App;
+use crate::panels::arxiv_feed::ArxivFeedPanelAction;
+use crate::panels::arxiv_updates::ArxivUpdatesPanelAction;
 use crate::panels::citation_graph::{CitationGraphPanel, CitationGraphPanelAction, GraphMode};
+use crate::panels::duplicates::DuplicatesPanelAction;
 use crate::panels::find_download::{
     FindDownloadPanel, FindDownloadPanelAction, FindResult, FindSource,
 };
+use crate::panels::merge_review::MergeReviewPanelAction;
 use crate::panels::references::{ReferenceAddTarget, ReferencesPanelAction};
 use crate::popup::{Popup, TelescopeMode};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
@@ -944,6 +948,104 @@ fn handle_science_popup_key(app: &mut App, code: KeyCode, modifiers: KeyModifier
             }
             true
         }
+        Popup::Duplicates(mut panel) => {
+            let mut keep_open = true;
+            let key = KeyEvent::new(code, modifiers);
+
+            if let Some(action) = panel.handle_key(key) {
+                match action {
+                    DuplicatesPanelAction::Merge { group } => {
+                        if app.merge_duplicate_group(&panel, group) {
+                            panel.remove_group(group);
+                            keep_open = !panel.is_empty();
+                        }
+                    }
+                    DuplicatesPanelAction::Close => {
+                        keep_open = false;
+                    }
+                }
+            }
+
+            if keep_open && app.popup.is_none() {
+                app.popup = Some(Popup::Duplicates(panel));
+            }
+            true
+        }
+        Popup::ArxivUpdates(mut panel) => {
+            let mut keep_open = true;
+            let key = KeyEvent::new(code, modifiers);
+
+            if let Some(action) = panel.handle_key(key) {
+                match action {
+                    ArxivUpdatesPanelAction::Accept {
+                        index,
+                        download_pdf,
+                    } => {
+                        let update = panel.items[index].update.clone();
+                        if app.accept_arxiv_update(update, download_pdf) {
+                            panel.remove(index);
+                            keep_open = !panel.is_empty();
+                        }
+                    }
+                    ArxivUpdatesPanelAction::Dismiss { index } => {
+                        app.dismiss_arxiv_update(&panel.items[index].update);
+                        panel.remove(index);
+                        keep_open = !panel.is_empty();
+                    }
+                    ArxivUpdatesPanelAction::Close => {
+                        keep_open = false;
+                    }
+                }
+            }
+
+            if keep_open && app.popup.is_none() {
+                app.popup = Some(Popup::ArxivUpdates(panel));
+            }
+            true
+        }
+        Popup::ArxivFeed(mut panel) => {
+            let mut keep_open = true;
+            let key = KeyEvent::new(code, modifiers);
+
+            if let Some(action) = panel.handle_key(key) {
+                match action {
+                    ArxivFeedPanelAction::Add { index } => {
+                        app.add_arxiv_paper(&panel.entries[index].metadata);
+                    }
+                    ArxivFeedPanelAction::Open { index } => {
+                        let url = panel.entries[index].metadata.abs_url.clone();
+                        app.open_external_url(&url, "arXiv abs");
+                    }
+                    ArxivFeedPanelAction::Refresh => {
+                        panel.loading = true;
+                        app.refresh_arxiv_feed(&panel);
+                    }
+                    ArxivFeedPanelAction::Close => {
+                        keep_open = false;
+                    }
+                }
+            }
+
+            if keep_open && app.popup.is_none() {
+                app.popup = Some(Popup::ArxivFeed(panel));
+            }
+            true
+        }
+        Popup::MergeReview(mut panel) => {
+            let key = KeyEvent::new(code, modifiers);
+            match panel.handle_key(key) {
+                Some(MergeReviewPanelAction::Apply) => {
+                    app.apply_metadata_review(panel.picked_selections());
+                }
+                Some(MergeReviewPanelAction::Cancel) => app.cancel_metadata_review(),
+                None => {
+                    if app.popup.is_none() {
+                        app.popup = Some(Popup::MergeReview(panel));
+                    }
+                }
+            }
+            true
+        }
         Popup::EditDoi {
             book_id,
             mut input,
@@ -0,0 +1,393 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use omniscope_science::arxiv::types::ArxivMetadata;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+
+use crate::theme::NordTheme;
+
+/// A submission listed in a feed or search.
+#[derive(Debug, Clone)]
+pub struct ArxivFeedEntry {
+    pub metadata: ArxivMetadata,
+    /// Submitted since the feed was last visited.
+    pub is_new: bool,
+    pub in_library: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArxivFeedPanelAction {
+    Add { index: usize },
+    Open { index: usize },
+    Refresh,
+    Close,
+}
+
+/// Results of a saved feed or an ad-hoc `:arxiv` search: submissions on the
+/// left, the selected one's abstract on the right.
+#[derive(Debug, Clone, Default)]
+pub struct ArxivFeedPanel {
+    pub title: String,
+    /// The saved feed shown, `None` for an ad-hoc search.
+    pub feed: Option<String>,
+    pub entries: Vec<ArxivFeedEntry>,
+    pub cursor: usize,
+    pub loading: bool,
+}
+
+impl ArxivFeedPanel {
+    /// An empty panel waiting for results.
+    pub fn loading(title: impl Into<String>, feed: Option<String>) -> Self {
+        Self {
+            title: title.into(),
+            feed,
+            entries: Vec::new(),
+            cursor: 0,
+            loading: true,
+        }
+    }
+
+    pub fn set_entries(&mut self, entries: Vec<ArxivFeedEntry>) {
+        self.entries = entries;
+        self.cursor = self.cursor.min(self.entries.len().saturating_sub(1));
+        self.loading = false;
+    }
+
+    pub fn selected(&self) -> Option<&ArxivFeedEntry> {
+        self.entries.get(self.cursor)
+    }
+
+    /// Flag the entry with this arXiv id as added.
+    pub fn mark_in_library(&mut self, arxiv_id: &str) {
+        for entry in &mut self.entries {
+            if entry.metadata.arxiv_id.id == arxiv_id {
+                entry.in_library = true;
+            }
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<ArxivFeedPanelAction> {
+        match key.code {
+            KeyCode::Down | KeyCode::Char('j') if key.modifiers == KeyModifiers::NONE => {
+                if self.cursor + 1 < self.entries.len() {
+                    self.cursor += 1;
+                }
+                None
+            }
+            KeyCode::Up | KeyCode::Char('k') if key.modifiers == KeyModifiers::NONE => {
+                self.cursor = self.cursor.saturating_sub(1);
+                None
+            }
+            KeyCode::Char('g') if key.modifiers == KeyModifiers::NONE => {
+                self.cursor = 0;
+                None
+            }
+            KeyCode::Char('G') => {
+                self.cursor = self.entries.len().saturating_sub(1);
+                None
+            }
+            KeyCode::Enter | KeyCode::Char('a') if key.modifiers == KeyModifiers::NONE => {
+                if self.selected()?.in_library {
+                    return None;
+                }
+                Some(ArxivFeedPanelAction::Add { index: self.cursor })
+            }
+            KeyCode::Char('o') if key.modifiers == KeyModifiers::NONE => {
+                self.selected()?;
+                Some(ArxivFeedPanelAction::Open { index: self.cursor })
+            }
+            KeyCode::Char('r') if key.modifiers == KeyModifiers::NONE => {
+                if self.loading {
+                    return None;
+                }
+                Some(ArxivFeedPanelAction::Refresh)
+            }
+            KeyCode::Esc | KeyCode::Char('q') => Some(ArxivFeedPanelAction::Close),
+            _ => None,
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &NordTheme) {
+        if area.is_empty() {
+            return;
+        }
+
+        let new_count = self.entries.iter().filter(|entry| entry.is_new).count();
+        let title = if new_count > 0 {
+            format!(" 󰑫 {} ({} new) ", self.title, new_count)
+        } else {
+            format!(" 󰑫 {} ({}) ", self.title, self.entries.len())
+        };
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.active_panel()))
+            .style(Style::default().bg(theme.bg()));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if inner.height < 6 || inner.width < 40 {
+            return;
+        }
+
+        let sections = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(1)])
+            .split(inner);
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+            .split(sections[0]);
+
+        let list_block = Block::default()
+            .borders(Borders::RIGHT)
+            .border_style(Style::default().fg(theme.border()));
+        let list_inner = list_block.inner(columns[0]);
+        frame.render_widget(list_block, columns[0]);
+        frame.render_widget(
+            Paragraph::new(self.list_lines(
+                usize::from(list_inner.width),
+                usize::from(list_inner.height),
+                theme,
+            )),
+            list_inner,
+        );
+
+        let detail_area = Rect {
+            x: columns[1].x + 1,
+            width: columns[1].width.saturating_sub(1),
+            ..columns[1]
+        };
+        frame.render_widget(
+            Paragraph::new(self.detail_lines(theme)).wrap(Wrap { trim: false }),
+            detail_area,
+        );
+
+        frame.render_widget(footer_hint(theme), sections[1]);
+    }
+
+    fn list_lines(
+        &self,
+        max_width: usize,
+        max_height: usize,
+        theme: &NordTheme,
+    ) -> Vec<Line<'static>> {
+        if self.entries.is_empty() {
+            let message = if self.loading {
+                "Fetching from arXiv…"
+            } else {
+                "No matching submissions."
+            };
+            return vec![Line::from(Span::styled(
+                message,
+                Style::default().fg(theme.muted()),
+            ))];
+        }
+
+        // Each entry takes two lines; scroll so the cursor stays in view.
+        let visible = (max_height / 2).max(1);
+        let first = self.cursor.saturating_sub(visible - 1);
+        self.entries
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(visible)
+            .flat_map(|(idx, entry)| {
+                let selected = idx == self.cursor;
+                let marker = if selected { "▸ " } else { "  " };
+                let badge = if entry.in_library {
+                    "✓ "
+                } else if entry.is_new {
+                    "● "
+                } else {
+                    "  "
+                };
+                let title_style = if selected {
+                    Style::default()
+                        .fg(theme.frost_ice())
+                        .add_modifier(Modifier::BOLD)
+                } else if entry.in_library {
+                    Style::default().fg(theme.muted())
+                } else {
+                    Style::default().fg(theme.fg())
+                };
+                let badge_style = if entry.in_library {
+                    Style::default().fg(theme.green())
+                } else {
+                    Style::default().fg(theme.yellow())
+                };
+                let metadata = &entry.metadata;
+                let text_width = max_width.saturating_sub(4);
+                [
+                    Line::from(vec![
+                        Span::raw(marker),
+                        Span::styled(badge, badge_style),
+                        Span::styled(truncate_text(&metadata.title, text_width), title_style),
+                    ]),
+                    Line::from(Span::styled(
+                        truncate_text(
+                            &format!(
+                                "    {}  {}  {}",
+                                metadata.published.format("%Y-%m-%d"),
+                                metadata.arxiv_id.id,
+                                metadata.primary_category
+                            ),
+                            max_width,
+                        ),
+                        Style::default().fg(theme.muted()),
+                    )),
+                ]
+            })
+            .collect()
+    }
+
+    fn detail_lines(&self, theme: &NordTheme) -> Vec<Line<'static>> {
+        let Some(entry) = self.selected() else {
+            return Vec::new();
+        };
+        let metadata = &entry.metadata;
+        let label = Style::default()
+            .fg(theme.yellow())
+            .add_modifier(Modifier::BOLD);
+        let muted = Style::default().fg(theme.muted());
+
+        let authors: Vec<&str> = metadata
+            .authors
+            .iter()
+            .map(|author| author.name.as_str())
+            .collect();
+        let mut lines = vec![
+            Line::from(Span::styled(
+                metadata.title.clone(),
+                Style::default()
+                    .fg(theme.fg_bright())
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from(Span::styled(
+                authors.join(", "),
+                Style::default().fg(theme.frost_mint()),
+            )),
+            Line::from(Span::styled(
+                format!(
+                    "arXiv:{}  ·  {}  ·  {}",
+                    metadata.arxiv_id.id,
+                    metadata.published.format("%Y-%m-%d"),
+                    metadata.categories.join(" ")
+                ),
+                muted,
+            )),
+        ];
+        if let Some(comment) = &metadata.comment {
+            lines.push(Line::from(Span::styled(comment.clone(), muted)));
+        }
+        if entry.in_library {
+            lines.push(Line::from(Span::styled(
+                "In your library.",
+                Style::default().fg(theme.green()),
+            )));
+        }
+        lines.push(Line::default());
+        lines.push(Line::from(Span::styled("Abstract", label)));
+        lines.push(Line::from(metadata.abstract_text.clone()));
+        lines
+    }
+}
+
+fn truncate_text(text: &str, max_width: usize) -> String {
+    if max_width == 0 {
+        return String::new();
+    }
+    if text.chars().count() <= max_width {
+        return text.to_string();
+    }
+    let truncated: String = text.chars().take(max_width.saturating_sub(1)).collect();
+    format!("{truncated}…")
+}
+
+fn footer_hint(theme: &NordTheme) -> Paragraph<'static> {
+    let key = Style::default()
+        .fg(theme.yellow())
+        .add_modifier(Modifier::BOLD);
+    let text = Style::default()
+        .fg(theme.muted())
+        .add_modifier(Modifier::DIM);
+    Paragraph::new(Line::from(vec![
+        Span::styled("[Enter]", key),
+        Span::styled(" add to library  ", text),
+        Span::styled("[o]", key),
+        Span::styled(" open abstract page  ", text),
+        Span::styled("[r]", key),
+        Span::styled(" refresh  ", text),
+        Span::styled("[Esc]", key),
+        Span::styled(" close", text),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use omniscope_science::identifiers::arxiv::ArxivId;
+
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn entry(id: &str, in_library: bool) -> ArxivFeedEntry {
+        let arxiv_id = ArxivId::parse(id).unwrap();
+        let now = chrono::Utc::now();
+        ArxivFeedEntry {
+            metadata: ArxivMetadata {
+                arxiv_id: arxiv_id.clone(),
+                doi: None,
+                title: format!("Paper {id}"),
+                authors: Vec::new(),
+                abstract_text: String::new(),
+                published: now,
+                updated: now,
+                categories: vec!["cs.LG".to_string()],
+                primary_category: "cs.LG".to_string(),
+                comment: None,
+                journal_ref: None,
+                pdf_url: arxiv_id.pdf_url.clone(),
+                abs_url: arxiv_id.abs_url,
+            },
+            is_new: true,
+            in_library,
+        }
+    }
+
+    #[test]
+    fn papers_already_in_the_library_cannot_be_added_again() {
+        let mut panel = ArxivFeedPanel::loading("ml", Some("ml".to_string()));
+        assert_eq!(panel.handle_key(key(KeyCode::Char('r'))), None);
+
+        panel.set_entries(vec![entry("2603.00001", false), entry("2603.00002", true)]);
+        assert_eq!(
+            panel.handle_key(key(KeyCode::Enter)),
+            Some(ArxivFeedPanelAction::Add { index: 0 })
+        );
+        panel.handle_key(key(KeyCode::Char('j')));
+        assert_eq!(panel.handle_key(key(KeyCode::Enter)), None);
+        assert_eq!(
+            panel.handle_key(key(KeyCode::Char('o'))),
+            Some(ArxivFeedPanelAction::Open { index: 1 })
+        );
+    }
+
+    #[test]
+    fn mark_in_library_flags_matching_entry() {
+        let mut panel = ArxivFeedPanel::loading("search", None);
+        panel.set_entries(vec![entry("2603.00001", false), entry("2603.00002", false)]);
+        panel.mark_in_library("2603.00002");
+
+        assert!(!panel.entries[0].in_library);
+        assert!(panel.entries[1].in_library);
+        assert_eq!(
+            panel.handle_key(key(KeyCode::Char('r'))),
+            Some(ArxivFeedPanelAction::Refresh)
+        );
+    }
+}
@@ -0,0 +1,363 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use omniscope_science::arxiv::{ArxivUpdateDiff, ArxivUpdateResult};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+
+use crate::theme::NordTheme;
+
+/// A pending arXiv update and what it would change on the card.
+#[derive(Debug, Clone)]
+pub struct ArxivUpdateItem {
+    pub update: ArxivUpdateResult,
+    /// `None` when the card could not be loaded.
+    pub diff: Option<ArxivUpdateDiff>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArxivUpdatesPanelAction {
+    Accept { index: usize, download_pdf: bool },
+    Dismiss { index: usize },
+    Close,
+}
+
+/// Inbox of papers with a newer arXiv version: list on the left, the
+/// selected paper's title/author/abstract diff on the right.
+#[derive(Debug, Clone, Default)]
+pub struct ArxivUpdatesPanel {
+    pub items: Vec<ArxivUpdateItem>,
+    pub cursor: usize,
+    /// Also fetch the new PDF when accepting.
+    pub download_pdf: bool,
+}
+
+impl ArxivUpdatesPanel {
+    pub fn new(items: Vec<ArxivUpdateItem>) -> Self {
+        Self {
+            items,
+            cursor: 0,
+            download_pdf: false,
+        }
+    }
+
+    pub fn selected(&self) -> Option<&ArxivUpdateItem> {
+        self.items.get(self.cursor)
+    }
+
+    /// Drops an accepted or dismissed item.
+    pub fn remove(&mut self, index: usize) {
+        if index >= self.items.len() {
+            return;
+        }
+        self.items.remove(index);
+        if self.cursor >= self.items.len() {
+            self.cursor = self.items.len().saturating_sub(1);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<ArxivUpdatesPanelAction> {
+        match key.code {
+            KeyCode::Down | KeyCode::Char('j') if key.modifiers == KeyModifiers::NONE => {
+                if self.cursor + 1 < self.items.len() {
+                    self.cursor += 1;
+                }
+                None
+            }
+            KeyCode::Up | KeyCode::Char('k') if key.modifiers == KeyModifiers::NONE => {
+                self.cursor = self.cursor.saturating_sub(1);
+                None
+            }
+            KeyCode::Char('d') if key.modifiers == KeyModifiers::NONE => {
+                self.download_pdf = !self.download_pdf;
+                None
+            }
+            KeyCode::Enter | KeyCode::Char('a') if key.modifiers == KeyModifiers::NONE => {
+                self.selected()?;
+                Some(ArxivUpdatesPanelAction::Accept {
+                    index: self.cursor,
+                    download_pdf: self.download_pdf,
+                })
+            }
+            KeyCode::Char('x') if key.modifiers == KeyModifiers::NONE => {
+                self.selected()?;
+                Some(ArxivUpdatesPanelAction::Dismiss { index: self.cursor })
+            }
+            KeyCode::Esc | KeyCode::Char('q') => Some(ArxivUpdatesPanelAction::Close),
+            _ => None,
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &NordTheme) {
+        if area.is_empty() {
+            return;
+        }
+
+        let block = Block::default()
+            .title(format!(" ⟳ ARXIV UPDATES ({}) ", self.items.len()))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.active_panel()))
+            .style(Style::default().bg(theme.bg()));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if inner.height < 6 || inner.width < 40 {
+            return;
+        }
+
+        let sections = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(1)])
+            .split(inner);
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(sections[0]);
+
+        let list_block = Block::default()
+            .borders(Borders::RIGHT)
+            .border_style(Style::default().fg(theme.border()));
+        let list_inner = list_block.inner(columns[0]);
+        frame.render_widget(list_block, columns[0]);
+        frame.render_widget(
+            Paragraph::new(self.list_lines(
+                usize::from(list_inner.width),
+                usize::from(list_inner.height),
+                theme,
+            )),
+            list_inner,
+        );
+
+        let diff_area = Rect {
+            x: columns[1].x + 1,
+            width: columns[1].width.saturating_sub(1),
+            ..columns[1]
+        };
+        frame.render_widget(
+            Paragraph::new(self.diff_lines(theme)).wrap(Wrap { trim: false }),
+            diff_area,
+        );
+
+        frame.render_widget(footer_hint(self.download_pdf, theme), sections[1]);
+    }
+
+    fn list_lines(
+        &self,
+        max_width: usize,
+        max_height: usize,
+        theme: &NordTheme,
+    ) -> Vec<Line<'static>> {
+        if self.items.is_empty() {
+            return vec![Line::from(Span::styled(
+                "No pending updates.",
+                Style::default().fg(theme.muted()),
+            ))];
+        }
+
+        // Each entry takes two lines; scroll so the cursor stays in view.
+        let visible = (max_height / 2).max(1);
+        let first = self.cursor.saturating_sub(visible - 1);
+        self.items
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(visible)
+            .flat_map(|(idx, item)| {
+                let selected = idx == self.cursor;
+                let marker = if selected { "▸ " } else { "  " };
+                let title_style = if selected {
+                    Style::default()
+                        .fg(theme.frost_ice())
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.fg())
+                };
+                let update = &item.update;
+                [
+                    Line::from(Span::styled(
+                        truncate_text(&format!("{marker}{}", update.new_metadata.title), max_width),
+                        title_style,
+                    )),
+                    Line::from(Span::styled(
+                        truncate_text(
+                            &format!(
+                                "    arXiv:{} {}",
+                                update.arxiv_id.id,
+                                update.version_label()
+                            ),
+                            max_width,
+                        ),
+                        Style::default().fg(theme.muted()),
+                    )),
+                ]
+            })
+            .collect()
+    }
+
+    fn diff_lines(&self, theme: &NordTheme) -> Vec<Line<'static>> {
+        let Some(item) = self.selected() else {
+            return Vec::new();
+        };
+        let label = Style::default()
+            .fg(theme.yellow())
+            .add_modifier(Modifier::BOLD);
+        let old = Style::default()
+            .fg(theme.red())
+            .add_modifier(Modifier::CROSSED_OUT);
+        let new = Style::default().fg(theme.green());
+
+        let Some(diff) = &item.diff else {
+            return vec![Line::from(Span::styled(
+                "Card could not be loaded for comparison.",
+                Style::default().fg(theme.muted()),
+            ))];
+        };
+        if diff.is_empty() {
+            return vec![Line::from(Span::styled(
+                "Only the version number changed.",
+                Style::default().fg(theme.muted()),
+            ))];
+        }
+
+        let mut lines = Vec::new();
+        let mut field = |name: &'static str, before: String, after: String| {
+            if !lines.is_empty() {
+                lines.push(Line::default());
+            }
+            lines.push(Line::from(Span::styled(name, label)));
+            lines.push(Line::from(vec![
+                Span::styled("- ", old),
+                Span::styled(before, old),
+            ]));
+            lines.push(Line::from(vec![
+                Span::styled("+ ", new),
+                Span::styled(after, new),
+            ]));
+        };
+        if let Some((before, after)) = &diff.title {
+            field("Title", before.clone(), after.clone());
+        }
+        if let Some((before, after)) = &diff.authors {
+            field("Authors", before.join(", "), after.join(", "));
+        }
+        if let Some((before, after)) = &diff.abstract_text {
+            field(
+                "Abstract",
+                before.clone().unwrap_or_else(|| "—".to_string()),
+                after.clone(),
+            );
+        }
+        lines
+    }
+}
+
+fn truncate_text(text: &str, max_width: usize) -> String {
+    if max_width == 0 {
+        return String::new();
+    }
+    if text.chars().count() <= max_width {
+        return text.to_string();
+    }
+    let truncated: String = text.chars().take(max_width.saturating_sub(1)).collect();
+    format!("{truncated}…")
+}
+
+fn footer_hint(download_pdf: bool, theme: &NordTheme) -> Paragraph<'static> {
+    let key = Style::default()
+        .fg(theme.yellow())
+        .add_modifier(Modifier::BOLD);
+    let text = Style::default()
+        .fg(theme.muted())
+        .add_modifier(Modifier::DIM);
+    let pdf = if download_pdf { "on" } else { "off" };
+    Paragraph::new(Line::from(vec![
+        Span::styled("[Enter]", key),
+        Span::styled(" accept  ", text),
+        Span::styled("[d]", key),
+        Span::styled(format!(" download PDF: {pdf}  "), text),
+        Span::styled("[x]", key),
+        Span::styled(" dismiss  ", text),
+        Span::styled("[Esc]", key),
+        Span::styled(" close", text),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use omniscope_science::arxiv::types::{ArxivAuthor, ArxivMetadata};
+    use omniscope_science::identifiers::arxiv::ArxivId;
+
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn item(id: &str, version: u8) -> ArxivUpdateItem {
+        let arxiv_id = ArxivId::parse(&format!("{id}v{version}")).unwrap();
+        let now = chrono::Utc::now();
+        ArxivUpdateItem {
+            update: ArxivUpdateResult {
+                book_id: uuid::Uuid::now_v7(),
+                arxiv_id: ArxivId::parse(id).unwrap(),
+                old_version: Some(1),
+                new_version: version,
+                new_metadata: ArxivMetadata {
+                    arxiv_id: arxiv_id.clone(),
+                    doi: None,
+                    title: "Updated".to_string(),
+                    authors: vec![ArxivAuthor {
+                        name: "Author".to_string(),
+                        affiliation: None,
+                    }],
+                    abstract_text: "Abstract".to_string(),
+                    published: now,
+                    updated: now,
+                    categories: Vec::new(),
+                    primary_category: "cs.CL".to_string(),
+                    comment: None,
+                    journal_ref: None,
+                    pdf_url: format!("https://arxiv.org/pdf/{id}v{version}"),
+                    abs_url: arxiv_id.abs_url,
+                },
+            },
+            diff: Some(ArxivUpdateDiff::default()),
+        }
+    }
+
+    #[test]
+    fn accept_carries_download_toggle() {
+        let mut panel = ArxivUpdatesPanel::new(vec![item("1706.03762", 7), item("2301.04567", 3)]);
+        panel.handle_key(key(KeyCode::Char('j')));
+        panel.handle_key(key(KeyCode::Char('d')));
+
+        assert_eq!(
+            panel.handle_key(key(KeyCode::Enter)),
+            Some(ArxivUpdatesPanelAction::Accept {
+                index: 1,
+                download_pdf: true
+            })
+        );
+    }
+
+    #[test]
+    fn removing_last_item_moves_cursor_back() {
+        let mut panel = ArxivUpdatesPanel::new(vec![item("1706.03762", 7), item("2301.04567", 3)]);
+        panel.handle_key(key(KeyCode::Char('j')));
+        assert_eq!(
+            panel.handle_key(key(KeyCode::Char('x'))),
+            Some(ArxivUpdatesPanelAction::Dismiss { index: 1 })
+        );
+
+        panel.remove(1);
+        assert_eq!(panel.cursor, 0);
+        panel.remove(0);
+        assert!(panel.is_empty());
+        assert_eq!(panel.handle_key(key(KeyCode::Enter)), None);
+    }
+}
@@ -0,0 +1,395 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use omniscope_core::models::BookCard;
+use omniscope_science::dedup::DedupStrategy;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+use crate::theme::NordTheme;
+
+/// Cards that look like the same book, with the one to keep marked.
+#[derive(Debug, Clone)]
+pub struct DuplicateGroupView {
+    pub strategy: DedupStrategy,
+    pub cards: Vec<BookCard>,
+    pub canonical: usize,
+}
+
+impl DuplicateGroupView {
+    /// Builds a group with `cards[0]` as the suggested canonical card.
+    pub fn new(strategy: DedupStrategy, cards: Vec<BookCard>) -> Self {
+        Self {
+            strategy,
+            cards,
+            canonical: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicatesPanelAction {
+    Merge { group: usize },
+    Close,
+}
+
+/// Side-by-side review of duplicate candidates, one group at a time.
+#[derive(Debug, Clone, Default)]
+pub struct DuplicatesPanel {
+    pub groups: Vec<DuplicateGroupView>,
+    pub group_cursor: usize,
+    pub card_cursor: usize,
+}
+
+impl DuplicatesPanel {
+    pub fn new(groups: Vec<DuplicateGroupView>) -> Self {
+        Self {
+            groups,
+            group_cursor: 0,
+            card_cursor: 0,
+        }
+    }
+
+    pub fn current(&self) -> Option<&DuplicateGroupView> {
+        self.groups.get(self.group_cursor)
+    }
+
+    /// Drops a group once it has been merged or skipped.
+    pub fn remove_group(&mut self, group: usize) {
+        if group >= self.groups.len() {
+            return;
+        }
+        self.groups.remove(group);
+        if self.group_cursor >= self.groups.len() {
+            self.group_cursor = self.groups.len().saturating_sub(1);
+        }
+        self.card_cursor = 0;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    fn move_group(&mut self, forward: bool) {
+        if self.groups.is_empty() {
+            return;
+        }
+        self.group_cursor = if forward {
+            (self.group_cursor + 1).min(self.groups.len() - 1)
+        } else {
+            self.group_cursor.saturating_sub(1)
+        };
+        self.card_cursor = 0;
+    }
+
+    fn move_card(&mut self, forward: bool) {
+        let len = self.current().map_or(0, |group| group.cards.len());
+        if len == 0 {
+            self.card_cursor = 0;
+            return;
+        }
+        self.card_cursor = if forward {
+            (self.card_cursor + 1).min(len - 1)
+        } else {
+            self.card_cursor.saturating_sub(1)
+        };
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<DuplicatesPanelAction> {
+        match key.code {
+            KeyCode::Left | KeyCode::Char('h') if key.modifiers == KeyModifiers::NONE => {
+                self.move_card(false);
+                None
+            }
+            KeyCode::Right | KeyCode::Char('l') if key.modifiers == KeyModifiers::NONE => {
+                self.move_card(true);
+                None
+            }
+            KeyCode::Down | KeyCode::Char('j') if key.modifiers == KeyModifiers::NONE => {
+                self.move_group(true);
+                None
+            }
+            KeyCode::Up | KeyCode::Char('k') if key.modifiers == KeyModifiers::NONE => {
+                self.move_group(false);
+                None
+            }
+            KeyCode::Char(' ') | KeyCode::Char('c') if key.modifiers == KeyModifiers::NONE => {
+                let cursor = self.card_cursor;
+                if let Some(group) = self.groups.get_mut(self.group_cursor)
+                    && cursor < group.cards.len()
+                {
+                    group.canonical = cursor;
+                }
+                None
+            }
+            KeyCode::Char('s') if key.modifiers == KeyModifiers::NONE => {
+                self.remove_group(self.group_cursor);
+                if self.groups.is_empty() {
+                    Some(DuplicatesPanelAction::Close)
+                } else {
+                    None
+                }
+            }
+            KeyCode::Enter | KeyCode::Char('m') if key.modifiers == KeyModifiers::NONE => {
+                self.current()?;
+                Some(DuplicatesPanelAction::Merge {
+                    group: self.group_cursor,
+                })
+            }
+            KeyCode::Esc | KeyCode::Char('q') => Some(DuplicatesPanelAction::Close),
+            _ => None,
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &NordTheme) {
+        if area.is_empty() {
+            return;
+        }
+
+        let block = Block::default()
+            .title(format!(" ⧉ DUPLICATES ({}) ", self.groups.len()))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.active_panel()))
+            .style(Style::default().bg(theme.bg()));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if inner.height < 6 || inner.width < 30 {
+            return;
+        }
+
+        let sections = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Min(3),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        let Some(group) = self.current() else {
+            frame.render_widget(
+                Paragraph::new(Line::from(Span::styled(
+                    "No duplicates left.",
+                    Style::default().fg(theme.muted()),
+                ))),
+                sections[1],
+            );
+            frame.render_widget(footer_hint(theme), sections[2]);
+            return;
+        };
+
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled(
+                    format!("Group {}/{}", self.group_cursor + 1, self.groups.len()),
+                    Style::default()
+                        .fg(theme.frost_ice())
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("  matched by {}", group.strategy.as_str()),
+                    Style::default().fg(theme.muted()),
+                ),
+            ])),
+            sections[0],
+        );
+
+        let count = group.cards.len().max(1) as u32;
+        let constraints: Vec<Constraint> =
+            (0..count).map(|_| Constraint::Ratio(1, count)).collect();
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(constraints)
+            .split(sections[1]);
+
+        for (idx, card) in group.cards.iter().enumerate() {
+            let selected = idx == self.card_cursor;
+            let keep = idx == group.canonical;
+            let title = if keep { " ★ keep " } else { " merge " };
+            let border = if selected {
+                theme.active_panel()
+            } else {
+                theme.border()
+            };
+            let column = Block::default()
+                .title(Span::styled(
+                    title,
+                    Style::default().fg(if keep { theme.yellow() } else { theme.muted() }),
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(border))
+                .style(Style::default().bg(if selected {
+                    theme.bg_secondary()
+                } else {
+                    theme.bg()
+                }));
+            let column_inner = column.inner(columns[idx]);
+            frame.render_widget(column, columns[idx]);
+            let lines = card_lines(card, usize::from(column_inner.width), theme);
+            frame.render_widget(Paragraph::new(lines), column_inner);
+        }
+
+        frame.render_widget(footer_hint(theme), sections[2]);
+    }
+}
+
+fn card_lines(card: &BookCard, max_width: usize, theme: &NordTheme) -> Vec<Line<'static>> {
+    let dash = || "—".to_string();
+    let join = |values: &[String]| {
+        if values.is_empty() {
+            dash()
+        } else {
+            values.join(", ")
+        }
+    };
+    let doi = card
+        .identifiers
+        .as_ref()
+        .and_then(|ids| ids.doi.clone())
+        .unwrap_or_else(dash);
+    let fields = [
+        ("Authors", join(&card.metadata.authors)),
+        (
+            "Year",
+            card.metadata
+                .year
+                .map_or_else(dash, |year| year.to_string()),
+        ),
+        ("DOI", doi),
+        ("ISBN", join(&card.metadata.isbn)),
+        (
+            "Publisher",
+            card.metadata.publisher.clone().unwrap_or_else(dash),
+        ),
+        (
+            "File",
+            card.file
+                .as_ref()
+                .map_or_else(dash, |file| file.path.clone()),
+        ),
+        ("Tags", join(&card.organization.tags)),
+        ("Folders", join(&card.organization.folders)),
+        ("Notes", card.notes.len().to_string()),
+    ];
+
+    let mut lines = vec![
+        styled_line(
+            card.metadata.title.clone(),
+            Style::default().fg(theme.fg()).add_modifier(Modifier::BOLD),
+            max_width,
+        ),
+        styled_line(
+            card.id.to_string(),
+            Style::default().fg(theme.muted()),
+            max_width,
+        ),
+        Line::default(),
+    ];
+    for (label, value) in fields {
+        let label = format!("{label:<10}");
+        let room = max_width.saturating_sub(label.chars().count());
+        lines.push(Line::from(vec![
+            Span::styled(label, Style::default().fg(theme.muted())),
+            Span::styled(truncate_text(&value, room), Style::default().fg(theme.fg())),
+        ]));
+    }
+    lines
+}
+
+fn styled_line(text: String, style: Style, max_width: usize) -> Line<'static> {
+    Line::from(Span::styled(truncate_text(&text, max_width), style))
+}
+
+fn truncate_text(text: &str, max_width: usize) -> String {
+    if max_width == 0 {
+        return String::new();
+    }
+    if text.chars().count() <= max_width {
+        return text.to_string();
+    }
+    let truncated: String = text.chars().take(max_width.saturating_sub(1)).collect();
+    format!("{truncated}…")
+}
+
+fn footer_hint(theme: &NordTheme) -> Paragraph<'static> {
+    let key = Style::default()
+        .fg(theme.yellow())
+        .add_modifier(Modifier::BOLD);
+    let text = Style::default()
+        .fg(theme.muted())
+        .add_modifier(Modifier::DIM);
+    Paragraph::new(Line::from(vec![
+        Span::styled("[h/l]", key),
+        Span::styled(" card  ", text),
+        Span::styled("[j/k]", key),
+        Span::styled(" group  ", text),
+        Span::styled("[Space]", key),
+        Span::styled(" keep this  ", text),
+        Span::styled("[Enter]", key),
+        Span::styled(" merge  ", text),
+        Span::styled("[s]", key),
+        Span::styled(" skip  ", text),
+        Span::styled("[Esc]", key),
+        Span::styled(" close", text),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn sample_panel() -> DuplicatesPanel {
+        DuplicatesPanel::new(vec![
+            DuplicateGroupView::new(
+                DedupStrategy::Doi,
+                vec![
+                    BookCard::new("Attention"),
+                    BookCard::new("Attention (copy)"),
+                ],
+            ),
+            DuplicateGroupView::new(
+                DedupStrategy::TitleFuzzy,
+                vec![BookCard::new("Rust"), BookCard::new("rust")],
+            ),
+        ])
+    }
+
+    #[test]
+    fn space_marks_selected_card_as_canonical() {
+        let mut panel = sample_panel();
+        assert_eq!(panel.groups[0].canonical, 0);
+
+        panel.handle_key(key(KeyCode::Char('l')));
+        panel.handle_key(key(KeyCode::Char(' ')));
+        assert_eq!(panel.groups[0].canonical, 1);
+
+        // Moving past the last card stays on it.
+        panel.handle_key(key(KeyCode::Char('l')));
+        assert_eq!(panel.card_cursor, 1);
+    }
+
+    #[test]
+    fn enter_merges_current_group_and_skip_drops_it() {
+        let mut panel = sample_panel();
+        panel.handle_key(key(KeyCode::Char('j')));
+        assert_eq!(
+            panel.handle_key(key(KeyCode::Enter)),
+            Some(DuplicatesPanelAction::Merge { group: 1 })
+        );
+
+        assert_eq!(panel.handle_key(key(KeyCode::Char('s'))), None);
+        assert_eq!(panel.groups.len(), 1);
+        assert_eq!(panel.group_cursor, 0);
+        assert_eq!(
+            panel.handle_key(key(KeyCode::Char('s'))),
+            Some(DuplicatesPanelAction::Close)
+        );
+        assert_eq!(panel.handle_key(key(KeyCode::Enter)), None);
+    }
+}
@@ -0,0 +1,439 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use omniscope_core::models::BookCard;
+use omniscope_science::enrichment::merge::field_source;
+use omniscope_science::enrichment::review::field_value;
+use omniscope_science::enrichment::{FieldCandidate, FieldReview};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+
+use crate::theme::NordTheme;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeReviewPanelAction {
+    Apply,
+    Cancel,
+}
+
+/// Fields where enrichment disagreed with the card: field list on the
+/// left, the current value and every candidate on the right.
+#[derive(Debug, Clone, Default)]
+pub struct MergeReviewPanel {
+    pub book_title: String,
+    pub fields: Vec<FieldReview>,
+    /// Per field: 0 keeps the current value, `n` takes `candidates[n - 1]`.
+    pub choices: Vec<usize>,
+    /// Per field: whether the user picked a value or confirmed the one
+    /// offered. Only these are locked on apply.
+    pub picked: Vec<bool>,
+    pub cursor: usize,
+}
+
+impl MergeReviewPanel {
+    /// Each field starts on the value the automatic merge picked in `merged`.
+    /// A merged value no single source offered (authors gathered from
+    /// several, say) is added as the first candidate.
+    pub fn new(book_title: String, mut fields: Vec<FieldReview>, merged: &BookCard) -> Self {
+        let choices = fields
+            .iter_mut()
+            .map(|review| {
+                let Some(merged_value) = field_value(merged, review.field) else {
+                    return 0;
+                };
+                if merged_value == review.current {
+                    return 0;
+                }
+                match review
+                    .candidates
+                    .iter()
+                    .position(|candidate| candidate.value == merged_value)
+                {
+                    Some(index) => index + 1,
+                    None => {
+                        review.candidates.insert(
+                            0,
+                            FieldCandidate {
+                                field: review.field,
+                                value: merged_value,
+                                source: field_source(merged, review.field),
+                            },
+                        );
+                        1
+                    }
+                }
+            })
+            .collect();
+        Self {
+            book_title,
+            picked: vec![false; fields.len()],
+            fields,
+            choices,
+            cursor: 0,
+        }
+    }
+
+    /// `(field, value)` for every field, as currently chosen.
+    pub fn selections(&self) -> Vec<(&'static str, String)> {
+        self.fields
+            .iter()
+            .zip(&self.choices)
+            .map(|(review, &choice)| (review.field, chosen_value(review, choice).to_string()))
+            .collect()
+    }
+
+    /// Like [`Self::selections`], but only the fields the user picked or
+    /// confirmed.
+    pub fn picked_selections(&self) -> Vec<(&'static str, String)> {
+        self.selections()
+            .into_iter()
+            .zip(&self.picked)
+            .filter(|(_, picked)| **picked)
+            .map(|(selection, _)| selection)
+            .collect()
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<MergeReviewPanelAction> {
+        if key.modifiers != KeyModifiers::NONE && key.modifiers != KeyModifiers::SHIFT {
+            return None;
+        }
+        match key.code {
+            KeyCode::Down | KeyCode::Char('j') => {
+                if self.cursor + 1 < self.fields.len() {
+                    self.cursor += 1;
+                }
+                None
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.cursor = self.cursor.saturating_sub(1);
+                None
+            }
+            KeyCode::Right | KeyCode::Char('l') | KeyCode::Tab => {
+                self.cycle_choice(1);
+                None
+            }
+            KeyCode::Left | KeyCode::Char('h') | KeyCode::BackTab => {
+                self.cycle_choice(-1);
+                None
+            }
+            KeyCode::Char(digit @ '0'..='9') => {
+                let choice = digit as usize - '0' as usize;
+                if let Some(review) = self.fields.get(self.cursor)
+                    && choice <= review.candidates.len()
+                {
+                    self.choices[self.cursor] = choice;
+                    self.picked[self.cursor] = true;
+                }
+                None
+            }
+            KeyCode::Char(' ') => {
+                if let Some(picked) = self.picked.get_mut(self.cursor) {
+                    *picked = !*picked;
+                }
+                None
+            }
+            KeyCode::Enter => Some(MergeReviewPanelAction::Apply),
+            KeyCode::Esc | KeyCode::Char('q') => Some(MergeReviewPanelAction::Cancel),
+            _ => None,
+        }
+    }
+
+    fn cycle_choice(&mut self, step: isize) {
+        let Some(review) = self.fields.get(self.cursor) else {
+            return;
+        };
+        let options = review.candidates.len() as isize + 1;
+        let current = self.choices[self.cursor] as isize;
+        self.choices[self.cursor] = (current + step).rem_euclid(options) as usize;
+        self.picked[self.cursor] = true;
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &NordTheme) {
+        if area.is_empty() {
+            return;
+        }
+
+        let block = Block::default()
+            .title(format!(
+                " ⇄ REVIEW ENRICHMENT: {} ({} field(s)) ",
+                self.book_title,
+                self.fields.len()
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.active_panel()))
+            .style(Style::default().bg(theme.bg()));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if inner.height < 6 || inner.width < 40 {
+            return;
+        }
+
+        let sections = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(1)])
+            .split(inner);
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+            .split(sections[0]);
+
+        let list_block = Block::default()
+            .borders(Borders::RIGHT)
+            .border_style(Style::default().fg(theme.border()));
+        let list_inner = list_block.inner(columns[0]);
+        frame.render_widget(list_block, columns[0]);
+        frame.render_widget(
+            Paragraph::new(self.list_lines(usize::from(list_inner.width), theme)),
+            list_inner,
+        );
+
+        let options_area = Rect {
+            x: columns[1].x + 1,
+            width: columns[1].width.saturating_sub(1),
+            ..columns[1]
+        };
+        frame.render_widget(
+            Paragraph::new(self.option_lines(theme)).wrap(Wrap { trim: false }),
+            options_area,
+        );
+
+        frame.render_widget(footer_hint(theme), sections[1]);
+    }
+
+    fn list_lines(&self, max_width: usize, theme: &NordTheme) -> Vec<Line<'static>> {
+        self.fields
+            .iter()
+            .zip(&self.choices)
+            .enumerate()
+            .map(|(idx, (review, &choice))| {
+                let selected = idx == self.cursor;
+                let marker = if selected { "▸ " } else { "  " };
+                let label_style = if selected {
+                    Style::default()
+                        .fg(theme.frost_ice())
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.fg())
+                };
+                let chosen = if choice == 0 {
+                    "keep".to_string()
+                } else {
+                    review.candidates[choice - 1].source.as_tag().to_string()
+                };
+                let chosen_color = if self.picked[idx] {
+                    theme.green()
+                } else {
+                    theme.muted()
+                };
+                let label = format!("{marker}{}", review.label);
+                let pad = max_width.saturating_sub(label.chars().count() + chosen.chars().count());
+                Line::from(vec![
+                    Span::styled(label, label_style),
+                    Span::raw(" ".repeat(pad)),
+                    Span::styled(chosen, Style::default().fg(chosen_color)),
+                ])
+            })
+            .collect()
+    }
+
+    fn option_lines(&self, theme: &NordTheme) -> Vec<Line<'static>> {
+        let Some(review) = self.fields.get(self.cursor) else {
+            return Vec::new();
+        };
+        let choice = self.choices[self.cursor];
+        let label = Style::default()
+            .fg(theme.yellow())
+            .add_modifier(Modifier::BOLD);
+        let source_style = Style::default().fg(theme.muted());
+
+        let current_source = review
+            .current_source
+            .map_or("unknown", |source| source.as_tag());
+        let mut options = vec![(format!("current, {current_source}"), review.current.clone())];
+        options.extend(review.candidates.iter().map(|candidate| {
+            (
+                candidate.source.as_tag().to_string(),
+                candidate.value.clone(),
+            )
+        }));
+
+        let mut lines = vec![
+            Line::from(Span::styled(review.label, label)),
+            Line::default(),
+        ];
+        for (index, (source, value)) in options.into_iter().enumerate() {
+            let selected = index == choice;
+            let (radio, value_style) = if selected {
+                ("(•)", Style::default().fg(theme.green()))
+            } else {
+                ("( )", Style::default().fg(theme.fg()))
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{radio} [{index}] "), value_style),
+                Span::styled(source, source_style),
+            ]));
+            lines.push(Line::from(Span::styled(
+                format!("    {value}"),
+                value_style,
+            )));
+            lines.push(Line::default());
+        }
+        lines
+    }
+}
+
+fn chosen_value(review: &FieldReview, choice: usize) -> &str {
+    match choice {
+        0 => &review.current,
+        n => &review.candidates[n - 1].value,
+    }
+}
+
+fn footer_hint(theme: &NordTheme) -> Paragraph<'static> {
+    let key = Style::default()
+        .fg(theme.yellow())
+        .add_modifier(Modifier::BOLD);
+    let text = Style::default()
+        .fg(theme.muted())
+        .add_modifier(Modifier::DIM);
+    Paragraph::new(Line::from(vec![
+        Span::styled("[h/l]", key),
+        Span::styled(" pick value  ", text),
+        Span::styled("[0-9]", key),
+        Span::styled(" pick by number  ", text),
+        Span::styled("[Space]", key),
+        Span::styled(" confirm  ", text),
+        Span::styled("[Enter]", key),
+        Span::styled(" apply, lock picked  ", text),
+        Span::styled("[Esc]", key),
+        Span::styled(" discard enrichment", text),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use omniscope_science::enrichment::MetadataSource;
+    use omniscope_science::enrichment::review::review_fields;
+
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn panel() -> MergeReviewPanel {
+        let mut before = BookCard::new("Typed title");
+        before.metadata.year = Some(2017);
+        let candidates = vec![
+            FieldCandidate {
+                field: "metadata.title",
+                value: "CrossRef Title".to_string(),
+                source: MetadataSource::CrossRef,
+            },
+            FieldCandidate {
+                field: "metadata.title",
+                value: "Semantic Scholar Title".to_string(),
+                source: MetadataSource::SemanticScholar,
+            },
+            FieldCandidate {
+                field: "metadata.year",
+                value: "2018".to_string(),
+                source: MetadataSource::OpenAlex,
+            },
+        ];
+        let mut merged = before.clone();
+        merged.metadata.title = "CrossRef Title".to_string();
+
+        MergeReviewPanel::new(
+            "Typed title".to_string(),
+            review_fields(&before, &candidates),
+            &merged,
+        )
+    }
+
+    #[test]
+    fn choices_start_on_the_merged_value() {
+        let panel = panel();
+        assert_eq!(
+            panel.selections(),
+            vec![
+                ("metadata.title", "CrossRef Title".to_string()),
+                ("metadata.year", "2017".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn keys_pick_values_per_field() {
+        let mut panel = panel();
+        panel.handle_key(key(KeyCode::Char('l')));
+        panel.handle_key(key(KeyCode::Char('j')));
+        panel.handle_key(key(KeyCode::Char('1')));
+        panel.handle_key(key(KeyCode::Char('7')));
+
+        assert_eq!(
+            panel.selections(),
+            vec![
+                ("metadata.title", "Semantic Scholar Title".to_string()),
+                ("metadata.year", "2018".to_string()),
+            ]
+        );
+
+        panel.handle_key(key(KeyCode::Char('k')));
+        panel.handle_key(key(KeyCode::Char('l')));
+        assert_eq!(panel.selections()[0].1, "Typed title");
+        assert_eq!(
+            panel.handle_key(key(KeyCode::Enter)),
+            Some(MergeReviewPanelAction::Apply)
+        );
+        assert_eq!(
+            panel.handle_key(key(KeyCode::Esc)),
+            Some(MergeReviewPanelAction::Cancel)
+        );
+    }
+
+    #[test]
+    fn only_picked_or_confirmed_fields_are_applied() {
+        let mut panel = panel();
+        assert!(panel.picked_selections().is_empty());
+
+        panel.handle_key(key(KeyCode::Char(' ')));
+        panel.handle_key(key(KeyCode::Char('j')));
+        assert_eq!(
+            panel.picked_selections(),
+            vec![("metadata.title", "CrossRef Title".to_string())]
+        );
+
+        panel.handle_key(key(KeyCode::Char('1')));
+        assert_eq!(panel.picked_selections().len(), 2);
+    }
+
+    #[test]
+    fn merged_value_no_source_offered_is_an_option() {
+        let mut before = BookCard::new("Paper");
+        before.metadata.authors = vec!["Ada Lovelace".to_string()];
+        let candidates = vec![FieldCandidate {
+            field: "metadata.authors",
+            value: "Charles Babbage".to_string(),
+            source: MetadataSource::CrossRef,
+        }];
+        let mut merged = before.clone();
+        merged.metadata.authors.push("Charles Babbage".to_string());
+
+        let panel = MergeReviewPanel::new(
+            "Paper".to_string(),
+            review_fields(&before, &candidates),
+            &merged,
+        );
+        assert_eq!(
+            panel.selections(),
+            vec![(
+                "metadata.authors",
+                "Ada Lovelace; Charles Babbage".to_string()
+            )]
+        );
+        assert_eq!(panel.fields[0].candidates.len(), 2);
+    }
+}
@@ -1,4 +1,8 @@
 pub mod article_card;
+pub mod arxiv_feed;
+pub mod arxiv_updates;
 pub mod citation_graph;
+pub mod duplicates;
 pub mod find_download;
+pub mod merge_review;
 pub mod references;
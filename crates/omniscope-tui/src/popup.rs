@@ -1,7 +1,11 @@
 use omniscope_core::{BookSummaryView, ReadStatus};
 
+use crate::panels::arxiv_feed::ArxivFeedPanel;
+use crate::panels::arxiv_updates::ArxivUpdatesPanel;
 use crate::panels::citation_graph::CitationGraphPanel;
+use crate::panels::duplicates::DuplicatesPanel;
 use crate::panels::find_download::FindDownloadPanel;
+use crate::panels::merge_review::MergeReviewPanel;
 use crate::panels::references::ReferencesPanel;
 
 /// Popup dialog types.
@@ -60,6 +64,14 @@ pub enum Popup {
     ScienceCitationGraph(CitationGraphPanel),
     /// Scientific find/download panel.
     ScienceFindDownload(FindDownloadPanel),
+    /// Duplicate groups, reviewed side by side before merging.
+    Duplicates(DuplicatesPanel),
+    /// Papers with a newer arXiv version, with a diff of what changed.
+    ArxivUpdates(ArxivUpdatesPanel),
+    /// Submissions of a saved arXiv feed or an `:arxiv` search.
+    ArxivFeed(ArxivFeedPanel),
+    /// Fields where enrichment disagreed with the card, held until reviewed.
+    MergeReview(MergeReviewPanel),
     /// Inline DOI edit popup.
     EditDoi {
         book_id: String,
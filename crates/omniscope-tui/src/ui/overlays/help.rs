@@ -89,7 +89,7 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
-                "  gr refs   gR cited-by   gs related   @m metadata   @e ai-meta   @r ai-refs",
+                "  gr refs   gR cited-by   gs related   @m metadata   @e ai-meta   @r ai-refs   @c ai-chat",
                 Style::default().fg(app.theme.fg()),
             ),
         ]),
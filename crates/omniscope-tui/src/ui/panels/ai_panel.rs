@@ -1,10 +1,12 @@
+use omniscope_ai::chat::describe_action;
+use omniscope_ai::provider::Role;
 use ratatui::Frame;
-use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratatui::widgets::{Block, Borders, Paragraph};
 
-use crate::app::App;
+use crate::app::{App, Mode};
 
 pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     if app.ai_panel_active {
@@ -40,92 +42,156 @@ fn render_ai_chat(frame: &mut Frame, app: &App, area: Rect) {
         ])
         .split(inner);
 
-    if let Some(book) = app.selected_book() {
-        // 1. Book title
-        let title_line = Line::from(vec![
-            Span::styled(" ─── ", Style::default().fg(app.theme.border())),
-            Span::styled(&book.title, Style::default().fg(app.theme.fg())),
-            Span::styled(" ─── ", Style::default().fg(app.theme.border())),
-        ]);
-        frame.render_widget(
-            Paragraph::new(title_line).alignment(ratatui::layout::Alignment::Center),
-            chunks[0],
-        );
-    }
-
-    // 2. Mock Assistant response (per Step 14 example)
-    let assistant_msg = vec![
-        Line::from(Span::styled(
-            " Assistant",
-            Style::default().fg(app.theme.muted()),
-        )),
-        Line::from(vec![
-            Span::styled(" ┌", Style::default().fg(app.theme.frost_blue())),
-            Span::styled(
-                "─".repeat(chunks[1].width as usize - 4),
-                Style::default().fg(app.theme.border()),
-            ),
-            Span::styled("┐", Style::default().fg(app.theme.frost_blue())),
-        ]),
-        Line::from(vec![
-            Span::styled(" │ ", Style::default().fg(app.theme.frost_blue())),
-            Span::styled(
-                "Это фундаментальная книга по Rust...",
-                Style::default().fg(app.theme.fg()),
-            ),
-        ]),
-        Line::from(vec![
-            Span::styled(" └", Style::default().fg(app.theme.frost_blue())),
-            Span::styled(
-                "─".repeat(chunks[1].width as usize - 4),
-                Style::default().fg(app.theme.border()),
-            ),
-            Span::styled("┘", Style::default().fg(app.theme.frost_blue())),
-        ]),
-    ];
+    // 1. Book title
+    let title_line = Line::from(vec![
+        Span::styled(" ─── ", Style::default().fg(app.theme.border())),
+        Span::styled(
+            app.ai_chat.book_title.as_str(),
+            Style::default().fg(app.theme.fg()),
+        ),
+        Span::styled(" ─── ", Style::default().fg(app.theme.border())),
+    ]);
     frame.render_widget(
-        Paragraph::new(assistant_msg).wrap(Wrap { trim: false }),
-        chunks[1],
+        Paragraph::new(title_line).alignment(Alignment::Center),
+        chunks[0],
     );
 
+    // 2. Chat history, pinned to the bottom unless scrolled up
+    let lines = history_lines(app, chunks[1].width.saturating_sub(3) as usize);
+    let height = chunks[1].height as usize;
+    let max_scroll = lines.len().saturating_sub(height);
+    let top = max_scroll - app.ai_chat.scroll.min(max_scroll);
+    let visible: Vec<Line> = lines.into_iter().skip(top).take(height).collect();
+    frame.render_widget(Paragraph::new(visible), chunks[1]);
+
     // 3. Suggested actions
-    let suggested = Line::from(vec![
-        Span::styled(
-            " Suggested actions: ",
-            Style::default().fg(app.theme.muted()),
-        ),
-        Span::styled(
-            " [1] ",
-            Style::default()
-                .fg(app.theme.frost_ice())
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::raw("Добавить теги "),
-        Span::styled(
-            " [2] ",
+    let mut suggested = vec![Span::styled(
+        " Suggested actions: ",
+        Style::default().fg(app.theme.muted()),
+    )];
+    if app.ai_chat.suggestions.is_empty() {
+        suggested.push(Span::styled("none", Style::default().fg(app.theme.muted())));
+    }
+    for (index, action) in app.ai_chat.suggestions.iter().take(9).enumerate() {
+        suggested.push(Span::styled(
+            format!(" [{}] ", index + 1),
             Style::default()
                 .fg(app.theme.frost_ice())
                 .add_modifier(Modifier::BOLD),
-        ),
-        Span::raw("Интекс"),
-    ]);
-    frame.render_widget(Paragraph::new(suggested), chunks[2]);
+        ));
+        suggested.push(Span::raw(describe_action(action)));
+    }
+    frame.render_widget(Paragraph::new(Line::from(suggested)), chunks[2]);
 
     // 4. Input field
+    let typing = app.mode == Mode::Insert;
     let input_block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(app.theme.frost_ice()))
+        .border_style(Style::default().fg(if typing {
+            app.theme.frost_ice()
+        } else {
+            app.theme.border()
+        }))
         .style(Style::default().bg(app.theme.bg_secondary()));
-    let input_para = Paragraph::new(app.ai_input.as_str()).block(input_block);
-    frame.render_widget(input_para, chunks[3]);
+    let input = if typing {
+        format!("{}▏", app.ai_input)
+    } else {
+        app.ai_input.clone()
+    };
+    frame.render_widget(Paragraph::new(input).block(input_block), chunks[3]);
 
     // 5. Hints
-    let hints = Line::from(vec![
-        Span::styled(" Ctrl+Enter ", Style::default().fg(app.theme.yellow())),
-        Span::styled("отправить", Style::default().fg(app.theme.muted())),
-    ]);
+    let hints: &[(&str, &str)] = if typing {
+        &[("Enter", "send"), ("Esc", "normal")]
+    } else {
+        &[
+            ("i", "type"),
+            ("1-9", "apply"),
+            ("J/K", "scroll"),
+            ("q", "close"),
+        ]
+    };
+    let mut spans = Vec::new();
+    for (key, desc) in hints {
+        spans.push(Span::styled(
+            format!(" {key} "),
+            Style::default().fg(app.theme.yellow()),
+        ));
+        spans.push(Span::styled(*desc, Style::default().fg(app.theme.muted())));
+    }
     frame.render_widget(
-        Paragraph::new(hints).alignment(ratatui::layout::Alignment::Center),
+        Paragraph::new(Line::from(spans)).alignment(Alignment::Center),
         chunks[4],
     );
 }
+
+fn history_lines(app: &App, width: usize) -> Vec<Line<'static>> {
+    let chat = &app.ai_chat;
+    let mut lines = Vec::new();
+    let mut push_message = |label: &str, color, text: &str| {
+        lines.push(Line::from(Span::styled(
+            format!(" {label}"),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        )));
+        for row in wrap_text(text, width) {
+            lines.push(Line::from(vec![
+                Span::styled(" │ ", Style::default().fg(color)),
+                Span::styled(row, Style::default().fg(app.theme.fg())),
+            ]));
+        }
+        lines.push(Line::default());
+    };
+
+    for turn in chat.history.iter().flat_map(|h| &h.turns) {
+        match turn.role {
+            Role::User => push_message("You", app.theme.frost_blue(), &turn.content),
+            Role::Assistant => push_message("Assistant", app.theme.purple(), &turn.content),
+            Role::System => {}
+        }
+    }
+    if let Some(reply) = &chat.pending_reply {
+        push_message("Assistant", app.theme.purple(), &format!("{reply}▍"));
+    }
+    if let Some(err) = &chat.error {
+        push_message("Error", app.theme.red(), err);
+    }
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " Ask anything about this book.",
+            Style::default().fg(app.theme.muted()),
+        )));
+    }
+    lines
+}
+
+/// Hard-wrap `text` at `width` characters, preferring word boundaries.
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(8);
+    let mut rows = Vec::new();
+    for paragraph in text.lines() {
+        let mut row = String::new();
+        let mut row_len = 0;
+        for word in paragraph.split(' ') {
+            let word_len = word.chars().count();
+            if row_len > 0 && row_len + 1 + word_len > width {
+                rows.push(std::mem::take(&mut row));
+                row_len = 0;
+            }
+            if row_len > 0 {
+                row.push(' ');
+                row_len += 1;
+            }
+            let mut chars = word.chars().peekable();
+            while chars.peek().is_some() {
+                if row_len == width {
+                    rows.push(std::mem::take(&mut row));
+                    row_len = 0;
+                }
+                row.push(chars.next().unwrap_or_default());
+                row_len += 1;
+            }
+        }
+        rows.push(row);
+    }
+    rows
+}
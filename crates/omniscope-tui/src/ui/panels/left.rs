@@ -59,6 +59,28 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
                         Style::default().fg(app.theme.frost_blue()),
                     )
                 }
+                SidebarItem::FeedHeader => (
+                    " ─── FEEDS ───".to_string(),
+                    Style::default()
+                        .fg(app.theme.muted())
+                        .add_modifier(Modifier::DIM),
+                ),
+                SidebarItem::Feed { name, new_count } => {
+                    let prefix = if is_selected { "▶ " } else { "  " };
+                    if *new_count > 0 {
+                        (
+                            format!("{prefix}󰑫 {name}    {new_count} new"),
+                            Style::default()
+                                .fg(app.theme.yellow())
+                                .add_modifier(Modifier::BOLD),
+                        )
+                    } else {
+                        (
+                            format!("{prefix}󰑫 {name}"),
+                            Style::default().fg(app.theme.muted()),
+                        )
+                    }
+                }
                 SidebarItem::FolderHeader => (
                     " ─── FOLDERS ───".to_string(),
                     Style::default()
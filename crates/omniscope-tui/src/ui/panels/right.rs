@@ -1,6 +1,7 @@
 use omniscope_core::BookSummaryView;
 use omniscope_core::models::BookCard;
 use omniscope_core::storage::json_cards;
+use omniscope_science::enrichment::field_provenance;
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Modifier, Style};
@@ -42,14 +43,18 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     let body_width = usize::from(chunks[0].width.saturating_sub(1)).max(20);
     let content = if let Some(book) = app.selected_book() {
         let maybe_card = json_cards::load_card_by_id(&app.cards_dir(), &book.id).ok();
-        if let Some(card) = maybe_card
+        let mut lines = if let Some(card) = maybe_card
             .as_ref()
             .filter(|card| article_card::is_scientific_article(card))
         {
             article_card::build_preview_lines(card, body_width, &app.theme)
         } else {
             render_default_preview(book, maybe_card.as_ref(), body_width, app)
+        };
+        if let Some(card) = maybe_card.as_ref() {
+            lines.extend(provenance_lines(card, body_width, app));
         }
+        lines
     } else {
         vec![
             Line::from(""),
@@ -214,6 +219,42 @@ fn render_default_preview(
     ]
 }
 
+/// Which source set each field, with a lock mark on fields enrichment skips.
+fn provenance_lines(card: &BookCard, body_width: usize, app: &App) -> Vec<Line<'static>> {
+    let provenance = field_provenance(card);
+    if provenance.is_empty() {
+        return Vec::new();
+    }
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "  PROVENANCE",
+            Style::default().fg(app.theme.muted()),
+        )),
+    ];
+    let name_width = body_width.saturating_sub(4).clamp(8, 22);
+    for entry in provenance {
+        let name = entry
+            .label
+            .map(str::to_string)
+            .unwrap_or_else(|| entry.field.clone());
+        let lock = if entry.locked { "  locked" } else { "" };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:<name_width$} ", truncate_text(&name, name_width)),
+                Style::default().fg(app.theme.fg()),
+            ),
+            Span::styled(
+                entry.source.unwrap_or_else(|| "—".to_string()),
+                Style::default().fg(app.theme.frost_blue()),
+            ),
+            Span::styled(lock, Style::default().fg(app.theme.yellow())),
+        ]));
+    }
+    lines
+}
+
 fn hints_block(app: &App, width: usize, start: usize, max_scroll: usize) -> Paragraph<'static> {
     let scroll_label = if max_scroll == 0 {
         "scroll: 1/1".to_string()
@@ -293,6 +293,38 @@ pub(crate) fn render_popup(frame: &mut Frame, app: &App, popup: &Popup, area: Re
             cloned.render(frame, popup_area, &app.theme);
         }
 
+        Popup::Duplicates(panel) => {
+            let popup_area = centered_rect(94, 90, area);
+            frame.render_widget(Clear, popup_area);
+
+            let mut cloned = panel.clone();
+            cloned.render(frame, popup_area, &app.theme);
+        }
+
+        Popup::ArxivUpdates(panel) => {
+            let popup_area = centered_rect(94, 90, area);
+            frame.render_widget(Clear, popup_area);
+
+            let mut cloned = panel.clone();
+            cloned.render(frame, popup_area, &app.theme);
+        }
+
+        Popup::ArxivFeed(panel) => {
+            let popup_area = centered_rect(94, 90, area);
+            frame.render_widget(Clear, popup_area);
+
+            let mut cloned = panel.clone();
+            cloned.render(frame, popup_area, &app.theme);
+        }
+
+        Popup::MergeReview(panel) => {
+            let popup_area = centered_rect(94, 90, area);
+            frame.render_widget(Clear, popup_area);
+
+            let mut cloned = panel.clone();
+            cloned.render(frame, popup_area, &app.theme);
+        }
+
         Popup::TextViewer {
             title,
             body,